@@ -0,0 +1,172 @@
+//! 账号分享片段：导出账号的非敏感配置（Token 替换为占位符），方便分享代理配置
+//!
+//! 支持 JSON 和 `.env` 两种格式，按文件扩展名区分。`.env` 片段中的变量与切换账号时
+//! 写入 settings.local.json 的 env 一致，不包含备注。
+
+use crate::models::{Account, AuthStyle, ProxyConfig, PROXY_ENV_KEYS};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 片段中代替 Token 的占位符
+pub const TOKEN_PLACEHOLDER: &str = "REPLACE_ME";
+
+/// 片段格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnippetFormat {
+    Json,
+    Env,
+}
+
+impl SnippetFormat {
+    /// `.env` 扩展名或文件名为 `.env` 时使用 env 格式，其余按 JSON 处理
+    pub fn from_path(path: &Path) -> Self {
+        let is_env = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("env"))
+            || path.file_name().is_some_and(|name| name.eq_ignore_ascii_case(".env"));
+        if is_env {
+            SnippetFormat::Env
+        } else {
+            SnippetFormat::Json
+        }
+    }
+}
+
+/// 不含 Token 的账号配置
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountSnippet {
+    pub name: String,
+    pub base_url: String,
+    #[serde(default)]
+    pub model: String,
+    #[serde(default)]
+    pub auth_style: AuthStyle,
+    #[serde(default)]
+    pub proxy: ProxyConfig,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// 导出时始终为占位符，导入时忽略
+    #[serde(default)]
+    pub token: String,
+}
+
+impl AccountSnippet {
+    pub fn from_account(account: &Account) -> Self {
+        Self {
+            name: account.name.clone(),
+            base_url: account.base_url.clone(),
+            model: account.model.clone(),
+            auth_style: account.auth_style,
+            proxy: account.proxy_config(),
+            note: account.note.clone(),
+            token: TOKEN_PLACEHOLDER.to_string(),
+        }
+    }
+
+    pub fn render(&self, format: SnippetFormat) -> Result<String> {
+        match format {
+            SnippetFormat::Json => Ok(serde_json::to_string_pretty(self)?),
+            SnippetFormat::Env => Ok(self.render_env()),
+        }
+    }
+
+    fn render_env(&self) -> String {
+        let mut lines = vec![format!("# {}", self.name)];
+        for key in self.auth_style.env_keys() {
+            lines.push(format!("{}={}", key, TOKEN_PLACEHOLDER));
+        }
+        lines.push(format!("ANTHROPIC_BASE_URL={}", self.base_url));
+        lines.push(format!("USER_NAME={}", self.name));
+        if !self.model.is_empty() {
+            lines.push(format!("ANTHROPIC_MODEL={}", self.model));
+        }
+        for (key, value) in self.proxy.env_entries() {
+            lines.push(format!("{}={}", key, value));
+        }
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    /// 解析片段，片段中的 Token 一律丢弃，由导入方重新填写
+    pub fn parse(content: &str, format: SnippetFormat) -> Result<Self> {
+        let mut snippet = match format {
+            SnippetFormat::Json => serde_json::from_str(content).context("账号片段不是合法的 JSON")?,
+            SnippetFormat::Env => Self::parse_env(content)?,
+        };
+        snippet.token.clear();
+        Ok(snippet)
+    }
+
+    fn parse_env(content: &str) -> Result<Self> {
+        let vars: HashMap<&str, &str> = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim().trim_matches('"')))
+            .collect();
+
+        let base_url = vars
+            .get("ANTHROPIC_BASE_URL")
+            .context("账号片段缺少 ANTHROPIC_BASE_URL")?;
+        let auth_style = match (
+            vars.contains_key("ANTHROPIC_API_KEY"),
+            vars.contains_key("ANTHROPIC_AUTH_TOKEN"),
+        ) {
+            (true, false) => AuthStyle::ApiKey,
+            (false, true) => AuthStyle::BearerToken,
+            _ => AuthStyle::Both,
+        };
+        let proxy_value = |index: usize| vars.get(PROXY_ENV_KEYS[index]).unwrap_or(&"").to_string();
+
+        Ok(Self {
+            name: vars.get("USER_NAME").unwrap_or(&"").to_string(),
+            base_url: base_url.to_string(),
+            model: vars.get("ANTHROPIC_MODEL").unwrap_or(&"").to_string(),
+            auth_style,
+            proxy: ProxyConfig {
+                http_proxy: proxy_value(0),
+                https_proxy: proxy_value(1),
+                no_proxy: proxy_value(2),
+            },
+            note: None,
+            token: String::new(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snippet() -> AccountSnippet {
+        AccountSnippet {
+            name: "team-proxy".to_string(),
+            base_url: "https://proxy.example.com".to_string(),
+            model: "claude-sonnet-4".to_string(),
+            auth_style: AuthStyle::BearerToken,
+            proxy: ProxyConfig {
+                http_proxy: String::new(),
+                https_proxy: "http://127.0.0.1:7890".to_string(),
+                no_proxy: "localhost".to_string(),
+            },
+            note: None,
+            token: TOKEN_PLACEHOLDER.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_snippet_roundtrip_without_token() {
+        for format in [SnippetFormat::Json, SnippetFormat::Env] {
+            let rendered = snippet().render(format).unwrap();
+            assert!(rendered.contains(TOKEN_PLACEHOLDER));
+
+            let parsed = AccountSnippet::parse(&rendered, format).unwrap();
+            assert_eq!(parsed, AccountSnippet { token: String::new(), ..snippet() });
+        }
+
+        assert_eq!(SnippetFormat::from_path(Path::new("proxy.env")), SnippetFormat::Env);
+        assert_eq!(SnippetFormat::from_path(Path::new(".env")), SnippetFormat::Env);
+        assert_eq!(SnippetFormat::from_path(Path::new("proxy.json")), SnippetFormat::Json);
+    }
+}
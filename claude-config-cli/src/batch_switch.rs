@@ -0,0 +1,130 @@
+//! 批量切换的结果记录：每个目录写入后立即保存，程序中断或重启后也能只重试失败的目录
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// 记录文件名，保存在应用数据目录中
+const RECORD_FILE: &str = "last_batch_switch.json";
+
+/// 单个目录的切换状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchStatus {
+    /// 尚未执行（批量切换中断时保留此状态）
+    Pending,
+    Succeeded,
+    /// 配置已一致，没有重写
+    Unchanged,
+    Failed,
+}
+
+impl BatchStatus {
+    /// 重试时需要重新执行的状态；成功或无需修改的目录永远不会再次写入
+    pub fn needs_retry(&self) -> bool {
+        matches!(self, BatchStatus::Pending | BatchStatus::Failed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchEntry {
+    pub directory_id: i64,
+    pub directory_name: String,
+    pub status: BatchStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// 最近一次批量切换的参数与每个目录的结果
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchRecord {
+    pub account_id: i64,
+    pub account_name: String,
+    #[serde(default)]
+    pub template_id: Option<i64>,
+    pub skip_permissions: bool,
+    pub use_proxy: bool,
+    pub started_at: DateTime<Utc>,
+    pub entries: Vec<BatchEntry>,
+}
+
+impl BatchRecord {
+    /// 需要重试的目录数
+    pub fn retry_count(&self) -> usize {
+        self.entries.iter().filter(|entry| entry.status.needs_retry()).count()
+    }
+
+    pub fn count(&self, status: BatchStatus) -> usize {
+        self.entries.iter().filter(|entry| entry.status == status).count()
+    }
+
+    /// 更新某个目录的状态
+    pub fn set_status(&mut self, directory_id: i64, status: BatchStatus, error: Option<String>) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.directory_id == directory_id) {
+            entry.status = status;
+            entry.error = error;
+        }
+    }
+}
+
+fn record_path() -> PathBuf {
+    crate::config_manager::ConfigManager::get_app_data_dir().join(RECORD_FILE)
+}
+
+/// 读取最近一次批量切换记录，没有记录或无法解析时返回 None
+pub fn load() -> Option<BatchRecord> {
+    let content = std::fs::read_to_string(record_path()).ok()?;
+    serde_json::from_str(&content)
+        .map_err(|e| tracing::warn!("批量切换记录解析失败: {}", e))
+        .ok()
+}
+
+pub fn save(record: &BatchRecord) -> Result<()> {
+    let path = record_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_string_pretty(record)?)
+        .with_context(|| format!("保存批量切换记录失败: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(directory_id: i64, status: BatchStatus) -> BatchEntry {
+        BatchEntry {
+            directory_id,
+            directory_name: format!("dir-{}", directory_id),
+            status,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_retry_skips_succeeded_entries() {
+        let mut record = BatchRecord {
+            account_id: 1,
+            account_name: "team".to_string(),
+            template_id: None,
+            skip_permissions: true,
+            use_proxy: false,
+            started_at: Utc::now(),
+            entries: vec![
+                entry(1, BatchStatus::Succeeded),
+                entry(2, BatchStatus::Failed),
+                entry(3, BatchStatus::Unchanged),
+                entry(4, BatchStatus::Pending),
+            ],
+        };
+        assert_eq!(record.retry_count(), 2);
+
+        record.set_status(2, BatchStatus::Succeeded, None);
+        assert_eq!(record.retry_count(), 1);
+        assert_eq!(record.count(BatchStatus::Succeeded), 2);
+
+        let restored: BatchRecord = serde_json::from_str(&serde_json::to_string(&record).unwrap()).unwrap();
+        assert_eq!(restored, record);
+    }
+}
@@ -0,0 +1,621 @@
+//! 同步配置包（WebDAV 上的 JSON）的解析、导入预览与导入
+
+use crate::database::Database;
+use crate::models::*;
+use crate::{names, t};
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// 配置包中没有模型字段时使用的默认模型
+const DEFAULT_IMPORT_MODEL: &str = "claude-sonnet-4-20250514";
+
+/// 导入时对单条记录执行的动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportAction {
+    /// 本地不存在，将新增
+    Add,
+    /// 本地存在但内容不同，将更新
+    Update,
+    /// 本地已存在且内容相同，跳过
+    Skip,
+}
+
+/// 配置包中的记录类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportKind {
+    Account,
+    BaseUrl,
+}
+
+/// 导入计划中的一条记录，`key` 为自然键（账号名 / Base URL 名称）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportItem {
+    pub kind: ImportKind,
+    pub key: String,
+    pub action: ImportAction,
+}
+
+/// 导入预览结果，不修改数据库
+#[derive(Debug, Default)]
+pub struct ImportPlan {
+    pub items: Vec<ImportItem>,
+    /// 只存在于本地的记录：合并导入时保留，覆盖导入时删除
+    pub local_only: Vec<ImportItem>,
+}
+
+impl ImportPlan {
+    pub fn count(&self, action: ImportAction) -> usize {
+        self.items.iter().filter(|item| item.action == action).count()
+    }
+}
+
+/// 实际导入的数量
+#[derive(Debug, Default)]
+pub struct ImportSummary {
+    pub accounts: usize,
+    pub base_urls: usize,
+    /// 锁定状态发生变化的本地目录数
+    pub directory_locks: usize,
+}
+
+/// 配置包的说明信息（`meta` 对象）；不认识的字段忽略，便于以后扩展
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct BundleMeta {
+    pub exported_at: Option<String>,
+    pub app_version: Option<String>,
+    pub note: Option<String>,
+}
+
+/// 读取配置包的说明信息；没有 `meta` 的旧配置包只取顶层的 `exported_at`
+pub fn bundle_meta(data: &Value) -> Option<BundleMeta> {
+    match data.get("meta") {
+        Some(meta) => serde_json::from_value(meta.clone()).ok(),
+        None => data.get("exported_at").and_then(|v| v.as_str()).map(|exported_at| BundleMeta {
+            exported_at: Some(exported_at.to_string()),
+            ..Default::default()
+        }),
+    }
+}
+
+/// 导出配置包：账号、Base URL、Claude 配置和目录锁定状态，附带导出时间、版本和备注
+pub async fn export_bundle(db: &Database, note: Option<&str>) -> Result<Value> {
+    let accounts = db
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(1000),
+            search: None,
+            base_url: None,
+        })
+        .await?;
+    let base_urls = db.get_base_urls().await?;
+    let claude_settings: Value = serde_json::from_str(&db.get_claude_settings().await?)?;
+    let directory_locks = directory_locks(&db.get_directories().await?);
+
+    Ok(serde_json::json!({
+        "meta": {
+            "exported_at": chrono::Utc::now().to_rfc3339(),
+            "app_version": env!("CARGO_PKG_VERSION"),
+            "note": note.map(str::trim).filter(|note| !note.is_empty()),
+        },
+        "accounts": accounts.accounts,
+        "base_urls": base_urls,
+        "claude_settings": claude_settings,
+        "directory_locks": directory_locks,
+    }))
+}
+
+/// 导出目录锁定状态。目录路径因机器而异，导入时按目录名称匹配
+pub fn directory_locks(directories: &[Directory]) -> Value {
+    Value::Array(
+        directories
+            .iter()
+            .map(|d| serde_json::json!({ "name": d.name, "is_locked": d.is_locked }))
+            .collect(),
+    )
+}
+
+/// 解析配置包中的目录锁定状态：(目录名称, 是否锁定)
+fn parse_directory_locks(data: &Value) -> Vec<(String, bool)> {
+    let Some(locks) = data.get("directory_locks").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    locks
+        .iter()
+        .filter_map(|lock| {
+            let name = lock.get("name")?.as_str()?;
+            let is_locked = lock.get("is_locked")?.as_bool()?;
+            Some((name.to_string(), is_locked))
+        })
+        .collect()
+}
+
+/// 解析配置包中的账号，缺少必填字段的条目被忽略
+fn parse_accounts(data: &Value) -> Vec<CreateAccountRequest> {
+    let Some(accounts) = data.get("accounts").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    accounts
+        .iter()
+        .filter_map(|account_data| {
+            let text = |key: &str| account_data.get(key).and_then(|v| v.as_str());
+            let (name, token, base_url) = (text("name")?, text("token")?, text("base_url")?);
+
+            let auth_style = account_data
+                .get("auth_style")
+                .and_then(|v| serde_json::from_value(v.clone()).ok())
+                .unwrap_or_default();
+            let proxy = ProxyConfig {
+                http_proxy: text("http_proxy").unwrap_or("").to_string(),
+                https_proxy: text("https_proxy").unwrap_or("").to_string(),
+                no_proxy: text("no_proxy").unwrap_or("").to_string(),
+            };
+
+            // 没有 note 字段的旧配置包不改动本地备注，null 表示清除
+            let note = account_data
+                .get("note")
+                .map(|v| v.as_str().unwrap_or_default().trim().to_string());
+
+            // 同上：没有该字段时保留本地设置
+            let extra_auth_env_key = account_data
+                .get("extra_auth_env_key")
+                .map(|v| v.as_str().unwrap_or_default().trim().to_string());
+            let staging_base_url = account_data
+                .get("staging_base_url")
+                .map(|v| v.as_str().unwrap_or_default().trim().to_string());
+            let token_command = account_data
+                .get("token_command")
+                .map(|v| v.as_str().unwrap_or_default().trim().to_string());
+
+            Some(CreateAccountRequest {
+                name: name.to_string(),
+                token: token.to_string(),
+                base_url: base_url.to_string(),
+                model: text("model").unwrap_or(DEFAULT_IMPORT_MODEL).to_string(),
+                auth_style,
+                proxy,
+                note,
+                extra_auth_env_key,
+                staging_base_url,
+                token_command,
+            })
+        })
+        .collect()
+}
+
+/// 解析配置包中的 Base URL，缺少名称或地址的条目被忽略
+fn parse_base_urls(data: &Value) -> Vec<CreateBaseUrlRequest> {
+    let Some(base_urls) = data.get("base_urls").and_then(|v| v.as_array()) else {
+        return Vec::new();
+    };
+
+    base_urls
+        .iter()
+        .filter_map(|base_url_data| {
+            let text = |key: &str| base_url_data.get(key).and_then(|v| v.as_str());
+            Some(CreateBaseUrlRequest {
+                name: text("name")?.to_string(),
+                url: text("url")?.to_string(),
+                description: text("description").map(|s| s.to_string()),
+                api_key: text("api_key").map(|s| s.to_string()),
+                is_default: base_url_data.get("is_default").and_then(|v| v.as_bool()),
+            })
+        })
+        .collect()
+}
+
+fn account_matches(local: &Account, incoming: &CreateAccountRequest) -> bool {
+    local.token == incoming.token
+        && local.base_url == incoming.base_url
+        && local.model == incoming.model
+        && local.auth_style == incoming.auth_style
+        && local.proxy_config() == incoming.proxy
+        && incoming
+            .note
+            .as_deref()
+            .is_none_or(|note| local.note.as_deref().unwrap_or_default() == note)
+        && incoming
+            .extra_auth_env_key
+            .as_deref()
+            .is_none_or(|key| local.extra_auth_env_key.as_deref().unwrap_or_default() == key)
+        && incoming
+            .staging_base_url
+            .as_deref()
+            .is_none_or(|url| local.staging_base_url.as_deref().unwrap_or_default() == url)
+        && incoming
+            .token_command
+            .as_deref()
+            .is_none_or(|command| local.token_command.as_deref().unwrap_or_default() == command)
+}
+
+fn base_url_matches(local: &BaseUrl, incoming: &CreateBaseUrlRequest) -> bool {
+    local.url == incoming.url
+        && local.description == incoming.description
+        && local.is_default == incoming.is_default.unwrap_or(false)
+}
+
+/// 按自然键（账号名、Base URL 名称）比较配置包与本地数据，生成导入计划
+pub fn plan_import(data: &Value, accounts: &[Account], base_urls: &[BaseUrl]) -> ImportPlan {
+    let mut plan = ImportPlan::default();
+
+    let incoming_base_urls = parse_base_urls(data);
+    for incoming in &incoming_base_urls {
+        let action = match base_urls.iter().find(|b| b.name == incoming.name) {
+            None => ImportAction::Add,
+            Some(local) if base_url_matches(local, incoming) => ImportAction::Skip,
+            Some(_) => ImportAction::Update,
+        };
+        plan.items.push(ImportItem {
+            kind: ImportKind::BaseUrl,
+            key: incoming.name.clone(),
+            action,
+        });
+    }
+
+    let incoming_accounts = parse_accounts(data);
+    for incoming in &incoming_accounts {
+        let action = match accounts.iter().find(|a| a.name == incoming.name) {
+            None => ImportAction::Add,
+            Some(local) if account_matches(local, incoming) => ImportAction::Skip,
+            Some(_) => ImportAction::Update,
+        };
+        plan.items.push(ImportItem {
+            kind: ImportKind::Account,
+            key: incoming.name.clone(),
+            action,
+        });
+    }
+
+    plan.local_only.extend(
+        base_urls
+            .iter()
+            .filter(|local| !incoming_base_urls.iter().any(|b| b.name == local.name))
+            .map(|local| ImportItem {
+                kind: ImportKind::BaseUrl,
+                key: local.name.clone(),
+                action: ImportAction::Skip,
+            }),
+    );
+    plan.local_only.extend(
+        accounts
+            .iter()
+            .filter(|local| !incoming_accounts.iter().any(|a| a.name == local.name))
+            .map(|local| ImportItem {
+                kind: ImportKind::Account,
+                key: local.name.clone(),
+                action: ImportAction::Skip,
+            }),
+    );
+
+    plan
+}
+
+async fn load_local(db: &Database) -> Result<(Vec<Account>, Vec<BaseUrl>)> {
+    let accounts = db
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(1000),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts;
+    let base_urls = db.get_base_urls().await?;
+    Ok((accounts, base_urls))
+}
+
+/// 预览导入配置包会产生的变化（只读）
+pub async fn preview_import(db: &Database, data: &Value) -> Result<ImportPlan> {
+    let (accounts, base_urls) = load_local(db).await?;
+    Ok(plan_import(data, &accounts, &base_urls))
+}
+
+/// 合并导入前按自然键预先校验配置包，避免事务写到一半才因约束失败：
+/// 账号名称合法且不重复，Base URL 名称和地址不重复，且地址不与本地其他名称的 Base URL 冲突
+fn validate_natural_keys(
+    accounts: &[CreateAccountRequest],
+    base_urls: &[CreateBaseUrlRequest],
+    local_base_urls: &[BaseUrl],
+) -> Result<()> {
+    let mut account_names = HashSet::new();
+    for account in accounts {
+        let name = names::validate_name(&account.name).map_err(|e| {
+            anyhow::anyhow!(
+                t!("bundle.import.invalid_account_name")
+                    .replacen("{}", &account.name, 1)
+                    .replacen("{}", &e.to_string(), 1)
+            )
+        })?;
+        if !account_names.insert(name) {
+            bail!("{}", t!("bundle.import.duplicate_account").replace("{}", &account.name));
+        }
+    }
+
+    let (mut names, mut urls) = (HashSet::new(), HashSet::new());
+    for base_url in base_urls {
+        if !names.insert(base_url.name.as_str()) || !urls.insert(base_url.url.as_str()) {
+            bail!("{}", t!("bundle.import.duplicate_base_url").replace("{}", &base_url.name));
+        }
+        if let Some(local) = local_base_urls
+            .iter()
+            .find(|local| local.url == base_url.url && local.name != base_url.name)
+        {
+            bail!(
+                "{}",
+                t!("bundle.import.base_url_conflict")
+                    .replacen("{}", &base_url.name, 1)
+                    .replacen("{}", &local.name, 1)
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 导入配置包
+///
+/// `merge` 为 true 时按自然键新增或更新，保留只存在于本地的记录；
+/// 为 false 时先清空本地账号和 Base URL，再完整导入（覆盖）。
+/// 整个导入在一个事务中完成，任一记录失败时全部回滚、本地数据不变，错误信息指明失败的记录，
+/// 因此失败后可以放心重试。
+pub async fn import_bundle(db: &Database, data: &Value, merge: bool) -> Result<ImportSummary> {
+    let incoming_base_urls = parse_base_urls(data);
+    let incoming_accounts = parse_accounts(data);
+    let incoming_locks = parse_directory_locks(data);
+
+    // 本地数据在事务开始前读取：内存数据库的连接池只有一个连接
+    let (accounts, base_urls) = if merge {
+        let (accounts, base_urls) = load_local(db).await?;
+        validate_natural_keys(&incoming_accounts, &incoming_base_urls, &base_urls)?;
+        (accounts, base_urls)
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    let directories = if incoming_locks.is_empty() {
+        Vec::new()
+    } else {
+        db.get_directories().await?
+    };
+
+    let mut tx = db.get_pool().begin().await?;
+    if !merge {
+        sqlx::query("DELETE FROM accounts").execute(&mut *tx).await?;
+        sqlx::query("DELETE FROM base_urls").execute(&mut *tx).await?;
+    }
+
+    let mut summary = ImportSummary::default();
+
+    // 先导入 Base URL，账号中的 base_url 以配置包为准
+    for incoming in incoming_base_urls {
+        let name = incoming.name.clone();
+        match base_urls.iter().find(|b| b.name == incoming.name) {
+            None => Database::create_base_url_in(&mut tx, incoming).await.map(|_| ()),
+            Some(local) if base_url_matches(local, &incoming) => continue,
+            Some(local) => Database::update_base_url_in(
+                &mut tx,
+                local.id,
+                UpdateBaseUrlRequest {
+                    name: None,
+                    url: Some(incoming.url),
+                    description: incoming.description,
+                    api_key: incoming.api_key,
+                    is_default: incoming.is_default,
+                },
+            )
+            .await
+            .map(|_| ()),
+        }
+        .with_context(|| t!("bundle.import.base_url_failed").replace("{}", &name))?;
+        summary.base_urls += 1;
+    }
+
+    for incoming in incoming_accounts {
+        let name = incoming.name.clone();
+        match accounts.iter().find(|a| a.name == incoming.name) {
+            None => Database::create_account_in(&mut tx, incoming).await.map(|_| ()),
+            Some(local) if account_matches(local, &incoming) => continue,
+            Some(local) => Database::update_account_in(
+                &mut tx,
+                local.id,
+                UpdateAccountRequest {
+                    name: None,
+                    token: Some(incoming.token),
+                    base_url: Some(incoming.base_url),
+                    model: Some(incoming.model),
+                    auth_style: Some(incoming.auth_style),
+                    proxy: Some(incoming.proxy),
+                    note: incoming.note,
+                    extra_auth_env_key: incoming.extra_auth_env_key,
+                    staging_base_url: incoming.staging_base_url,
+                    token_command: incoming.token_command,
+                },
+            )
+            .await
+            .map(|_| ()),
+        }
+        .with_context(|| t!("bundle.import.account_failed").replace("{}", &name))?;
+        summary.accounts += 1;
+    }
+
+    // 目录不随配置包同步，只把锁定状态应用到同名的本地目录
+    for directory in &directories {
+        let Some((_, is_locked)) = incoming_locks.iter().find(|(name, _)| *name == directory.name) else {
+            continue;
+        };
+        if *is_locked == directory.is_locked {
+            continue;
+        }
+        Database::set_directory_locked_in(&mut tx, directory.id, *is_locked)
+            .await
+            .with_context(|| t!("bundle.import.directory_lock_failed").replace("{}", &directory.name))?;
+        summary.directory_locks += 1;
+    }
+
+    // Claude 设置整体替换
+    if let Some(claude_settings) = data.get("claude_settings") {
+        let settings_json = serde_json::to_string(claude_settings)?;
+        Database::save_claude_settings_in(&mut tx, &settings_json).await?;
+    }
+
+    tx.commit().await?;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use serde_json::json;
+
+    fn local_account(name: &str, token: &str) -> Account {
+        Account {
+            id: 1,
+            name: name.to_string(),
+            token: token.to_string(),
+            base_url: "https://api.anthropic.com".to_string(),
+            model: DEFAULT_IMPORT_MODEL.to_string(),
+            auth_style: AuthStyle::default(),
+            http_proxy: String::new(),
+            https_proxy: String::new(),
+            no_proxy: String::new(),
+            note: None,
+            extra_auth_env_key: None,
+            staging_base_url: None,
+            token_command: None,
+            claude_md_template: None,
+            is_active: false,
+            last_used_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_plan_import_by_natural_key() {
+        let local = vec![
+            local_account("same", "sk-ant-same"),
+            local_account("changed", "sk-ant-old"),
+            local_account("local-only", "sk-ant-local"),
+        ];
+        let data = json!({
+            "accounts": [
+                {"name": "same", "token": "sk-ant-same", "base_url": "https://api.anthropic.com"},
+                {"name": "changed", "token": "sk-ant-new", "base_url": "https://api.anthropic.com"},
+                {"name": "new", "token": "sk-ant-new", "base_url": "https://api.anthropic.com"},
+                {"name": "missing-token", "base_url": "https://api.anthropic.com"}
+            ]
+        });
+
+        let plan = plan_import(&data, &local, &[]);
+
+        let actions: Vec<(&str, ImportAction)> =
+            plan.items.iter().map(|i| (i.key.as_str(), i.action)).collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("same", ImportAction::Skip),
+                ("changed", ImportAction::Update),
+                ("new", ImportAction::Add),
+            ]
+        );
+        assert_eq!(plan.local_only.len(), 1);
+        assert_eq!(plan.local_only[0].key, "local-only");
+    }
+
+    #[test]
+    fn test_plan_import_note() {
+        let mut local = local_account("noted", "sk-ant-same");
+        local.note = Some("work proxy".to_string());
+        let account = |note: Value| {
+            let mut data = json!({"name": "noted", "token": "sk-ant-same", "base_url": "https://api.anthropic.com"});
+            if !note.is_null() {
+                data["note"] = note;
+            }
+            json!({ "accounts": [data] })
+        };
+
+        // 旧配置包没有 note 字段时不视为变化
+        let plan = plan_import(&account(Value::Null), std::slice::from_ref(&local), &[]);
+        assert_eq!(plan.items[0].action, ImportAction::Skip);
+
+        let plan = plan_import(&account(json!("expires Q3")), std::slice::from_ref(&local), &[]);
+        assert_eq!(plan.items[0].action, ImportAction::Update);
+    }
+
+    #[test]
+    fn test_bundle_meta() {
+        let data = json!({
+            "meta": {
+                "exported_at": "2026-01-01T00:00:00Z",
+                "app_version": "1.5.0",
+                "note": "before reinstall",
+                "exported_by": "future field",
+            },
+        });
+        assert_eq!(
+            bundle_meta(&data),
+            Some(BundleMeta {
+                exported_at: Some("2026-01-01T00:00:00Z".to_string()),
+                app_version: Some("1.5.0".to_string()),
+                note: Some("before reinstall".to_string()),
+            })
+        );
+
+        let legacy = json!({ "exported_at": "2025-06-01T00:00:00Z", "accounts": [] });
+        assert_eq!(bundle_meta(&legacy).unwrap().exported_at.as_deref(), Some("2025-06-01T00:00:00Z"));
+        assert!(bundle_meta(&json!({ "accounts": [] })).is_none());
+    }
+
+    #[test]
+    fn test_parse_directory_locks() {
+        let data = json!({
+            "directory_locks": [
+                {"name": "prod", "is_locked": true},
+                {"name": "dev", "is_locked": false},
+                {"name": "broken"}
+            ]
+        });
+
+        assert_eq!(
+            parse_directory_locks(&data),
+            vec![("prod".to_string(), true), ("dev".to_string(), false)]
+        );
+        assert!(parse_directory_locks(&json!({})).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_bundle_rolls_back_on_bad_row() {
+        let db = Database::new_in_memory().await.unwrap();
+        db.create_account(CreateAccountRequest {
+            name: "local".to_string(),
+            token: "sk-ant-local".to_string(),
+            base_url: "https://api.anthropic.com".to_string(),
+            model: DEFAULT_IMPORT_MODEL.to_string(),
+            auth_style: AuthStyle::default(),
+            proxy: ProxyConfig::default(),
+            note: None,
+            extra_auth_env_key: None,
+            staging_base_url: None,
+            token_command: None,
+        })
+        .await
+        .unwrap();
+
+        let data = json!({
+            "accounts": [
+                {"name": "first", "token": "sk-ant-first", "base_url": "https://api.anthropic.com"},
+                {"name": "bad\nname", "token": "sk-ant-bad", "base_url": "https://api.anthropic.com"}
+            ]
+        });
+
+        for merge in [false, true] {
+            assert!(import_bundle(&db, &data, merge).await.is_err());
+            let (accounts, _) = load_local(&db).await.unwrap();
+            let names: Vec<_> = accounts.iter().map(|a| a.name.as_str()).collect();
+            assert_eq!(names, vec!["local"]);
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! 目录配置变更记录：收集 ClaudeConfigManager 的变更通知，写入 config_audit 表
+
+use crate::database::Database;
+use claude_config_core::audit::{ChangeHook, ConfigChange};
+use claude_config_core::checksum::sha256_hex;
+use std::sync::{Arc, Mutex};
+use tracing::warn;
+
+/// 快照内容的大小上限，超过时只保存哈希
+const SNAPSHOT_MAX_BYTES: usize = 64 * 1024;
+
+/// 收集某个目录的配置变更，操作结束后统一写入数据库
+pub struct ChangeRecorder {
+    directory_id: i64,
+    base_url: Option<String>,
+    /// 为 false 时快照只保存哈希（配置中含有不应落库的 Token）
+    snapshot_content: bool,
+    changes: Arc<Mutex<Vec<ConfigChange>>>,
+}
+
+impl ChangeRecorder {
+    pub fn new(directory_id: i64) -> Self {
+        Self {
+            directory_id,
+            base_url: None,
+            snapshot_content: true,
+            changes: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// 记录本次写入使用的 Base URL（切换时可能是账号的备用地址）
+    pub fn with_base_url(mut self, base_url: Option<String>) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// 是否在快照中保存配置内容；Token 由命令动态获取时不应写入数据库，只保存哈希
+    pub fn with_snapshot_content(mut self, snapshot_content: bool) -> Self {
+        self.snapshot_content = snapshot_content;
+        self
+    }
+
+    /// 注册到 ClaudeConfigManager 的变更钩子
+    pub fn hook(&self) -> ChangeHook {
+        let changes = self.changes.clone();
+        Arc::new(move |change: &ConfigChange| {
+            changes.lock().unwrap().push(change.clone());
+        })
+    }
+
+    /// 手动记录一次变更（多步写入需要合并为一条记录时使用）
+    pub fn record(&self, change: ConfigChange) {
+        self.changes.lock().unwrap().push(change);
+    }
+
+    /// 写入收集到的变更；记录失败只告警，不影响已完成的配置操作
+    pub async fn flush(&self, db: &Database) {
+        let changes: Vec<ConfigChange> = self.changes.lock().unwrap().drain(..).collect();
+        let actor = current_actor();
+        for change in &changes {
+            if let Err(e) = db
+                .record_config_change(self.directory_id, change, self.base_url.as_deref(), &actor).await {
+                warn!("记录目录 {} 的配置变更失败: {}", self.directory_id, e);
+            }
+        }
+
+        if let Some(last) = changes.last() {
+            self.save_snapshot(db, last).await;
+        }
+    }
+
+    /// 保存最后一次写入后的配置快照，供“检测外部修改”比较
+    async fn save_snapshot(&self, db: &Database, change: &ConfigChange) {
+        let content = change.after_hash.as_ref().filter(|_| self.snapshot_content).and_then(|after_hash| {
+            let bytes = std::fs::read(&change.settings_path).ok()?;
+            // 写入后文件又被其他程序修改时，快照与哈希不一致，只保留哈希
+            if bytes.len() > SNAPSHOT_MAX_BYTES || &sha256_hex(&bytes) != after_hash {
+                return None;
+            }
+            String::from_utf8(bytes).ok()
+        });
+
+        if let Err(e) = db
+            .save_settings_snapshot(
+                self.directory_id,
+                &change.settings_path.to_string_lossy(),
+                change.after_hash.as_deref(),
+                content.as_deref(),
+            )
+            .await
+        {
+            warn!("保存目录 {} 的配置快照失败: {}", self.directory_id, e);
+        }
+    }
+}
+
+/// 当前操作系统用户名
+fn current_actor() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_default()
+}
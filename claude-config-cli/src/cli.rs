@@ -0,0 +1,157 @@
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Claude Code 配置管理器 - 命令行版本
+///
+/// 不带子命令运行时进入交互式菜单。
+#[derive(Debug, Parser)]
+#[command(name = "claude-config", version, about)]
+pub struct Cli {
+    /// 进入首次运行向导（即使已有账号和目录）
+    #[arg(long)]
+    pub setup: bool,
+
+    /// 离线模式：拒绝所有网络请求（连通性测试、WebDAV），只管理本地配置
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// 不使用表情符号，状态以 [ACTIVE] / [MISSING] 等 ASCII 标签显示
+    #[arg(long, global = true)]
+    pub no_emoji: bool,
+
+    /// 不使用颜色输出（也可设置 NO_COLOR 环境变量）
+    #[arg(long, global = true)]
+    pub no_color: bool,
+
+    /// 使用内存数据库：不读取也不写入数据库文件，退出后所有账号、目录等数据都会丢弃（适合 CI 和临时测试）
+    #[arg(long, global = true)]
+    pub ephemeral: bool,
+
+    /// 切换账号时即使目录配置没有变化也强制重写
+    #[arg(long)]
+    pub force: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// 子命令的目标目录：按 ID 或名称指定，二者必须且只能指定其一
+#[derive(Debug, Args)]
+#[group(required = true, multiple = false)]
+pub struct DirTarget {
+    /// 目录 ID
+    #[arg(long)]
+    pub dir: Option<i64>,
+
+    /// 目录名称；存在同名目录时用 `名称@路径` 区分
+    #[arg(long)]
+    pub dir_name: Option<String>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 监听目录的 .claude/settings.local.json，检测 Anthropic 配置是否被外部修改
+    Watch {
+        #[command(flatten)]
+        target: DirTarget,
+
+        /// 检测到配置与账号不一致时自动重新应用
+        #[arg(long)]
+        auto_fix: bool,
+
+        /// 防抖间隔（毫秒）
+        #[arg(long, default_value_t = 500)]
+        debounce_ms: u64,
+    },
+
+    /// 核对每个目录的配置是否与关联账号一致（只读）
+    Audit,
+
+    /// 检查单个目录的配置状态，适合在脚本或 shell 提示符中使用
+    ///
+    /// 退出码：0 配置与账号一致；1 已配置但与账号不一致（设置了预期账号时按预期账号核对）；2 未配置（没有关联账号或没有写入配置）；
+    /// 3 目录路径不存在。目录 ID 不存在等其他错误同样以非零状态退出，原因输出到标准错误。
+    Status {
+        #[command(flatten)]
+        target: DirTarget,
+
+        /// 不输出任何内容，只通过退出码表示状态
+        #[arg(long, short)]
+        quiet: bool,
+    },
+
+    /// 以单个 JSON 对象输出目录的完整配置视图（生效的配置文件、env、合并结果、CLAUDE.local.md 状态、关联账号），
+    /// 用于提交问题时附带；Token 默认遮盖
+    DebugDump {
+        #[command(flatten)]
+        target: DirTarget,
+
+        /// 输出未遮盖的 Token
+        #[arg(long)]
+        show_secrets: bool,
+    },
+
+    /// 将账号应用到指定路径的目录，路径尚未登记时先登记（名称取自目录名）
+    Switch {
+        /// 目录路径
+        #[arg(long)]
+        path: PathBuf,
+
+        /// 账号 ID
+        #[arg(long)]
+        account: i64,
+
+        /// 路径未登记时不询问，直接登记
+        #[arg(long, short)]
+        yes: bool,
+    },
+
+    /// 检查数据库、应用数据目录、账号、目录路径和 WebDAV 凭据，硬性检查失败时以非零状态退出
+    Doctor,
+
+    /// 在临时目录中写入、读回并清除一组测试配置，验证本机的读写流程是否正常（不修改真实配置），失败时以非零状态退出
+    SelfTest,
+
+    /// 将旧的 claude_config.json / .claude_config 迁移到 .claude/settings.local.json
+    Migrate {
+        /// 只迁移指定 ID 的目录，默认处理所有目录
+        #[arg(long, conflicts_with = "dir_name")]
+        dir: Option<i64>,
+
+        /// 只迁移指定名称的目录；存在同名目录时用 `名称@路径` 区分
+        #[arg(long)]
+        dir_name: Option<String>,
+    },
+
+    /// 按 JSON Pointer（如 /permissions/allow/0）读取或修改目录配置中的任意键
+    Setting {
+        #[command(flatten)]
+        target: DirTarget,
+
+        /// JSON Pointer
+        pointer: String,
+
+        /// 要写入的值（按 JSON 解析，失败时作为字符串）；省略时只读取
+        value: Option<String>,
+
+        /// 自动创建缺失的中间对象
+        #[arg(long)]
+        create: bool,
+    },
+
+    /// 导出应用自身的设置（超时、界面偏好、WebDAV 配置）到文件，不含账号和目录
+    ExportSettings {
+        /// 备份文件路径
+        path: PathBuf,
+
+        /// 同时导出 WebDAV 密码
+        #[arg(long)]
+        include_secrets: bool,
+    },
+
+    /// 从备份文件恢复应用设置，WebDAV 配置按名称新增或更新
+    ImportSettings {
+        /// 备份文件路径
+        path: PathBuf,
+    },
+}
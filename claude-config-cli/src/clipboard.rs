@@ -0,0 +1,35 @@
+//! 读取系统剪贴板文本：调用各平台自带的剪贴板命令，避免为此引入图形库依赖
+
+use anyhow::{bail, Result};
+use std::process::Command;
+
+/// 按平台依次尝试的剪贴板读取命令
+#[cfg(target_os = "macos")]
+const PASTE_COMMANDS: &[(&str, &[&str])] = &[("pbpaste", &[])];
+
+#[cfg(windows)]
+const PASTE_COMMANDS: &[(&str, &[&str])] = &[("powershell", &["-NoProfile", "-Command", "Get-Clipboard -Raw"])];
+
+#[cfg(all(unix, not(target_os = "macos")))]
+const PASTE_COMMANDS: &[(&str, &[&str])] = &[
+    ("wl-paste", &["--no-newline"]),
+    ("xclip", &["-selection", "clipboard", "-o"]),
+    ("xsel", &["--clipboard", "--output"]),
+];
+
+/// 读取剪贴板中的文本，所有命令都不可用时返回错误
+pub fn read_text() -> Result<String> {
+    for (program, args) in PASTE_COMMANDS {
+        match Command::new(program).args(*args).output() {
+            Ok(output) if output.status.success() => {
+                return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+            }
+            Ok(output) => {
+                tracing::debug!("{} 读取剪贴板失败: {}", program, String::from_utf8_lossy(&output.stderr).trim());
+            }
+            Err(e) => tracing::debug!("无法执行 {}: {}", program, e),
+        }
+    }
+    let programs: Vec<&str> = PASTE_COMMANDS.iter().map(|(program, _)| *program).collect();
+    bail!("无法读取剪贴板（已尝试 {}）", programs.join(", "))
+}
@@ -0,0 +1,45 @@
+use crate::{settings, t, DbState};
+use anyhow::Result;
+use colored::Colorize;
+use std::path::Path;
+
+/// 导出应用设置备份，默认不包含 WebDAV 密码
+pub async fn export(db: &DbState, path: &Path, include_secrets: bool) -> Result<()> {
+    settings::export_app_settings(&*db.lock().await, path, include_secrets).await?;
+    println!(
+        "{}",
+        t!("app_settings.export.success")
+            .replace("{}", &path.display().to_string())
+            .green()
+    );
+    if !include_secrets {
+        println!("{}", t!("app_settings.export.no_secrets").yellow());
+    }
+    Ok(())
+}
+
+/// 从备份文件恢复应用设置
+pub async fn import(db: &DbState, path: &Path) -> Result<()> {
+    let summary = settings::import_app_settings(&*db.lock().await, path).await?;
+    println!(
+        "{}",
+        t!("app_settings.import.success")
+            .replace("{}", &path.display().to_string())
+            .green()
+    );
+    println!(
+        "{}",
+        t!("app_settings.import.webdav")
+            .replacen("{}", &summary.webdav_created.to_string(), 1)
+            .replacen("{}", &summary.webdav_updated.to_string(), 1)
+    );
+    if !summary.missing_passwords.is_empty() {
+        println!(
+            "{}",
+            t!("app_settings.import.missing_passwords")
+                .replace("{}", &summary.missing_passwords.join(", "))
+                .yellow()
+        );
+    }
+    Ok(())
+}
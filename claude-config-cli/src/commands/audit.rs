@@ -0,0 +1,236 @@
+use crate::{
+    claude_config::{self, ClaudeConfigManager},
+    database::Database,
+    models::{Account, Directory, TokenSource},
+    progress::Progress,
+    settings, t, DbState,
+};
+use anyhow::Result;
+use colored::Colorize;
+use comfy_table::{Attribute, Cell, Color};
+
+/// 单个目录的核对结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditStatus {
+    Match,
+    Mismatch,
+    /// 目录设置了预期账号，磁盘配置与预期账号不一致
+    Deviated,
+    NotConfigured,
+    NoAccount,
+}
+
+impl AuditStatus {
+    fn cell(&self) -> Cell {
+        match self {
+            AuditStatus::Match => Cell::new(t!("audit.status.match")).fg(Color::Green),
+            AuditStatus::Mismatch => Cell::new(t!("audit.status.mismatch"))
+                .fg(Color::Red)
+                .add_attribute(Attribute::Bold),
+            AuditStatus::Deviated => Cell::new(t!("audit.status.deviated"))
+                .fg(Color::Red)
+                .add_attribute(Attribute::Bold),
+            AuditStatus::NotConfigured => {
+                Cell::new(t!("audit.status.not_configured")).fg(Color::Yellow)
+            }
+            AuditStatus::NoAccount => Cell::new(t!("audit.status.no_account")).fg(Color::Yellow),
+        }
+    }
+}
+
+/// 目录配置中的 Base URL 与 Token 和关联账号的比对
+pub struct Inspection {
+    pub actual_base_url: Option<String>,
+    /// 实际 Token 的指纹
+    pub actual_fingerprint: Option<String>,
+    pub expected_fingerprint: String,
+    pub base_url_ok: bool,
+    pub token_ok: bool,
+}
+
+impl Inspection {
+    pub fn status(&self) -> AuditStatus {
+        if self.actual_base_url.is_none() && self.actual_fingerprint.is_none() {
+            AuditStatus::NotConfigured
+        } else if self.base_url_ok && self.token_ok {
+            AuditStatus::Match
+        } else {
+            AuditStatus::Mismatch
+        }
+    }
+
+    /// 按预期账号核对时，不一致记为偏离预期
+    pub fn pinned_status(&self, pinned: bool) -> AuditStatus {
+        match self.status() {
+            AuditStatus::Mismatch if pinned => AuditStatus::Deviated,
+            status => status,
+        }
+    }
+}
+
+/// 核对目录时使用的账号及是否为预期账号：设置了预期账号时按预期账号核对，否则按关联账号核对
+pub async fn account_to_check(db: &Database, directory: &Directory) -> Result<(Option<Account>, bool)> {
+    if let Some(expected_id) = directory.expected_account_id {
+        match db.get_account(expected_id).await {
+            Ok(account) => return Ok((Some(account), true)),
+            Err(e) => tracing::warn!("目录 {} 的预期账号 {} 读取失败: {}", directory.name, expected_id, e),
+        }
+    }
+    Ok((db.get_directory_account(directory.id).await?, false))
+}
+
+/// 读取目录的生效配置（settings.json 与本地配置合并后的结果）并与账号比对（只读）
+pub fn inspect(manager: &ClaudeConfigManager, account: &Account) -> Inspection {
+    let env = manager.get_effective_env_config().unwrap_or_default();
+    let actual_base_url = env.get("ANTHROPIC_BASE_URL").cloned();
+    let keys = account.auth_style.env_keys();
+    // 通过命令获取 Token 的账号核对时不执行命令，只确认 Token 已写入
+    let (expected_token, expected_fingerprint) = match account.token_source() {
+        TokenSource::Inline(token) => {
+            let fingerprint = claude_config::token_fingerprint(&token);
+            (Some(token), fingerprint)
+        }
+        TokenSource::Command(_) => (None, t!("audit.token_from_command").to_string()),
+    };
+    let key_ok = |value: Option<&String>| match &expected_token {
+        Some(token) => value == Some(token),
+        None => value.is_some_and(|value| !value.is_empty()),
+    };
+    // 认证方式包含多个变量时，任一变量缺失或不一致都视为不一致
+    let actual_token = keys
+        .iter()
+        .map(|key| env.get(*key))
+        .find(|value| !key_ok(*value))
+        .unwrap_or_else(|| env.get(keys[0]));
+
+    let actual_fingerprint = actual_token.map(|token| claude_config::token_fingerprint(token));
+    Inspection {
+        base_url_ok: actual_base_url.as_deref() == Some(account.base_url.as_str()),
+        token_ok: key_ok(actual_token),
+        actual_base_url,
+        actual_fingerprint,
+        expected_fingerprint,
+    }
+}
+
+/// 逐个目录核对磁盘上的配置是否与数据库关联的账号一致（只读）
+///
+/// `interactive` 为 false（命令行子命令）时不显示进度条，只写日志
+pub async fn run(db: &DbState, interactive: bool) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+
+    if directories.is_empty() {
+        println!("\n{}", t!("directory.list.no_records").yellow());
+        return Ok(());
+    }
+
+    let mut expected = Vec::with_capacity(directories.len());
+    for directory in &directories {
+        expected.push(account_to_check(&db_lock, directory).await?);
+    }
+    drop(db_lock);
+
+    let workspace_root = settings::load_workspace_root().await;
+
+    let mut table = crate::menu::create_table();
+    table.set_header(
+        [
+            t!("audit.header.directory"),
+            t!("audit.header.account"),
+            t!("audit.header.base_url"),
+            t!("audit.header.token"),
+            t!("audit.header.result"),
+        ]
+        .into_iter()
+        .map(|header| {
+            Cell::new(header)
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan)
+        }),
+    );
+
+    let mut mismatch_count = 0;
+    let mut progress = Progress::new(t!("audit.progress"), directories.len(), interactive);
+
+    for (directory, (account, pinned)) in directories.iter().zip(expected) {
+        progress.inc(&directory.name);
+        let path = directory.resolved_path(&workspace_root);
+        let manager = ClaudeConfigManager::new(path.clone());
+        let directory_label = format!("{}\n{}", directory.name, path);
+
+        let Some(account) = account else {
+            table.add_row(vec![
+                Cell::new(directory_label),
+                Cell::new("-"),
+                Cell::new("-"),
+                Cell::new("-"),
+                AuditStatus::NoAccount.cell(),
+            ]);
+            continue;
+        };
+
+        let inspection = inspect(&manager, &account);
+        let status = inspection.pinned_status(pinned);
+        let account_label = if pinned {
+            format!("{} {}", account.name, t!("audit.pinned"))
+        } else {
+            account.name.clone()
+        };
+        if status == AuditStatus::NotConfigured {
+            table.add_row(vec![
+                Cell::new(directory_label),
+                Cell::new(&account_label),
+                Cell::new("-"),
+                Cell::new("-"),
+                AuditStatus::NotConfigured.cell(),
+            ]);
+            continue;
+        }
+
+        let actual_base_url = inspection.actual_base_url.as_deref().unwrap_or("-");
+        let base_url_cell = if inspection.base_url_ok {
+            Cell::new(actual_base_url)
+        } else {
+            Cell::new(format!("{}\n≠ {}", actual_base_url, account.base_url)).fg(Color::Red)
+        };
+        let token_cell = if inspection.token_ok {
+            Cell::new(&inspection.expected_fingerprint)
+        } else {
+            Cell::new(format!(
+                "{}\n≠ {}",
+                inspection.actual_fingerprint.as_deref().unwrap_or("-"),
+                inspection.expected_fingerprint
+            ))
+            .fg(Color::Red)
+        };
+
+        if matches!(status, AuditStatus::Mismatch | AuditStatus::Deviated) {
+            mismatch_count += 1;
+        }
+
+        table.add_row(vec![
+            Cell::new(directory_label),
+            Cell::new(&account_label),
+            base_url_cell,
+            token_cell,
+            status.cell(),
+        ]);
+    }
+    progress.finish();
+
+    println!("\n{}", table);
+    if mismatch_count > 0 {
+        println!(
+            "{}",
+            t!("audit.summary_mismatch")
+                .replace("{}", &mismatch_count.to_string())
+                .red()
+                .bold()
+        );
+    } else {
+        println!("{}", t!("audit.summary_ok").green());
+    }
+
+    Ok(())
+}
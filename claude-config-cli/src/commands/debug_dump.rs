@@ -0,0 +1,162 @@
+use crate::{connectivity::mask_token, settings, t, DbState};
+use anyhow::{Context, Result};
+use claude_config_core::claude_config::{ClaudeConfigManager, ClaudeMdStatus, EnvSource, SECRET_ENV_KEYS};
+use claude_config_core::error::ConfigResult;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// 输出目录的完整配置视图（单个 JSON 对象），用于排查问题；默认遮盖 Token
+pub async fn run(db: &DbState, dir: i64, show_secrets: bool) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directory = db_lock
+        .get_directory(dir)
+        .await
+        .with_context(|| t!("watch.dir_not_found").replace("{}", &dir.to_string()))?;
+    let account = db_lock.get_directory_account(directory.id).await?;
+    drop(db_lock);
+
+    let path = directory.resolved_path(&settings::load_workspace_root().await);
+    let manager = ClaudeConfigManager::new(path.clone()).with_write_format(settings::load_write_format().await);
+    let (effective, env_sources) = effective(&manager);
+
+    let mut dump = json!({
+        "generated_at": chrono::Utc::now(),
+        "app_version": env!("CARGO_PKG_VERSION"),
+        "secrets_masked": !show_secrets,
+        "directory": directory,
+        "resolved_path": path,
+        "exists": Path::new(&path).is_dir(),
+        "account": account,
+        "settings_files": settings_files(&manager),
+        "env": or_error(manager.get_env_config().map(|env| env.into_iter().collect::<BTreeMap<_, _>>())),
+        "effective": effective,
+        "claude_local_md": or_error(manager.claude_local_md_status().map(claude_md_status_name)),
+    });
+
+    if !show_secrets {
+        let mut secret_keys: Vec<&str> = SECRET_ENV_KEYS.to_vec();
+        secret_keys.push("token");
+        if let Some(key) = account.as_ref().and_then(|a| a.extra_auth_env_key.as_deref()) {
+            secret_keys.push(key);
+        }
+        mask_secrets(&mut dump, &secret_keys, false);
+    }
+    // 来源是文件路径，键名与 env 相同，在遮盖之后再加入
+    if let Some(effective) = dump.get_mut("effective").and_then(Value::as_object_mut) {
+        effective.insert("env_sources".to_string(), env_sources);
+    }
+
+    println!("{}", serde_json::to_string_pretty(&dump)?);
+    Ok(())
+}
+
+/// 配置文件的解析结果：生效文件、写入目标、候选文件及被遮蔽的旧文件
+fn settings_files(manager: &ClaudeConfigManager) -> Value {
+    let candidates: Vec<Value> = manager
+        .settings_file_candidates()
+        .into_iter()
+        .map(|path| json!({ "exists": path.exists(), "path": path }))
+        .collect();
+    json!({
+        "active": manager.resolve_active_settings_path(),
+        "write_target": manager.write_target_path(),
+        "candidates": candidates,
+        "shadowed": manager.shadowed_settings_files(),
+        "stale": manager.stale_settings_files(),
+        "claude_dir_outside_directory": manager.claude_dir_outside_directory(),
+    })
+}
+
+/// 分层合并后的配置及每个值的来源，加上考虑进程环境变量后的生效 env；
+/// 生效 env 每个变量的来源单独返回
+fn effective(manager: &ClaudeConfigManager) -> (Value, Value) {
+    let mut result = Map::new();
+    match manager.read_effective_settings() {
+        Ok(effective) => {
+            result.insert("settings".to_string(), effective.settings);
+            result.insert("sources".to_string(), json!(effective.sources));
+        }
+        Err(e) => {
+            result.insert("error".to_string(), Value::String(e.to_string()));
+        }
+    }
+
+    let mut sources = Map::new();
+    if let Ok(entries) = manager.effective_env() {
+        let mut env = Map::new();
+        let mut overridden = Map::new();
+        for entry in entries {
+            let source = match &entry.source {
+                EnvSource::File(path) => json!(path),
+                EnvSource::Process => json!("process"),
+            };
+            sources.insert(entry.key.clone(), source);
+            if let Some(file_value) = entry.masked_value {
+                overridden.insert(entry.key.clone(), Value::String(file_value));
+            }
+            env.insert(entry.key, Value::String(entry.value));
+        }
+        result.insert("env".to_string(), Value::Object(env));
+        result.insert("overridden_by_process".to_string(), Value::Object(overridden));
+    }
+
+    (Value::Object(result), Value::Object(sources))
+}
+
+fn claude_md_status_name(status: ClaudeMdStatus) -> &'static str {
+    match status {
+        ClaudeMdStatus::Identical => "identical",
+        ClaudeMdStatus::Outdated => "outdated",
+        ClaudeMdStatus::Modified => "modified",
+        ClaudeMdStatus::Missing => "missing",
+    }
+}
+
+/// 读取失败时以 `{"error": ...}` 代替，不中断整个输出
+fn or_error<T: Serialize>(result: ConfigResult<T>) -> Value {
+    match result {
+        Ok(value) => json!(value),
+        Err(e) => json!({ "error": e.to_string() }),
+    }
+}
+
+/// 遮盖键名属于 `secret_keys` 的字符串值
+fn mask_secrets(value: &mut Value, secret_keys: &[&str], secret: bool) {
+    match value {
+        Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                mask_secrets(child, secret_keys, secret_keys.contains(&key.as_str()));
+            }
+        }
+        Value::Array(items) => {
+            for item in items {
+                mask_secrets(item, secret_keys, secret);
+            }
+        }
+        Value::String(s) if secret => *s = mask_token(s),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_secrets_only_touches_secret_keys() {
+        let mut dump = json!({
+            "account": { "token": "sk-ant-api03-abcdefgh1234", "base_url": "https://api.anthropic.com" },
+            "env": { "ANTHROPIC_AUTH_TOKEN": "sk-ant-api03-abcdefgh1234", "OPENAI_API_KEY": "sk-openai-abcdefgh1234" },
+        });
+        mask_secrets(&mut dump, &["token", "ANTHROPIC_AUTH_TOKEN", "OPENAI_API_KEY"], false);
+        assert_eq!(
+            dump,
+            json!({
+                "account": { "token": "sk-ant****1234", "base_url": "https://api.anthropic.com" },
+                "env": { "ANTHROPIC_AUTH_TOKEN": "sk-ant****1234", "OPENAI_API_KEY": "sk-ope****1234" },
+            })
+        );
+    }
+}
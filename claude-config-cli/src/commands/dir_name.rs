@@ -0,0 +1,89 @@
+use crate::{cli::DirTarget, models::Directory, settings, t, DbState};
+use anyhow::{bail, Result};
+use std::path::Path;
+
+/// 把子命令的 `--dir` / `--dir-name` 解析为目录 ID
+pub async fn resolve(db: &DbState, target: &DirTarget) -> Result<i64> {
+    match (&target.dir, &target.dir_name) {
+        (Some(id), _) => Ok(*id),
+        (None, Some(name)) => {
+            let directories = db.lock().await.get_directories().await?;
+            find_by_name(&directories, name, &settings::load_workspace_root().await)
+        }
+        (None, None) => unreachable!("clap 保证 --dir 与 --dir-name 必须指定其一"),
+    }
+}
+
+/// 按名称查找目录；重名时可用 `名称@路径` 指定，路径可以是登记的路径或解析后的绝对路径
+pub fn find_by_name(directories: &[Directory], spec: &str, workspace_root: &Path) -> Result<i64> {
+    let by_name: Vec<&Directory> = directories.iter().filter(|d| d.name == spec).collect();
+    match by_name.as_slice() {
+        [directory] => return Ok(directory.id),
+        [] => {}
+        _ => bail!(
+            "{}",
+            t!("dir_name.ambiguous").replacen("{}", spec, 1).replacen(
+                "{}",
+                &by_name
+                    .iter()
+                    .map(|d| format!("{} ({}@{})", d.id, d.name, d.resolved_path(workspace_root)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                1
+            )
+        ),
+    }
+
+    // 名称和路径都可能包含 @，依次尝试每个分隔位置
+    for (index, _) in spec.match_indices('@') {
+        let (name, path) = (&spec[..index], &spec[index + 1..]);
+        let path = path.trim_end_matches(['/', '\\']);
+        if let Some(directory) = directories
+            .iter()
+            .find(|d| d.name == name && (d.path == path || d.resolved_path(workspace_root) == path))
+        {
+            return Ok(directory.id);
+        }
+    }
+
+    bail!("{}", t!("dir_name.not_found").replace("{}", spec))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    fn directory(id: i64, name: &str, path: &str) -> Directory {
+        Directory {
+            id,
+            path: path.to_string(),
+            name: name.to_string(),
+            is_active: false,
+            is_relative: false,
+            is_locked: false,
+            expected_account_id: None,
+            last_switched_at: None,
+            deleted_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_find_by_name_requires_disambiguation_for_duplicates() {
+        let directories = vec![
+            directory(1, "api", "/work/a/api"),
+            directory(2, "api", "/work/b/api"),
+            directory(3, "web@v2", "/work/web"),
+        ];
+        let root = Path::new("/");
+
+        assert!(find_by_name(&directories, "api", root).is_err());
+        assert_eq!(find_by_name(&directories, "api@/work/b/api", root).unwrap(), 2);
+        assert_eq!(find_by_name(&directories, "api@/work/a/api/", root).unwrap(), 1);
+        assert_eq!(find_by_name(&directories, "web@v2", root).unwrap(), 3);
+        assert_eq!(find_by_name(&directories, "web@v2@/work/web", root).unwrap(), 3);
+        assert!(find_by_name(&directories, "missing", root).is_err());
+    }
+}
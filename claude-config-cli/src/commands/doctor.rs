@@ -0,0 +1,236 @@
+use crate::{
+    claude_config, config_manager::ConfigManager, database::Database, models::GetAccountsRequest,
+    offline, output, settings, t, webdav, DbState,
+};
+use anyhow::Result;
+use colored::Colorize;
+use std::path::Path;
+
+/// 检查结果；Fail 为硬性失败，命令行模式下以非零状态退出
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+struct CheckResult {
+    name: String,
+    status: CheckStatus,
+    detail: String,
+    /// 未通过时的修复建议
+    hint: Option<&'static str>,
+}
+
+impl CheckResult {
+    fn pass(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Pass,
+            detail: detail.into(),
+            hint: None,
+        }
+    }
+
+    fn warn(name: impl Into<String>, detail: impl Into<String>, hint: &'static str) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Warn,
+            detail: detail.into(),
+            hint: Some(hint),
+        }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, hint: &'static str) -> Self {
+        Self {
+            name: name.into(),
+            status: CheckStatus::Fail,
+            detail: detail.into(),
+            hint: Some(hint),
+        }
+    }
+
+    fn print(&self) {
+        let line = format!("{}: {}", self.name, self.detail);
+        match self.status {
+            CheckStatus::Pass => println!("{} {}", output::symbol("✓").green(), line),
+            CheckStatus::Warn => println!("{} {}", output::symbol("⚠").yellow(), line.yellow()),
+            CheckStatus::Fail => println!("{} {}", output::symbol("✗").red(), line.red()),
+        }
+        if let Some(hint) = self.hint {
+            println!("    {}", t!("doctor.hint").replace("{}", hint).cyan());
+        }
+    }
+}
+
+async fn check_database(db: &Database) -> CheckResult {
+    let name = t!("doctor.check.database");
+    match sqlx::query_scalar::<_, i64>("SELECT 1").fetch_one(db.get_pool()).await {
+        Ok(_) => CheckResult::pass(name, t!("doctor.ok")),
+        Err(e) => CheckResult::fail(name, e.to_string(), t!("doctor.hint.database")),
+    }
+}
+
+/// 在应用数据目录中写入并删除一个临时文件
+fn check_config_dir() -> CheckResult {
+    let name = t!("doctor.check.config_dir");
+    let dir = ConfigManager::get_app_data_dir();
+    let probe = dir.join(".doctor-write-test");
+    let result = std::fs::create_dir_all(&dir)
+        .and_then(|_| std::fs::write(&probe, b"ok"))
+        .and_then(|_| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => CheckResult::pass(name, dir.display().to_string()),
+        Err(e) => CheckResult::fail(
+            name,
+            format!("{}: {}", dir.display(), e),
+            t!("doctor.hint.config_dir"),
+        ),
+    }
+}
+
+async fn check_accounts(db: &Database) -> CheckResult {
+    let name = t!("doctor.check.accounts");
+    let request = GetAccountsRequest {
+        page: Some(1),
+        per_page: Some(1),
+        search: None,
+        base_url: None,
+    };
+    match db.get_accounts(request).await {
+        Ok(response) if response.pagination.total > 0 => CheckResult::pass(
+            name,
+            t!("doctor.accounts_count").replace("{}", &response.pagination.total.to_string()),
+        ),
+        Ok(_) => CheckResult::fail(name, t!("doctor.no_accounts"), t!("doctor.hint.accounts")),
+        Err(e) => CheckResult::fail(name, e.to_string(), t!("doctor.hint.database")),
+    }
+}
+
+/// 每个目录的路径必须存在且是目录；不存在只告警，存在但不是目录为失败
+async fn check_directories(db: &Database) -> Vec<CheckResult> {
+    let directories = match db.get_directories().await {
+        Ok(directories) => directories,
+        Err(e) => {
+            return vec![CheckResult::fail(
+                t!("doctor.check.directories"),
+                e.to_string(),
+                t!("doctor.hint.database"),
+            )]
+        }
+    };
+    if directories.is_empty() {
+        return vec![CheckResult::warn(
+            t!("doctor.check.directories"),
+            t!("doctor.no_directories"),
+            t!("doctor.hint.add_directory"),
+        )];
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    directories
+        .iter()
+        .map(|directory| {
+            let name = t!("doctor.check.directory").replace("{}", &directory.name);
+            let path = directory.resolved_path(&workspace_root);
+            let path_ref = Path::new(&path);
+            if path_ref.is_dir() {
+                CheckResult::pass(name, path)
+            } else if path_ref.exists() {
+                CheckResult::fail(
+                    name,
+                    t!("doctor.not_a_directory").replace("{}", &path),
+                    t!("doctor.hint.fix_directory"),
+                )
+            } else {
+                CheckResult::warn(
+                    name,
+                    t!("doctor.directory_missing").replace("{}", &path),
+                    t!("doctor.hint.fix_directory"),
+                )
+            }
+        })
+        .collect()
+}
+
+fn check_claude_local_md() -> CheckResult {
+    let name = t!("doctor.check.claude_local_md");
+    if claude_config::claude_local_md_template().trim().is_empty() {
+        CheckResult::fail(name, t!("doctor.claude_local_md_empty"), t!("doctor.hint.reinstall"))
+    } else {
+        CheckResult::pass(name, t!("doctor.claude_local_md_bundled"))
+    }
+}
+
+/// 逐个验证已保存的 WebDAV 凭据；离线模式下跳过
+async fn check_webdav(db: &Database) -> Vec<CheckResult> {
+    let configs = match webdav::get_webdav_configs(db.get_pool()).await {
+        Ok(configs) => configs,
+        Err(e) => {
+            return vec![CheckResult::fail(
+                t!("doctor.check.webdav").replace("{}", "-"),
+                e.to_string(),
+                t!("doctor.hint.database"),
+            )]
+        }
+    };
+
+    let mut results = Vec::new();
+    for config in configs {
+        let name = t!("doctor.check.webdav").replace("{}", &config.name);
+        if offline::is_enabled() {
+            results.push(CheckResult::warn(name, t!("offline.enabled"), t!("doctor.hint.offline")));
+            continue;
+        }
+        let outcome = match webdav::WebDavManager::from_config(config).await {
+            Ok(manager) => manager.test_connection().await,
+            Err(e) => Err(e),
+        };
+        results.push(match outcome {
+            Ok(_) => CheckResult::pass(name, t!("doctor.ok")),
+            Err(e) => CheckResult::fail(name, e.to_string(), t!("doctor.hint.webdav")),
+        });
+    }
+    results
+}
+
+/// 检查整体环境并逐项输出结果，返回是否所有硬性检查都通过
+pub async fn run(db: &DbState) -> Result<bool> {
+    println!("\n{}", t!("doctor.title").cyan().bold());
+
+    let db_lock = db.lock().await;
+    let mut results = vec![check_database(&db_lock).await, check_config_dir()];
+    results.push(check_accounts(&db_lock).await);
+    results.extend(check_directories(&db_lock).await);
+    results.push(check_claude_local_md());
+    results.extend(check_webdav(&db_lock).await);
+    drop(db_lock);
+
+    println!();
+    for result in &results {
+        result.print();
+    }
+
+    let failed = results.iter().filter(|r| r.status == CheckStatus::Fail).count();
+    let warned = results.iter().filter(|r| r.status == CheckStatus::Warn).count();
+    println!();
+    if failed > 0 {
+        println!(
+            "{}",
+            t!("doctor.summary_failed")
+                .replacen("{}", &failed.to_string(), 1)
+                .replacen("{}", &warned.to_string(), 1)
+                .red()
+                .bold()
+        );
+    } else if warned > 0 {
+        println!(
+            "{}",
+            t!("doctor.summary_warned").replace("{}", &warned.to_string()).yellow()
+        );
+    } else {
+        println!("{}", t!("doctor.summary_ok").green());
+    }
+
+    Ok(failed == 0)
+}
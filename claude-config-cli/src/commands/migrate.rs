@@ -0,0 +1,75 @@
+use crate::{claude_config::ClaudeConfigManager, settings, t, DbState};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use tracing::{error, info};
+
+/// 将仍以 claude_config.json / .claude_config 为生效配置的目录迁移到 settings.local.json
+///
+/// `dir` 为 None 时处理所有目录
+pub async fn run(db: &DbState, dir: Option<i64>) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = match dir {
+        Some(id) => vec![db_lock
+            .get_directory(id)
+            .await
+            .with_context(|| t!("watch.dir_not_found").replace("{}", &id.to_string()))?],
+        None => db_lock.get_directories().await?,
+    };
+    drop(db_lock);
+
+    if directories.is_empty() {
+        println!("\n{}", t!("directory.list.no_records").yellow());
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let indent = settings::load_indent().await;
+    let mut migrated = 0;
+    println!();
+
+    for directory in &directories {
+        if crate::menu::directory::refuse_if_locked(directory) {
+            continue;
+        }
+        let manager = ClaudeConfigManager::new(directory.resolved_path(&workspace_root))
+            .with_indent(indent);
+        match manager.migrate_legacy_config() {
+            Ok(report) => {
+                let (Some(file), Some(renamed_to)) = (report.migrated_file, report.renamed_to) else {
+                    continue;
+                };
+                migrated += 1;
+                info!("目录 {} 已迁移旧配置文件 {}", directory.name, file.display());
+                println!(
+                    "{}",
+                    t!("migrate.success")
+                        .replacen("{}", &directory.name, 1)
+                        .replacen("{}", &file.display().to_string(), 1)
+                        .replacen("{}", &renamed_to.display().to_string(), 1)
+                        .green()
+                );
+            }
+            Err(e) => {
+                error!("目录 {} 迁移旧配置文件失败: {}", directory.name, e);
+                println!(
+                    "{}",
+                    t!("migrate.error")
+                        .replacen("{}", &directory.name, 1)
+                        .replacen("{}", &e.to_string(), 1)
+                        .red()
+                );
+            }
+        }
+    }
+
+    if migrated == 0 {
+        println!("{}", t!("migrate.nothing").yellow());
+    } else {
+        println!(
+            "\n{}",
+            t!("migrate.summary").replace("{}", &migrated.to_string()).cyan()
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+pub mod app_settings;
+pub mod audit;
+pub mod debug_dump;
+pub mod dir_name;
+pub mod doctor;
+pub mod migrate;
+pub mod self_test;
+pub mod setting;
+pub mod status;
+pub mod switch;
+pub mod watch;
@@ -0,0 +1,110 @@
+use crate::{claude_config::ClaudeConfigManager, models::AuthStyle, output, t};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 自检写入的测试值，不是真实凭据
+const TEST_TOKEN: &str = "sk-ant-REDACTED";
+const TEST_BASE_URL: &str = "https://self-test.invalid";
+
+/// 在临时目录中写入、读回并清除认证配置，验证核心读写流程；不访问数据库和真实配置。
+/// 返回是否全部通过
+pub fn run() -> bool {
+    println!("{}", t!("self_test.title").cyan().bold());
+
+    let dir = std::env::temp_dir().join(format!("claude-config-self-test-{}", uuid::Uuid::new_v4()));
+    let result = round_trip(&dir);
+    let cleanup = if dir.exists() {
+        std::fs::remove_dir_all(&dir).with_context(|| dir.display().to_string())
+    } else {
+        Ok(())
+    };
+    let cleanup = report(t!("self_test.step.cleanup"), cleanup);
+
+    let passed = result.is_ok() && cleanup.is_ok();
+    if passed {
+        println!("\n{}", t!("self_test.passed").green().bold());
+    } else {
+        println!("\n{}", t!("self_test.failed").red().bold());
+    }
+    passed
+}
+
+/// 依次执行各步骤，遇到失败立即停止
+fn round_trip(dir: &Path) -> Result<()> {
+    report(
+        t!("self_test.step.create_dir"),
+        std::fs::create_dir_all(dir).with_context(|| dir.display().to_string()),
+    )?;
+
+    let manager = ClaudeConfigManager::new(dir.display().to_string());
+    let write = manager
+        .update_env_config_with_options(TEST_TOKEN.to_string(), TEST_BASE_URL.to_string(), AuthStyle::Both, true)
+        .map(|_| ())
+        .map_err(Into::into);
+    report(t!("self_test.step.write"), write)?;
+
+    let read = manager.get_env_config().map_err(Into::into).and_then(|env| {
+        let expected = [
+            ("ANTHROPIC_API_KEY", TEST_TOKEN),
+            ("ANTHROPIC_AUTH_TOKEN", TEST_TOKEN),
+            ("ANTHROPIC_BASE_URL", TEST_BASE_URL),
+            ("IS_SANDBOX", "1"),
+        ];
+        check_values(&env, &expected)
+    });
+    report(t!("self_test.step.read"), read)?;
+
+    let clear = manager
+        .clear_env_config()
+        .map_err(Into::into)
+        .and_then(|_| manager.get_env_config().map_err(Into::into))
+        .and_then(|env| {
+            let left: Vec<&str> = ["ANTHROPIC_API_KEY", "ANTHROPIC_AUTH_TOKEN", "ANTHROPIC_BASE_URL"]
+                .into_iter()
+                .filter(|key| env.contains_key(*key))
+                .collect();
+            if left.is_empty() {
+                Ok(())
+            } else {
+                anyhow::bail!(t!("self_test.not_cleared").replace("{}", &left.join(", ")))
+            }
+        });
+    report(t!("self_test.step.clear"), clear)
+}
+
+/// 比较读回的变量与写入的值，列出所有不一致的键
+fn check_values(env: &HashMap<String, String>, expected: &[(&str, &str)]) -> Result<()> {
+    let mismatched: Vec<&str> = expected
+        .iter()
+        .filter(|(key, value)| env.get(*key).map(String::as_str) != Some(*value))
+        .map(|(key, _)| *key)
+        .collect();
+    if mismatched.is_empty() {
+        Ok(())
+    } else {
+        anyhow::bail!(t!("self_test.mismatch").replace("{}", &mismatched.join(", ")))
+    }
+}
+
+/// 输出一个步骤的结果，并原样返回便于用 ? 中止后续步骤
+fn report(name: &str, result: Result<()>) -> Result<()> {
+    match &result {
+        Ok(()) => println!("{} {}", output::symbol("✓").green(), name),
+        Err(e) => println!("{} {}: {}", output::symbol("✗").red(), name, format!("{:#}", e).red()),
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_self_test_round_trip() {
+        let dir = std::env::temp_dir().join(format!("claude-config-self-test-{}", uuid::Uuid::new_v4()));
+        assert!(round_trip(&dir).is_ok());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
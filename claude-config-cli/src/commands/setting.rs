@@ -0,0 +1,47 @@
+use crate::{change_log::ChangeRecorder, claude_config::ClaudeConfigManager, settings, t, DbState};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde_json::Value;
+use tracing::info;
+
+/// 按 JSON Pointer 读取或写入目录配置中的任意键
+///
+/// `value` 为 None 时只读取；写入的值优先按 JSON 解析，解析失败时作为字符串写入
+pub async fn run(db: &DbState, dir: i64, pointer: &str, value: Option<String>, create: bool) -> Result<()> {
+    let directory = db
+        .lock()
+        .await
+        .get_directory(dir)
+        .await
+        .with_context(|| t!("watch.dir_not_found").replace("{}", &dir.to_string()))?;
+    let recorder = ChangeRecorder::new(directory.id);
+    let manager = ClaudeConfigManager::new(directory.resolved_path(&settings::load_workspace_root().await))
+        .with_change_hook(recorder.hook())
+        .with_write_format(settings::load_write_format().await)
+        .with_indent(settings::load_indent().await);
+
+    let Some(raw) = value else {
+        match manager.get_setting(pointer)? {
+            Some(value) => println!("{}", serde_json::to_string_pretty(&value)?),
+            None => println!("{}", t!("setting.not_found").replace("{}", pointer).yellow()),
+        }
+        return Ok(());
+    };
+
+    if directory.is_locked {
+        anyhow::bail!(t!("directory.lock.refused").replace("{}", &directory.name));
+    }
+
+    let value = serde_json::from_str::<Value>(&raw).unwrap_or(Value::String(raw));
+    manager.set_setting_with_options(pointer, value, create)?;
+    recorder.flush(&*db.lock().await).await;
+    info!("已更新目录 {} 的配置 {}", directory.name, pointer);
+    println!(
+        "{}",
+        t!("setting.updated")
+            .replacen("{}", pointer, 1)
+            .replacen("{}", &directory.name, 1)
+            .green()
+    );
+    Ok(())
+}
@@ -0,0 +1,132 @@
+use super::audit::{self, AuditStatus};
+use crate::{claude_config::ClaudeConfigManager, settings, t, DbState};
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+/// 配置存在且与账号一致
+pub const EXIT_OK: i32 = 0;
+/// 配置存在但与账号不一致（包括偏离预期账号）
+pub const EXIT_DRIFTED: i32 = 1;
+/// 目录未配置（没有关联账号或没有写入 Anthropic 配置）
+pub const EXIT_UNCONFIGURED: i32 = 2;
+/// 目录路径不存在
+pub const EXIT_MISSING: i32 = 3;
+
+/// 检查单个目录的配置状态，返回退出码；`quiet` 时不输出任何内容
+pub async fn run(db: &DbState, dir: i64, quiet: bool) -> Result<i32> {
+    let db_lock = db.lock().await;
+    let directory = db_lock
+        .get_directory(dir)
+        .await
+        .with_context(|| t!("watch.dir_not_found").replace("{}", &dir.to_string()))?;
+    let (account, pinned) = audit::account_to_check(&db_lock, &directory).await?;
+    drop(db_lock);
+
+    let path = directory.resolved_path(&settings::load_workspace_root().await);
+    let (code, label) = if !std::path::Path::new(&path).is_dir() {
+        (EXIT_MISSING, t!("status.missing").red())
+    } else {
+        match account.map(|account| {
+            audit::inspect(&ClaudeConfigManager::new(path.clone()), &account).pinned_status(pinned)
+        }) {
+            Some(AuditStatus::Match) => (EXIT_OK, t!("audit.status.match").green()),
+            Some(AuditStatus::Mismatch) => (EXIT_DRIFTED, t!("audit.status.mismatch").red()),
+            Some(AuditStatus::Deviated) => (EXIT_DRIFTED, t!("audit.status.deviated").red()),
+            Some(AuditStatus::NotConfigured) => (EXIT_UNCONFIGURED, t!("audit.status.not_configured").yellow()),
+            Some(AuditStatus::NoAccount) | None => (EXIT_UNCONFIGURED, t!("audit.status.no_account").yellow()),
+        }
+    };
+
+    if !quiet {
+        println!("{} {} ({})", label, directory.name, path);
+    }
+    Ok(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::Database;
+    use crate::models::*;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+
+    #[tokio::test]
+    async fn test_status_still_resolves_after_rename() {
+        let dir = std::env::temp_dir().join(format!("claude-config-status-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.display().to_string();
+
+        let database = Database::new_in_memory().await.unwrap();
+        let account = database
+            .create_account(CreateAccountRequest {
+                name: "before".to_string(),
+                token: "sk-ant-status-test".to_string(),
+                base_url: "https://api.anthropic.com".to_string(),
+                model: String::new(),
+                auth_style: AuthStyle::default(),
+                proxy: ProxyConfig::default(),
+                note: None,
+                extra_auth_env_key: None,
+                staging_base_url: None,
+                token_command: None,
+            })
+            .await
+            .unwrap();
+        let directory = database
+            .create_directory(CreateDirectoryRequest {
+                path: path.clone(),
+                name: "status-test".to_string(),
+                is_relative: false,
+            })
+            .await
+            .unwrap();
+        database
+            .switch_account(SwitchAccountRequest {
+                account_id: account.id,
+                directory_id: directory.id,
+            })
+            .await
+            .unwrap();
+        ClaudeConfigManager::new(path)
+            .update_env_config_full(
+                account.token.clone(),
+                account.base_url.clone(),
+                account.auth_style,
+                true,
+                &ProxyConfig::default(),
+            )
+            .unwrap();
+
+        let db: DbState = Arc::new(Mutex::new(database));
+        assert_eq!(run(&db, directory.id, true).await.unwrap(), EXIT_OK);
+
+        db.lock().await.rename_account(account.id, "after").await.unwrap();
+        assert_eq!(run(&db, directory.id, true).await.unwrap(), EXIT_OK);
+
+        // 预期账号与磁盘配置不一致时视为偏离
+        let pinned = db
+            .lock()
+            .await
+            .create_account(CreateAccountRequest {
+                name: "pinned".to_string(),
+                token: "sk-ant-pinned-test".to_string(),
+                base_url: "https://api.anthropic.com".to_string(),
+                model: String::new(),
+                auth_style: AuthStyle::default(),
+                proxy: ProxyConfig::default(),
+                note: None,
+                extra_auth_env_key: None,
+                staging_base_url: None,
+                token_command: None,
+            })
+            .await
+            .unwrap();
+        db.lock().await.set_directory_expected_account(directory.id, Some(pinned.id)).await.unwrap();
+        assert_eq!(run(&db, directory.id, true).await.unwrap(), EXIT_DRIFTED);
+        db.lock().await.set_directory_expected_account(directory.id, Some(account.id)).await.unwrap();
+        assert_eq!(run(&db, directory.id, true).await.unwrap(), EXIT_OK);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{
+    menu,
+    models::{CreateDirectoryRequest, Directory},
+    settings, t, DbState,
+};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use dialoguer::Confirm;
+use std::path::{Path, PathBuf};
+use tracing::info;
+
+/// 将账号应用到指定路径的目录；路径尚未登记时先登记（`yes` 时不询问），名称取自目录名
+pub async fn run(db: &DbState, path: &Path, account_id: i64, yes: bool) -> Result<()> {
+    let account = db
+        .lock()
+        .await
+        .get_account(account_id)
+        .await
+        .with_context(|| t!("switch.cli.account_not_found").replace("{}", &account_id.to_string()))?;
+
+    let Some(directory) = find_or_register_directory(db, path, yes).await? else {
+        anyhow::bail!(t!("common.cancel"));
+    };
+
+    menu::switch::apply_with_defaults(db, &account, &directory).await
+}
+
+/// 按规范化后的路径查找已登记的目录；未登记时询问后登记（`yes` 时不询问），取消时返回 None
+pub async fn find_or_register_directory(db: &DbState, path: &Path, yes: bool) -> Result<Option<Directory>> {
+    let target = absolute_path(path)?;
+    let workspace_root = settings::load_workspace_root().await;
+    let directories = db.lock().await.get_directories().await?;
+    if let Some(directory) = directories
+        .into_iter()
+        .find(|d| absolute_path(Path::new(&d.resolved_path(&workspace_root))).is_ok_and(|p| p == target))
+    {
+        return Ok(Some(directory));
+    }
+
+    let path = target.display().to_string();
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.clone());
+
+    if !yes
+        && !Confirm::new()
+            .with_prompt(t!("switch.cli.prompt_register").replacen("{}", &path, 1).replacen("{}", &name, 1))
+            .default(true)
+            .interact()?
+    {
+        return Ok(None);
+    }
+
+    // 与菜单中添加目录相同的路径检查
+    let Some((stored_path, is_relative)) = menu::directory::prepare_directory_path(&path, &workspace_root, yes)?
+    else {
+        return Ok(None);
+    };
+    let directory = db
+        .lock()
+        .await
+        .create_directory(CreateDirectoryRequest {
+            path: stored_path,
+            name: name.clone(),
+            is_relative,
+        })
+        .await
+        .map_err(|e| anyhow::anyhow!(t!("directory.add.error").replace("{}", &e.to_string())))?;
+    info!("切换时登记新目录: {} ({})", directory.name, path);
+    println!("{}", t!("directory.add.success").replace("{}", &name).green());
+    Ok(Some(directory))
+}
+
+/// 转为绝对路径；路径存在时解析符号链接，便于与已登记的目录比较
+fn absolute_path(path: &Path) -> Result<PathBuf> {
+    if let Ok(path) = path.canonicalize() {
+        return Ok(path);
+    }
+    Ok(std::env::current_dir()?.join(path))
+}
@@ -0,0 +1,187 @@
+use crate::{
+    change_log::ChangeRecorder, claude_config::ClaudeConfigManager, models::Account, settings, t,
+    DbState,
+};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use notify::{RecursiveMode, Watcher};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::{error, info, warn};
+
+/// 检查目录配置中的 Anthropic 相关变量是否与账号一致，返回不一致的键
+fn find_mismatched_keys(manager: &ClaudeConfigManager, account: &Account) -> Result<Vec<String>> {
+    let env = manager.get_env_config()?;
+    let mut mismatched = Vec::new();
+    // 空值不写入时，变量缺失即与空值一致
+    let matches = |key: &str, expected: &str| match env.get(key) {
+        Some(value) => value == expected,
+        None => manager.omits_empty_env() && expected.is_empty(),
+    };
+
+    if !matches("ANTHROPIC_BASE_URL", &account.base_url) {
+        mismatched.push("ANTHROPIC_BASE_URL".to_string());
+    }
+    for key in account.auth_style.env_keys() {
+        if !matches(key, &account.token) {
+            mismatched.push(key.to_string());
+        }
+    }
+
+    Ok(mismatched)
+}
+
+/// 事件是否涉及写入目标或当前生效的配置文件；生效文件会随候选文件的增删而变化，每次重新解析
+fn is_settings_event(manager: &ClaudeConfigManager, event: &notify::Event) -> bool {
+    let targets = [manager.write_target_path(), manager.resolve_active_settings_path()];
+    event
+        .paths
+        .iter()
+        .any(|path| targets.iter().any(|target| path.file_name() == target.file_name()))
+}
+
+/// 监听目录的配置文件（写入目标及当前生效的候选文件），在 Anthropic 配置被外部修改时告警或自动修复
+pub async fn run(db: &DbState, directory_id: i64, auto_fix: bool, debounce_ms: u64) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directory = db_lock
+        .get_directory(directory_id)
+        .await
+        .with_context(|| t!("watch.dir_not_found").replace("{}", &directory_id.to_string()))?;
+    let account = db_lock
+        .get_directory_account(directory_id)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!(t!("watch.no_account").replace("{}", &directory.name)))?;
+    drop(db_lock);
+    // 通过命令获取 Token 的账号在开始监听时执行一次命令，Token 只保存在内存中
+    let account = crate::token_source::resolved_account(&account).await?;
+
+    // 锁定的目录不允许自动修复改写配置，降级为只告警
+    let auto_fix = if auto_fix && directory.is_locked {
+        println!("{}", t!("watch.locked_no_fix").replace("{}", &directory.name).yellow());
+        false
+    } else {
+        auto_fix
+    };
+
+    let directory_path = directory.resolved_path(&settings::load_workspace_root().await);
+    // 自动修复写入的配置记入目录变更记录
+    let recorder = ChangeRecorder::new(directory_id);
+    let manager = ClaudeConfigManager::new(directory_path.clone())
+        .with_change_hook(recorder.hook())
+        .with_claude_local_md(account.claude_md_template.clone())
+        .with_extra_auth_env_key(account.extra_auth_env_key.clone())
+        .with_write_format(settings::load_write_format().await)
+        .with_indent(settings::load_indent().await)
+        .with_omit_empty_env(settings::load_omit_empty_env().await);
+    let claude_dir = manager.claude_dir_path();
+    let settings_file = manager.resolve_active_settings_path();
+    std::fs::create_dir_all(&claude_dir)?;
+
+    // notify 回调运行在独立线程中，通过通道转发事件
+    let (tx, mut rx) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+        let _ = tx.send(res);
+    })?;
+    // 监听 .claude 目录而不是文件本身，以兼容"写临时文件再重命名"的编辑器
+    watcher.watch(&claude_dir, RecursiveMode::NonRecursive)?;
+    // .claude_config、CLAUDE.md 等候选文件位于目录根部
+    watcher.watch(std::path::Path::new(&directory_path), RecursiveMode::NonRecursive)?;
+
+    println!(
+        "{}",
+        t!("watch.start").replace("{}", &settings_file.display().to_string()).cyan()
+    );
+    println!("{}", t!("watch.expected_account").replace("{}", &account.name));
+    println!(
+        "{}",
+        if auto_fix {
+            t!("watch.mode_auto_fix")
+        } else {
+            t!("watch.mode_warn")
+        }
+    );
+    info!(
+        "开始监听目录配置: {}，账号: {}，自动修复: {}",
+        settings_file.display(),
+        account.name,
+        auto_fix
+    );
+
+    let debounce = Duration::from_millis(debounce_ms);
+
+    loop {
+        tokio::select! {
+            _ = tokio::signal::ctrl_c() => {
+                println!("\n{}", t!("watch.stopped").yellow());
+                info!("停止监听目录配置: {}", settings_file.display());
+                break;
+            }
+            event = rx.recv() => {
+                let Some(event) = event else { break };
+                let mut relevant = match event {
+                    Ok(event) => is_settings_event(&manager, &event),
+                    Err(e) => {
+                        warn!("文件监听错误: {}", e);
+                        false
+                    }
+                };
+
+                // 防抖：在静默期内持续合并后续事件
+                while let Ok(Some(next)) = tokio::time::timeout(debounce, rx.recv()).await {
+                    if let Ok(next) = next {
+                        relevant |= is_settings_event(&manager, &next);
+                    }
+                }
+
+                if !relevant {
+                    continue;
+                }
+
+                let mismatched = match find_mismatched_keys(&manager, &account) {
+                    Ok(keys) => keys,
+                    Err(e) => {
+                        warn!("读取配置失败: {}", e);
+                        println!("{}", t!("watch.read_error").replace("{}", &e.to_string()).red());
+                        continue;
+                    }
+                };
+
+                if mismatched.is_empty() {
+                    info!("配置已变更，Anthropic 配置仍与账号 {} 一致", account.name);
+                    continue;
+                }
+
+                warn!(
+                    "检测到目录 {} 的配置被外部修改，不一致的键: {}",
+                    directory_path,
+                    mismatched.join(", ")
+                );
+                println!(
+                    "{}",
+                    t!("watch.drift").replace("{}", &mismatched.join(", ")).yellow()
+                );
+
+                if auto_fix {
+                    match manager.update_env_config_with_options(
+                        account.token.clone(),
+                        account.base_url.clone(),
+                        account.auth_style,
+                        true,
+                    ) {
+                        Ok(_) => {
+                            recorder.flush(&*db.lock().await).await;
+                            info!("已重新应用账号 {} 的配置", account.name);
+                            println!("{}", t!("watch.fixed").replace("{}", &account.name).green());
+                        }
+                        Err(e) => {
+                            error!("重新应用配置失败: {}", e);
+                            println!("{}", t!("watch.fix_error").replace("{}", &e.to_string()).red());
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
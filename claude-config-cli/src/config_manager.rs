@@ -72,8 +72,8 @@ impl ConfigManager {
 
         // 尝试从resources目录加载config.json
         if let Some(resource_config_path) = Self::get_resource_path("config.json") {
-            if let Ok(_) = manager.load_from_file(&resource_config_path) {
-                println!(
+            if manager.load_from_file(&resource_config_path).is_ok() {
+                tracing::debug!(
                     "从resources目录加载配置文件: {}",
                     resource_config_path.display()
                 );
@@ -86,7 +86,7 @@ impl ConfigManager {
             let config_path = current_dir.join("config.json");
             if config_path.exists() {
                 let _ = manager.load_from_file(&config_path);
-                println!("从当前目录加载配置文件: {}", config_path.display());
+                tracing::debug!("从当前目录加载配置文件: {}", config_path.display());
             }
         }
 
@@ -123,55 +123,42 @@ impl ConfigManager {
 
         for path in possible_paths {
             if path.exists() {
-                println!("找到资源文件: {}", path.display());
+                tracing::debug!("找到资源文件: {}", path.display());
                 return Some(path);
             }
         }
 
-        println!("未找到资源文件: {}", filename);
+        tracing::debug!("未找到资源文件: {}", filename);
         None
     }
 
-    /// 获取应用数据目录（用于存储用户数据，如数据库文件）
-    /// Windows: %APPDATA%\claude-config-manager
-    /// Linux/Mac: ~/.claude-config-manager
-    #[allow(dead_code)]
-    pub fn get_app_data_dir() -> Option<PathBuf> {
-        // 跨平台的应用数据目录获取
-        #[cfg(target_os = "windows")]
-        {
-            // Windows: 使用 APPDATA 目录，不加点前缀
-            if let Ok(appdata) = std::env::var("APPDATA") {
-                let app_data_dir = PathBuf::from(appdata).join("claude-config-manager");
-                println!("Windows应用数据目录: {}", app_data_dir.display());
-                return Some(app_data_dir);
+    /// 获取应用数据目录（数据库、日志、应用设置都存放在这里）
+    ///
+    /// 优先使用环境变量 CLAUDE_CONFIG_HOME，否则按平台约定：
+    /// Linux: $XDG_CONFIG_HOME/claude-config-manager（默认 ~/.config/claude-config-manager）
+    /// macOS: ~/Library/Application Support/claude-config-manager
+    /// Windows: %APPDATA%\claude-config-manager\config
+    pub fn get_app_data_dir() -> PathBuf {
+        if let Ok(custom) = std::env::var("CLAUDE_CONFIG_HOME") {
+            if !custom.trim().is_empty() {
+                return PathBuf::from(custom);
             }
         }
 
-        #[cfg(not(target_os = "windows"))]
-        {
-            // Linux/macOS: 使用 HOME 目录，加点前缀
-            if let Ok(home) = std::env::var("HOME") {
-                let app_data_dir = PathBuf::from(home).join(".claude-config-manager");
-                println!("Unix应用数据目录: {}", app_data_dir.display());
-                return Some(app_data_dir);
-            }
+        if let Some(project_dirs) = directories::ProjectDirs::from("", "", "claude-config-manager") {
+            return project_dirs.config_dir().to_path_buf();
         }
 
-        // 回退方案：使用 USERPROFILE (Windows) 或 HOME
-        if let Ok(user_dir) = std::env::var("USERPROFILE").or_else(|_| std::env::var("HOME")) {
-            #[cfg(target_os = "windows")]
-            let app_data_dir = PathBuf::from(user_dir).join("claude-config-manager");
-
-            #[cfg(not(target_os = "windows"))]
-            let app_data_dir = PathBuf::from(user_dir).join(".claude-config-manager");
-
-            println!("回退应用数据目录: {}", app_data_dir.display());
-            return Some(app_data_dir);
-        }
+        // 回退方案：无法确定平台目录时使用旧的 ~/.claude-config-manager
+        Self::get_legacy_app_data_dir().unwrap_or_else(|| PathBuf::from("."))
+    }
 
-        println!("无法确定应用数据目录");
-        None
+    /// 旧版本回退策略使用的应用数据目录 ~/.claude-config-manager
+    pub fn get_legacy_app_data_dir() -> Option<PathBuf> {
+        std::env::var("HOME")
+            .or_else(|_| std::env::var("USERPROFILE"))
+            .ok()
+            .map(|home| PathBuf::from(home).join(".claude-config-manager"))
     }
 
     /// 获取resources目录的路径（用于存储数据库等数据文件）
@@ -181,8 +168,8 @@ impl ConfigManager {
             if let Some(exe_dir) = exe_path.parent() {
                 let resources_dir = exe_dir.join("resources");
 
-                println!("resources目录路径: {}", resources_dir.display());
-                println!("resources目录是否存在: {}", resources_dir.exists());
+                tracing::debug!("resources目录路径: {}", resources_dir.display());
+                tracing::debug!("resources目录是否存在: {}", resources_dir.exists());
 
                 // 返回 resources 目录路径（无论是否存在）
                 // 调用者会负责创建目录
@@ -190,7 +177,7 @@ impl ConfigManager {
             }
         }
 
-        println!("无法确定可执行文件路径");
+        tracing::debug!("无法确定可执行文件路径");
         None
     }
 
@@ -0,0 +1,381 @@
+use crate::{
+    models::{Account, AuthStyle, ProxyConfig, TokenSource},
+    progress::Progress,
+    t,
+};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// 批量测试时同时进行的请求数上限，避免压垮共享代理
+pub const MAX_CONCURRENCY: usize = 8;
+
+/// Anthropic API 版本请求头
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// 429 响应按 Retry-After 等待的上限，避免一次健康检查被拖得过久
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+/// 429 响应没有可用的 Retry-After 时的等待时间
+const DEFAULT_RETRY_AFTER: Duration = Duration::from_secs(2);
+
+/// HTTP 429 Too Many Requests
+const STATUS_TOO_MANY_REQUESTS: u16 = 429;
+
+/// 单个账号的连通性测试结果
+#[derive(Debug, Clone)]
+pub struct ConnectivityResult {
+    pub account_id: i64,
+    pub name: String,
+    pub base_url: String,
+    pub masked_token: String,
+    pub status: Option<u16>,
+    pub latency: Duration,
+    pub error: Option<String>,
+    /// 测试过程中是否收到过 429（重试后成功也记为 true）
+    pub throttled: bool,
+}
+
+impl ConnectivityResult {
+    pub fn is_success(&self) -> bool {
+        matches!(self.status, Some(code) if (200..300).contains(&code))
+    }
+
+    /// 重试后仍然返回 429：账号可能是好的，只是被限流
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == Some(STATUS_TOO_MANY_REQUESTS)
+    }
+}
+
+/// 解析 Retry-After 响应头：秒数或 HTTP 日期，结果不超过 MAX_RETRY_AFTER
+fn parse_retry_after(value: &str, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let value = value.trim();
+    let wait = match value.parse::<u64>() {
+        Ok(secs) => Duration::from_secs(secs),
+        Err(_) => {
+            let at = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+            (at.with_timezone(&chrono::Utc) - now).to_std().unwrap_or(Duration::ZERO)
+        }
+    };
+    Some(wait.min(MAX_RETRY_AFTER))
+}
+
+/// 遮盖 Token，只保留前 6 位和后 4 位
+pub fn mask_token(token: &str) -> String {
+    let chars: Vec<char> = token.chars().collect();
+    if chars.len() <= 10 {
+        return "****".to_string();
+    }
+    let head: String = chars[..6].iter().collect();
+    let tail: String = chars[chars.len() - 4..].iter().collect();
+    format!("{}****{}", head, tail)
+}
+
+/// 按账号代理配置构建 HTTP 客户端
+fn build_proxied_client(proxy: &ProxyConfig) -> reqwest::Result<reqwest::Client> {
+    let no_proxy = reqwest::NoProxy::from_string(proxy.no_proxy.trim());
+    let mut builder = reqwest::Client::builder();
+    if !proxy.http_proxy.trim().is_empty() {
+        let http = reqwest::Proxy::http(proxy.http_proxy.trim())?;
+        builder = builder.proxy(http.no_proxy(no_proxy.clone()));
+    }
+    if !proxy.https_proxy.trim().is_empty() {
+        let https = reqwest::Proxy::https(proxy.https_proxy.trim())?;
+        builder = builder.proxy(https.no_proxy(no_proxy));
+    }
+    builder.build()
+}
+
+/// 请求账号 Base URL 下的 /v1/models，按账号认证方式携带 Token
+pub async fn test_account(
+    client: &reqwest::Client,
+    account: &Account,
+    timeout: Duration,
+) -> ConnectivityResult {
+    let url = format!("{}/v1/models", account.base_url.trim_end_matches('/'));
+
+    if let Err(e) = crate::offline::ensure_online() {
+        return ConnectivityResult {
+            account_id: account.id,
+            name: account.name.clone(),
+            base_url: account.base_url.clone(),
+            masked_token: mask_token(account.token.trim()),
+            status: None,
+            latency: Duration::ZERO,
+            error: Some(e.to_string()),
+            throttled: false,
+        };
+    }
+
+    // 通过命令获取 Token 的账号在测试时执行命令
+    let token = match crate::token_source::resolve(account).await {
+        Ok(token) => token,
+        Err(e) => {
+            return ConnectivityResult {
+                account_id: account.id,
+                name: account.name.clone(),
+                base_url: account.base_url.clone(),
+                masked_token: mask_token(""),
+                status: None,
+                latency: Duration::ZERO,
+                error: Some(e.to_string()),
+                throttled: false,
+            };
+        }
+    };
+    let token = token.trim();
+
+    // 账号配置了代理时，使用带代理的独立客户端
+    let proxy = account.proxy_config();
+    let proxied_client;
+    let client = if proxy.is_empty() {
+        client
+    } else {
+        match build_proxied_client(&proxy) {
+            Ok(built) => {
+                proxied_client = built;
+                &proxied_client
+            }
+            Err(e) => {
+                return ConnectivityResult {
+                    account_id: account.id,
+                    name: account.name.clone(),
+                    base_url: account.base_url.clone(),
+                    masked_token: mask_token(token),
+                    status: None,
+                    latency: Duration::ZERO,
+                    error: Some(e.to_string()),
+                    throttled: false,
+                };
+            }
+        }
+    };
+
+    let mut request = client
+        .get(&url)
+        .header("anthropic-version", ANTHROPIC_VERSION);
+    if matches!(account.auth_style, AuthStyle::ApiKey | AuthStyle::Both) {
+        request = request.header("x-api-key", token);
+    }
+    if matches!(account.auth_style, AuthStyle::BearerToken | AuthStyle::Both) {
+        request = request.bearer_auth(token);
+    }
+
+    let mut throttled = false;
+    loop {
+        // GET 请求总能克隆，这里只是兜底
+        let Some(attempt) = request.try_clone() else {
+            return send_once(account, token, request, timeout, throttled).await.result;
+        };
+        let Attempt { result, retry_after } = send_once(account, token, attempt, timeout, throttled).await;
+        // 429 只按 Retry-After 等待后重试一次，仍被限流则如实返回
+        match retry_after {
+            Some(wait) if !throttled => {
+                throttled = true;
+                tracing::warn!("账号 {} 被限流，{} 秒后重试", account.name, wait.as_secs());
+                tokio::time::sleep(wait).await;
+            }
+            _ => return result,
+        }
+    }
+}
+
+/// 单次请求的结果，`retry_after` 仅在收到 429 时有值
+struct Attempt {
+    result: ConnectivityResult,
+    retry_after: Option<Duration>,
+}
+
+async fn send_once(
+    account: &Account,
+    token: &str,
+    request: reqwest::RequestBuilder,
+    timeout: Duration,
+    throttled: bool,
+) -> Attempt {
+    let started = Instant::now();
+    // 每个账号单独计时，单个请求卡住不会拖住整批测试
+    let outcome = tokio::time::timeout(timeout, request.send()).await;
+    let latency = started.elapsed();
+
+    let mut retry_after = None;
+    let (status, error) = match outcome {
+        Ok(Ok(response)) => {
+            let status = response.status().as_u16();
+            if status == STATUS_TOO_MANY_REQUESTS {
+                let wait = response
+                    .headers()
+                    .get(reqwest::header::RETRY_AFTER)
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| parse_retry_after(v, chrono::Utc::now()));
+                retry_after = Some(wait.unwrap_or(DEFAULT_RETRY_AFTER));
+            }
+            (Some(status), None)
+        }
+        Ok(Err(e)) => (None, Some(e.to_string())),
+        Err(_) => {
+            let message = t!("account.test_all.timeout").replace("{}", &timeout.as_secs().to_string());
+            (None, Some(message))
+        }
+    };
+
+    Attempt {
+        result: ConnectivityResult {
+            account_id: account.id,
+            name: account.name.clone(),
+            base_url: account.base_url.clone(),
+            masked_token: mask_token(token),
+            status,
+            latency,
+            error,
+            throttled: throttled || retry_after.is_some(),
+        },
+        retry_after,
+    }
+}
+
+/// 并发测试所有账号（最多 MAX_CONCURRENCY 个同时进行），失败的排在前面
+///
+/// 每出现一个被限流的账号，就收回一个并发名额（至少保留 1 个），避免继续冲击共享代理
+pub async fn test_accounts(
+    accounts: Vec<Account>,
+    timeout: Duration,
+    progress: &mut Progress,
+) -> Vec<ConnectivityResult> {
+    let client = reqwest::Client::new();
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENCY));
+    let concurrency = Arc::new(AtomicUsize::new(MAX_CONCURRENCY));
+    let mut tasks = JoinSet::new();
+    let mut results = Vec::new();
+
+    // 获取 Token 的命令会继承终端的 stdin（密码管理器可能提示解锁），
+    // 在并发测试开始前逐个执行，避免多个命令同时争抢终端
+    // （离线时不执行，由 test_account 直接返回离线错误）
+    let offline = crate::offline::ensure_online().is_err();
+    let mut resolved = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        if offline || !matches!(account.token_source(), TokenSource::Command(_)) {
+            resolved.push(account);
+            continue;
+        }
+        match crate::token_source::resolve(&account).await {
+            Ok(token) => resolved.push(Account {
+                token,
+                token_command: None,
+                ..account
+            }),
+            Err(e) => {
+                progress.inc(&account.name);
+                results.push(ConnectivityResult {
+                    account_id: account.id,
+                    name: account.name.clone(),
+                    base_url: account.base_url.clone(),
+                    masked_token: mask_token(""),
+                    status: None,
+                    latency: Duration::ZERO,
+                    error: Some(e.to_string()),
+                    throttled: false,
+                });
+            }
+        }
+    }
+
+    for account in resolved {
+        let client = client.clone();
+        let semaphore = semaphore.clone();
+        let concurrency = concurrency.clone();
+        tasks.spawn(async move {
+            let permit = semaphore.acquire_owned().await;
+            let result = test_account(&client, &account, timeout).await;
+            if result.throttled {
+                let lowered = concurrency
+                    .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| (n > 1).then(|| n - 1))
+                    .is_ok();
+                if let (true, Ok(permit)) = (lowered, permit) {
+                    // 不归还名额，后续请求的并发数随之降低
+                    permit.forget();
+                    tracing::info!("检测到限流，并发数降为 {}", concurrency.load(Ordering::SeqCst));
+                }
+            }
+            result
+        });
+    }
+
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok(result) => {
+                progress.inc(&result.name);
+                results.push(result);
+            }
+            Err(e) => tracing::error!("连通性测试任务异常退出: {}", e),
+        }
+    }
+
+    sort_results(&mut results);
+    results
+}
+
+/// 失败的排在前面，其次是被限流的，同类按延迟升序
+fn sort_results(results: &mut [ConnectivityResult]) {
+    let rank = |r: &ConnectivityResult| (r.is_success(), r.is_rate_limited());
+    results.sort_by(|a, b| rank(a).cmp(&rank(b)).then(a.latency.cmp(&b.latency)));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, status: Option<u16>, latency_ms: u64) -> ConnectivityResult {
+        ConnectivityResult {
+            account_id: 0,
+            name: name.to_string(),
+            base_url: String::new(),
+            masked_token: String::new(),
+            status,
+            latency: Duration::from_millis(latency_ms),
+            error: None,
+            throttled: status == Some(429),
+        }
+    }
+
+    #[test]
+    fn test_mask_token() {
+        assert_eq!(mask_token("sk-ant-api03-abcdefgh1234"), "sk-ant****1234");
+        assert_eq!(mask_token("short"), "****");
+    }
+
+    #[test]
+    fn test_sort_results_failures_first() {
+        let mut results = vec![
+            result("ok-slow", Some(200), 300),
+            result("unauthorized", Some(401), 50),
+            result("ok-fast", Some(200), 100),
+            result("timeout", None, 5000),
+            result("throttled", Some(429), 10),
+        ];
+
+        sort_results(&mut results);
+
+        let names: Vec<&str> = results.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["unauthorized", "timeout", "throttled", "ok-fast", "ok-slow"]);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = chrono::DateTime::parse_from_rfc2822("Wed, 21 Oct 2026 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        assert_eq!(parse_retry_after("5", now), Some(Duration::from_secs(5)));
+        assert_eq!(parse_retry_after("3600", now), Some(MAX_RETRY_AFTER));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2026 07:28:10 GMT", now),
+            Some(Duration::from_secs(10))
+        );
+        // 已经过去的时间点不需要等待
+        assert_eq!(parse_retry_after("Wed, 21 Oct 2026 07:00:00 GMT", now), Some(Duration::ZERO));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+}
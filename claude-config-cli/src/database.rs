@@ -1,7 +1,12 @@
 use crate::config_manager::ConfigManager;
 use crate::models::*;
+use crate::names;
+use claude_config_core::audit::ConfigChange;
 use chrono::Utc;
-use sqlx::{sqlite::SqlitePool, Error as SqlxError, Row};
+use sqlx::{
+    sqlite::{SqliteConnection, SqlitePool, SqlitePoolOptions},
+    Error as SqlxError, Row,
+};
 use std::path::PathBuf;
 use tracing::{error, info, warn};
 
@@ -9,6 +14,16 @@ pub struct Database {
     pub pool: SqlitePool,
 }
 
+/// 校验账号、目录名称并去除首尾空白，不合法时返回 InvalidArgument
+fn checked_name(name: &str) -> Result<String, SqlxError> {
+    names::validate_name(name).map_err(|e| SqlxError::InvalidArgument(e.to_string()))
+}
+
+/// 去除可选文本（备注、额外认证变量名）首尾空白，空值存为 NULL
+fn normalize_optional(text: Option<&str>) -> Option<&str> {
+    text.map(str::trim).filter(|text| !text.is_empty())
+}
+
 impl Database {
     /// 获取数据库连接池引用
     pub fn get_pool(&self) -> &SqlitePool {
@@ -90,6 +105,63 @@ impl Database {
         Ok(db)
     }
 
+    /// 默认数据库文件的位置（应用数据目录下）；配置的是绝对路径或其他数据库时返回 None
+    pub fn default_database_path() -> Option<PathBuf> {
+        let config_manager = ConfigManager::new();
+        let url = &config_manager.get_default_database_config()?.url;
+        if url.starts_with("sqlite:///") && !url.starts_with("sqlite:////") {
+            Some(ConfigManager::get_app_data_dir().join(url.replace("sqlite:///", "")))
+        } else {
+            None
+        }
+    }
+
+    /// 创建内存数据库：不读写任何数据库文件，进程退出后数据全部丢弃（--ephemeral 和测试使用）
+    pub async fn new_in_memory() -> Result<Self, SqlxError> {
+        // 每个连接各自拥有一个内存数据库，连接池只保留一个永不回收的连接
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await?;
+
+        let db = Self { pool };
+        db.migrate().await?;
+        info!("已创建内存数据库，退出后数据不会保留");
+        Ok(db)
+    }
+
+    /// 首次运行新版本时，把旧位置（程序目录下的 resources 或 ~/.claude-config-manager）
+    /// 的数据库复制到新的应用数据目录；旧文件保留不动
+    fn migrate_legacy_database(target: &std::path::Path, db_filename: &str) {
+        if target.exists() {
+            return;
+        }
+
+        let legacy_paths = [
+            ConfigManager::get_resource_dir().map(|dir| dir.join(db_filename)),
+            ConfigManager::get_legacy_app_data_dir().map(|dir| dir.join(db_filename)),
+        ];
+
+        for legacy in legacy_paths.into_iter().flatten() {
+            if legacy == target || !legacy.is_file() {
+                continue;
+            }
+
+            match std::fs::copy(&legacy, target) {
+                Ok(_) => {
+                    info!("已将旧数据库 {} 迁移到 {}", legacy.display(), target.display());
+                    println!("已将旧数据库迁移到: {}", target.display());
+                }
+                Err(e) => {
+                    warn!("迁移旧数据库 {} 失败: {}", legacy.display(), e);
+                }
+            }
+            return;
+        }
+    }
+
     pub async fn new() -> Result<Self, SqlxError> {
         info!("开始初始化数据库");
 
@@ -108,30 +180,15 @@ impl Database {
             let db_filename = database_url.replace("sqlite:///", "");
             info!("提取的数据库文件名: {}", db_filename);
 
-            // 使用程序目录下的 resources 目录存储数据库
-            let final_db_path = if let Some(resources_dir) = ConfigManager::get_resource_dir() {
-                info!(
-                    "使用resources目录作为数据库位置: {}",
-                    resources_dir.display()
-                );
-
-                // 确保 resources 目录存在
-                if !resources_dir.exists() {
-                    info!("创建 resources 目录: {}", resources_dir.display());
-                    std::fs::create_dir_all(&resources_dir).map_err(|e| {
-                        SqlxError::Configuration(format!("创建resources目录失败: {}", e).into())
-                    })?;
-                }
+            // 数据库存放在平台约定的应用数据目录（可通过 CLAUDE_CONFIG_HOME 覆盖）
+            let app_data_dir = ConfigManager::get_app_data_dir();
+            info!("使用应用数据目录作为数据库位置: {}", app_data_dir.display());
+            std::fs::create_dir_all(&app_data_dir).map_err(|e| {
+                SqlxError::Configuration(format!("创建应用数据目录失败: {}", e).into())
+            })?;
 
-                resources_dir.join(&db_filename)
-            } else {
-                // 如果无法获取 resources 目录，使用当前目录
-                warn!("无法获取resources目录，使用当前目录");
-                let current_dir = std::env::current_dir().map_err(|e| {
-                    SqlxError::Configuration(format!("获取当前目录失败: {}", e).into())
-                })?;
-                current_dir.join(&db_filename)
-            };
+            let final_db_path = app_data_dir.join(&db_filename);
+            Self::migrate_legacy_database(&final_db_path, &db_filename);
 
             // 检查数据库文件状态
             match std::fs::metadata(&final_db_path) {
@@ -293,7 +350,17 @@ impl Database {
                 token TEXT NOT NULL,
                 base_url TEXT NOT NULL,
                 model TEXT NOT NULL DEFAULT '',
+                auth_style TEXT NOT NULL DEFAULT 'both',
+                http_proxy TEXT NOT NULL DEFAULT '',
+                https_proxy TEXT NOT NULL DEFAULT '',
+                no_proxy TEXT NOT NULL DEFAULT '',
+                note TEXT,
+                extra_auth_env_key TEXT,
+                staging_base_url TEXT,
+                token_command TEXT,
+                claude_md_template TEXT,
                 is_active BOOLEAN NOT NULL DEFAULT FALSE,
+                last_used_at DATETIME,
                 created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
             )
@@ -310,6 +377,11 @@ impl Database {
                 path TEXT NOT NULL UNIQUE,
                 name TEXT NOT NULL,
                 is_active BOOLEAN NOT NULL DEFAULT FALSE,
+                is_relative BOOLEAN NOT NULL DEFAULT FALSE,
+                is_locked BOOLEAN NOT NULL DEFAULT FALSE,
+                expected_account_id INTEGER,
+                last_switched_at DATETIME,
+                deleted_at DATETIME,
                 created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
                 updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
             )
@@ -377,6 +449,7 @@ impl Database {
                 username TEXT NOT NULL,
                 password TEXT NOT NULL,
                 remote_path TEXT NOT NULL DEFAULT '/claude-config',
+                namespace TEXT,
                 auto_sync BOOLEAN NOT NULL DEFAULT FALSE,
                 sync_interval INTEGER NOT NULL DEFAULT 3600,
                 is_active BOOLEAN NOT NULL DEFAULT FALSE,
@@ -406,8 +479,111 @@ impl Database {
         .execute(&self.pool)
         .await?;
 
+        // Create templates table for reusable settings fragments
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS templates (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                description TEXT,
+                settings_json TEXT NOT NULL,
+                is_builtin BOOLEAN NOT NULL DEFAULT FALSE,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // 配置档案；账号或模板删除后引用置空
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS profiles (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                account_id INTEGER,
+                template_id INTEGER,
+                skip_permissions BOOLEAN NOT NULL DEFAULT TRUE,
+                use_proxy BOOLEAN NOT NULL DEFAULT FALSE,
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // 目录配置变更记录，只追加不修改；目录被永久删除后仍保留
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS config_audit (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                directory_id INTEGER NOT NULL,
+                action TEXT NOT NULL CHECK(action IN ('apply', 'clear', 'reset')),
+                before_hash TEXT,
+                after_hash TEXT,
+                base_url TEXT,
+                actor TEXT NOT NULL DEFAULT '',
+                created_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_config_audit_directory ON config_audit (directory_id, id)",
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // 账号最近一次连通性测试结果，账号列表据此显示可用性
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS account_health (
+                account_id INTEGER PRIMARY KEY,
+                status INTEGER,
+                error TEXT,
+                checked_at DATETIME NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // 账号的备用账号链：切换前主账号不可用时按 position 顺序尝试
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS account_fallbacks (
+                account_id INTEGER NOT NULL,
+                fallback_id INTEGER NOT NULL,
+                position INTEGER NOT NULL,
+                PRIMARY KEY (account_id, fallback_id)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        // 每个目录最近一次由本工具写入的配置快照，用于检测外部修改
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS settings_snapshots (
+                directory_id INTEGER PRIMARY KEY,
+                settings_path TEXT NOT NULL,
+                content_hash TEXT,
+                content TEXT,
+                updated_at DATETIME NOT NULL DEFAULT CURRENT_TIMESTAMP
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
         // Initialize only essential default data
         self.initialize_default_base_urls().await?;
+        self.initialize_default_templates().await?;
         // 不再初始化示例账号和目录数据
 
         // 输出初始化完成信息
@@ -415,8 +591,7 @@ impl Database {
             .fetch_one(&self.pool)
             .await?;
 
-        println!("数据库初始化完成 - 默认 API 端点: {} 个", base_url_count);
-        println!("数据库已就绪，请在界面中添加您的账号和项目目录");
+        info!("数据库初始化完成 - 默认 API 端点: {} 个", base_url_count);
 
         Ok(())
     }
@@ -456,10 +631,50 @@ impl Database {
         Ok(())
     }
 
+    async fn initialize_default_templates(&self) -> Result<(), SqlxError> {
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM templates")
+            .fetch_one(&self.pool)
+            .await?;
+
+        if count == 0 {
+            let default_templates = [
+                (
+                    "关闭遥测",
+                    "禁用遥测、错误上报和非必要网络请求",
+                    r#"{"env":{"DISABLE_TELEMETRY":"1","DISABLE_ERROR_REPORTING":"1","CLAUDE_CODE_DISABLE_NONESSENTIAL_TRAFFIC":"1"}}"#,
+                ),
+                (
+                    "Sonnet 模型",
+                    "固定使用 Sonnet 模型",
+                    r#"{"env":{"ANTHROPIC_MODEL":"claude-sonnet-4-20250514"}}"#,
+                ),
+            ];
+
+            for (name, description, settings_json) in default_templates {
+                sqlx::query(
+                    "INSERT INTO templates (name, description, settings_json, is_builtin, created_at, updated_at)
+                     VALUES (?, ?, ?, TRUE, ?, ?)",
+                )
+                .bind(name)
+                .bind(description)
+                .bind(settings_json)
+                .bind(Utc::now())
+                .bind(Utc::now())
+                .execute(&self.pool)
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// 迁移数据库，确保所有表都存在
     pub async fn migrate(&self) -> Result<(), SqlxError> {
         info!("开始数据库迁移检查");
 
+        // 先运行所有表创建语句（使用 IF NOT EXISTS，不会影响现有表），保证全新数据库也能完成迁移
+        self.initialize().await?;
+
         // 检查 accounts 表是否存在 model 字段
         let has_model_field: i64 = sqlx::query_scalar(
             "SELECT COUNT(*) FROM pragma_table_info('accounts') WHERE name = 'model'"
@@ -478,8 +693,238 @@ impl Database {
             info!("accounts 表已包含 model 字段，无需添加");
         }
 
-        // 重新运行所有表创建语句（使用 IF NOT EXISTS，不会影响现有表）
-        self.initialize().await?;
+        // 检查 accounts 表是否存在 auth_style 字段
+        let has_auth_style_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('accounts') WHERE name = 'auth_style'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_auth_style_field == 0 {
+            info!("检测到 accounts 表缺少 auth_style 字段，开始添加...");
+            sqlx::query("ALTER TABLE accounts ADD COLUMN auth_style TEXT NOT NULL DEFAULT 'both'")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 auth_style 字段到 accounts 表");
+        }
+
+        // 检查 directories 表是否存在 is_relative 字段
+        let has_is_relative_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('directories') WHERE name = 'is_relative'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_is_relative_field == 0 {
+            info!("检测到 directories 表缺少 is_relative 字段，开始添加...");
+            sqlx::query(
+                "ALTER TABLE directories ADD COLUMN is_relative BOOLEAN NOT NULL DEFAULT FALSE",
+            )
+            .execute(&self.pool)
+            .await?;
+            info!("已成功添加 is_relative 字段到 directories 表");
+        }
+
+        // 检查 directories 表是否存在 last_switched_at 字段
+        let has_last_switched_at_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('directories') WHERE name = 'last_switched_at'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_last_switched_at_field == 0 {
+            info!("检测到 directories 表缺少 last_switched_at 字段，开始添加...");
+            sqlx::query("ALTER TABLE directories ADD COLUMN last_switched_at DATETIME")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 last_switched_at 字段到 directories 表");
+        }
+
+        // 检查 directories 表是否存在 deleted_at 字段
+        let has_deleted_at_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('directories') WHERE name = 'deleted_at'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_deleted_at_field == 0 {
+            info!("检测到 directories 表缺少 deleted_at 字段，开始添加...");
+            sqlx::query("ALTER TABLE directories ADD COLUMN deleted_at DATETIME")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 deleted_at 字段到 directories 表");
+        }
+
+        // 检查 accounts 表是否存在 last_used_at 字段
+        let has_last_used_at_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('accounts') WHERE name = 'last_used_at'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_last_used_at_field == 0 {
+            info!("检测到 accounts 表缺少 last_used_at 字段，开始添加...");
+            sqlx::query("ALTER TABLE accounts ADD COLUMN last_used_at DATETIME")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 last_used_at 字段到 accounts 表");
+        }
+
+        // 检查 accounts 表是否存在代理字段
+        for column in ["http_proxy", "https_proxy", "no_proxy"] {
+            let has_column: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM pragma_table_info('accounts') WHERE name = ?",
+            )
+            .bind(column)
+            .fetch_one(&self.pool)
+            .await?;
+
+            if has_column == 0 {
+                info!("检测到 accounts 表缺少 {} 字段，开始添加...", column);
+                sqlx::query(&format!(
+                    "ALTER TABLE accounts ADD COLUMN {} TEXT NOT NULL DEFAULT ''",
+                    column
+                ))
+                .execute(&self.pool)
+                .await?;
+                info!("已成功添加 {} 字段到 accounts 表", column);
+            }
+        }
+
+        // 检查 directories 表是否存在 is_locked 字段
+        let has_is_locked_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('directories') WHERE name = 'is_locked'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_is_locked_field == 0 {
+            info!("检测到 directories 表缺少 is_locked 字段，开始添加...");
+            sqlx::query("ALTER TABLE directories ADD COLUMN is_locked BOOLEAN NOT NULL DEFAULT FALSE")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 is_locked 字段到 directories 表");
+        }
+
+        // 检查 directories 表是否存在 expected_account_id 字段
+        let has_expected_account_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('directories') WHERE name = 'expected_account_id'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_expected_account_field == 0 {
+            info!("检测到 directories 表缺少 expected_account_id 字段，开始添加...");
+            sqlx::query("ALTER TABLE directories ADD COLUMN expected_account_id INTEGER")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 expected_account_id 字段到 directories 表");
+        }
+
+        // 检查 accounts 表是否存在 claude_md_template 字段
+        let has_claude_md_template_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('accounts') WHERE name = 'claude_md_template'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_claude_md_template_field == 0 {
+            info!("检测到 accounts 表缺少 claude_md_template 字段，开始添加...");
+            sqlx::query("ALTER TABLE accounts ADD COLUMN claude_md_template TEXT")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 claude_md_template 字段到 accounts 表");
+        }
+
+        // 检查 accounts 表是否存在 note 字段
+        let has_note_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('accounts') WHERE name = 'note'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_note_field == 0 {
+            info!("检测到 accounts 表缺少 note 字段，开始添加...");
+            sqlx::query("ALTER TABLE accounts ADD COLUMN note TEXT")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 note 字段到 accounts 表");
+        }
+
+        // 检查 accounts 表是否存在 extra_auth_env_key 字段
+        let has_extra_auth_env_key_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('accounts') WHERE name = 'extra_auth_env_key'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_extra_auth_env_key_field == 0 {
+            info!("检测到 accounts 表缺少 extra_auth_env_key 字段，开始添加...");
+            sqlx::query("ALTER TABLE accounts ADD COLUMN extra_auth_env_key TEXT")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 extra_auth_env_key 字段到 accounts 表");
+        }
+
+        // 检查 accounts 表是否存在 staging_base_url 字段
+        let has_staging_base_url_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('accounts') WHERE name = 'staging_base_url'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_staging_base_url_field == 0 {
+            info!("检测到 accounts 表缺少 staging_base_url 字段，开始添加...");
+            sqlx::query("ALTER TABLE accounts ADD COLUMN staging_base_url TEXT")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 staging_base_url 字段到 accounts 表");
+        }
+
+        // 检查 accounts 表是否存在 token_command 字段
+        let has_token_command_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('accounts') WHERE name = 'token_command'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_token_command_field == 0 {
+            info!("检测到 accounts 表缺少 token_command 字段，开始添加...");
+            sqlx::query("ALTER TABLE accounts ADD COLUMN token_command TEXT")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 token_command 字段到 accounts 表");
+        }
+
+        // 检查 config_audit 表是否存在 base_url 字段（记录切换时实际写入的 Base URL）
+        let has_audit_base_url_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('config_audit') WHERE name = 'base_url'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_audit_base_url_field == 0 {
+            info!("检测到 config_audit 表缺少 base_url 字段，开始添加...");
+            sqlx::query("ALTER TABLE config_audit ADD COLUMN base_url TEXT")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 base_url 字段到 config_audit 表");
+        }
+
+        // 检查 webdav_configs 表是否存在 namespace 字段（多台机器在同一服务器上各用一个子目录）
+        let has_webdav_namespace_field: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM pragma_table_info('webdav_configs') WHERE name = 'namespace'"
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        if has_webdav_namespace_field == 0 {
+            info!("检测到 webdav_configs 表缺少 namespace 字段，开始添加...");
+            sqlx::query("ALTER TABLE webdav_configs ADD COLUMN namespace TEXT")
+                .execute(&self.pool)
+                .await?;
+            info!("已成功添加 namespace 字段到 webdav_configs 表");
+        }
 
         info!("数据库迁移完成");
         Ok(())
@@ -491,7 +936,7 @@ impl Database {
         request: GetAccountsRequest,
     ) -> Result<AccountsResponse, SqlxError> {
         let page = request.page.unwrap_or(1).max(1);
-        let per_page = request.per_page.unwrap_or(10).max(1).min(100);
+        let per_page = request.per_page.unwrap_or(10).clamp(1, 100);
         let offset = (page - 1) * per_page;
 
         let mut query = "SELECT * FROM accounts WHERE 1=1".to_string();
@@ -555,44 +1000,85 @@ impl Database {
         })
     }
 
-    pub async fn create_account(
-        &self,
-        request: CreateAccountRequest,
-    ) -> Result<Account, SqlxError> {
+    pub async fn create_account(&self, request: CreateAccountRequest) -> Result<Account, SqlxError> {
+        Self::create_account_in(&mut *self.pool.acquire().await?, request).await
+    }
+
+    /// 在指定连接上创建账号，导入配置包时传入事务
+    pub async fn create_account_in(conn: &mut SqliteConnection, request: CreateAccountRequest) -> Result<Account, SqlxError> {
+        let name = checked_name(&request.name)?;
+        let token_command = normalize_optional(request.token_command.as_deref());
+        // 通过命令获取 Token 的账号不在数据库中保存 Token
+        let token = if token_command.is_some() { "" } else { request.token.as_str() };
         let now = Utc::now();
         let result = sqlx::query(
-            "INSERT INTO accounts (name, token, base_url, model, created_at, updated_at) 
-             VALUES (?, ?, ?, ?, ?, ?)",
+            "INSERT INTO accounts (name, token, base_url, model, auth_style, http_proxy, https_proxy, no_proxy, note, extra_auth_env_key, staging_base_url, token_command, created_at, updated_at) 
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
         )
-        .bind(&request.name)
-        .bind(&request.token)
+        .bind(&name)
+        .bind(token)
         .bind(&request.base_url)
         .bind(&request.model)
+        .bind(request.auth_style)
+        .bind(request.proxy.http_proxy.trim())
+        .bind(request.proxy.https_proxy.trim())
+        .bind(request.proxy.no_proxy.trim())
+        .bind(normalize_optional(request.note.as_deref()))
+        .bind(normalize_optional(request.extra_auth_env_key.as_deref()))
+        .bind(normalize_optional(request.staging_base_url.as_deref()))
+        .bind(token_command)
         .bind(now)
         .bind(now)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
         let account = sqlx::query_as::<_, Account>("SELECT * FROM accounts WHERE id = ?")
             .bind(result.last_insert_rowid())
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *conn)
             .await?;
 
         Ok(account)
     }
 
-    pub async fn update_account(
-        &self,
+    /// 只修改账号的显示名称，其他记录都按 ID 引用账号
+    pub async fn rename_account(&self, id: i64, name: &str) -> Result<Account, SqlxError> {
+        let name = checked_name(name)?;
+        let result = sqlx::query("UPDATE accounts SET name = ?, updated_at = ? WHERE id = ?")
+            .bind(&name)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(SqlxError::RowNotFound);
+        }
+        info!("已重命名账号 {} 为 {}", id, name);
+        self.get_account(id).await
+    }
+
+    pub async fn update_account(&self, id: i64, request: UpdateAccountRequest) -> Result<Account, SqlxError> {
+        Self::update_account_in(&mut *self.pool.acquire().await?, id, request).await
+    }
+
+    /// 在指定连接上更新账号，导入配置包时传入事务
+    pub async fn update_account_in(
+        conn: &mut SqliteConnection,
         id: i64,
         request: UpdateAccountRequest,
     ) -> Result<Account, SqlxError> {
+        let name = request.name.as_deref().map(checked_name).transpose()?;
+        // 改为通过命令获取 Token 时清空已保存的 Token
+        let token = match normalize_optional(request.token_command.as_deref()) {
+            Some(_) => Some(String::new()),
+            None => request.token.clone(),
+        };
         let now = Utc::now();
         let mut updates = Vec::new();
 
         if let Some(_name) = &request.name {
             updates.push("name = ?");
         }
-        if let Some(_token) = &request.token {
+        if let Some(_token) = &token {
             updates.push("token = ?");
         }
         if let Some(_base_url) = &request.base_url {
@@ -601,9 +1087,29 @@ impl Database {
         if let Some(_model) = &request.model {
             updates.push("model = ?");
         }
+        if let Some(_auth_style) = request.auth_style {
+            updates.push("auth_style = ?");
+        }
+        if let Some(_proxy) = &request.proxy {
+            updates.push("http_proxy = ?");
+            updates.push("https_proxy = ?");
+            updates.push("no_proxy = ?");
+        }
+        if let Some(_note) = &request.note {
+            updates.push("note = ?");
+        }
+        if let Some(_key) = &request.extra_auth_env_key {
+            updates.push("extra_auth_env_key = ?");
+        }
+        if let Some(_url) = &request.staging_base_url {
+            updates.push("staging_base_url = ?");
+        }
+        if let Some(_command) = &request.token_command {
+            updates.push("token_command = ?");
+        }
 
         if updates.is_empty() {
-            return self.get_account(id).await;
+            return Self::get_account_in(conn, id).await;
         }
 
         updates.push("updated_at = ?");
@@ -611,10 +1117,10 @@ impl Database {
 
         let mut q = sqlx::query(&query);
 
-        if let Some(name) = &request.name {
+        if let Some(name) = &name {
             q = q.bind(name);
         }
-        if let Some(token) = &request.token {
+        if let Some(token) = &token {
             q = q.bind(token);
         }
         if let Some(base_url) = &request.base_url {
@@ -623,17 +1129,57 @@ impl Database {
         if let Some(model) = &request.model {
             q = q.bind(model);
         }
+        if let Some(auth_style) = request.auth_style {
+            q = q.bind(auth_style);
+        }
+        if let Some(proxy) = &request.proxy {
+            q = q
+                .bind(proxy.http_proxy.trim())
+                .bind(proxy.https_proxy.trim())
+                .bind(proxy.no_proxy.trim());
+        }
+        if let Some(note) = &request.note {
+            q = q.bind(normalize_optional(Some(note)));
+        }
+        if let Some(key) = &request.extra_auth_env_key {
+            q = q.bind(normalize_optional(Some(key)));
+        }
+        if let Some(url) = &request.staging_base_url {
+            q = q.bind(normalize_optional(Some(url)));
+        }
+        if let Some(command) = &request.token_command {
+            q = q.bind(normalize_optional(Some(command)));
+        }
 
         q = q.bind(now).bind(id);
-        q.execute(&self.pool).await?;
+        q.execute(&mut *conn).await?;
 
-        self.get_account(id).await
+        Self::get_account_in(conn, id).await
+    }
+
+    /// 设置账号的 CLAUDE.local.md 模板，None 表示恢复使用内置模板
+    pub async fn set_account_claude_md(&self, id: i64, content: Option<&str>) -> Result<(), SqlxError> {
+        let result = sqlx::query("UPDATE accounts SET claude_md_template = ?, updated_at = ? WHERE id = ?")
+            .bind(content)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(SqlxError::RowNotFound);
+        }
+        Ok(())
     }
 
     pub async fn get_account(&self, id: i64) -> Result<Account, SqlxError> {
+        Self::get_account_in(&mut *self.pool.acquire().await?, id).await
+    }
+
+    pub async fn get_account_in(conn: &mut SqliteConnection, id: i64) -> Result<Account, SqlxError> {
         sqlx::query_as::<_, Account>("SELECT * FROM accounts WHERE id = ?")
             .bind(id)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *conn)
             .await
     }
 
@@ -659,6 +1205,24 @@ impl Database {
                 .await?;
         }
 
+        sqlx::query("DELETE FROM account_health WHERE account_id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("DELETE FROM account_fallbacks WHERE account_id = ? OR fallback_id = ?")
+            .bind(id)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("UPDATE profiles SET account_id = NULL WHERE account_id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        sqlx::query("UPDATE directories SET expected_account_id = NULL WHERE expected_account_id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
         // 删除账号记录
         let result = sqlx::query("DELETE FROM accounts WHERE id = ?")
             .bind(id)
@@ -673,23 +1237,88 @@ impl Database {
         Ok(())
     }
 
+    /// 在一个事务中批量删除账号及其关联记录，返回实际删除的账号数
+    pub async fn delete_accounts(&self, ids: &[i64]) -> Result<u64, SqlxError> {
+        let mut tx = self.pool.begin().await?;
+        let mut deleted = 0;
+
+        for id in ids {
+            sqlx::query("DELETE FROM account_directories WHERE account_id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM account_health WHERE account_id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("DELETE FROM account_fallbacks WHERE account_id = ? OR fallback_id = ?")
+                .bind(id)
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("UPDATE profiles SET account_id = NULL WHERE account_id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            sqlx::query("UPDATE directories SET expected_account_id = NULL WHERE expected_account_id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+            deleted += sqlx::query("DELETE FROM accounts WHERE id = ?")
+                .bind(id)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+
+        tx.commit().await?;
+        info!("批量删除账号 {} 个，ID: {:?}", deleted, ids);
+        Ok(deleted)
+    }
+
     // Directory methods
     pub async fn get_directories(&self) -> Result<Vec<Directory>, SqlxError> {
-        sqlx::query_as::<_, Directory>("SELECT * FROM directories ORDER BY created_at DESC")
-            .fetch_all(&self.pool)
-            .await
+        sqlx::query_as::<_, Directory>(
+            "SELECT * FROM directories WHERE deleted_at IS NULL ORDER BY created_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 获取回收站中的目录，最近删除的排在前面
+    pub async fn get_trashed_directories(&self) -> Result<Vec<Directory>, SqlxError> {
+        sqlx::query_as::<_, Directory>(
+            "SELECT * FROM directories WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC",
+        )
+        .fetch_all(&self.pool)
+        .await
     }
 
     pub async fn create_directory(
         &self,
         request: CreateDirectoryRequest,
     ) -> Result<Directory, SqlxError> {
+        let name = checked_name(&request.name)?;
+
+        // 回收站中存在相同路径时，重新添加视为放弃旧记录
+        let trashed_id: Option<i64> = sqlx::query_scalar(
+            "SELECT id FROM directories WHERE path = ? AND deleted_at IS NOT NULL",
+        )
+        .bind(&request.path)
+        .fetch_optional(&self.pool)
+        .await?;
+        if let Some(trashed_id) = trashed_id {
+            info!("回收站中已有相同路径的目录 {}，永久删除后重新添加", trashed_id);
+            self.purge_directory(trashed_id).await?;
+        }
+
         let now = Utc::now();
         let result = sqlx::query(
-            "INSERT INTO directories (path, name, created_at, updated_at) VALUES (?, ?, ?, ?)",
+            "INSERT INTO directories (path, name, is_relative, created_at, updated_at) VALUES (?, ?, ?, ?, ?)",
         )
         .bind(&request.path)
-        .bind(&request.name)
+        .bind(&name)
+        .bind(request.is_relative)
         .bind(now)
         .bind(now)
         .execute(&self.pool)
@@ -708,6 +1337,7 @@ impl Database {
         id: i64,
         request: UpdateDirectoryRequest,
     ) -> Result<Directory, SqlxError> {
+        let name = request.name.as_deref().map(checked_name).transpose()?;
         let now = Utc::now();
         let mut updates = Vec::new();
 
@@ -717,6 +1347,9 @@ impl Database {
         if let Some(_name) = &request.name {
             updates.push("name = ?");
         }
+        if let Some(_is_relative) = request.is_relative {
+            updates.push("is_relative = ?");
+        }
 
         if updates.is_empty() {
             return self.get_directory(id).await;
@@ -730,9 +1363,12 @@ impl Database {
         if let Some(path) = &request.path {
             q = q.bind(path);
         }
-        if let Some(name) = &request.name {
+        if let Some(name) = &name {
             q = q.bind(name);
         }
+        if let Some(is_relative) = request.is_relative {
+            q = q.bind(is_relative);
+        }
 
         q = q.bind(now).bind(id);
         q.execute(&self.pool).await?;
@@ -740,6 +1376,67 @@ impl Database {
         self.get_directory(id).await
     }
 
+    /// 锁定或解锁目录
+    /// 设置目录的预期账号，None 表示取消
+    pub async fn set_directory_expected_account(&self, id: i64, account_id: Option<i64>) -> Result<(), SqlxError> {
+        let result = sqlx::query("UPDATE directories SET expected_account_id = ?, updated_at = ? WHERE id = ?")
+            .bind(account_id)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(SqlxError::RowNotFound);
+        }
+        info!("目录 {} 的预期账号已设置为 {:?}", id, account_id);
+        Ok(())
+    }
+
+    pub async fn set_directory_locked(&self, id: i64, locked: bool) -> Result<(), SqlxError> {
+        Self::set_directory_locked_in(&mut *self.pool.acquire().await?, id, locked).await
+    }
+
+    /// 在指定连接上设置目录锁定状态，导入配置包时传入事务
+    pub async fn set_directory_locked_in(conn: &mut SqliteConnection, id: i64, locked: bool) -> Result<(), SqlxError> {
+        let result = sqlx::query("UPDATE directories SET is_locked = ?, updated_at = ? WHERE id = ?")
+            .bind(locked)
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&mut *conn)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(SqlxError::RowNotFound);
+        }
+        info!("目录 {} 已{}", id, if locked { "锁定" } else { "解锁" });
+        Ok(())
+    }
+
+    /// 在一个事务中批量设置目录的激活状态，锁定的目录不修改，返回状态实际变化的目录数
+    pub async fn set_directories_active(&self, ids: &[i64], active: bool) -> Result<u64, SqlxError> {
+        let mut tx = self.pool.begin().await?;
+        let mut changed = 0;
+
+        for id in ids {
+            changed += sqlx::query(
+                "UPDATE directories SET is_active = ?, updated_at = ?
+                 WHERE id = ? AND is_active != ? AND is_locked = FALSE AND deleted_at IS NULL",
+            )
+            .bind(active)
+            .bind(Utc::now())
+            .bind(id)
+            .bind(active)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        }
+
+        tx.commit().await?;
+        info!("批量{}目录 {} 个", if active { "激活" } else { "取消激活" }, changed);
+        Ok(changed)
+    }
+
     pub async fn get_directory(&self, id: i64) -> Result<Directory, SqlxError> {
         sqlx::query_as::<_, Directory>("SELECT * FROM directories WHERE id = ?")
             .bind(id)
@@ -747,7 +1444,43 @@ impl Database {
             .await
     }
 
+    /// 删除目录：移入回收站（软删除），关联记录保留以便恢复
     pub async fn delete_directory(&self, id: i64) -> Result<(), SqlxError> {
+        let result = sqlx::query(
+            "UPDATE directories SET deleted_at = ?, is_active = FALSE WHERE id = ? AND deleted_at IS NULL",
+        )
+        .bind(Utc::now())
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(SqlxError::RowNotFound);
+        }
+
+        info!("目录已移入回收站，ID: {}", id);
+        Ok(())
+    }
+
+    /// 从回收站恢复目录
+    pub async fn restore_directory(&self, id: i64) -> Result<(), SqlxError> {
+        let result = sqlx::query(
+            "UPDATE directories SET deleted_at = NULL WHERE id = ? AND deleted_at IS NOT NULL",
+        )
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(SqlxError::RowNotFound);
+        }
+
+        info!("已从回收站恢复目录，ID: {}", id);
+        Ok(())
+    }
+
+    /// 永久删除目录记录及其关联记录
+    pub async fn purge_directory(&self, id: i64) -> Result<(), SqlxError> {
         // 启用外键约束
         sqlx::query("PRAGMA foreign_keys = ON")
             .execute(&self.pool)
@@ -821,8 +1554,13 @@ impl Database {
         .await
     }
 
-    pub async fn create_base_url(
-        &self,
+    pub async fn create_base_url(&self, request: CreateBaseUrlRequest) -> Result<BaseUrl, SqlxError> {
+        Self::create_base_url_in(&mut *self.pool.acquire().await?, request).await
+    }
+
+    /// 在指定连接上创建 Base URL，导入配置包时传入事务
+    pub async fn create_base_url_in(
+        conn: &mut SqliteConnection,
         request: CreateBaseUrlRequest,
     ) -> Result<BaseUrl, SqlxError> {
         let now = Utc::now();
@@ -831,7 +1569,7 @@ impl Database {
         // If setting as default, unset other defaults
         if is_default {
             sqlx::query("UPDATE base_urls SET is_default = FALSE")
-                .execute(&self.pool)
+                .execute(&mut *conn)
                 .await?;
         }
 
@@ -845,39 +1583,48 @@ impl Database {
         .bind(is_default)
         .bind(now)
         .bind(now)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
         let base_url = sqlx::query_as::<_, BaseUrl>("SELECT * FROM base_urls WHERE id = ?")
             .bind(result.last_insert_rowid())
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *conn)
             .await?;
 
         Ok(base_url)
     }
 
     pub async fn get_base_url(&self, id: i64) -> Result<BaseUrl, SqlxError> {
+        Self::get_base_url_in(&mut *self.pool.acquire().await?, id).await
+    }
+
+    pub async fn get_base_url_in(conn: &mut SqliteConnection, id: i64) -> Result<BaseUrl, SqlxError> {
         sqlx::query_as::<_, BaseUrl>("SELECT * FROM base_urls WHERE id = ?")
             .bind(id)
-            .fetch_one(&self.pool)
+            .fetch_one(&mut *conn)
             .await
     }
 
-    pub async fn update_base_url(
-        &self,
+    pub async fn update_base_url(&self, id: i64, request: UpdateBaseUrlRequest) -> Result<BaseUrl, SqlxError> {
+        Self::update_base_url_in(&mut *self.pool.acquire().await?, id, request).await
+    }
+
+    /// 在指定连接上更新 Base URL（URL 变化时级联更新账号），导入配置包时传入事务
+    pub async fn update_base_url_in(
+        conn: &mut SqliteConnection,
         id: i64,
         request: UpdateBaseUrlRequest,
     ) -> Result<BaseUrl, SqlxError> {
         let now = Utc::now();
 
         // 获取旧的 base_url 信息，用于级联更新账号
-        let old_base_url = self.get_base_url(id).await?;
+        let old_base_url = Self::get_base_url_in(conn, id).await?;
         let old_url = old_base_url.url.clone();
 
         // If setting as default, unset other defaults
         if let Some(true) = request.is_default {
             sqlx::query("UPDATE base_urls SET is_default = FALSE")
-                .execute(&self.pool)
+                .execute(&mut *conn)
                 .await?;
         }
 
@@ -896,7 +1643,7 @@ impl Database {
         }
 
         if updates.is_empty() {
-            return self.get_base_url(id).await;
+            return Self::get_base_url_in(conn, id).await;
         }
 
         updates.push("updated_at = ?");
@@ -918,7 +1665,7 @@ impl Database {
         }
 
         q = q.bind(now).bind(id);
-        q.execute(&self.pool).await?;
+        q.execute(&mut *conn).await?;
 
         // 如果 URL 发生了变化，级联更新所有使用该 URL 的账号
         if let Some(new_url) = &request.url {
@@ -929,7 +1676,7 @@ impl Database {
                 .bind(new_url)
                 .bind(now)
                 .bind(&old_url)
-                .execute(&self.pool)
+                .execute(&mut *conn)
                 .await?;
 
                 let affected_rows = result.rows_affected();
@@ -942,7 +1689,7 @@ impl Database {
             }
         }
 
-        self.get_base_url(id).await
+        Self::get_base_url_in(conn, id).await
     }
 
     pub async fn delete_base_url(&self, id: i64) -> Result<(), SqlxError> {
@@ -987,6 +1734,147 @@ impl Database {
         Ok(())
     }
 
+    // Template methods
+    pub async fn get_templates(&self) -> Result<Vec<Template>, SqlxError> {
+        sqlx::query_as::<_, Template>(
+            "SELECT * FROM templates ORDER BY is_builtin DESC, created_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn get_template(&self, id: i64) -> Result<Template, SqlxError> {
+        sqlx::query_as::<_, Template>("SELECT * FROM templates WHERE id = ?")
+            .bind(id)
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    pub async fn create_template(
+        &self,
+        request: CreateTemplateRequest,
+    ) -> Result<Template, SqlxError> {
+        let now = Utc::now();
+        let result = sqlx::query(
+            "INSERT INTO templates (name, description, settings_json, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?)",
+        )
+        .bind(&request.name)
+        .bind(&request.description)
+        .bind(&request.settings_json)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        self.get_template(result.last_insert_rowid()).await
+    }
+
+    pub async fn update_template(
+        &self,
+        id: i64,
+        request: UpdateTemplateRequest,
+    ) -> Result<Template, SqlxError> {
+        let now = Utc::now();
+        let mut updates = Vec::new();
+
+        if let Some(_name) = &request.name {
+            updates.push("name = ?");
+        }
+        if let Some(_description) = &request.description {
+            updates.push("description = ?");
+        }
+        if let Some(_settings_json) = &request.settings_json {
+            updates.push("settings_json = ?");
+        }
+
+        if updates.is_empty() {
+            return self.get_template(id).await;
+        }
+
+        updates.push("updated_at = ?");
+        let query = format!("UPDATE templates SET {} WHERE id = ?", updates.join(", "));
+
+        let mut q = sqlx::query(&query);
+
+        if let Some(name) = &request.name {
+            q = q.bind(name);
+        }
+        if let Some(description) = &request.description {
+            q = q.bind(normalize_optional(Some(description)));
+        }
+        if let Some(settings_json) = &request.settings_json {
+            q = q.bind(settings_json);
+        }
+
+        q = q.bind(now).bind(id);
+        q.execute(&self.pool).await?;
+
+        self.get_template(id).await
+    }
+
+    pub async fn delete_template(&self, id: i64) -> Result<(), SqlxError> {
+        sqlx::query("UPDATE profiles SET template_id = NULL WHERE template_id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        let result = sqlx::query("DELETE FROM templates WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(SqlxError::RowNotFound);
+        }
+
+        info!("成功删除模板，ID: {}", id);
+        Ok(())
+    }
+
+    // Profile methods
+    pub async fn get_profiles(&self) -> Result<Vec<Profile>, SqlxError> {
+        sqlx::query_as::<_, Profile>("SELECT * FROM profiles ORDER BY name ASC")
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    pub async fn create_profile(&self, request: CreateProfileRequest) -> Result<Profile, SqlxError> {
+        let now = Utc::now();
+        let result = sqlx::query(
+            "INSERT INTO profiles (name, account_id, template_id, skip_permissions, use_proxy, created_at, updated_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(&request.name)
+        .bind(request.account_id)
+        .bind(request.template_id)
+        .bind(request.skip_permissions)
+        .bind(request.use_proxy)
+        .bind(now)
+        .bind(now)
+        .execute(&self.pool)
+        .await?;
+
+        sqlx::query_as::<_, Profile>("SELECT * FROM profiles WHERE id = ?")
+            .bind(result.last_insert_rowid())
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    pub async fn delete_profile(&self, id: i64) -> Result<(), SqlxError> {
+        let result = sqlx::query("DELETE FROM profiles WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(SqlxError::RowNotFound);
+        }
+
+        info!("成功删除配置档案，ID: {}", id);
+        Ok(())
+    }
+
     // Switch account functionality
     pub async fn switch_account(&self, request: SwitchAccountRequest) -> Result<String, SqlxError> {
         // Reset all active states
@@ -1007,10 +1895,11 @@ impl Database {
             .execute(&self.pool)
             .await?;
 
-        // Create or update association
+        // Create or update association（更新时间用于确定目录最近一次使用的账号）
         sqlx::query(
-            "INSERT OR IGNORE INTO account_directories (account_id, directory_id, created_at) 
-             VALUES (?, ?, ?)",
+            "INSERT INTO account_directories (account_id, directory_id, created_at)
+             VALUES (?, ?, ?)
+             ON CONFLICT(account_id, directory_id) DO UPDATE SET created_at = excluded.created_at",
         )
         .bind(request.account_id)
         .bind(request.directory_id)
@@ -1028,8 +1917,186 @@ impl Database {
         ))
     }
 
+    /// 记录目录最近一次成功应用配置的时间
+    pub async fn mark_directory_switched(&self, directory_id: i64) -> Result<(), SqlxError> {
+        sqlx::query("UPDATE directories SET last_switched_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(directory_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// 记录账号最近一次被应用的时间
+    pub async fn mark_account_used(&self, account_id: i64) -> Result<(), SqlxError> {
+        sqlx::query("UPDATE accounts SET last_used_at = ? WHERE id = ?")
+            .bind(Utc::now())
+            .bind(account_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// 获取最近使用过的账号，按使用时间倒序
+    pub async fn get_recent_accounts(&self, limit: i64) -> Result<Vec<Account>, SqlxError> {
+        sqlx::query_as::<_, Account>(
+            "SELECT * FROM accounts WHERE last_used_at IS NOT NULL ORDER BY last_used_at DESC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 获取目录最近一次切换所使用的账号
+    pub async fn get_directory_account(
+        &self,
+        directory_id: i64,
+    ) -> Result<Option<Account>, SqlxError> {
+        sqlx::query_as::<_, Account>(
+            r#"
+            SELECT a.* FROM accounts a
+            JOIN account_directories ad ON ad.account_id = a.id
+            WHERE ad.directory_id = ?
+            ORDER BY ad.created_at DESC, ad.id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(directory_id)
+        .fetch_optional(&self.pool)
+        .await
+    }
+
+    /// 追加一条目录配置变更记录，`base_url` 为切换时实际写入的 Base URL
+    pub async fn record_config_change(
+        &self,
+        directory_id: i64,
+        change: &ConfigChange,
+        base_url: Option<&str>,
+        actor: &str,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT INTO config_audit (directory_id, action, before_hash, after_hash, base_url, actor)
+             VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind(directory_id)
+        .bind(change.action.as_str())
+        .bind(&change.before_hash)
+        .bind(&change.after_hash)
+        .bind(base_url)
+        .bind(actor)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// 获取目录的配置变更记录，最新的在前
+    pub async fn get_config_audit(&self, directory_id: i64) -> Result<Vec<ConfigAuditEntry>, SqlxError> {
+        sqlx::query_as::<_, ConfigAuditEntry>(
+            "SELECT * FROM config_audit WHERE directory_id = ? ORDER BY id DESC",
+        )
+        .bind(directory_id)
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// 保存账号最近一次连通性测试结果，覆盖旧结果
+    pub async fn save_account_health(
+        &self,
+        account_id: i64,
+        status: Option<u16>,
+        error: Option<&str>,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            r#"
+            INSERT INTO account_health (account_id, status, error, checked_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(account_id) DO UPDATE SET
+                status = excluded.status,
+                error = excluded.error,
+                checked_at = excluded.checked_at
+            "#,
+        )
+        .bind(account_id)
+        .bind(status.map(i64::from))
+        .bind(error)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_account_health(&self) -> Result<Vec<AccountHealth>, SqlxError> {
+        sqlx::query_as::<_, AccountHealth>("SELECT * FROM account_health")
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// 账号的备用账号 ID，按尝试顺序排列
+    pub async fn get_account_fallbacks(&self, account_id: i64) -> Result<Vec<i64>, SqlxError> {
+        sqlx::query_scalar("SELECT fallback_id FROM account_fallbacks WHERE account_id = ? ORDER BY position")
+            .bind(account_id)
+            .fetch_all(&self.pool)
+            .await
+    }
+
+    /// 整体替换账号的备用账号链，传入空列表清除
+    pub async fn set_account_fallbacks(&self, account_id: i64, fallback_ids: &[i64]) -> Result<(), SqlxError> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM account_fallbacks WHERE account_id = ?")
+            .bind(account_id)
+            .execute(&mut *tx)
+            .await?;
+        for (position, fallback_id) in fallback_ids.iter().enumerate() {
+            sqlx::query("INSERT INTO account_fallbacks (account_id, fallback_id, position) VALUES (?, ?, ?)")
+                .bind(account_id)
+                .bind(fallback_id)
+                .bind(position as i64)
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        info!("已更新账号 {} 的备用账号链: {:?}", account_id, fallback_ids);
+        Ok(())
+    }
+
+    /// 保存目录最近一次写入的配置快照，覆盖旧快照
+    pub async fn save_settings_snapshot(
+        &self,
+        directory_id: i64,
+        settings_path: &str,
+        content_hash: Option<&str>,
+        content: Option<&str>,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            r#"
+            INSERT INTO settings_snapshots (directory_id, settings_path, content_hash, content, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(directory_id) DO UPDATE SET
+                settings_path = excluded.settings_path,
+                content_hash = excluded.content_hash,
+                content = excluded.content,
+                updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(directory_id)
+        .bind(settings_path)
+        .bind(content_hash)
+        .bind(content)
+        .bind(Utc::now())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn get_settings_snapshots(&self) -> Result<Vec<SettingsSnapshot>, SqlxError> {
+        sqlx::query_as::<_, SettingsSnapshot>("SELECT * FROM settings_snapshots")
+            .fetch_all(&self.pool)
+            .await
+    }
+
     // Claude Settings methods
-    pub async fn save_claude_settings(&self, settings_json: &str) -> Result<(), SqlxError> {
+    /// 在指定连接上保存 Claude 设置，导入配置包时传入事务
+    pub async fn save_claude_settings_in(conn: &mut SqliteConnection, settings_json: &str) -> Result<(), SqlxError> {
         // First try to update existing settings
         let result = sqlx::query(
             r#"
@@ -1039,7 +2106,7 @@ impl Database {
             "#,
         )
         .bind(settings_json)
-        .execute(&self.pool)
+        .execute(&mut *conn)
         .await?;
 
         // If no rows were affected, insert a new record
@@ -1051,7 +2118,7 @@ impl Database {
                 "#,
             )
             .bind(settings_json)
-            .execute(&self.pool)
+            .execute(&mut *conn)
             .await?;
         }
 
@@ -1089,3 +2156,117 @@ impl Database {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_database_keeps_state_across_queries() {
+        let db = Database::new_in_memory().await.unwrap();
+        let account = db
+            .create_account(CreateAccountRequest {
+                name: "ci".to_string(),
+                token: "sk-ant-test".to_string(),
+                base_url: "https://api.anthropic.com".to_string(),
+                model: String::new(),
+                auth_style: AuthStyle::default(),
+                proxy: ProxyConfig::default(),
+                note: None,
+                extra_auth_env_key: None,
+                staging_base_url: None,
+                token_command: None,
+            })
+            .await
+            .unwrap();
+
+        // 连接池只有一个连接，后续查询看到的是同一个内存数据库
+        assert_eq!(db.get_account(account.id).await.unwrap().name, "ci");
+        db.delete_accounts(&[account.id]).await.unwrap();
+        assert!(db.get_account(account.id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_names_are_rejected() {
+        let db = Database::new_in_memory().await.unwrap();
+        let request = |name: &str| CreateDirectoryRequest {
+            path: "/tmp/names".to_string(),
+            name: name.to_string(),
+            is_relative: false,
+        };
+        assert!(matches!(
+            db.create_directory(request("bad\nname")).await,
+            Err(SqlxError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            db.create_directory(request(&"x".repeat(names::DEFAULT_MAX_NAME_LENGTH + 1))).await,
+            Err(SqlxError::InvalidArgument(_))
+        ));
+        assert!(db.get_directories().await.unwrap().is_empty());
+
+        let directory = db.create_directory(request("  names  ")).await.unwrap();
+        assert_eq!(directory.name, "names");
+        let rename = UpdateDirectoryRequest {
+            path: None,
+            name: Some("tab\there".to_string()),
+            is_relative: None,
+        };
+        assert!(db.update_directory(directory.id, rename).await.is_err());
+        assert_eq!(db.get_directory(directory.id).await.unwrap().name, "names");
+    }
+
+    #[tokio::test]
+    async fn test_set_directories_active_skips_locked() {
+        let db = Database::new_in_memory().await.unwrap();
+        let mut ids = Vec::new();
+        for name in ["a", "b", "c"] {
+            let directory = db
+                .create_directory(CreateDirectoryRequest {
+                    path: format!("/tmp/{}", name),
+                    name: name.to_string(),
+                    is_relative: false,
+                })
+                .await
+                .unwrap();
+            ids.push(directory.id);
+        }
+        db.set_directory_locked(ids[2], true).await.unwrap();
+
+        assert_eq!(db.set_directories_active(&ids, true).await.unwrap(), 2);
+        // 已是目标状态的目录不计入
+        assert_eq!(db.set_directories_active(&ids[..1], true).await.unwrap(), 0);
+        assert_eq!(db.set_directories_active(&ids, false).await.unwrap(), 2);
+        assert!(db.get_directories().await.unwrap().iter().all(|d| !d.is_active));
+    }
+
+    #[tokio::test]
+    async fn test_account_fallbacks_keep_order_and_follow_deletes() {
+        let db = Database::new_in_memory().await.unwrap();
+        let mut ids = Vec::new();
+        for name in ["primary", "backup-1", "backup-2"] {
+            let account = db
+                .create_account(CreateAccountRequest {
+                    name: name.to_string(),
+                    token: "sk-ant-test".to_string(),
+                    base_url: "https://api.anthropic.com".to_string(),
+                    model: String::new(),
+                    auth_style: AuthStyle::default(),
+                    proxy: ProxyConfig::default(),
+                    note: None,
+                extra_auth_env_key: None,
+                staging_base_url: None,
+                token_command: None,
+                })
+                .await
+                .unwrap();
+            ids.push(account.id);
+        }
+
+        db.set_account_fallbacks(ids[0], &[ids[2], ids[1]]).await.unwrap();
+        assert_eq!(db.get_account_fallbacks(ids[0]).await.unwrap(), vec![ids[2], ids[1]]);
+
+        // 删除的账号从其他账号的备用链中移除
+        db.delete_account(ids[2]).await.unwrap();
+        assert_eq!(db.get_account_fallbacks(ids[0]).await.unwrap(), vec![ids[1]]);
+    }
+}
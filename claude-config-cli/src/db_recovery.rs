@@ -0,0 +1,273 @@
+//! 数据库文件损坏时的引导恢复
+//!
+//! 启动时只读检查数据库文件，损坏时先复制一份到 `<文件名>.corrupt-<时间>`，再由用户选择
+//! 重新初始化空数据库，或从 WebDAV / 本地配置包恢复账号和 Base URL。用户选择退出时不修改原文件。
+
+use crate::{bundle, database::Database, models::WebDavConfig, t, webdav};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use console::Term;
+use dialoguer::{Input, Select};
+use serde_json::Value;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection};
+use sqlx::{Connection, Error as SqlxError};
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// SQLite 的 SQLITE_CORRUPT / SQLITE_NOTADB 错误
+fn is_corruption_error(e: &SqlxError) -> bool {
+    e.as_database_error()
+        .and_then(|e| e.code())
+        .and_then(|code| code.parse::<i32>().ok())
+        .is_some_and(|code| matches!(code & 0xff, 11 | 26))
+}
+
+async fn open_read_only(path: &Path) -> Result<SqliteConnection, SqlxError> {
+    let options = SqliteConnectOptions::new().filename(path).read_only(true);
+    SqliteConnection::connect_with(&options).await
+}
+
+/// 只读打开数据库并执行 quick_check，损坏时返回详情；
+/// 文件不存在或因其他原因（如被占用）无法打开时返回 None，交给正常的初始化流程处理
+pub async fn detect_corruption(path: &Path) -> Option<String> {
+    if !path.is_file() {
+        return None;
+    }
+
+    let mut conn = match open_read_only(path).await {
+        Ok(conn) => conn,
+        Err(e) => return is_corruption_error(&e).then(|| e.to_string()),
+    };
+    let result = sqlx::query_scalar::<_, String>("PRAGMA quick_check")
+        .fetch_all(&mut conn)
+        .await;
+    let _ = conn.close().await;
+
+    match result {
+        Ok(rows) if rows.iter().all(|row| row == "ok") => None,
+        Ok(rows) => Some(rows.join("; ")),
+        Err(e) => is_corruption_error(&e).then(|| e.to_string()),
+    }
+}
+
+/// 数据库文件及其 WAL / SHM 文件
+fn database_files(path: &Path) -> Vec<PathBuf> {
+    let mut files = vec![path.to_path_buf()];
+    for suffix in ["-wal", "-shm"] {
+        let mut file = path.as_os_str().to_os_string();
+        file.push(suffix);
+        files.push(PathBuf::from(file));
+    }
+    files
+}
+
+/// 把损坏的数据库（连同 WAL / SHM 文件）复制到 `<文件名>.corrupt-<时间>`，返回数据库副本的路径
+fn backup_corrupt_files(path: &Path) -> Result<PathBuf> {
+    let suffix = format!(".corrupt-{}", chrono::Local::now().format("%Y%m%d-%H%M%S"));
+    let mut backup = None;
+    for file in database_files(path).into_iter().filter(|file| file.exists()) {
+        let mut target = file.as_os_str().to_os_string();
+        target.push(&suffix);
+        let target = PathBuf::from(target);
+        std::fs::copy(&file, &target)
+            .with_context(|| format!("备份损坏的数据库失败: {}", file.display()))?;
+        backup.get_or_insert(target);
+    }
+    backup.context("数据库文件不存在")
+}
+
+/// 尽量从损坏的数据库中读出 WebDAV 配置，读取失败时返回空列表
+async fn salvage_webdav_configs(path: &Path) -> Vec<WebDavConfig> {
+    let Ok(mut conn) = open_read_only(path).await else {
+        return Vec::new();
+    };
+    let configs = sqlx::query_as::<_, WebDavConfig>("SELECT * FROM webdav_configs ORDER BY created_at DESC")
+        .fetch_all(&mut conn)
+        .await;
+    let _ = conn.close().await;
+    configs.unwrap_or_else(|e| {
+        warn!("无法从损坏的数据库读取 WebDAV 配置: {}", e);
+        Vec::new()
+    })
+}
+
+/// 从 WebDAV 下载备份，文件按名称倒序排列（默认文件名带时间，最新的在最前）
+async fn load_from_webdav(configs: &[WebDavConfig]) -> Result<Option<(WebDavConfig, String, Value)>> {
+    let config = if configs.len() == 1 {
+        &configs[0]
+    } else {
+        let items: Vec<String> = configs.iter().map(|c| format!("{} - {}", c.name, c.url)).collect();
+        match Select::new()
+            .with_prompt(t!("db.corrupt.select_webdav"))
+            .items(&items)
+            .default(0)
+            .interact_opt()?
+        {
+            Some(idx) => &configs[idx],
+            None => return Ok(None),
+        }
+    };
+
+    let manager = webdav::WebDavManager::from_config(config.clone()).await?;
+    let mut files = manager.list_remote_files().await?;
+    if files.is_empty() {
+        anyhow::bail!(t!("db.corrupt.no_files"));
+    }
+    files.sort_by(|a, b| b.cmp(a));
+
+    let Some(idx) = Select::new()
+        .with_prompt(t!("db.corrupt.select_file"))
+        .items(&files)
+        .default(0)
+        .interact_opt()?
+    else {
+        return Ok(None);
+    };
+    let data = manager.download_config(&files[idx]).await?;
+    Ok(Some((config.clone(), files[idx].clone(), data)))
+}
+
+/// 读取本地配置包文件（WebDAV 上传的 JSON）
+fn load_from_file() -> Result<Option<(String, Value)>> {
+    let path: String = Input::new()
+        .with_prompt(t!("db.corrupt.prompt_bundle_path"))
+        .allow_empty(true)
+        .interact_text()?;
+    let path = path.trim();
+    if path.is_empty() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path).with_context(|| format!("读取配置包失败: {}", path))?;
+    Ok(Some((path.to_string(), serde_json::from_str(&content)?)))
+}
+
+/// 删除损坏的数据库文件并创建新的数据库；残留的 WAL 会被应用到新文件上，一并删除
+async fn reinitialize(path: &Path) -> Result<Database> {
+    for file in database_files(path).into_iter().filter(|file| file.exists()) {
+        std::fs::remove_file(&file).with_context(|| format!("删除损坏的数据库失败: {}", file.display()))?;
+    }
+    Ok(Database::new().await?)
+}
+
+/// 引导用户从损坏的数据库中恢复，返回可用的新数据库；用户选择退出时返回错误，原文件保持不变
+pub async fn recover(path: &Path, detail: &str) -> Result<Database> {
+    println!(
+        "\n{}",
+        t!("db.corrupt.detected")
+            .replacen("{}", &path.display().to_string(), 1)
+            .replacen("{}", detail, 1)
+            .red()
+            .bold()
+    );
+    warn!("数据库文件已损坏: {} ({})", path.display(), detail);
+
+    // 无人值守（脚本、CI）时不做任何修改
+    if !Term::stdout().is_term() {
+        anyhow::bail!(t!("db.corrupt.non_interactive").replace("{}", &path.display().to_string()));
+    }
+
+    let backup = backup_corrupt_files(path)?;
+    println!("{}", t!("db.corrupt.backed_up").replace("{}", &backup.display().to_string()).green());
+    info!("已备份损坏的数据库到 {}", backup.display());
+
+    let webdav_configs = salvage_webdav_configs(&backup).await;
+
+    loop {
+        let mut items = vec![t!("db.corrupt.option_exit"), t!("db.corrupt.option_fresh")];
+        items.push(t!("db.corrupt.option_bundle"));
+        if !webdav_configs.is_empty() {
+            items.push(t!("db.corrupt.option_webdav"));
+        }
+
+        let selection = Select::new()
+            .with_prompt(t!("db.corrupt.prompt"))
+            .items(&items)
+            .default(0)
+            .interact_opt()?;
+
+        // 先取得备份数据，成功后再删除损坏的文件，失败时可以换一种方式
+        let (source, data, webdav_config) = match selection {
+            Some(1) => {
+                let db = reinitialize(path).await?;
+                info!("数据库恢复方式: 重新初始化空数据库");
+                println!("{}", t!("db.corrupt.fresh_done").green());
+                return Ok(db);
+            }
+            Some(2) => match load_from_file() {
+                Ok(Some((file, data))) => (file, data, None),
+                Ok(None) => continue,
+                Err(e) => {
+                    println!("{}", t!("db.corrupt.load_error").replace("{}", &e.to_string()).red());
+                    continue;
+                }
+            },
+            Some(3) => match load_from_webdav(&webdav_configs).await {
+                Ok(Some((config, file, data))) => (format!("WebDAV {}", file), data, Some(config)),
+                Ok(None) => continue,
+                Err(e) => {
+                    println!("{}", t!("db.corrupt.load_error").replace("{}", &e.to_string()).red());
+                    continue;
+                }
+            },
+            _ => anyhow::bail!(t!("db.corrupt.aborted")),
+        };
+
+        crate::menu::webdav::print_bundle_meta(&data);
+        let db = reinitialize(path).await?;
+        let summary = bundle::import_bundle(&db, &data, true).await?;
+        // 恢复所用的 WebDAV 配置一并写回，之后可以继续同步
+        if let Some(config) = webdav_config {
+            let created = webdav::create_webdav_config(
+                db.get_pool(),
+                &config.name,
+                &config.url,
+                &config.username,
+                &config.password,
+                &config.remote_path,
+                config.auto_sync,
+                config.sync_interval,
+            )
+            .await?;
+            webdav::update_webdav_namespace(db.get_pool(), created.id, config.namespace.as_deref()).await?;
+        }
+
+        info!(
+            "数据库恢复方式: 从 {} 恢复，账号 {} 个，Base URL {} 个",
+            source, summary.accounts, summary.base_urls
+        );
+        println!(
+            "{}",
+            t!("db.corrupt.restored")
+                .replacen("{}", &source, 1)
+                .replacen("{}", &summary.accounts.to_string(), 1)
+                .replacen("{}", &summary.base_urls.to_string(), 1)
+                .green()
+        );
+        return Ok(db);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_detect_corruption() {
+        let dir = std::env::temp_dir().join(format!("claude-config-db-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        // 不存在的文件交给正常流程创建
+        let path = dir.join("claude_config.db");
+        assert_eq!(detect_corruption(&path).await, None);
+
+        // 中断写入后残留的非数据库内容
+        std::fs::write(&path, vec![0x42u8; 8192]).unwrap();
+        assert!(detect_corruption(&path).await.is_some());
+
+        let backup = backup_corrupt_files(&path).unwrap();
+        assert!(backup.to_string_lossy().contains(".corrupt-"));
+        assert!(path.exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,94 @@
+//! 批量操作前按通配符或正则表达式筛选目录，匹配目录名称或路径
+//!
+//! 通配符整体匹配：`*` 不跨越路径分隔符，`**` 可跨越，`?` 匹配单个字符，开头的 `~` 展开为用户主目录。
+//! 正则表达式只要在名称或路径中找到匹配即可（需要整体匹配时自行加 `^` / `$`）。
+
+use regex::Regex;
+
+/// 筛选方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternKind {
+    Glob,
+    Regex,
+}
+
+/// 编译好的目录筛选条件
+#[derive(Debug)]
+pub struct DirectoryPattern {
+    regex: Regex,
+}
+
+impl DirectoryPattern {
+    /// 解析用户输入，无效的正则表达式返回错误信息
+    pub fn parse(kind: PatternKind, input: &str) -> Result<Self, String> {
+        let input = input.trim();
+        let source = match kind {
+            PatternKind::Glob => glob_to_regex(&expand_home(input)),
+            PatternKind::Regex => input.to_string(),
+        };
+        Regex::new(&source)
+            .map(|regex| Self { regex })
+            .map_err(|e| e.to_string())
+    }
+
+    /// 名称或路径任一匹配即可
+    pub fn matches(&self, name: &str, path: &str) -> bool {
+        self.regex.is_match(name) || self.regex.is_match(path)
+    }
+}
+
+/// 开头的 `~` 展开为用户主目录
+fn expand_home(input: &str) -> String {
+    let Some(rest) = input.strip_prefix('~') else {
+        return input.to_string();
+    };
+    if !(rest.is_empty() || rest.starts_with('/')) {
+        return input.to_string();
+    }
+    match directories::UserDirs::new() {
+        Some(dirs) => format!("{}{}", dirs.home_dir().display(), rest),
+        None => input.to_string(),
+    }
+}
+
+/// 通配符转为整体匹配的正则表达式
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                regex.push_str(".*");
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directory_pattern() {
+        let glob = DirectoryPattern::parse(PatternKind::Glob, "/home/me/work/*").unwrap();
+        assert!(glob.matches("api", "/home/me/work/api"));
+        assert!(!glob.matches("api", "/home/me/work/team/api"));
+        assert!(DirectoryPattern::parse(PatternKind::Glob, "/home/me/work/**")
+            .unwrap()
+            .matches("api", "/home/me/work/team/api"));
+        assert!(DirectoryPattern::parse(PatternKind::Glob, "*-api")
+            .unwrap()
+            .matches("billing-api", "/srv/billing"));
+
+        let regex = DirectoryPattern::parse(PatternKind::Regex, "-api$").unwrap();
+        assert!(regex.matches("billing-api", "/srv/billing"));
+        assert!(!regex.matches("api-docs", "/srv/docs"));
+        assert!(DirectoryPattern::parse(PatternKind::Regex, "(unclosed").is_err());
+    }
+}
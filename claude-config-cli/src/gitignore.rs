@@ -0,0 +1,82 @@
+//! 确保 git 仓库的 .gitignore 忽略本工具生成的本地配置文件，避免误提交 Token
+//!
+//! 只追加缺少的条目，已有内容（包括注释和空行）原样保留。
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// 需要忽略的生成文件，相对仓库根目录
+pub const GENERATED_ENTRIES: &[&str] = &[".claude/settings.local.json", "CLAUDE.local.md"];
+
+/// 目录是否为 git 仓库（或工作树、子模块）的根目录
+pub fn is_git_repo(directory: &Path) -> bool {
+    directory.join(".git").exists()
+}
+
+/// .gitignore 中已有的行是否忽略了该条目（允许以 `/` 锚定到根目录）
+fn covers(line: &str, entry: &str) -> bool {
+    line.trim().trim_start_matches('/') == entry
+}
+
+/// 向目录的 .gitignore 追加缺少的生成文件条目，返回追加的条目；
+/// 目录不是 git 仓库时不做任何修改
+pub fn ensure_generated_ignored(directory: &Path) -> io::Result<Vec<&'static str>> {
+    if !is_git_repo(directory) {
+        return Ok(Vec::new());
+    }
+
+    let path = directory.join(".gitignore");
+    let mut content = match fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    let missing: Vec<&'static str> = GENERATED_ENTRIES
+        .iter()
+        .copied()
+        .filter(|entry| !content.lines().any(|line| covers(line, entry)))
+        .collect();
+    if missing.is_empty() {
+        return Ok(missing);
+    }
+
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    for entry in &missing {
+        content.push_str(entry);
+        content.push('\n');
+    }
+    fs::write(&path, content)?;
+    tracing::info!("已向 {} 追加忽略条目: {:?}", path.display(), missing);
+    Ok(missing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ensure_generated_ignored() {
+        let dir = std::env::temp_dir().join(format!("claude-config-gitignore-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        // 不是 git 仓库时不创建 .gitignore
+        assert!(ensure_generated_ignored(&dir).unwrap().is_empty());
+        assert!(!dir.join(".gitignore").exists());
+
+        fs::create_dir(dir.join(".git")).unwrap();
+        fs::write(dir.join(".gitignore"), "# build\ntarget\n/CLAUDE.local.md").unwrap();
+        assert_eq!(ensure_generated_ignored(&dir).unwrap(), vec![".claude/settings.local.json"]);
+        assert_eq!(
+            fs::read_to_string(dir.join(".gitignore")).unwrap(),
+            "# build\ntarget\n/CLAUDE.local.md\n.claude/settings.local.json\n"
+        );
+
+        // 再次调用不会重复追加
+        assert!(ensure_generated_ignored(&dir).unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,119 @@
+//! 账号可用性后台检测：定期测试账号连通性并缓存最近一次结果，账号列表据此显示状态
+//!
+//! 检测逐个账号进行，遇到限流立即结束本轮，等下一轮再继续，避免冲击共享代理。
+
+use crate::{
+    connectivity::{self, ConnectivityResult},
+    database::Database,
+    models::{AccountHealth, GetAccountsRequest, TokenSource},
+    settings, t, DbState,
+};
+use chrono::Utc;
+use std::collections::HashMap;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// 检查是否有账号到期需要检测的间隔
+const TICK: Duration = Duration::from_secs(60);
+
+/// 单轮检测的账号数上限（数据库分页上限）
+const MAX_ACCOUNTS: i64 = 100;
+
+/// 启动后台检测任务；未开启时任务只定期读取设置，开启后立即生效
+pub fn spawn_refresher(db: DbState) {
+    tokio::spawn(async move {
+        loop {
+            let app_settings = settings::load_or_default().await;
+            if app_settings.health_check && !crate::offline::is_enabled() {
+                let interval = Duration::from_secs(app_settings.health_check_interval_mins.max(1) * 60);
+                let timeout = Duration::from_secs(app_settings.http_timeout_secs);
+                refresh_due(&db, interval, timeout).await;
+            }
+            tokio::time::sleep(TICK).await;
+        }
+    });
+}
+
+/// 检测上次结果已超过间隔（或从未检测）的账号
+async fn refresh_due(db: &DbState, interval: Duration, timeout: Duration) {
+    let db_lock = db.lock().await;
+    let accounts = db_lock
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(MAX_ACCOUNTS),
+            search: None,
+            base_url: None,
+        })
+        .await;
+    let health = db_lock.get_account_health().await;
+    drop(db_lock);
+
+    let (accounts, health) = match (accounts, health) {
+        (Ok(response), Ok(health)) => (response.accounts, health),
+        (Err(e), _) | (_, Err(e)) => {
+            warn!("后台检测读取账号失败: {}", e);
+            return;
+        }
+    };
+    let checked_at: HashMap<i64, _> = health.iter().map(|h| (h.account_id, h.checked_at)).collect();
+    let interval = chrono::Duration::from_std(interval).unwrap_or(chrono::Duration::MAX);
+
+    let client = reqwest::Client::new();
+    for account in accounts {
+        // 获取 Token 的命令可能需要用户解锁密码管理器，不在后台执行
+        if matches!(account.token_source(), TokenSource::Command(_)) {
+            continue;
+        }
+        let due = checked_at
+            .get(&account.id)
+            .is_none_or(|checked_at| Utc::now() - *checked_at >= interval);
+        if !due {
+            continue;
+        }
+
+        let result = connectivity::test_account(&client, &account, timeout).await;
+        save_result(&*db.lock().await, &result).await;
+        if result.throttled {
+            info!("后台检测账号 {} 时被限流，本轮检测提前结束", account.name);
+            break;
+        }
+    }
+}
+
+/// 缓存一次连通性测试结果；离线模式拒绝的请求不覆盖已有结果
+pub async fn save_result(db: &Database, result: &ConnectivityResult) {
+    if result.status.is_none() && crate::offline::is_enabled() {
+        return;
+    }
+    if let Err(e) = db
+        .save_account_health(result.account_id, result.status, result.error.as_deref())
+        .await
+    {
+        warn!("保存账号 {} 的检测结果失败: {}", result.name, e);
+    }
+}
+
+/// 账号可用性的显示级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthLevel {
+    Ok,
+    RateLimited,
+    Failed,
+    Unknown,
+}
+
+/// 可用性标签文本与级别；结果过期也照常显示，并附上检测时间
+pub fn badge(health: Option<&AccountHealth>) -> (String, HealthLevel) {
+    let Some(health) = health else {
+        return (t!("health.never").to_string(), HealthLevel::Unknown);
+    };
+
+    let (label, level) = match health.status {
+        Some(code) if (200..300).contains(&code) => (t!("health.ok").to_string(), HealthLevel::Ok),
+        Some(429) => (t!("health.rate_limited").to_string(), HealthLevel::RateLimited),
+        Some(code) => (t!("health.failed_status").replace("{}", &code.to_string()), HealthLevel::Failed),
+        None => (t!("health.unreachable").to_string(), HealthLevel::Failed),
+    };
+    let age = crate::menu::humanize_since(Some(health.checked_at));
+    (format!("{} · {}", label, age), level)
+}
@@ -0,0 +1,153 @@
+//! 切换后钩子：切换成功后执行用户配置的命令（如重载守护进程、发送通知），输出写入日志
+//!
+//! 目录路径、账号名和 Base URL 通过环境变量 `CLAUDE_HOOK_DIR`、`CLAUDE_HOOK_ACCOUNT`、`CLAUDE_HOOK_BASE_URL`
+//! 传给命令（Unix 下用 `"$CLAUDE_HOOK_DIR"`，Windows 下用 `"!CLAUDE_HOOK_DIR!"` 引用）。
+//! 命令模板中的 `{dir}`、`{account}`、`{base_url}` 会替换为对应的变量引用，值本身不会拼进命令行，
+//! 账号名或目录中的引号、`$(...)`、`%VAR%`、`&` 等都不会被 Shell 解释。
+
+use crate::t;
+use anyhow::{bail, Context, Result};
+use std::time::Duration;
+use tokio::process::Command;
+
+/// 钩子命令的最长执行时间，超时后终止
+const HOOK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 钩子命令模板中可用的占位符取值
+pub struct HookContext<'a> {
+    pub dir: &'a str,
+    pub account: &'a str,
+    pub base_url: &'a str,
+}
+
+impl HookContext<'_> {
+    /// 与 HOOK_VARIABLES 一一对应的取值
+    fn values(&self) -> [&str; 3] {
+        [self.dir, self.account, self.base_url]
+    }
+}
+
+/// 命令模板中的占位符及传给命令的环境变量名
+const HOOK_VARIABLES: [(&str, &str); 3] = [
+    ("{dir}", "CLAUDE_HOOK_DIR"),
+    ("{account}", "CLAUDE_HOOK_ACCOUNT"),
+    ("{base_url}", "CLAUDE_HOOK_BASE_URL"),
+];
+
+/// 按当前平台 Shell 的语法引用环境变量；Windows 使用延迟展开（`!VAR!`），
+/// 展开发生在命令解析之后，值中的 `&`、`|`、`%` 不会被 cmd 解释
+fn env_reference(name: &str) -> String {
+    if cfg!(windows) {
+        format!("\"!{}!\"", name)
+    } else {
+        format!("\"${}\"", name)
+    }
+}
+
+/// 把命令模板中的占位符替换为环境变量引用
+pub fn render_command(template: &str) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match HOOK_VARIABLES.iter().find(|(name, _)| rest.starts_with(name)) {
+            Some((name, variable)) => {
+                rendered.push_str(&env_reference(variable));
+                rest = &rest[name.len()..];
+            }
+            None => {
+                rendered.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// 通过当前平台的 Shell 执行命令
+///
+/// cmd 不按 MSVC 规则解析参数，Windows 下命令原样追加到 `cmd /C` 之后，不做任何转义。
+pub fn shell_command(command: &str) -> Command {
+    shell_command_with(command, false)
+}
+
+#[cfg(windows)]
+fn shell_command_with(command: &str, delayed_expansion: bool) -> Command {
+    let mut process = Command::new("cmd");
+    process
+        .raw_arg(if delayed_expansion { "/V:ON /C" } else { "/C" })
+        .raw_arg(command);
+    process
+}
+
+#[cfg(not(windows))]
+fn shell_command_with(command: &str, _delayed_expansion: bool) -> Command {
+    let mut process = Command::new("sh");
+    process.arg("-c").arg(command);
+    process
+}
+
+/// 在目标目录中通过 Shell 执行钩子命令，退出码非 0 或超时时返回错误
+pub async fn run_post_switch_hook(template: &str, ctx: &HookContext<'_>) -> Result<()> {
+    let command = render_command(template);
+    tracing::info!("执行切换后钩子: {}", command);
+
+    let mut process = shell_command_with(&command, true);
+    for ((_, variable), value) in HOOK_VARIABLES.iter().zip(ctx.values()) {
+        process.env(variable, value);
+    }
+    process.current_dir(ctx.dir).kill_on_drop(true);
+
+    let output = tokio::time::timeout(HOOK_TIMEOUT, process.output())
+        .await
+        .map_err(|_| anyhow::anyhow!(t!("hook.timeout").replace("{}", &HOOK_TIMEOUT.as_secs().to_string())))?
+        .with_context(|| t!("hook.spawn_failed").replace("{}", &command))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stdout.trim().is_empty() {
+        tracing::info!("切换后钩子 stdout:\n{}", stdout.trim_end());
+    }
+    if !stderr.trim().is_empty() {
+        tracing::warn!("切换后钩子 stderr:\n{}", stderr.trim_end());
+    }
+    if !output.status.success() {
+        bail!("{}", t!("hook.failed").replace("{}", &output.status.to_string()));
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_command_references_env_variables() {
+        assert_eq!(
+            render_command("notify {account} {dir} --url={base_url} {unknown}"),
+            "notify \"$CLAUDE_HOOK_ACCOUNT\" \"$CLAUDE_HOOK_DIR\" --url=\"$CLAUDE_HOOK_BASE_URL\" {unknown}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hook_values_are_not_interpreted_by_shell() {
+        let dir = std::env::temp_dir().join(format!("claude-config-hook-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir_str = dir.display().to_string();
+        let account = "it's \"me\" $(touch pwned) {dir} `touch pwned`; touch pwned";
+        let ctx = HookContext {
+            dir: &dir_str,
+            account,
+            base_url: "https://api.example.com",
+        };
+
+        run_post_switch_hook("printf '%s' {account} > out.txt", &ctx).await.unwrap();
+
+        assert_eq!(std::fs::read_to_string(dir.join("out.txt")).unwrap(), account);
+        assert!(!dir.join("pwned").exists());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -71,13 +71,35 @@ macro_rules! t {
 /// 翻译文本
 pub fn translate(key: TransKey) -> &'static str {
     let lang = current_language();
-    TRANSLATIONS
+    let translations = if crate::output::no_emoji() {
+        &PLAIN_TRANSLATIONS
+    } else {
+        &TRANSLATIONS
+    };
+    translations
         .get(&lang)
         .and_then(|map| map.get(key))
         .copied()
         .unwrap_or(key)
 }
 
+/// 去掉表情符号的翻译文本，首次使用无表情模式时生成（生成的字符串在程序运行期间一直使用）
+static PLAIN_TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> = Lazy::new(|| {
+    TRANSLATIONS
+        .iter()
+        .map(|(lang, map)| {
+            let plain = map
+                .iter()
+                .map(|(key, text)| {
+                    let text: &'static str = Box::leak(crate::output::strip_emoji(text).into_boxed_str());
+                    (*key, text)
+                })
+                .collect();
+            (*lang, plain)
+        })
+        .collect()
+});
+
 /// 所有翻译文本
 static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> = Lazy::new(|| {
     let mut translations = HashMap::new();
@@ -96,13 +118,50 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("menu.main.account", "📋 账号管理");
     zh_cn.insert("menu.main.directory", "📁 目录管理");
     zh_cn.insert("menu.main.url", "🌐 URL 管理");
+    zh_cn.insert("menu.main.template", "🧩 模板管理");
     zh_cn.insert("menu.main.switch", "⚡ 配置切换");
+    zh_cn.insert("menu.main.current_dir", "📍 配置当前目录");
+    zh_cn.insert("menu.main.repeat_switch", "🔁 快速重复上次切换");
+    zh_cn.insert("menu.main.quick_clear", "🧹 清除当前目录配置");
+    zh_cn.insert("menu.main.undo", "↩️  撤销最近的配置修改");
+    zh_cn.insert("menu.main.batch_switch", "📦 批量切换");
+    zh_cn.insert("menu.main.model", "🤖 模型选择");
+    zh_cn.insert("menu.main.profile", "🗂️  配置档案");
+    zh_cn.insert("menu.main.global", "🌐 全局配置");
+    zh_cn.insert("menu.main.doctor", "🩺 环境诊断");
+    zh_cn.insert("menu.hotkey.prompt", "[a] 账号  [d] 目录  [s] 切换  [h] 当前目录  [c] 清除当前目录  [u] 撤销  [w] WebDAV  [l] 日志  [m] 完整菜单  [q] 退出");
+    zh_cn.insert("menu.hotkey.invalid", "无效按键 '{}'，请重新输入");
     zh_cn.insert("menu.main.webdav", "☁️  WebDAV 同步");
     zh_cn.insert("menu.main.logs", "📝 查看日志");
     zh_cn.insert("menu.main.remove_root", "🔓 删除限制代码");
     zh_cn.insert("menu.main.settings", "⚙️  设置");
     zh_cn.insert("menu.main.language", "🌐 English");
     zh_cn.insert("menu.main.exit", "❌ 退出程序");
+    zh_cn.insert("undo.empty", "本次会话没有可撤销的配置修改");
+    zh_cn.insert("undo.summary", "将撤销 {} 的 {} 操作（目录 '{}'）");
+    zh_cn.insert("undo.path", "  文件: {}");
+    zh_cn.insert("undo.remaining", "  可撤销步数: {}");
+    zh_cn.insert("undo.will_delete", "修改前文件不存在，撤销将删除该文件");
+    zh_cn.insert("undo.confirm", "确认恢复为修改前的内容？");
+    zh_cn.insert("undo.success", "✓ 已恢复目录 '{}' 的配置");
+    zh_cn.insert("undo.error", "✗ 撤销失败: {}");
+    zh_cn.insert("model.menu.title", "模型选择");
+    zh_cn.insert("model.menu.directory", "🤖 设置目录使用的模型");
+    zh_cn.insert("model.menu.account_default", "👤 设置账号默认模型");
+    zh_cn.insert("model.pick.prompt", "选择模型");
+    zh_cn.insert("model.pick.current", "(当前)");
+    zh_cn.insert("model.pick.custom", "✏️  自定义模型...");
+    zh_cn.insert("model.pick.clear", "🧹 清除模型设置");
+    zh_cn.insert("model.pick.prompt_custom", "模型名称");
+    zh_cn.insert("model.pick.custom_empty", "模型名称不能为空");
+    zh_cn.insert("model.current", "当前模型: {}");
+    zh_cn.insert("model.directory.prompt", "选择目录");
+    zh_cn.insert("model.directory.set", "✓ 目录 '{}' 已使用模型 {}");
+    zh_cn.insert("model.directory.cleared", "✓ 已清除目录 '{}' 的模型设置");
+    zh_cn.insert("model.account.prompt", "选择账号");
+    zh_cn.insert("model.account.set", "✓ 账号 '{}' 的默认模型已设为 {}，切换到该账号时自动写入");
+    zh_cn.insert("model.account.cleared", "✓ 已清除账号 '{}' 的默认模型");
+    zh_cn.insert("model.error", "✗ 设置模型失败: {}");
 
     // 设置菜单
     zh_cn.insert("menu.settings.title", "设置");
@@ -111,11 +170,73 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("menu.settings.current_lang", "当前语言");
     zh_cn.insert("menu.settings.select_lang", "请选择语言");
     zh_cn.insert("menu.settings.lang_changed", "语言已切换");
+    zh_cn.insert("menu.settings.network", "⏱️  网络与同步设置");
+    zh_cn.insert("menu.settings.hotkey_menu", "⌨️  单键快捷菜单");
+    zh_cn.insert("menu.settings.hotkey_menu_on", "已开启单键快捷菜单，返回主菜单后生效");
+    zh_cn.insert("menu.settings.hotkey_menu_off", "已关闭单键快捷菜单");
+    zh_cn.insert("menu.settings.offline", "🔒 离线模式");
+    zh_cn.insert("menu.settings.offline_on", "已开启离线模式，连通性测试和 WebDAV 将被拒绝");
+    zh_cn.insert("menu.settings.offline_off", "已关闭离线模式");
+    zh_cn.insert("menu.settings.offline_forced", "当前由 --offline 参数启动，无法在设置中关闭");
+    zh_cn.insert("menu.settings.plain_output", "🔤 纯文本输出（无表情、无颜色）");
+    zh_cn.insert("menu.settings.plain_output_on", "已开启纯文本输出");
+    zh_cn.insert("menu.settings.plain_output_off", "已关闭纯文本输出");
+    zh_cn.insert("menu.settings.console_timestamps", "🕒 控制台消息显示时间和级别");
+    zh_cn.insert("menu.settings.console_timestamps_on", "已开启控制台时间和级别前缀，消息同时写入日志文件");
+    zh_cn.insert("menu.settings.console_timestamps_off", "已关闭控制台时间和级别前缀");
+    zh_cn.insert("menu.settings.preserve_format", "按原格式写回 YAML/TOML 配置");
+    zh_cn.insert("menu.settings.preserve_format_on", "已开启：生效配置为 YAML/TOML 时按原格式写回");
+    zh_cn.insert("menu.settings.preserve_format_off", "已关闭：配置始终写入 settings.local.json");
+    zh_cn.insert("menu.settings.indent", "JSON 配置缩进");
+    zh_cn.insert("menu.settings.indent_two", "2 个空格");
+    zh_cn.insert("menu.settings.indent_four", "4 个空格");
+    zh_cn.insert("menu.settings.indent_tab", "Tab");
+    zh_cn.insert("menu.settings.page_size", "每页显示条数");
+    zh_cn.insert("menu.settings.max_name_length", "名称最大长度");
+    zh_cn.insert("menu.settings.directory_env_summary", "📋 目录列表显示配置摘要");
+    zh_cn.insert("menu.settings.directory_env_summary_on", "已开启：目录列表将显示每个目录的 Base URL 主机和 Token 状态");
+    zh_cn.insert("menu.settings.directory_env_summary_off", "已关闭目录列表的配置摘要");
+    zh_cn.insert("menu.settings.gitignore_generated", "生成文件加入 .gitignore");
+    zh_cn.insert("menu.settings.gitignore_generated_on", "✓ 切换账号后会确保 git 仓库的 .gitignore 忽略 settings.local.json 和 CLAUDE.local.md");
+    zh_cn.insert("menu.settings.gitignore_generated_off", "✓ 不再修改 .gitignore");
+    zh_cn.insert("menu.settings.allow_symlinked_claude_dir", "允许 .claude 符号链接到目录之外");
+    zh_cn.insert("menu.settings.allow_symlinked_claude_dir_on", "✓ .claude 指向目录之外时不再提示");
+    zh_cn.insert("menu.settings.allow_symlinked_claude_dir_off", "✓ .claude 指向目录之外时写入前需要确认");
+    zh_cn.insert("menu.settings.omit_empty_env", "空值环境变量不写入");
+    zh_cn.insert("menu.settings.omit_empty_env_on", "✓ 值为空的环境变量将不写入，已有的同名变量会被移除");
+    zh_cn.insert("menu.settings.omit_empty_env_off", "✓ 值为空的环境变量将以空字符串写入");
+    zh_cn.insert("menu.settings.post_switch_hook", "切换后钩子");
+    zh_cn.insert("menu.settings.post_switch_hook_enable", "启用切换后钩子？（会以当前用户身份执行命令）");
+    zh_cn.insert("menu.settings.post_switch_hook_hint", "可用占位符: {dir} 目录路径、{account} 账号名、{base_url} Base URL，也可直接引用环境变量 CLAUDE_HOOK_DIR、CLAUDE_HOOK_ACCOUNT、CLAUDE_HOOK_BASE_URL（Unix: $VAR，Windows: !VAR!），输出写入日志");
+    zh_cn.insert("menu.settings.post_switch_hook_command", "钩子命令");
+    zh_cn.insert("menu.settings.post_switch_hook_empty", "命令不能为空");
+    zh_cn.insert("menu.settings.post_switch_hook_strict", "钩子执行失败时将切换视为失败？");
+    zh_cn.insert("menu.settings.bulk_confirm_threshold", "批量操作输入条数确认阈值");
+    zh_cn.insert("menu.settings.health_check", "后台检测账号可用性");
+    zh_cn.insert("menu.settings.health_check_every", "每 {} 分钟");
+    zh_cn.insert("menu.settings.health_check_enable", "是否在后台定期检测账号可用性？");
+    zh_cn.insert("menu.settings.health_check_interval", "检测间隔（分钟）");
+    zh_cn.insert("menu.settings.health_check_offline", "当前为离线模式，关闭离线模式后才会开始检测");
+    zh_cn.insert("menu.settings.export", "💾 导出应用设置");
+    zh_cn.insert("menu.settings.import", "📥 恢复应用设置");
+    zh_cn.insert("menu.settings.backup_path", "备份文件路径");
+    zh_cn.insert("menu.settings.include_secrets", "是否包含 WebDAV 密码？");
+    zh_cn.insert("menu.settings.import_confirm", "恢复将覆盖当前的应用设置，并按名称更新 WebDAV 配置，是否继续？");
+    zh_cn.insert("menu.settings.file", "设置文件: {}");
+    zh_cn.insert("menu.settings.http_timeout", "HTTP 超时（秒）");
+    zh_cn.insert("menu.settings.max_retries", "最大重试次数");
+    zh_cn.insert("menu.settings.sync_interval", "同步间隔（分钟）");
+    zh_cn.insert("menu.settings.workspace_root", "工作区根目录");
+    zh_cn.insert("menu.settings.workspace_root_hint", "留空则使用 $WORKSPACE 或用户主目录");
+    zh_cn.insert("menu.settings.saved", "设置已保存");
+    zh_cn.insert("menu.settings.must_be_positive", "请输入大于 0 的整数");
 
     // 通用操作
     zh_cn.insert("common.success", "✓ 操作成功");
     zh_cn.insert("common.error", "✗ 操作失败");
     zh_cn.insert("common.cancel", "操作已取消");
+    zh_cn.insert("common.enabled", "已开启");
+    zh_cn.insert("common.disabled", "已关闭");
     zh_cn.insert("common.back", "返回");
     zh_cn.insert("common.back_cancel", "🔙 取消");
     zh_cn.insert("common.continue", "按 Enter 继续");
@@ -128,6 +249,23 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
 
     // 数据库
     zh_cn.insert("db.init", "正在初始化数据库...");
+    zh_cn.insert("db.ephemeral", "使用内存数据库（--ephemeral），退出后数据不会保留");
+    zh_cn.insert("db.corrupt.detected", "✗ 数据库文件已损坏: {} ({})");
+    zh_cn.insert("db.corrupt.non_interactive", "数据库文件已损坏，请在终端中直接运行 claude-config 按提示恢复: {}");
+    zh_cn.insert("db.corrupt.backed_up", "已将损坏的数据库备份到: {}");
+    zh_cn.insert("db.corrupt.prompt", "选择恢复方式");
+    zh_cn.insert("db.corrupt.option_exit", "退出（不修改数据库文件）");
+    zh_cn.insert("db.corrupt.option_fresh", "重新初始化空数据库（现有数据将丢失）");
+    zh_cn.insert("db.corrupt.option_bundle", "从本地配置包文件恢复");
+    zh_cn.insert("db.corrupt.option_webdav", "从 WebDAV 备份恢复");
+    zh_cn.insert("db.corrupt.select_webdav", "选择 WebDAV 配置");
+    zh_cn.insert("db.corrupt.select_file", "选择备份文件（最新的在最前）");
+    zh_cn.insert("db.corrupt.no_files", "WebDAV 上没有备份文件");
+    zh_cn.insert("db.corrupt.prompt_bundle_path", "配置包文件路径（留空返回）");
+    zh_cn.insert("db.corrupt.load_error", "✗ 读取备份失败: {}");
+    zh_cn.insert("db.corrupt.fresh_done", "✓ 已重新初始化空数据库");
+    zh_cn.insert("db.corrupt.restored", "✓ 已从 {} 恢复 {} 个账号、{} 个 Base URL");
+    zh_cn.insert("db.corrupt.aborted", "已退出，数据库文件未修改");
     zh_cn.insert("db.init_success", "✓ 数据库初始化成功");
     zh_cn.insert("db.init_error", "✗ 数据库初始化失败");
     zh_cn.insert("db.fallback", "尝试使用默认配置创建数据库...");
@@ -139,21 +277,67 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("account.menu.list", "📝 查看所有账号");
     zh_cn.insert("account.menu.add", "➕ 添加新账号");
     zh_cn.insert("account.menu.edit", "✏️  编辑账号");
+    zh_cn.insert("account.menu.rename", "🏷️  重命名账号");
+    zh_cn.insert("account.menu.clone", "📋 复制账号");
     zh_cn.insert("account.menu.delete", "🗑️  删除账号");
+    zh_cn.insert("account.menu.batch_delete", "🧹 批量删除账号");
+    zh_cn.insert("account.menu.test_all", "🩺 测试所有账号");
+    zh_cn.insert("account.menu.claude_md", "📝 CLAUDE.local.md 模板");
+    zh_cn.insert("account.menu.export_snippet", "📤 导出分享片段（不含 Token）");
+    zh_cn.insert("account.menu.import_snippet", "📥 从分享片段导入");
+    zh_cn.insert("account.menu.fallbacks", "🔗 设置备用账号链");
     zh_cn.insert("account.list.no_records", "暂无账号记录");
     zh_cn.insert("account.list.header_id", "ID");
     zh_cn.insert("account.list.header_name", "账号名称");
     zh_cn.insert("account.list.header_base_url", "Base URL");
     zh_cn.insert("account.list.header_model", "模型");
+    zh_cn.insert("account.list.header_auth_style", "认证方式");
     zh_cn.insert("account.list.header_status", "状态");
+    zh_cn.insert("account.list.header_health", "可用性");
+    zh_cn.insert("account.list.header_note", "备注");
     zh_cn.insert("account.list.status_active", "🟢 活跃");
     zh_cn.insert("account.list.status_inactive", "⚪ 未活跃");
-    zh_cn.insert("account.list.total", "共 {} 个账号");
+    zh_cn.insert("account.test_all.running", "正在测试 {} 个账号的连通性...");
+    zh_cn.insert("account.test_all.header_status", "HTTP 状态");
+    zh_cn.insert("account.test_all.header_latency", "延迟");
+    zh_cn.insert("account.test_all.header_token", "Token");
+    zh_cn.insert("account.test_all.summary", "成功 {} 个，失败 {} 个");
+    zh_cn.insert("account.test_all.timeout", "超时（{} 秒）");
+    zh_cn.insert("account.test_all.progress", "测试账号");
+    zh_cn.insert("account.test_all.rate_limited", "限流");
+    zh_cn.insert("health.never", "未检测");
+    zh_cn.insert("health.ok", "可用");
+    zh_cn.insert("health.rate_limited", "限流");
+    zh_cn.insert("health.failed_status", "失败 ({})");
+    zh_cn.insert("health.unreachable", "无法连接");
+    zh_cn.insert("account.test_all.rate_limited_summary", "⚠ 测试中遇到限流，已按 Retry-After 重试并降低并发，重试后仍被限流 {} 个");
     zh_cn.insert("account.add.title", "添加新账号");
     zh_cn.insert("account.add.prompt_name", "账号名称");
     zh_cn.insert("account.add.prompt_token", "API Token");
+    zh_cn.insert("account.token_source.prompt", "Token 来源");
+    zh_cn.insert("account.token_source.inline", "直接输入 Token");
+    zh_cn.insert("account.token_source.command", "通过命令获取（pass、op 等，不保存 Token）");
+    zh_cn.insert("account.token_source.prompt_command", "获取 Token 的命令，标准输出即 Token（回车取消）");
+    zh_cn.insert("account.list.token_from_command", "命令获取");
     zh_cn.insert("account.add.prompt_base_url", "Base URL");
     zh_cn.insert("account.add.prompt_model", "模型");
+    zh_cn.insert("account.add.select_auth_style", "选择认证方式");
+    zh_cn.insert("account.add.prompt_configure_proxy", "为该账号配置代理吗?");
+    zh_cn.insert("account.add.prompt_http_proxy", "HTTP_PROXY（留空表示不设置）");
+    zh_cn.insert("account.add.prompt_https_proxy", "HTTPS_PROXY（留空表示不设置）");
+    zh_cn.insert("account.add.prompt_no_proxy", "NO_PROXY（逗号分隔，留空表示不设置）");
+    zh_cn.insert("account.add.prompt_note", "备注（可选，清空表示删除）");
+    zh_cn.insert("account.add.prompt_extra_auth_env_key", "额外写入 Token 的环境变量名（如 OPENAI_API_KEY，留空不写入）");
+    zh_cn.insert("account.add.prompt_staging_base_url", "备用 Base URL（用于临时切换到测试地址，留空不设置）");
+    zh_cn.insert("account.add.invalid_staging_base_url", "备用 Base URL 格式无效");
+    zh_cn.insert("account.add.invalid_env_key", "变量名只能包含字母、数字和下划线，且不能以数字开头");
+    zh_cn.insert("name.empty", "名称不能为空");
+    zh_cn.insert("name.too_long", "名称最多 {} 个字符（当前 {} 个）");
+    zh_cn.insert("name.control_char", "名称不能包含换行或其他控制字符");
+    zh_cn.insert("account.add.invalid_proxy", "代理地址无效，例如 http://proxy.example.com:8080");
+    zh_cn.insert("account.auth_style.both", "两者都写入 (ANTHROPIC_API_KEY + ANTHROPIC_AUTH_TOKEN)");
+    zh_cn.insert("account.auth_style.api_key", "仅 x-api-key (ANTHROPIC_API_KEY)");
+    zh_cn.insert("account.auth_style.bearer_token", "仅 Bearer (ANTHROPIC_AUTH_TOKEN)");
     zh_cn.insert("account.add.no_base_url", "暂无可用的 Base URL，请手动输入");
     zh_cn.insert("account.add.select_base_url", "选择 Base URL");
     zh_cn.insert("account.add.success", "✓ 账号 '{}' 创建成功");
@@ -161,10 +345,57 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("account.edit.prompt", "选择要编辑的账号");
     zh_cn.insert("account.edit.success", "✓ 账号更新成功");
     zh_cn.insert("account.edit.error", "✗ 更新失败: {}");
+    zh_cn.insert("account.rename.prompt", "选择要重命名的账号");
+    zh_cn.insert("account.rename.prompt_name", "新名称");
+    zh_cn.insert("account.rename.duplicate", "✗ 已存在名为 {} 的账号");
+    zh_cn.insert("account.rename.success", "✓ 已将账号 {} 重命名为 {}");
+    zh_cn.insert("account.rename.user_name_hint", "目录配置中的 USER_NAME 会在下次切换时更新，档案和目录关联不受影响");
+    zh_cn.insert("account.token.empty", "Token 不能为空");
+    zh_cn.insert("account.token.whitespace", "Token 中不能包含空格或换行");
+    zh_cn.insert("account.token.unexpected_prefix", "⚠️  Token 不是以 sk-ant- 或 sk- 开头，请确认是否输入正确");
+    zh_cn.insert("account.swap.base_url_looks_like_token", "⚠ Base URL 看起来像 Token（以 sk-ant- 开头或不是网址），可能与 Token 填反了");
+    zh_cn.insert("account.swap.token_looks_like_url", "⚠ Token 看起来像网址（以 http 开头），可能与 Base URL 填反了");
+    zh_cn.insert("account.swap.confirm", "部分代理格式特殊，确定仍要继续吗?");
+    zh_cn.insert("account.clone.prompt", "选择要复制的账号");
+    zh_cn.insert("account.snippet.export_prompt", "选择要导出的账号");
+    zh_cn.insert("account.snippet.format_hint", "文件以 .env 结尾时导出为环境变量格式，否则为 JSON");
+    zh_cn.insert("account.snippet.path", "片段文件路径");
+    zh_cn.insert("account.snippet.exported", "✓ 已导出分享片段: {}");
+    zh_cn.insert("account.snippet.placeholder_note", "Token 已替换为 {}，导入时需要填写自己的 Token");
+    zh_cn.insert("account.snippet.import_title", "分享片段中的配置：");
+    zh_cn.insert("account.snippet.error", "✗ 处理分享片段失败: {}");
+    zh_cn.insert("account.clone.title", "复制账号");
+    zh_cn.insert("account.clone.name_suffix", "{} (副本)");
+    zh_cn.insert("account.clone.keep_base_url", "保留源账号的地址 ({})");
     zh_cn.insert("account.delete.prompt", "选择要删除的账号");
     zh_cn.insert("account.delete.confirm", "确定要删除账号 '{}' 吗?");
     zh_cn.insert("account.delete.success", "✓ 账号删除成功");
     zh_cn.insert("account.delete.error", "✗ 删除失败: {}");
+    zh_cn.insert("account.fallbacks.need_two", "至少需要两个账号才能设置备用账号链");
+    zh_cn.insert("account.fallbacks.select_account", "选择主账号");
+    zh_cn.insert("account.fallbacks.none", "账号 {} 还没有备用账号");
+    zh_cn.insert("account.fallbacks.current", "账号 {} 当前的备用账号链: {}");
+    zh_cn.insert("account.fallbacks.hint", "按尝试顺序依次选择备用账号，选择“完成”保存（不选任何账号即清除），ESC 放弃修改");
+    zh_cn.insert("account.fallbacks.done", "✓ 完成");
+    zh_cn.insert("account.fallbacks.select_next", "第 {} 个备用账号");
+    zh_cn.insert("account.fallbacks.saved", "✓ 已保存 {} 的备用账号链: {}");
+    zh_cn.insert("account.fallbacks.cleared", "✓ 已清除 {} 的备用账号链");
+    zh_cn.insert("account.fallbacks.error", "✗ 保存备用账号链失败: {}");
+    zh_cn.insert("account.delete.profile_warning", "⚠ 以下配置档案引用了要删除的账号，删除后这些档案将不再关联账号: {}");
+    zh_cn.insert("account.batch_delete.prompt", "选择要删除的账号（空格选择，回车确认）");
+    zh_cn.insert("account.batch_delete.header_in_use", "正在使用的目录");
+    zh_cn.insert("account.batch_delete.in_use_warning", "⚠ 其中 {} 个账号正被目录使用，删除后这些目录的配置文件不会改变，但不再关联账号");
+    zh_cn.insert("account.batch_delete.confirm", "确定删除以上 {} 个账号吗?");
+    zh_cn.insert("account.batch_delete.success", "✓ 已删除 {} 个账号");
+    zh_cn.insert("account.claude_md.prompt", "选择要设置 CLAUDE.local.md 模板的账号");
+    zh_cn.insert("account.claude_md.custom", "自定义模板");
+    zh_cn.insert("account.claude_md.default", "内置模板");
+    zh_cn.insert("account.claude_md.action", "选择操作");
+    zh_cn.insert("account.claude_md.edit", "在编辑器中编辑");
+    zh_cn.insert("account.claude_md.restore_default", "恢复使用内置模板");
+    zh_cn.insert("account.claude_md.saved", "✓ 已保存账号 {} 的 CLAUDE.local.md 模板，下次切换时写入");
+    zh_cn.insert("account.claude_md.using_default", "✓ 账号 {} 将使用内置的 CLAUDE.local.md 模板");
+    zh_cn.insert("account.claude_md.error", "✗ 保存失败: {}");
     zh_cn.insert("account.default_indicator", "(默认)");
 
     // 目录管理
@@ -173,28 +404,199 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("directory.menu.add", "➕ 添加新目录");
     zh_cn.insert("directory.menu.edit", "✏️  编辑目录");
     zh_cn.insert("directory.menu.delete", "🗑️  删除目录");
+    zh_cn.insert("directory.menu.edit_settings", "📄 编辑配置文件");
+    zh_cn.insert("directory.menu.repair_settings", "🔧 修复配置");
+    zh_cn.insert("directory.menu.audit", "🔍 核对配置与账号");
+    zh_cn.insert("directory.menu.validate_settings", "✅ 校验配置");
+    zh_cn.insert("directory.menu.effective_env", "🔎 查看生效环境变量");
+    zh_cn.insert("directory.menu.trash", "♻️  回收站");
+    zh_cn.insert("directory.menu.reset", "🧹 重置目录配置");
+    zh_cn.insert("directory.menu.merge_settings", "🔀 合并配置文件");
+    zh_cn.insert("directory.menu.history", "📜 查看变更历史");
+    zh_cn.insert("directory.menu.migrate_legacy", "📦 迁移旧配置文件");
+    zh_cn.insert("directory.menu.lock", "🔒 锁定/解锁目录");
+    zh_cn.insert("directory.lock.marker", "🔒");
+    zh_cn.insert("directory.lock.prompt", "选择要锁定或解锁的目录");
+    zh_cn.insert("directory.lock.confirm_lock", "确定锁定目录 '{}'？锁定后将禁止切换、清除和重置配置");
+    zh_cn.insert("directory.lock.confirm_unlock", "确定解锁目录 '{}'？");
+    zh_cn.insert("directory.lock.locked", "✓ 目录 '{}' 已锁定");
+    zh_cn.insert("directory.lock.unlocked", "✓ 目录 '{}' 已解锁");
+    zh_cn.insert("directory.lock.refused", "⚠ 该目录已锁定: '{}'，请先解锁再修改配置");
+    zh_cn.insert("directory.lock.error", "✗ 更新锁定状态失败: {}");
+    zh_cn.insert("directory.menu.copy_config", "📋 从其他目录复制配置");
+    zh_cn.insert("directory.menu.mcp", "🧩 管理 MCP 服务器");
+    zh_cn.insert("directory.menu.external_changes", "🔎 检测外部修改");
+    zh_cn.insert("directory.menu.clipboard_env", "📋 从剪贴板导入环境变量");
+    zh_cn.insert("directory.menu.claude_md_report", "📄 CLAUDE.local.md 模板对比");
+    zh_cn.insert("directory.menu.deactivate_all", "⏸️  全部取消激活");
+    zh_cn.insert("directory.menu.activate_selected", "▶️  激活选中目录");
+    zh_cn.insert("directory.menu.expected_account", "📌 设置预期账号");
+    zh_cn.insert("directory.expected.current", "预期: {}");
+    zh_cn.insert("directory.expected.select_directory", "选择要设置预期账号的目录");
+    zh_cn.insert("directory.expected.select_account", "选择预期账号");
+    zh_cn.insert("directory.expected.none", "(不设置预期账号)");
+    zh_cn.insert("directory.expected.set", "✓ 目录 {} 的预期账号已设置为 {}");
+    zh_cn.insert("directory.expected.cleared", "✓ 已取消目录 {} 的预期账号");
+    zh_cn.insert("directory.expected.error", "✗ 设置预期账号失败: {}");
+    zh_cn.insert("directory.copy_config.need_two", "至少需要两个目录才能复制配置");
+    zh_cn.insert("directory.copy_config.source_prompt", "选择源目录（从这里复制）");
+    zh_cn.insert("directory.copy_config.target_prompt", "选择目标目录（复制到这里）");
+    zh_cn.insert("directory.copy_config.same", "源目录和目标目录相同");
+    zh_cn.insert("directory.copy_config.confirm_overwrite", "目录 '{}' 已有配置，覆盖其中的环境变量？");
+    zh_cn.insert("directory.copy_config.include_claude_md", "是否同时复制 CLAUDE.local.md？");
+    zh_cn.insert("directory.copy_config.success", "✓ 已从 '{}' 复制配置到 '{}'");
+    zh_cn.insert("directory.copy_config.env_keys", "复制的环境变量: {}");
+    zh_cn.insert("directory.copy_config.no_env", "源目录没有环境变量，未修改目标目录的环境变量");
+    zh_cn.insert("directory.copy_config.claude_md_copied", "已复制 CLAUDE.local.md");
+    zh_cn.insert("directory.copy_config.claude_md_missing", "源目录没有 CLAUDE.local.md，已跳过");
+    zh_cn.insert("directory.copy_config.error", "✗ 复制配置失败: {}");
+    zh_cn.insert("directory.active.none_active", "没有处于激活状态的目录");
+    zh_cn.insert("directory.active.deactivated", "✓ 已取消激活 {} 个目录");
+    zh_cn.insert("directory.active.activated", "✓ 已激活 {} 个目录");
+    zh_cn.insert("directory.active.locked_skipped", "已跳过锁定的目录: {}");
+    zh_cn.insert("directory.active.select_prompt", "选择要激活的目录（空格选择，回车确认）");
+    zh_cn.insert("directory.active.error", "✗ 更新激活状态失败: {}");
+    zh_cn.insert("directory.claude_md_report.header_status", "CLAUDE.local.md");
+    zh_cn.insert("directory.claude_md_report.identical", "与模板一致");
+    zh_cn.insert("directory.claude_md_report.outdated", "旧版模板（未修改）");
+    zh_cn.insert("directory.claude_md_report.modified", "已修改");
+    zh_cn.insert("directory.claude_md_report.missing", "不存在");
+    zh_cn.insert("directory.claude_md_report.error", "读取失败: {}");
+    zh_cn.insert("directory.claude_md_report.confirm_refresh", "有 {} 个目录使用旧版模板且未修改，刷新为当前模板？（修改过的文件不受影响）");
+    zh_cn.insert("directory.claude_md_report.refresh_error", "✗ 刷新目录 '{}' 失败: {}");
+    zh_cn.insert("directory.symlink_escape.warning", "⚠ 目录 {} 的 .claude 实际指向目录之外: {}");
+    zh_cn.insert("directory.symlink_escape.hint", "配置将写入上述位置。如果是有意共享 .claude，可在设置中允许符号链接");
+    zh_cn.insert("directory.symlink_escape.confirm", "仍然写入？");
+    zh_cn.insert("directory.clipboard_env.read_failed", "✗ 读取剪贴板失败: {}");
+    zh_cn.insert("directory.clipboard_env.empty", "剪贴板中没有 KEY=VALUE 格式的环境变量");
+    zh_cn.insert("directory.clipboard_env.invalid", "✗ 剪贴板内容不是环境变量：第 {} 行不是合法的 KEY=VALUE");
+    zh_cn.insert("directory.clipboard_env.found", "剪贴板中找到 {} 个环境变量（值已遮盖）:");
+    zh_cn.insert("directory.clipboard_env.prompt", "选择要合并环境变量的目录");
+    zh_cn.insert("directory.clipboard_env.confirm", "确认合并到目录 '{}' 的 env？同名变量将被覆盖");
+    zh_cn.insert("directory.clipboard_env.success", "✓ 已合并 {} 个环境变量到目录 '{}'");
+    zh_cn.insert("directory.clipboard_env.error", "✗ 合并环境变量失败: {}");
     zh_cn.insert("directory.list.no_records", "暂无目录记录");
+    zh_cn.insert("mcp.select_directory", "选择要管理 MCP 服务器的目录");
+    zh_cn.insert("mcp.menu.title", "MCP 服务器");
+    zh_cn.insert("mcp.menu.list", "📋 查看服务器");
+    zh_cn.insert("mcp.menu.set", "➕ 新增/编辑服务器");
+    zh_cn.insert("mcp.menu.remove", "🗑️  删除服务器");
+    zh_cn.insert("mcp.list.empty", "当前目录没有配置 MCP 服务器");
+    zh_cn.insert("mcp.header.name", "名称");
+    zh_cn.insert("mcp.header.command", "命令");
+    zh_cn.insert("mcp.header.args", "参数");
+    zh_cn.insert("mcp.header.env", "环境变量");
+    zh_cn.insert("mcp.header.other", "其他字段");
+    zh_cn.insert("mcp.set.prompt_name", "服务器名称（已存在时编辑）");
+    zh_cn.insert("mcp.set.editing", "正在编辑已有服务器: {}");
+    zh_cn.insert("mcp.set.prompt_command", "命令");
+    zh_cn.insert("mcp.set.prompt_args", "参数（以空格分隔）");
+    zh_cn.insert("mcp.set.current_env", "当前环境变量: {}");
+    zh_cn.insert("mcp.set.env_hint", "输入 KEY=VALUE 设置环境变量，KEY= 删除变量，直接按 Enter 结束");
+    zh_cn.insert("mcp.set.prompt_env", "环境变量");
+    zh_cn.insert("mcp.set.env_invalid", "格式无效，应为 KEY=VALUE");
+    zh_cn.insert("mcp.set.command_required", "命令不能为空，未保存");
+    zh_cn.insert("mcp.set.success", "✓ 已保存 MCP 服务器: {}");
+    zh_cn.insert("mcp.remove.prompt", "选择要删除的服务器");
+    zh_cn.insert("mcp.remove.confirm", "确定删除 MCP 服务器 {} 吗？");
+    zh_cn.insert("mcp.remove.success", "✓ 已删除 MCP 服务器: {}");
+    zh_cn.insert("mcp.error", "MCP 服务器配置操作失败: {}");
+    zh_cn.insert("external.header.name", "目录");
+    zh_cn.insert("external.header.state", "状态");
+    zh_cn.insert("external.header.written_at", "最后写入时间");
+    zh_cn.insert("external.header.path", "配置文件");
+    zh_cn.insert("external.state.unchanged", "未修改");
+    zh_cn.insert("external.state.modified", "已被外部修改");
+    zh_cn.insert("external.state.deleted", "已被外部删除");
+    zh_cn.insert("external.state.created", "已被外部创建");
+    zh_cn.insert("external.state.no_record", "未通过本工具写入");
+    zh_cn.insert("external.none", "✓ 所有目录的配置都与最后一次写入时一致");
+    zh_cn.insert("external.summary", "{} 个目录的配置在本工具最后一次写入后被修改");
+    zh_cn.insert("external.diff_prompt", "选择目录查看与最后一次写入的差异");
+    zh_cn.insert("external.diff_title", "差异（- 最后一次写入，+ 当前文件）: {}");
+    zh_cn.insert("external.no_snapshot", "最后一次写入的配置文件过大或包含命令获取的 Token，没有保存快照，无法显示差异");
     zh_cn.insert("directory.list.header_id", "ID");
     zh_cn.insert("directory.list.header_name", "目录名称");
     zh_cn.insert("directory.list.header_path", "路径");
     zh_cn.insert("directory.list.header_exists", "存在性");
     zh_cn.insert("directory.list.exists", "✓ 存在");
     zh_cn.insert("directory.list.not_exists", "✗ 不存在");
-    zh_cn.insert("directory.list.total", "共 {} 个目录");
+    zh_cn.insert("directory.list.header_last_switched", "最近切换");
+    zh_cn.insert("directory.list.header_base_url", "Base URL 主机");
+    zh_cn.insert("directory.list.header_token", "Token");
+    zh_cn.insert("directory.list.relative", "(相对)");
     zh_cn.insert("directory.add.title", "添加新目录");
     zh_cn.insert("directory.add.prompt_name", "目录名称");
     zh_cn.insert("directory.add.prompt_path", "路径");
     zh_cn.insert("directory.add.warn_path_not_exists", "⚠️  警告: 该路径不存在");
+    zh_cn.insert("directory.add.relative_hint", "相对路径将基于工作区根目录解析为: {}");
+    zh_cn.insert("directory.add.prompt_relative", "该路径位于工作区根目录 {} 下，是否以相对路径保存（便于跨机器同步）？");
     zh_cn.insert("directory.add.success", "✓ 目录 '{}' 添加成功");
     zh_cn.insert("directory.add.error", "✗ 添加失败: {}");
+    zh_cn.insert("directory.add.path_not_exists", "路径不存在: {}");
     zh_cn.insert("directory.edit.prompt", "选择要编辑的目录");
     zh_cn.insert("directory.edit.success", "✓ 目录更新成功");
     zh_cn.insert("directory.edit.error", "✗ 更新失败: {}");
+    zh_cn.insert("directory.edit.no_changes", "无变更");
+    zh_cn.insert("directory.edit.header_field", "字段");
+    zh_cn.insert("directory.edit.header_old", "原值");
+    zh_cn.insert("directory.edit.header_new", "新值");
+    zh_cn.insert("directory.edit.confirm", "确认保存以上修改？");
     zh_cn.insert("directory.delete.prompt", "选择要删除的目录");
     zh_cn.insert("directory.delete.confirm", "确定要删除目录 '{}' 吗?");
-    zh_cn.insert("directory.delete.warning", "(仅删除数据库记录，不删除实际文件)");
-    zh_cn.insert("directory.delete.success", "✓ 目录删除成功");
+    zh_cn.insert("directory.delete.warning", "(移入回收站，不删除实际文件)");
+    zh_cn.insert("directory.delete.success", "✓ 目录已移入回收站");
     zh_cn.insert("directory.delete.error", "✗ 删除失败: {}");
+    zh_cn.insert("directory.trash.empty", "回收站为空");
+    zh_cn.insert("directory.trash.prompt", "选择回收站中的目录");
+    zh_cn.insert("directory.trash.item", "{} - {}（{}删除）");
+    zh_cn.insert("directory.trash.action_prompt", "选择操作");
+    zh_cn.insert("directory.trash.restore", "恢复");
+    zh_cn.insert("directory.trash.purge", "永久删除");
+    zh_cn.insert("directory.trash.restore_success", "✓ 目录已恢复");
+    zh_cn.insert("directory.trash.purge_confirm", "确定要永久删除目录 '{}' 吗?");
+    zh_cn.insert("directory.trash.purge_confirm_again", "此操作无法撤销，关联记录也会一并删除。确定继续?");
+    zh_cn.insert("directory.trash.purge_success", "✓ 目录已永久删除");
+    zh_cn.insert("directory.trash.error", "✗ 操作失败: {}");
+    zh_cn.insert("directory.reset.prompt", "选择要重置的目录");
+    zh_cn.insert("directory.reset.select_steps", "选择要执行的步骤（空格切换，回车确认）");
+    zh_cn.insert("directory.reset.step_env", "移除 Anthropic 环境变量");
+    zh_cn.insert("directory.reset.step_sandbox", "移除 IS_SANDBOX");
+    zh_cn.insert("directory.reset.step_claude_md", "删除生成的 CLAUDE.local.md（仅在未被修改时）");
+    zh_cn.insert("directory.reset.step_claude_dir", "删除空的 .claude 目录");
+    zh_cn.insert("directory.reset.confirm", "确定要重置目录 '{}' 吗?");
+    zh_cn.insert("directory.reset.removed_keys", "✓ 已移除环境变量: {}");
+    zh_cn.insert("directory.reset.settings_removed", "✓ settings.local.json 已清空并删除");
+    zh_cn.insert("directory.reset.claude_md_removed", "✓ 已删除 CLAUDE.local.md");
+    zh_cn.insert("directory.reset.claude_md_kept", "⚠ CLAUDE.local.md 已被修改，已保留");
+    zh_cn.insert("directory.reset.claude_dir_removed", "✓ 已删除空的 .claude 目录");
+    zh_cn.insert("directory.quick_clear.title", "清除当前目录配置");
+    zh_cn.insert("directory.quick_clear.no_current", "尚未记录当前目录（还没有切换过），请选择目录");
+    zh_cn.insert("directory.quick_clear.prompt", "选择要清除配置的目录");
+    zh_cn.insert("directory.quick_clear.nothing", "目录 {} 中没有可清除的认证配置");
+    zh_cn.insert("directory.quick_clear.confirm", "确认清除目录 {} ({}) 的 Token 和 Base URL？");
+    zh_cn.insert("directory.quick_clear.success", "✓ 已清除目录 {} 的以下配置:");
+    zh_cn.insert("directory.quick_clear.error", "✗ 清除配置失败: {}");
+    zh_cn.insert("directory.reset.nothing", "没有需要清理的内容");
+    zh_cn.insert("directory.reset.error", "✗ 重置失败: {}");
+    zh_cn.insert("directory.merge_settings.prompt", "选择要合并配置文件的目录");
+    zh_cn.insert("directory.merge_settings.none", "没有需要合并的旧配置文件");
+    zh_cn.insert("directory.merge_settings.found", "以下文件将合并到 settings.local.json（已有值优先），并重命名为 .bak:");
+    zh_cn.insert("directory.merge_settings.confirm", "确定合并吗?");
+    zh_cn.insert("directory.merge_settings.success", "✓ 已合并 {} 个文件");
+    zh_cn.insert("directory.merge_settings.error", "✗ 合并失败: {}");
+    zh_cn.insert("directory.history.prompt", "选择要查看变更历史的目录");
+    zh_cn.insert("directory.history.empty", "该目录暂无配置变更记录");
+    zh_cn.insert("directory.history.title", "目录 {} 的配置变更历史（仅记录配置文件哈希）:");
+    zh_cn.insert("directory.history.header_time", "时间");
+    zh_cn.insert("directory.history.header_action", "操作");
+    zh_cn.insert("directory.history.header_before", "变更前");
+    zh_cn.insert("directory.history.header_after", "变更后");
+    zh_cn.insert("directory.history.header_actor", "操作者");
+    zh_cn.insert("directory.history.header_base_url", "Base URL");
+    zh_cn.insert("directory.history.action_apply", "应用配置");
+    zh_cn.insert("directory.history.action_clear", "清除配置");
+    zh_cn.insert("directory.history.action_reset", "重置目录");
 
     // URL管理
     zh_cn.insert("url.menu.title", "URL 管理");
@@ -234,8 +636,10 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("switch.no_accounts", "暂无账号记录，请先添加账号");
     zh_cn.insert("switch.no_directories", "暂无目录记录，请先添加目录");
     zh_cn.insert("switch.select_account", "选择账号");
+    zh_cn.insert("switch.recent_account", "[最近使用] {}");
     zh_cn.insert("switch.select_directory", "选择目录");
     zh_cn.insert("switch.prompt_skip_permissions", "跳过权限检查? (推荐选择 Yes)");
+    zh_cn.insert("switch.prompt_use_staging", "本次切换使用备用地址 {}？");
     zh_cn.insert("switch.prompt_use_proxy", "使用代理? (从 Claude 配置中加载代理设置)");
     zh_cn.insert("switch.switching", "正在切换配置...");
     zh_cn.insert("switch.success", "✓ 配置切换成功!");
@@ -244,6 +648,23 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("switch.directory", "  目录: {}");
     zh_cn.insert("switch.path", "  路径: {}");
     zh_cn.insert("switch.sandbox", "  沙盒模式: 已启用");
+    zh_cn.insert("switch.template", "  配置模板: {}");
+    zh_cn.insert("switch.invalid_token", "✗ 账号 Token 无效: {}，请先编辑账号");
+    zh_cn.insert("switch.not_expected_account", "⚠ 目录 {} 的预期账号是 {}，本次应用的账号偏离预期");
+    zh_cn.insert("switch.batch.not_expected_account", "⚠ 以下目录设置了其他预期账号，切换后将偏离预期: {}");
+    zh_cn.insert("token_command.timeout", "获取 Token 的命令超过 {} 秒未完成");
+    zh_cn.insert("token_command.spawn_failed", "无法执行获取 Token 的命令: {}");
+    zh_cn.insert("token_command.failed", "获取 Token 的命令失败: {}");
+    zh_cn.insert("token_command.empty_output", "获取 Token 的命令没有输出");
+    zh_cn.insert("hook.timeout", "切换后钩子超过 {} 秒未完成");
+    zh_cn.insert("hook.spawn_failed", "无法启动切换后钩子命令: {}");
+    zh_cn.insert("hook.failed", "切换后钩子退出状态: {}");
+    zh_cn.insert("switch.preview.title", "即将执行:");
+    zh_cn.insert("switch.preview.claude_md_create", "将创建");
+    zh_cn.insert("switch.preview.claude_md_overwrite", "将覆盖（现有内容与内置模板不同）");
+    zh_cn.insert("switch.preview.claude_md_skip_identical", "跳过（内容一致）");
+    zh_cn.insert("switch.preview.claude_md_skip_modified", "跳过（已被修改）");
+    zh_cn.insert("switch.preview.confirm", "确认执行切换？");
     zh_cn.insert("switch.permission", "  权限检查: {}");
     zh_cn.insert("switch.permission_skipped", "已跳过");
     zh_cn.insert("switch.permission_required", "需要确认");
@@ -255,8 +676,84 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
         "警告: 获取Claude配置失败，使用默认配置: {}",
     );
     zh_cn.insert("switch.warn_write_fail", "警告: Claude配置写入失败: {}");
+    zh_cn.insert("switch.gitignore_updated", "已在 .gitignore 中追加: {}");
+    zh_cn.insert("switch.gitignore_failed", "⚠ 更新 .gitignore 失败: {}");
+    zh_cn.insert("switch.batch.symlink_escape", ".claude 指向目录之外 ({})，已跳过");
+    zh_cn.insert("switch.hook_failed", "切换后钩子执行失败: {}（详见日志）");
     zh_cn.insert("switch.error_update", "✗ 配置文件更新失败: {}");
     zh_cn.insert("switch.error", "✗ 切换失败: {}");
+    zh_cn.insert("switch.unchanged", "配置无变化，未写入任何文件");
+    zh_cn.insert("switch.batch.title", "批量切换");
+    zh_cn.insert("switch.batch.last_record", "上次批量切换（账号 '{}'，{}）还有未完成的目录");
+    zh_cn.insert("switch.batch.retry_failed", "🔁 重试失败项（{} 个目录）");
+    zh_cn.insert("switch.batch.new", "📦 新建批量切换");
+    zh_cn.insert("switch.batch.locked_skipped", "已锁定的目录不参与批量切换: {}");
+    zh_cn.insert("switch.batch.select_directories", "选择要切换的目录（空格选择，回车确认）");
+    zh_cn.insert("switch.batch.filter_prompt", "按名称或路径预选目录");
+    zh_cn.insert("switch.batch.filter_none", "不筛选，手动勾选");
+    zh_cn.insert("switch.batch.filter_glob", "通配符（如 ~/work/** 或 *-api）");
+    zh_cn.insert("switch.batch.filter_regex", "正则表达式（如 -api$）");
+    zh_cn.insert("switch.batch.filter_pattern", "匹配目录名称或路径的表达式");
+    zh_cn.insert("switch.batch.filter_invalid", "✗ 表达式无效: {}");
+    zh_cn.insert("switch.batch.filter_no_match", "没有目录匹配 {}，请重新输入");
+    zh_cn.insert("switch.batch.filter_matched", "已预选匹配的 {} / {} 个目录，可在列表中继续调整");
+    zh_cn.insert("switch.batch.confirm", "将账号 '{}' 应用到 {} 个目录？");
+    zh_cn.insert("switch.batch.running", "正在切换 {} 个目录...");
+    zh_cn.insert("switch.batch.progress", "批量切换");
+    zh_cn.insert("switch.batch.directory_missing", "目录已被删除");
+    zh_cn.insert("switch.batch.account_missing", "✗ 批量切换使用的账号 '{}' 已不存在，无法重试");
+    zh_cn.insert("switch.batch.template_missing", "批量切换使用的模板已不存在，重试时不使用模板");
+    zh_cn.insert("switch.batch.header_error", "错误");
+    zh_cn.insert("switch.batch.status_succeeded", "成功");
+    zh_cn.insert("switch.batch.status_unchanged", "无变化");
+    zh_cn.insert("switch.batch.status_failed", "失败");
+    zh_cn.insert("switch.batch.status_pending", "未执行");
+    zh_cn.insert("switch.batch.summary", "成功 {} 个，无变化 {} 个，待重试 {} 个");
+    zh_cn.insert("switch.batch.retry_prompt", "重试失败项（{} 个目录）？");
+    zh_cn.insert("switch.batch.interrupted", "已中断：{} 个目录尚未执行，下次进入批量切换时可选择继续");
+    zh_cn.insert("interrupt.finishing", "正在完成当前写入，完成后停止（再次按 Ctrl-C 立即退出）");
+    zh_cn.insert("switch.worktree.found", "该目录是 git 仓库，检测到 {} 个其他工作树:");
+    zh_cn.insert("switch.worktree.confirm", "将同一账号应用到全部工作树？");
+    zh_cn.insert("switch.worktree.locked", "{}（目录已锁定，跳过）");
+    zh_cn.insert("switch.worktree.unchanged", "{}（无变化）");
+    zh_cn.insert("switch.worktree.summary", "工作树: 已更新 {} 个，无变化 {} 个，失败 {} 个");
+    zh_cn.insert("switch.fallback.confirm", "账号 {} 配置了 {} 个备用账号，切换前测试连通性并在不可用时自动回退？");
+    zh_cn.insert("switch.fallback.testing", "正在按顺序测试账号连通性...");
+    zh_cn.insert("switch.fallback.used", "⚠ 账号 {} 不可用，已改用备用账号 {}");
+    zh_cn.insert("switch.fallback.all_failed", "✗ 备用账号链中的所有账号都不可用");
+    zh_cn.insert("switch.fallback.use_primary", "仍使用主账号 {} 继续切换？");
+    zh_cn.insert("profile.menu.title", "配置档案");
+    zh_cn.insert("profile.menu.apply", "⚡ 应用配置档案");
+    zh_cn.insert("profile.menu.list", "📝 查看所有档案");
+    zh_cn.insert("profile.menu.add", "➕ 新建档案");
+    zh_cn.insert("profile.menu.delete", "🗑️  删除档案");
+    zh_cn.insert("profile.list.no_records", "暂无配置档案");
+    zh_cn.insert("profile.list.account_missing", "（账号已删除）");
+    zh_cn.insert("profile.header.name", "名称");
+    zh_cn.insert("profile.header.account", "账号");
+    zh_cn.insert("profile.header.template", "模板");
+    zh_cn.insert("profile.header.skip_permissions", "跳过权限确认");
+    zh_cn.insert("profile.header.use_proxy", "使用代理");
+    zh_cn.insert("profile.add.title", "新建配置档案");
+    zh_cn.insert("profile.add.prompt_name", "档案名称");
+    zh_cn.insert("profile.add.success", "✓ 配置档案已保存: {}");
+    zh_cn.insert("profile.add.error", "✗ 保存配置档案失败: {}");
+    zh_cn.insert("profile.apply.prompt", "选择要应用的配置档案");
+    zh_cn.insert("profile.apply.no_account", "配置档案 {} 引用的账号已被删除，请重新创建档案");
+    zh_cn.insert("profile.apply.account_error", "✗ 读取档案账号失败: {}");
+    zh_cn.insert("profile.apply.confirm", "将配置档案 {} 应用到目录 {}？");
+    zh_cn.insert("profile.delete.prompt", "选择要删除的配置档案");
+    zh_cn.insert("profile.delete.confirm", "确定要删除配置档案 '{}' 吗?");
+    zh_cn.insert("profile.delete.success", "✓ 配置档案已删除");
+    zh_cn.insert("profile.delete.error", "✗ 删除配置档案失败: {}");
+    zh_cn.insert("switch.unchanged_hint", "如需强制重写，请使用 --force 启动");
+    zh_cn.insert("switch.env_override", "⚠ 当前环境中设置了 {}，Claude Code 会优先使用环境变量而不是刚写入的配置");
+    zh_cn.insert("switch.terminal.prompt", "是否在该目录打开终端?");
+    zh_cn.insert("switch.terminal.shell_hint", "已在该目录启动 {}，输入 exit 返回菜单");
+    zh_cn.insert("switch.terminal.error", "⚠ 打开终端失败: {}");
+    zh_cn.insert("switch.repeat.title", "快速重复上次切换");
+    zh_cn.insert("switch.repeat.none", "没有可重复的切换记录（上次使用的账号或目录可能已被删除）");
+    zh_cn.insert("switch.repeat.confirm", "将账号 '{}' 应用到目录 '{}' ({})?");
 
     // WebDAV 同步
     zh_cn.insert("webdav.menu.title", "WebDAV 同步管理");
@@ -267,6 +764,8 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("webdav.menu.upload_config", "⬆️  上传配置到云端");
     zh_cn.insert("webdav.menu.download_config", "⬇️  从云端下载配置");
     zh_cn.insert("webdav.menu.list_remote", "📂 查看远程文件");
+    zh_cn.insert("webdav.menu.browse_remote", "🌐 浏览远程");
+    zh_cn.insert("webdav.menu.namespace", "🏷️  设置命名空间");
     zh_cn.insert("webdav.menu.delete_config", "🗑️  删除配置");
     zh_cn.insert("webdav.list.no_config", "暂无 WebDAV 配置");
     zh_cn.insert("webdav.list.header_id", "ID");
@@ -274,6 +773,7 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("webdav.list.header_url", "URL");
     zh_cn.insert("webdav.list.header_username", "用户名");
     zh_cn.insert("webdav.list.header_remote_path", "远程路径");
+    zh_cn.insert("webdav.list.header_namespace", "命名空间");
     zh_cn.insert("webdav.list.header_auto_sync", "自动同步");
     zh_cn.insert("webdav.list.header_status", "状态");
     zh_cn.insert("webdav.list.auto_sync_yes", "✓");
@@ -288,12 +788,19 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("webdav.add.prompt_password", "密码");
     zh_cn.insert("webdav.add.success", "✓ WebDAV 配置 '{}' 创建成功");
     zh_cn.insert("webdav.add.error", "✗ 创建失败: {}");
+    zh_cn.insert("webdav.add.summary", "待保存的配置：");
+    zh_cn.insert("webdav.add.probe_auth_failed", "✗ 认证失败（HTTP {}），请检查用户名和密码");
+    zh_cn.insert("webdav.add.probe_status", "✗ 服务器返回了异常状态（HTTP {}），请检查 URL");
+    zh_cn.insert("webdav.add.probe_unreachable", "✗ 无法连接到服务器: {}");
+    zh_cn.insert("webdav.add.save_anyway", "连接测试未通过，仍然保存？");
+    zh_cn.insert("webdav.add.not_saved", "未保存配置");
     zh_cn.insert("webdav.test.select_config", "选择要测试的配置");
     zh_cn.insert("webdav.test.testing", "正在测试连接...");
     zh_cn.insert("webdav.test.success", "✓ WebDAV 连接测试成功");
     zh_cn.insert("webdav.test.error", "✗ 连接测试失败: {}");
     zh_cn.insert("webdav.upload.select_config", "选择 WebDAV 配置");
     zh_cn.insert("webdav.upload.prompt_filename", "文件名");
+    zh_cn.insert("webdav.upload.prompt_note", "备注（可选）");
     zh_cn.insert("webdav.upload.uploading", "正在上传配置到云端...");
     zh_cn.insert("webdav.upload.clearing", "正在清空现有配置...");
     zh_cn.insert("webdav.upload.cleared", "✓ 已清空现有账号和 Base URLs");
@@ -301,11 +808,23 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("webdav.upload.imported_accounts", "✓ 成功导入 {} 个账号");
     zh_cn.insert("webdav.upload.importing_urls", "正在导入 Base URLs...");
     zh_cn.insert("webdav.upload.imported_urls", "✓ 成功导入 {} 个 Base URL");
+    zh_cn.insert("webdav.upload.imported_locks", "✓ 更新了 {} 个目录的锁定状态");
     zh_cn.insert("webdav.upload.success", "✓ 配置已成功上传到 WebDAV: {}");
     zh_cn.insert("webdav.upload.success_log", "成功上传配置文件: {}");
     zh_cn.insert("webdav.upload.error", "✗ 上传失败: {}");
     zh_cn.insert("webdav.download.getting_files", "正在获取远程文件列表...");
     zh_cn.insert("webdav.download.no_files", "远程没有配置文件");
+    zh_cn.insert("webdav.browse.select_namespace", "选择要浏览的命名空间");
+    zh_cn.insert("webdav.browse.this_machine", "(本机)");
+    zh_cn.insert("webdav.browse.error", "读取远程目录失败: {}");
+    zh_cn.insert("webdav.namespace.shared", "共享路径");
+    zh_cn.insert("webdav.namespace.current", "当前命名空间: {}");
+    zh_cn.insert("webdav.namespace.use_hostname", "使用主机名 {}");
+    zh_cn.insert("webdav.namespace.custom", "自定义名称");
+    zh_cn.insert("webdav.namespace.use_shared", "使用共享路径（与其他机器共用）");
+    zh_cn.insert("webdav.namespace.prompt", "命名空间（字母、数字、- _ .）");
+    zh_cn.insert("webdav.namespace.invalid", "命名空间不能为空");
+    zh_cn.insert("webdav.namespace.updated", "配置包将保存在 {}");
     zh_cn.insert("webdav.download.select_file", "选择要下载的文件");
     zh_cn.insert("webdav.download.downloading", "正在从云端下载配置...");
     zh_cn.insert(
@@ -314,6 +833,36 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     );
     zh_cn.insert("webdav.download.success_log", "成功下载并导入配置文件: {}");
     zh_cn.insert("webdav.download.error", "✗ 下载失败: {}");
+    zh_cn.insert("webdav.download.checksum_mismatch", "✗ 配置包校验失败，已中止导入\n  期望 SHA-256: {}\n  实际 SHA-256: {}");
+    zh_cn.insert("webdav.download.local_unchanged", "本地数据未做任何修改");
+    zh_cn.insert("webdav.import.preview_title", "导入预览（按账号名 / Base URL 名称匹配）:");
+    zh_cn.insert("webdav.import.meta_title", "配置包信息:");
+    zh_cn.insert("webdav.import.meta_exported_at", "  导出时间: {}");
+    zh_cn.insert("webdav.import.meta_app_version", "  导出版本: {}");
+    zh_cn.insert("webdav.import.meta_note", "  备注: {}");
+    zh_cn.insert("webdav.import.meta_unknown", "未知");
+    zh_cn.insert("webdav.import.empty", "配置包中没有可导入的记录");
+    zh_cn.insert("webdav.import.header_kind", "类型");
+    zh_cn.insert("webdav.import.header_name", "名称");
+    zh_cn.insert("webdav.import.header_action", "操作");
+    zh_cn.insert("webdav.import.kind_account", "账号");
+    zh_cn.insert("webdav.import.kind_base_url", "Base URL");
+    zh_cn.insert("webdav.import.action_add", "新增");
+    zh_cn.insert("webdav.import.action_update", "更新");
+    zh_cn.insert("webdav.import.action_skip", "跳过（相同）");
+    zh_cn.insert("webdav.import.action_local_only", "仅本地（合并保留 / 覆盖删除）");
+    zh_cn.insert("webdav.import.summary", "新增 {}，更新 {}，跳过 {}，仅本地 {}");
+    zh_cn.insert("webdav.import.select_mode", "选择导入方式");
+    zh_cn.insert("webdav.import.rolled_back", "✗ 导入失败，已回滚，本地数据未修改: {}");
+    zh_cn.insert("bundle.import.account_failed", "导入账号 '{}' 失败");
+    zh_cn.insert("bundle.import.base_url_failed", "导入 Base URL '{}' 失败");
+    zh_cn.insert("bundle.import.directory_lock_failed", "导入目录 '{}' 的锁定状态失败");
+    zh_cn.insert("bundle.import.invalid_account_name", "配置包中的账号名称 '{}' 不合法: {}");
+    zh_cn.insert("bundle.import.duplicate_account", "配置包中的账号名称重复: {}");
+    zh_cn.insert("bundle.import.duplicate_base_url", "配置包中的 Base URL 名称或地址重复: {}");
+    zh_cn.insert("bundle.import.base_url_conflict", "配置包中的 Base URL '{}' 与本地的 '{}' 地址相同");
+    zh_cn.insert("webdav.import.mode_merge", "合并：新增并更新，保留仅本地的记录");
+    zh_cn.insert("webdav.import.mode_overwrite", "覆盖：清空本地账号和 Base URL 后导入");
     zh_cn.insert("webdav.list.title", "远程文件列表:");
     zh_cn.insert("webdav.list.error", "✗ 获取文件列表失败: {}");
     zh_cn.insert("webdav.delete.select_config", "选择要删除的配置");
@@ -356,6 +905,220 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     zh_cn.insert("remove_root.error_stderr", "错误输出:\n{}");
     zh_cn.insert("remove_root.error", "✗ 删除限制代码脚本不存在: {}");
 
+    // 配置模板
+    zh_cn.insert("template.menu.title", "模板管理");
+    zh_cn.insert("template.menu.list", "📝 查看所有模板");
+    zh_cn.insert("template.menu.add", "➕ 添加新模板");
+    zh_cn.insert("template.menu.edit", "✏️  编辑模板");
+    zh_cn.insert("template.menu.delete", "🗑️  删除模板");
+    zh_cn.insert("template.list.no_records", "暂无模板记录");
+    zh_cn.insert("template.list.header_id", "ID");
+    zh_cn.insert("template.list.header_name", "模板名称");
+    zh_cn.insert("template.list.header_description", "描述");
+    zh_cn.insert("template.list.header_settings", "配置内容");
+    zh_cn.insert("template.list.header_builtin", "内置");
+    zh_cn.insert("template.list.builtin_yes", "是");
+    zh_cn.insert("template.list.total", "共 {} 个模板");
+    zh_cn.insert("template.add.title", "添加新模板");
+    zh_cn.insert("template.add.prompt_name", "模板名称");
+    zh_cn.insert("template.add.prompt_description", "描述（可选）");
+    zh_cn.insert("template.add.prompt_settings", "配置内容 (JSON)");
+    zh_cn.insert("template.add.json_hint", "提示: 输入 JSON 对象，例如 {\"env\":{\"DISABLE_TELEMETRY\":\"1\"}}，不要包含 Token");
+    zh_cn.insert("template.add.success", "✓ 模板 '{}' 创建成功");
+    zh_cn.insert("template.add.error", "✗ 创建失败: {}");
+    zh_cn.insert("template.edit.prompt", "选择要编辑的模板");
+    zh_cn.insert("template.edit.success", "✓ 模板更新成功");
+    zh_cn.insert("template.edit.error", "✗ 更新失败: {}");
+    zh_cn.insert("template.delete.prompt", "选择要删除的模板");
+    zh_cn.insert("template.delete.confirm", "确定要删除模板 '{}' 吗?");
+    zh_cn.insert("template.delete.success", "✓ 模板删除成功");
+    zh_cn.insert("template.delete.error", "✗ 删除失败: {}");
+    zh_cn.insert("template.select.prompt", "选择配置模板");
+    zh_cn.insert("template.select.none", "不使用模板");
+    zh_cn.insert("template.error.invalid_json", "JSON 格式错误: {}");
+    zh_cn.insert("template.error.not_object", "模板必须是 JSON 对象");
+    zh_cn.insert("template.error.contains_secret", "模板不能包含敏感信息: {}（Token 由账号提供）");
+
+    // 监听模式
+    zh_cn.insert("watch.dir_not_found", "未找到 ID 为 {} 的目录");
+    zh_cn.insert("dir_name.not_found", "未找到名为 {} 的目录");
+    zh_cn.insert("dir_name.ambiguous", "存在多个同名目录 {}，请用 id 或 名称@路径 指定: {}");
+    zh_cn.insert("switch.cli.account_not_found", "未找到 ID 为 {} 的账号");
+    zh_cn.insert("switch.cli.prompt_register", "目录 {} 尚未登记，是否以名称 '{}' 登记后应用？");
+    zh_cn.insert("switch.current_dir.title", "配置当前目录");
+    zh_cn.insert("switch.current_dir.matched", "当前目录: {} ({})");
+    zh_cn.insert("watch.no_account", "目录 {} 尚未关联账号，请先执行一次切换");
+    zh_cn.insert("watch.start", "👀 正在监听 {}（按 Ctrl+C 停止）");
+    zh_cn.insert("watch.expected_account", "期望账号: {}");
+    zh_cn.insert("watch.mode_auto_fix", "模式: 自动修复");
+    zh_cn.insert("watch.mode_warn", "模式: 仅告警");
+    zh_cn.insert("watch.locked_no_fix", "目录 '{}' 已锁定，自动修复已关闭，仅告警");
+    zh_cn.insert("watch.read_error", "读取配置失败: {}");
+    zh_cn.insert("watch.drift", "⚠️  检测到配置被外部修改，不一致的键: {}");
+    zh_cn.insert("watch.fixed", "✓ 已重新应用账号 {} 的配置");
+    zh_cn.insert("watch.fix_error", "重新应用配置失败: {}");
+    zh_cn.insert("watch.stopped", "已停止监听");
+    zh_cn.insert("migrate.success", "✓ {}: {} 已迁移到 settings.local.json，原文件重命名为 {}");
+    zh_cn.insert("migrate.error", "✗ {}: 迁移失败: {}");
+    zh_cn.insert("migrate.nothing", "没有目录在使用旧配置文件，无需迁移");
+    zh_cn.insert("migrate.summary", "共迁移 {} 个目录");
+    zh_cn.insert("setting.not_found", "配置中不存在 {}");
+    zh_cn.insert("setting.updated", "✓ 已更新 {}（目录 {}）");
+    zh_cn.insert("app_settings.export.success", "✓ 应用设置已导出到 {}");
+    zh_cn.insert("app_settings.export.no_secrets", "备份中不含 WebDAV 密码（使用 --include-secrets 可一并导出）");
+    zh_cn.insert("app_settings.import.success", "✓ 已从 {} 恢复应用设置");
+    zh_cn.insert("app_settings.import.webdav", "WebDAV 配置: 新增 {} 个，更新 {} 个");
+    zh_cn.insert("app_settings.import.missing_passwords", "⚠ 以下 WebDAV 配置没有密码，连接前请用包含密码的备份恢复或重新添加: {}");
+    zh_cn.insert("app_settings.error", "✗ 操作失败: {}");
+    zh_cn.insert("doctor.title", "环境诊断");
+    zh_cn.insert("self_test.title", "自检：在临时目录中验证配置读写");
+    zh_cn.insert("self_test.step.create_dir", "创建临时目录");
+    zh_cn.insert("self_test.step.write", "写入测试配置");
+    zh_cn.insert("self_test.step.read", "读回并核对配置");
+    zh_cn.insert("self_test.step.clear", "清除配置");
+    zh_cn.insert("self_test.step.cleanup", "删除临时目录");
+    zh_cn.insert("self_test.mismatch", "读回的值与写入的不一致: {}");
+    zh_cn.insert("self_test.not_cleared", "清除后仍存在: {}");
+    zh_cn.insert("self_test.passed", "✓ 自检通过");
+    zh_cn.insert("self_test.failed", "✗ 自检失败，请将以上输出附在问题反馈中");
+    zh_cn.insert("doctor.ok", "正常");
+    zh_cn.insert("doctor.hint", "建议: {}");
+    zh_cn.insert("doctor.check.database", "数据库连接");
+    zh_cn.insert("doctor.check.config_dir", "应用数据目录可写");
+    zh_cn.insert("doctor.check.accounts", "账号");
+    zh_cn.insert("doctor.check.directories", "目录");
+    zh_cn.insert("doctor.check.directory", "目录 {}");
+    zh_cn.insert("doctor.check.claude_local_md", "CLAUDE.local.md 模板");
+    zh_cn.insert("doctor.check.webdav", "WebDAV {}");
+    zh_cn.insert("doctor.accounts_count", "已配置 {} 个账号");
+    zh_cn.insert("doctor.no_accounts", "尚未配置任何账号");
+    zh_cn.insert("doctor.no_directories", "尚未添加任何目录");
+    zh_cn.insert("doctor.not_a_directory", "{} 不是目录");
+    zh_cn.insert("doctor.directory_missing", "{} 不存在");
+    zh_cn.insert("doctor.claude_local_md_bundled", "已内置");
+    zh_cn.insert("doctor.claude_local_md_empty", "内置模板为空");
+    zh_cn.insert("doctor.hint.database", "检查数据库文件权限，或删除损坏的数据库后重新启动");
+    zh_cn.insert("doctor.hint.config_dir", "检查应用数据目录权限，或通过 CLAUDE_CONFIG_HOME 指定其他位置");
+    zh_cn.insert("doctor.hint.accounts", "在“账号管理”中添加账号");
+    zh_cn.insert("doctor.hint.add_directory", "在“目录管理”中添加项目目录");
+    zh_cn.insert("doctor.hint.fix_directory", "在“目录管理”中修改路径或删除该目录");
+    zh_cn.insert("doctor.hint.reinstall", "重新安装或重新编译本程序");
+    zh_cn.insert("doctor.hint.offline", "关闭离线模式后重新检查");
+    zh_cn.insert("doctor.hint.webdav", "检查 WebDAV 地址、用户名和密码");
+    zh_cn.insert("doctor.summary_ok", "✓ 所有检查均已通过");
+    zh_cn.insert("doctor.summary_warned", "所有硬性检查已通过，{} 项警告");
+    zh_cn.insert("doctor.summary_failed", "✗ {} 项检查失败，{} 项警告");
+
+    // 编辑配置文件
+    zh_cn.insert("directory.edit_settings.prompt", "选择要编辑配置文件的目录");
+    zh_cn.insert("directory.edit_settings.candidates", "候选配置文件（按优先级）:");
+    zh_cn.insert("directory.edit_settings.active", "← 当前生效");
+    zh_cn.insert("directory.edit_settings.exists", "（存在）");
+    zh_cn.insert("directory.edit_settings.missing", "（不存在）");
+    zh_cn.insert("directory.edit_settings.created", "配置文件不存在，已创建: {}");
+    zh_cn.insert("directory.edit_settings.opening", "正在打开编辑器: {}");
+    zh_cn.insert("directory.edit_settings.editor_error", "打开编辑器失败: {}");
+    zh_cn.insert("directory.edit_settings.valid", "✓ 配置文件格式正确");
+    zh_cn.insert("directory.edit_settings.invalid", "✗ 配置文件语法错误: {}");
+    zh_cn.insert("directory.repair_settings.prompt", "选择要修复配置的目录");
+    zh_cn.insert("directory.repair_settings.repaired", "✓ 已将 env 改写为标准对象形式");
+    zh_cn.insert("directory.repair_settings.nothing", "配置无需修复");
+    zh_cn.insert("directory.repair_settings.error", "✗ 修复失败: {}");
+    zh_cn.insert("directory.validate_settings.prompt", "选择要校验配置的目录");
+    zh_cn.insert("directory.validate_settings.file", "配置文件: {}");
+    zh_cn.insert("directory.validate_settings.valid", "✓ 配置校验通过");
+    zh_cn.insert("directory.validate_settings.issues", "发现 {} 个问题:");
+    zh_cn.insert("directory.validate_settings.error", "✗ 校验失败: {}");
+    zh_cn.insert("directory.effective_env.prompt", "选择要查看生效环境变量的目录");
+    zh_cn.insert("directory.effective_env.empty", "未找到任何环境变量");
+    zh_cn.insert("directory.effective_env.key", "变量");
+    zh_cn.insert("directory.effective_env.value", "值");
+    zh_cn.insert("directory.effective_env.source", "来源");
+    zh_cn.insert("directory.effective_env.global", "全局");
+    zh_cn.insert("directory.effective_env.global_note", "其中 {} 个变量项目配置未设置，由全局配置提供（可在“全局配置”菜单中管理）");
+    zh_cn.insert("directory.effective_env.project", "项目");
+    zh_cn.insert("directory.effective_env.process", "环境变量");
+    zh_cn.insert("directory.effective_env.masked_note", "当前进程的环境变量覆盖了配置文件中的 {}");
+    zh_cn.insert("directory.effective_env.error", "✗ 读取配置失败: {}");
+
+    // 配置核对
+    zh_cn.insert("audit.header.directory", "目录");
+    zh_cn.insert("audit.header.account", "关联账号");
+    zh_cn.insert("audit.header.base_url", "Base URL");
+    zh_cn.insert("audit.header.token", "Token 指纹");
+    zh_cn.insert("audit.status.deviated", "偏离预期");
+    zh_cn.insert("audit.pinned", "(预期)");
+    zh_cn.insert("audit.token_from_command", "(命令获取)");
+    zh_cn.insert("audit.header.result", "结果");
+    zh_cn.insert("audit.status.match", "✓ 一致");
+    zh_cn.insert("audit.status.mismatch", "✗ 不一致");
+    zh_cn.insert("audit.status.not_configured", "未配置");
+    zh_cn.insert("audit.status.no_account", "未关联账号");
+    zh_cn.insert("audit.summary_mismatch", "发现 {} 个目录的配置与账号不一致");
+    zh_cn.insert("audit.summary_ok", "所有已配置目录均与账号一致");
+    zh_cn.insert("status.missing", "✗ 目录不存在");
+    zh_cn.insert("audit.progress", "核对目录");
+
+    // 相对时间
+    zh_cn.insert("time.never", "从未");
+    zh_cn.insert("time.just_now", "刚刚");
+    zh_cn.insert("time.minutes_ago", "{}分钟前");
+    zh_cn.insert("time.hours_ago", "{}小时前");
+    zh_cn.insert("time.days_ago", "{}天前");
+    zh_cn.insert("time.months_ago", "{}个月前");
+    zh_cn.insert("time.years_ago", "{}年前");
+
+    // 进度
+    zh_cn.insert("progress.summary", "{} 完成：共 {} 项，用时 {} 秒");
+
+    // 首次运行向导
+    zh_cn.insert("setup.title", "欢迎使用！首次运行向导");
+    zh_cn.insert("setup.intro", "接下来将引导你添加第一个账号和第一个目录，并可选择立即应用配置。");
+    zh_cn.insert("setup.step", "步骤 {}/{}：{}");
+    zh_cn.insert("setup.step_account", "添加账号");
+    zh_cn.insert("setup.step_directory", "添加目录");
+    zh_cn.insert("setup.step_apply", "应用配置");
+    zh_cn.insert("setup.prompt_apply", "现在把账号应用到该目录吗?");
+    zh_cn.insert("setup.aborted", "已退出向导，可稍后使用 --setup 重新运行");
+    zh_cn.insert("setup.summary_title", "向导完成");
+    zh_cn.insert("setup.summary_accounts", "账号: {}");
+    zh_cn.insert("setup.summary_directories", "目录: {}");
+    zh_cn.insert("setup.summary_hint", "之后可随时使用 --setup 重新运行向导");
+
+    // 全局配置
+    zh_cn.insert("global.menu.title", "全局配置 (~/.claude/settings.json)");
+    zh_cn.insert("global.menu.list", "📋 查看全局环境变量");
+    zh_cn.insert("global.menu.set", "✏️  设置环境变量");
+    zh_cn.insert("global.menu.remove", "🗑️  删除环境变量");
+    zh_cn.insert("global.menu.clear", "🧹 清除 Anthropic 认证变量");
+    zh_cn.insert("global.file", "全局配置文件: {}");
+    zh_cn.insert("global.no_home", "无法确定用户主目录，不能管理全局配置");
+    zh_cn.insert("global.error", "✗ 全局配置操作失败: {}");
+    zh_cn.insert("global.list.empty", "全局配置中没有环境变量");
+    zh_cn.insert("global.set.prompt_key", "变量名");
+    zh_cn.insert("global.set.prompt_value", "变量值");
+    zh_cn.insert("global.set.success", "✓ 已设置全局变量 {}");
+    zh_cn.insert("global.remove.prompt", "选择要删除的变量");
+    zh_cn.insert("global.remove.success", "✓ 已删除全局变量 {}");
+    zh_cn.insert("global.clear.confirm", "确定要从全局配置中移除 ANTHROPIC_API_KEY / ANTHROPIC_AUTH_TOKEN / ANTHROPIC_BASE_URL 吗?");
+    zh_cn.insert("global.clear.success", "✓ 已清除全局认证变量");
+
+    // 离线模式
+    zh_cn.insert("offline.enabled", "离线模式已启用");
+    zh_cn.insert("output.ascii.active", "[ACTIVE] 活跃");
+    zh_cn.insert("output.ascii.inactive", "[INACTIVE] 未活跃");
+    zh_cn.insert("output.ascii.exists", "[OK] 存在");
+    zh_cn.insert("output.ascii.missing", "[MISSING] 不存在");
+
+    // 分页
+    zh_cn.insert("pager.info", "第 {}/{} 页，共 {} 条");
+    zh_cn.insert("pager.prompt", "翻页");
+    zh_cn.insert("pager.next", "下一页");
+    zh_cn.insert("pager.prev", "上一页");
+    zh_cn.insert("pager.quit", "退出");
+    zh_cn.insert("bulk.type_count", "请输入条数 {} 以确认（直接按 Enter 取消）");
+    zh_cn.insert("bulk.count_mismatch", "输入的条数不一致，已取消");
+
     translations.insert(Language::ZhCN, zh_cn);
 
     // 英文翻译
@@ -375,13 +1138,50 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     en_us.insert("menu.main.account", "📋 Account Management");
     en_us.insert("menu.main.directory", "📁 Directory Management");
     en_us.insert("menu.main.url", "🌐 URL Management");
+    en_us.insert("menu.main.template", "🧩 Template Management");
     en_us.insert("menu.main.switch", "⚡ Configuration Switch");
+    en_us.insert("menu.main.current_dir", "📍 Configure Current Directory");
+    en_us.insert("menu.main.repeat_switch", "🔁 Repeat Last Switch");
+    en_us.insert("menu.main.quick_clear", "🧹 Clear Current Directory Config");
+    en_us.insert("menu.main.undo", "↩️  Undo Last Config Change");
+    en_us.insert("menu.main.batch_switch", "📦 Batch Switch");
+    en_us.insert("menu.main.model", "🤖 Model Selection");
+    en_us.insert("menu.main.profile", "🗂️  Profiles");
+    en_us.insert("menu.main.global", "🌐 Global Config");
+    en_us.insert("menu.main.doctor", "🩺 Doctor");
+    en_us.insert("menu.hotkey.prompt", "[a] Accounts  [d] Directories  [s] Switch  [h] Current dir  [c] Clear current dir  [u] Undo  [w] WebDAV  [l] Logs  [m] Full menu  [q] Quit");
+    en_us.insert("menu.hotkey.invalid", "Invalid key '{}', please try again");
     en_us.insert("menu.main.webdav", "☁️  WebDAV Sync");
     en_us.insert("menu.main.logs", "📝 View Logs");
     en_us.insert("menu.main.remove_root", "🔓 Remove Root Check");
     en_us.insert("menu.main.settings", "⚙️  Settings");
     en_us.insert("menu.main.language", "🌐 中文");
     en_us.insert("menu.main.exit", "❌ Exit");
+    en_us.insert("undo.empty", "No config changes to undo in this session");
+    en_us.insert("undo.summary", "Undo the change made at {} ({}) on directory '{}'");
+    en_us.insert("undo.path", "  File: {}");
+    en_us.insert("undo.remaining", "  Undo steps available: {}");
+    en_us.insert("undo.will_delete", "The file did not exist before; undo will delete it");
+    en_us.insert("undo.confirm", "Restore the content before the change?");
+    en_us.insert("undo.success", "✓ Restored config of directory '{}'");
+    en_us.insert("undo.error", "✗ Undo failed: {}");
+    en_us.insert("model.menu.title", "Model Selection");
+    en_us.insert("model.menu.directory", "🤖 Set Model for a Directory");
+    en_us.insert("model.menu.account_default", "👤 Set Account Default Model");
+    en_us.insert("model.pick.prompt", "Select a model");
+    en_us.insert("model.pick.current", "(current)");
+    en_us.insert("model.pick.custom", "✏️  Custom model...");
+    en_us.insert("model.pick.clear", "🧹 Clear model");
+    en_us.insert("model.pick.prompt_custom", "Model name");
+    en_us.insert("model.pick.custom_empty", "Model name cannot be empty");
+    en_us.insert("model.current", "Current model: {}");
+    en_us.insert("model.directory.prompt", "Select a directory");
+    en_us.insert("model.directory.set", "✓ Directory '{}' now uses model {}");
+    en_us.insert("model.directory.cleared", "✓ Cleared the model of directory '{}'");
+    en_us.insert("model.account.prompt", "Select an account");
+    en_us.insert("model.account.set", "✓ Default model of account '{}' set to {}; it is written when switching to this account");
+    en_us.insert("model.account.cleared", "✓ Cleared the default model of account '{}'");
+    en_us.insert("model.error", "✗ Failed to set model: {}");
 
     // Settings menu
     en_us.insert("menu.settings.title", "Settings");
@@ -390,11 +1190,73 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     en_us.insert("menu.settings.current_lang", "Current Language");
     en_us.insert("menu.settings.select_lang", "Please select a language");
     en_us.insert("menu.settings.lang_changed", "Language changed");
+    en_us.insert("menu.settings.network", "⏱️  Network & Sync Settings");
+    en_us.insert("menu.settings.hotkey_menu", "⌨️  Single-key Hotkey Menu");
+    en_us.insert("menu.settings.hotkey_menu_on", "Hotkey menu enabled, takes effect on the main menu");
+    en_us.insert("menu.settings.hotkey_menu_off", "Hotkey menu disabled");
+    en_us.insert("menu.settings.offline", "🔒 Offline Mode");
+    en_us.insert("menu.settings.offline_on", "Offline mode enabled; connectivity tests and WebDAV are refused");
+    en_us.insert("menu.settings.offline_off", "Offline mode disabled");
+    en_us.insert("menu.settings.offline_forced", "Started with --offline; it cannot be disabled from settings");
+    en_us.insert("menu.settings.plain_output", "🔤 Plain Output (no emoji, no color)");
+    en_us.insert("menu.settings.plain_output_on", "Plain output enabled");
+    en_us.insert("menu.settings.plain_output_off", "Plain output disabled");
+    en_us.insert("menu.settings.console_timestamps", "🕒 Console Timestamps & Levels");
+    en_us.insert("menu.settings.console_timestamps_on", "Console timestamps enabled; messages are also written to the log file");
+    en_us.insert("menu.settings.console_timestamps_off", "Console timestamps disabled");
+    en_us.insert("menu.settings.preserve_format", "Write back YAML/TOML settings in place");
+    en_us.insert("menu.settings.preserve_format_on", "Enabled: YAML/TOML settings are written back in their own format");
+    en_us.insert("menu.settings.preserve_format_off", "Disabled: settings are always written to settings.local.json");
+    en_us.insert("menu.settings.indent", "JSON settings indentation");
+    en_us.insert("menu.settings.indent_two", "2 spaces");
+    en_us.insert("menu.settings.indent_four", "4 spaces");
+    en_us.insert("menu.settings.indent_tab", "Tabs");
+    en_us.insert("menu.settings.page_size", "Items per page");
+    en_us.insert("menu.settings.max_name_length", "Maximum name length");
+    en_us.insert("menu.settings.directory_env_summary", "📋 Config Summary in Directory List");
+    en_us.insert("menu.settings.directory_env_summary_on", "Enabled: the directory list shows each directory's Base URL host and token status");
+    en_us.insert("menu.settings.directory_env_summary_off", "Config summary in the directory list disabled");
+    en_us.insert("menu.settings.gitignore_generated", "Add generated files to .gitignore");
+    en_us.insert("menu.settings.gitignore_generated_on", "✓ After switching, the repo's .gitignore will be kept ignoring settings.local.json and CLAUDE.local.md");
+    en_us.insert("menu.settings.gitignore_generated_off", "✓ .gitignore will no longer be modified");
+    en_us.insert("menu.settings.allow_symlinked_claude_dir", "Allow .claude symlinked outside the directory");
+    en_us.insert("menu.settings.allow_symlinked_claude_dir_on", "✓ No longer warning when .claude points outside the directory");
+    en_us.insert("menu.settings.allow_symlinked_claude_dir_off", "✓ Writes now need confirmation when .claude points outside the directory");
+    en_us.insert("menu.settings.omit_empty_env", "Omit empty env values");
+    en_us.insert("menu.settings.omit_empty_env_on", "✓ Empty env values are no longer written; existing keys with that name are removed");
+    en_us.insert("menu.settings.omit_empty_env_off", "✓ Empty env values are written as empty strings");
+    en_us.insert("menu.settings.post_switch_hook", "Post-switch hook");
+    en_us.insert("menu.settings.post_switch_hook_enable", "Enable the post-switch hook? (the command runs as the current user)");
+    en_us.insert("menu.settings.post_switch_hook_hint", "Placeholders: {dir} directory path, {account} account name, {base_url} Base URL; the values are also available as CLAUDE_HOOK_DIR, CLAUDE_HOOK_ACCOUNT, CLAUDE_HOOK_BASE_URL (Unix: $VAR, Windows: !VAR!); output goes to the log");
+    en_us.insert("menu.settings.post_switch_hook_command", "Hook command");
+    en_us.insert("menu.settings.post_switch_hook_empty", "Command cannot be empty");
+    en_us.insert("menu.settings.post_switch_hook_strict", "Treat the switch as failed when the hook fails?");
+    en_us.insert("menu.settings.bulk_confirm_threshold", "Bulk confirmation threshold");
+    en_us.insert("menu.settings.health_check", "Background account health check");
+    en_us.insert("menu.settings.health_check_every", "every {} min");
+    en_us.insert("menu.settings.health_check_enable", "Periodically check account health in the background?");
+    en_us.insert("menu.settings.health_check_interval", "Check interval (minutes)");
+    en_us.insert("menu.settings.health_check_offline", "Offline mode is on; checks start once offline mode is turned off");
+    en_us.insert("menu.settings.export", "💾 Export App Settings");
+    en_us.insert("menu.settings.import", "📥 Restore App Settings");
+    en_us.insert("menu.settings.backup_path", "Backup file path");
+    en_us.insert("menu.settings.include_secrets", "Include WebDAV passwords?");
+    en_us.insert("menu.settings.import_confirm", "Restoring overwrites current app settings and updates WebDAV configs by name. Continue?");
+    en_us.insert("menu.settings.file", "Settings file: {}");
+    en_us.insert("menu.settings.http_timeout", "HTTP timeout (seconds)");
+    en_us.insert("menu.settings.max_retries", "Max retries");
+    en_us.insert("menu.settings.sync_interval", "Sync interval (minutes)");
+    en_us.insert("menu.settings.workspace_root", "Workspace root");
+    en_us.insert("menu.settings.workspace_root_hint", "Leave empty to use $WORKSPACE or the home directory");
+    en_us.insert("menu.settings.saved", "Settings saved");
+    en_us.insert("menu.settings.must_be_positive", "Please enter an integer greater than 0");
 
     // Common operations
     en_us.insert("common.success", "✓ Operation successful");
     en_us.insert("common.error", "✗ Operation failed");
     en_us.insert("common.cancel", "Operation cancelled");
+    en_us.insert("common.enabled", "On");
+    en_us.insert("common.disabled", "Off");
     en_us.insert("common.back", "Back");
     en_us.insert("common.back_cancel", "🔙 Cancel");
     en_us.insert("common.continue", "Press Enter to continue");
@@ -407,6 +1269,23 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
 
     // Database
     en_us.insert("db.init", "Initializing database...");
+    en_us.insert("db.ephemeral", "Using an in-memory database (--ephemeral); nothing is kept after exit");
+    en_us.insert("db.corrupt.detected", "✗ The database file is corrupted: {} ({})");
+    en_us.insert("db.corrupt.non_interactive", "The database file is corrupted; run claude-config in a terminal to recover it: {}");
+    en_us.insert("db.corrupt.backed_up", "Backed up the corrupted database to: {}");
+    en_us.insert("db.corrupt.prompt", "Choose how to recover");
+    en_us.insert("db.corrupt.option_exit", "Exit (leave the database file untouched)");
+    en_us.insert("db.corrupt.option_fresh", "Reinitialize an empty database (existing data is lost)");
+    en_us.insert("db.corrupt.option_bundle", "Restore from a local bundle file");
+    en_us.insert("db.corrupt.option_webdav", "Restore from a WebDAV backup");
+    en_us.insert("db.corrupt.select_webdav", "Select a WebDAV configuration");
+    en_us.insert("db.corrupt.select_file", "Select a backup file (newest first)");
+    en_us.insert("db.corrupt.no_files", "No backup files on WebDAV");
+    en_us.insert("db.corrupt.prompt_bundle_path", "Bundle file path (empty to go back)");
+    en_us.insert("db.corrupt.load_error", "✗ Failed to load the backup: {}");
+    en_us.insert("db.corrupt.fresh_done", "✓ Reinitialized an empty database");
+    en_us.insert("db.corrupt.restored", "✓ Restored from {}: {} accounts, {} Base URLs");
+    en_us.insert("db.corrupt.aborted", "Exited; the database file was not modified");
     en_us.insert("db.init_success", "✓ Database initialized successfully");
     en_us.insert("db.init_error", "✗ Database initialization failed");
     en_us.insert(
@@ -424,21 +1303,67 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     en_us.insert("account.menu.list", "📝 View All Accounts");
     en_us.insert("account.menu.add", "➕ Add New Account");
     en_us.insert("account.menu.edit", "✏️  Edit Account");
+    en_us.insert("account.menu.rename", "🏷️  Rename Account");
+    en_us.insert("account.menu.clone", "📋 Duplicate Account");
     en_us.insert("account.menu.delete", "🗑️  Delete Account");
+    en_us.insert("account.menu.batch_delete", "🧹 Batch Delete Accounts");
+    en_us.insert("account.menu.test_all", "🩺 Test All Accounts");
+    en_us.insert("account.menu.claude_md", "📝 CLAUDE.local.md Template");
+    en_us.insert("account.menu.export_snippet", "📤 Export Shareable Snippet (no token)");
+    en_us.insert("account.menu.import_snippet", "📥 Import from Snippet");
+    en_us.insert("account.menu.fallbacks", "🔗 Fallback Chain");
     en_us.insert("account.list.no_records", "No account records");
     en_us.insert("account.list.header_id", "ID");
     en_us.insert("account.list.header_name", "Account Name");
     en_us.insert("account.list.header_base_url", "Base URL");
     en_us.insert("account.list.header_model", "Model");
+    en_us.insert("account.list.header_auth_style", "Auth Style");
     en_us.insert("account.list.header_status", "Status");
+    en_us.insert("account.list.header_health", "Health");
+    en_us.insert("account.list.header_note", "Note");
     en_us.insert("account.list.status_active", "🟢 Active");
     en_us.insert("account.list.status_inactive", "⚪ Inactive");
-    en_us.insert("account.list.total", "Total {} accounts");
+    en_us.insert("account.test_all.running", "Testing connectivity of {} accounts...");
+    en_us.insert("account.test_all.header_status", "HTTP Status");
+    en_us.insert("account.test_all.header_latency", "Latency");
+    en_us.insert("account.test_all.header_token", "Token");
+    en_us.insert("account.test_all.summary", "{} succeeded, {} failed");
+    en_us.insert("account.test_all.timeout", "Timed out after {}s");
+    en_us.insert("account.test_all.progress", "Testing accounts");
+    en_us.insert("account.test_all.rate_limited", "rate limited");
+    en_us.insert("health.never", "Not checked");
+    en_us.insert("health.ok", "OK");
+    en_us.insert("health.rate_limited", "Rate limited");
+    en_us.insert("health.failed_status", "Failed ({})");
+    en_us.insert("health.unreachable", "Unreachable");
+    en_us.insert("account.test_all.rate_limited_summary", "⚠ Rate limiting encountered; retried per Retry-After with lower concurrency, {} still rate limited");
     en_us.insert("account.add.title", "Add New Account");
     en_us.insert("account.add.prompt_name", "Account Name");
     en_us.insert("account.add.prompt_token", "API Token");
+    en_us.insert("account.token_source.prompt", "Token source");
+    en_us.insert("account.token_source.inline", "Enter the token");
+    en_us.insert("account.token_source.command", "Fetch with a command (pass, op, ...; token is not stored)");
+    en_us.insert("account.token_source.prompt_command", "Command that prints the token to stdout (Enter to cancel)");
+    en_us.insert("account.list.token_from_command", "via command");
     en_us.insert("account.add.prompt_base_url", "Base URL");
     en_us.insert("account.add.prompt_model", "Model");
+    en_us.insert("account.add.select_auth_style", "Select auth style");
+    en_us.insert("account.add.prompt_configure_proxy", "Configure a proxy for this account?");
+    en_us.insert("account.add.prompt_http_proxy", "HTTP_PROXY (leave empty to skip)");
+    en_us.insert("account.add.prompt_https_proxy", "HTTPS_PROXY (leave empty to skip)");
+    en_us.insert("account.add.prompt_no_proxy", "NO_PROXY (comma-separated, leave empty to skip)");
+    en_us.insert("account.add.prompt_note", "Note (optional, clear to remove)");
+    en_us.insert("account.add.prompt_extra_auth_env_key", "Extra env var name for the token (e.g. OPENAI_API_KEY, leave empty for none)");
+    en_us.insert("account.add.prompt_staging_base_url", "Staging base URL (for quick A/B switching, leave empty for none)");
+    en_us.insert("account.add.invalid_staging_base_url", "Invalid staging base URL");
+    en_us.insert("account.add.invalid_env_key", "Variable names may only contain letters, digits and underscores, and must not start with a digit");
+    en_us.insert("name.empty", "Name cannot be empty");
+    en_us.insert("name.too_long", "Name may be at most {} characters long (got {})");
+    en_us.insert("name.control_char", "Name cannot contain newlines or other control characters");
+    en_us.insert("account.add.invalid_proxy", "Invalid proxy URL, e.g. http://proxy.example.com:8080");
+    en_us.insert("account.auth_style.both", "Both (ANTHROPIC_API_KEY + ANTHROPIC_AUTH_TOKEN)");
+    en_us.insert("account.auth_style.api_key", "x-api-key only (ANTHROPIC_API_KEY)");
+    en_us.insert("account.auth_style.bearer_token", "Bearer only (ANTHROPIC_AUTH_TOKEN)");
     en_us.insert(
         "account.add.no_base_url",
         "No available Base URL, please enter manually",
@@ -449,6 +1374,28 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     en_us.insert("account.edit.prompt", "Select account to edit");
     en_us.insert("account.edit.success", "✓ Account updated successfully");
     en_us.insert("account.edit.error", "✗ Update failed: {}");
+    en_us.insert("account.rename.prompt", "Select the account to rename");
+    en_us.insert("account.rename.prompt_name", "New name");
+    en_us.insert("account.rename.duplicate", "✗ An account named {} already exists");
+    en_us.insert("account.rename.success", "✓ Renamed account {} to {}");
+    en_us.insert("account.rename.user_name_hint", "USER_NAME in directory configs is updated on the next switch; profiles and directory links are unaffected");
+    en_us.insert("account.token.empty", "Token cannot be empty");
+    en_us.insert("account.token.whitespace", "Token cannot contain spaces or line breaks");
+    en_us.insert("account.token.unexpected_prefix", "⚠️  Token does not start with sk-ant- or sk-, please double-check it");
+    en_us.insert("account.swap.base_url_looks_like_token", "⚠ The Base URL looks like a token (starts with sk-ant- or is not a URL); it may be swapped with the token");
+    en_us.insert("account.swap.token_looks_like_url", "⚠ The token looks like a URL (starts with http); it may be swapped with the Base URL");
+    en_us.insert("account.swap.confirm", "Some proxies use unusual formats. Continue anyway?");
+    en_us.insert("account.clone.prompt", "Select account to duplicate");
+    en_us.insert("account.snippet.export_prompt", "Select account to export");
+    en_us.insert("account.snippet.format_hint", "Files ending in .env are written as environment variables, others as JSON");
+    en_us.insert("account.snippet.path", "Snippet file path");
+    en_us.insert("account.snippet.exported", "✓ Snippet exported: {}");
+    en_us.insert("account.snippet.placeholder_note", "The token was replaced with {}; importers must enter their own token");
+    en_us.insert("account.snippet.import_title", "Configuration from the snippet:");
+    en_us.insert("account.snippet.error", "✗ Failed to process the snippet: {}");
+    en_us.insert("account.clone.title", "Duplicate Account");
+    en_us.insert("account.clone.name_suffix", "{} (Copy)");
+    en_us.insert("account.clone.keep_base_url", "Keep the source account's URL ({})");
     en_us.insert("account.delete.prompt", "Select account to delete");
     en_us.insert(
         "account.delete.confirm",
@@ -456,6 +1403,31 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     );
     en_us.insert("account.delete.success", "✓ Account deleted successfully");
     en_us.insert("account.delete.error", "✗ Deletion failed: {}");
+    en_us.insert("account.fallbacks.need_two", "At least two accounts are needed to set up a fallback chain");
+    en_us.insert("account.fallbacks.select_account", "Select the primary account");
+    en_us.insert("account.fallbacks.none", "Account {} has no fallback accounts yet");
+    en_us.insert("account.fallbacks.current", "Current fallback chain of {}: {}");
+    en_us.insert("account.fallbacks.hint", "Pick fallback accounts in the order they should be tried; choose \"Done\" to save (pick none to clear), ESC to discard");
+    en_us.insert("account.fallbacks.done", "✓ Done");
+    en_us.insert("account.fallbacks.select_next", "Fallback #{}");
+    en_us.insert("account.fallbacks.saved", "✓ Saved the fallback chain of {}: {}");
+    en_us.insert("account.fallbacks.cleared", "✓ Cleared the fallback chain of {}");
+    en_us.insert("account.fallbacks.error", "✗ Failed to save the fallback chain: {}");
+    en_us.insert("account.delete.profile_warning", "⚠ These profiles reference the account(s) being deleted and will lose their account: {}");
+    en_us.insert("account.batch_delete.prompt", "Select accounts to delete (Space to toggle, Enter to confirm)");
+    en_us.insert("account.batch_delete.header_in_use", "Used by Directories");
+    en_us.insert("account.batch_delete.in_use_warning", "⚠ {} of these accounts are used by directories; their config files stay unchanged but will no longer be linked to an account");
+    en_us.insert("account.batch_delete.confirm", "Delete the {} accounts above?");
+    en_us.insert("account.batch_delete.success", "✓ Deleted {} accounts");
+    en_us.insert("account.claude_md.prompt", "Select account to set the CLAUDE.local.md template for");
+    en_us.insert("account.claude_md.custom", "custom template");
+    en_us.insert("account.claude_md.default", "bundled template");
+    en_us.insert("account.claude_md.action", "Choose an action");
+    en_us.insert("account.claude_md.edit", "Edit in editor");
+    en_us.insert("account.claude_md.restore_default", "Restore bundled template");
+    en_us.insert("account.claude_md.saved", "✓ Saved the CLAUDE.local.md template for {}; it is written on the next switch");
+    en_us.insert("account.claude_md.using_default", "✓ {} now uses the bundled CLAUDE.local.md template");
+    en_us.insert("account.claude_md.error", "✗ Failed to save: {}");
     en_us.insert("account.default_indicator", "(default)");
 
     // Directory Management
@@ -464,37 +1436,208 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     en_us.insert("directory.menu.add", "➕ Add New Directory");
     en_us.insert("directory.menu.edit", "✏️  Edit Directory");
     en_us.insert("directory.menu.delete", "🗑️  Delete Directory");
+    en_us.insert("directory.menu.edit_settings", "📄 Edit Settings File");
+    en_us.insert("directory.menu.repair_settings", "🔧 Repair Settings");
+    en_us.insert("directory.menu.audit", "🔍 Audit Config vs Account");
+    en_us.insert("directory.menu.validate_settings", "✅ Validate Settings");
+    en_us.insert("directory.menu.effective_env", "🔎 View Effective Environment");
+    en_us.insert("directory.menu.trash", "♻️  Recycle Bin");
+    en_us.insert("directory.menu.reset", "🧹 Reset Directory");
+    en_us.insert("directory.menu.merge_settings", "🔀 Merge Settings Files");
+    en_us.insert("directory.menu.history", "📜 View Change History");
+    en_us.insert("directory.menu.migrate_legacy", "📦 Migrate Legacy Config Files");
+    en_us.insert("directory.menu.lock", "🔒 Lock/Unlock Directory");
+    en_us.insert("directory.lock.marker", "🔒");
+    en_us.insert("directory.lock.prompt", "Select a directory to lock or unlock");
+    en_us.insert("directory.lock.confirm_lock", "Lock directory '{}'? Switching, clearing and resetting will be blocked");
+    en_us.insert("directory.lock.confirm_unlock", "Unlock directory '{}'?");
+    en_us.insert("directory.lock.locked", "✓ Directory '{}' locked");
+    en_us.insert("directory.lock.unlocked", "✓ Directory '{}' unlocked");
+    en_us.insert("directory.lock.refused", "⚠ Directory '{}' is locked; unlock it before changing its configuration");
+    en_us.insert("directory.lock.error", "✗ Failed to update lock state: {}");
+    en_us.insert("directory.menu.copy_config", "📋 Copy Config From Another Directory");
+    en_us.insert("directory.menu.mcp", "🧩 Manage MCP Servers");
+    en_us.insert("directory.menu.external_changes", "🔎 Detect External Changes");
+    en_us.insert("directory.menu.clipboard_env", "📋 Import Env Vars from Clipboard");
+    en_us.insert("directory.menu.claude_md_report", "📄 Compare CLAUDE.local.md with Template");
+    en_us.insert("directory.menu.deactivate_all", "⏸️  Deactivate All Directories");
+    en_us.insert("directory.menu.activate_selected", "▶️  Activate Selected Directories");
+    en_us.insert("directory.menu.expected_account", "📌 Set Expected Account");
+    en_us.insert("directory.expected.current", "expected: {}");
+    en_us.insert("directory.expected.select_directory", "Select a directory to pin");
+    en_us.insert("directory.expected.select_account", "Select the expected account");
+    en_us.insert("directory.expected.none", "(No expected account)");
+    en_us.insert("directory.expected.set", "✓ Expected account of {} set to {}");
+    en_us.insert("directory.expected.cleared", "✓ Expected account of {} cleared");
+    en_us.insert("directory.expected.error", "✗ Failed to set expected account: {}");
+    en_us.insert("directory.copy_config.need_two", "At least two directories are needed to copy a config");
+    en_us.insert("directory.copy_config.source_prompt", "Select the source directory (copy from)");
+    en_us.insert("directory.copy_config.target_prompt", "Select the target directory (copy to)");
+    en_us.insert("directory.copy_config.same", "Source and target are the same directory");
+    en_us.insert("directory.copy_config.confirm_overwrite", "Directory '{}' already has a config. Overwrite its environment variables?");
+    en_us.insert("directory.copy_config.include_claude_md", "Also copy CLAUDE.local.md?");
+    en_us.insert("directory.copy_config.success", "✓ Copied config from '{}' to '{}'");
+    en_us.insert("directory.copy_config.env_keys", "Copied variables: {}");
+    en_us.insert("directory.copy_config.no_env", "The source has no environment variables; the target's were left unchanged");
+    en_us.insert("directory.copy_config.claude_md_copied", "Copied CLAUDE.local.md");
+    en_us.insert("directory.copy_config.claude_md_missing", "The source has no CLAUDE.local.md; skipped");
+    en_us.insert("directory.copy_config.error", "✗ Failed to copy config: {}");
+    en_us.insert("directory.active.none_active", "No directories are active");
+    en_us.insert("directory.active.deactivated", "✓ Deactivated {} directories");
+    en_us.insert("directory.active.activated", "✓ Activated {} directories");
+    en_us.insert("directory.active.locked_skipped", "Skipped locked directories: {}");
+    en_us.insert("directory.active.select_prompt", "Select directories to activate (space to toggle, enter to confirm)");
+    en_us.insert("directory.active.error", "✗ Failed to update active state: {}");
+    en_us.insert("directory.claude_md_report.header_status", "CLAUDE.local.md");
+    en_us.insert("directory.claude_md_report.identical", "Identical");
+    en_us.insert("directory.claude_md_report.outdated", "Outdated (unmodified)");
+    en_us.insert("directory.claude_md_report.modified", "Modified");
+    en_us.insert("directory.claude_md_report.missing", "Missing");
+    en_us.insert("directory.claude_md_report.error", "Read failed: {}");
+    en_us.insert("directory.claude_md_report.confirm_refresh", "{} directories use an outdated, unmodified template. Refresh them to the current template? (modified files are left alone)");
+    en_us.insert("directory.claude_md_report.refresh_error", "✗ Failed to refresh directory '{}': {}");
+    en_us.insert("directory.symlink_escape.warning", "⚠ The .claude of directory {} actually points outside it: {}");
+    en_us.insert("directory.symlink_escape.hint", "Config will be written to that location. If you share .claude on purpose, allow symlinks in settings");
+    en_us.insert("directory.symlink_escape.confirm", "Write anyway?");
+    en_us.insert("directory.clipboard_env.read_failed", "✗ Failed to read clipboard: {}");
+    en_us.insert("directory.clipboard_env.empty", "No KEY=VALUE env vars found in the clipboard");
+    en_us.insert("directory.clipboard_env.invalid", "✗ Clipboard content is not env vars: line {} is not a valid KEY=VALUE");
+    en_us.insert("directory.clipboard_env.found", "Found {} env vars in the clipboard (values masked):");
+    en_us.insert("directory.clipboard_env.prompt", "Select the directory to merge env vars into");
+    en_us.insert("directory.clipboard_env.confirm", "Merge into the env of directory '{}'? Existing vars with the same name will be overwritten");
+    en_us.insert("directory.clipboard_env.success", "✓ Merged {} env vars into directory '{}'");
+    en_us.insert("directory.clipboard_env.error", "✗ Failed to merge env vars: {}");
     en_us.insert("directory.list.no_records", "No directory records");
+    en_us.insert("mcp.select_directory", "Select a directory to manage MCP servers");
+    en_us.insert("mcp.menu.title", "MCP Servers");
+    en_us.insert("mcp.menu.list", "📋 List Servers");
+    en_us.insert("mcp.menu.set", "➕ Add/Edit Server");
+    en_us.insert("mcp.menu.remove", "🗑️  Remove Server");
+    en_us.insert("mcp.list.empty", "No MCP servers configured in this directory");
+    en_us.insert("mcp.header.name", "Name");
+    en_us.insert("mcp.header.command", "Command");
+    en_us.insert("mcp.header.args", "Args");
+    en_us.insert("mcp.header.env", "Env");
+    en_us.insert("mcp.header.other", "Other Fields");
+    en_us.insert("mcp.set.prompt_name", "Server name (edits if it exists)");
+    en_us.insert("mcp.set.editing", "Editing existing server: {}");
+    en_us.insert("mcp.set.prompt_command", "Command");
+    en_us.insert("mcp.set.prompt_args", "Arguments (space separated)");
+    en_us.insert("mcp.set.current_env", "Current env vars: {}");
+    en_us.insert("mcp.set.env_hint", "Enter KEY=VALUE to set an env var, KEY= to remove it, or press Enter to finish");
+    en_us.insert("mcp.set.prompt_env", "Env var");
+    en_us.insert("mcp.set.env_invalid", "Invalid format, expected KEY=VALUE");
+    en_us.insert("mcp.set.command_required", "Command cannot be empty, nothing saved");
+    en_us.insert("mcp.set.success", "✓ Saved MCP server: {}");
+    en_us.insert("mcp.remove.prompt", "Select a server to remove");
+    en_us.insert("mcp.remove.confirm", "Remove MCP server {}?");
+    en_us.insert("mcp.remove.success", "✓ Removed MCP server: {}");
+    en_us.insert("mcp.error", "MCP server operation failed: {}");
+    en_us.insert("external.header.name", "Directory");
+    en_us.insert("external.header.state", "State");
+    en_us.insert("external.header.written_at", "Last Written");
+    en_us.insert("external.header.path", "Settings File");
+    en_us.insert("external.state.unchanged", "Unchanged");
+    en_us.insert("external.state.modified", "Modified externally");
+    en_us.insert("external.state.deleted", "Deleted externally");
+    en_us.insert("external.state.created", "Created externally");
+    en_us.insert("external.state.no_record", "Never written by this tool");
+    en_us.insert("external.none", "✓ All settings match what this tool last wrote");
+    en_us.insert("external.summary", "{} directories were changed outside this tool since the last write");
+    en_us.insert("external.diff_prompt", "Select a directory to view the diff against the last write");
+    en_us.insert("external.diff_title", "Diff (- last written, + current file): {}");
+    en_us.insert("external.no_snapshot", "The last written settings file was too large or contains a command-sourced token, so no snapshot was saved and no diff is available");
     en_us.insert("directory.list.header_id", "ID");
     en_us.insert("directory.list.header_name", "Directory Name");
     en_us.insert("directory.list.header_path", "Path");
     en_us.insert("directory.list.header_exists", "Exists");
     en_us.insert("directory.list.exists", "✓ Exists");
     en_us.insert("directory.list.not_exists", "✗ Not Exists");
-    en_us.insert("directory.list.total", "Total {} directories");
+    en_us.insert("directory.list.header_last_switched", "Last Switched");
+    en_us.insert("directory.list.header_base_url", "Base URL Host");
+    en_us.insert("directory.list.header_token", "Token");
+    en_us.insert("directory.list.relative", "(relative)");
     en_us.insert("directory.add.title", "Add New Directory");
     en_us.insert("directory.add.prompt_name", "Directory Name");
     en_us.insert("directory.add.prompt_path", "Path");
     en_us.insert("directory.add.warn_path_not_exists", "⚠️  Warning: Path does not exist");
+    en_us.insert("directory.add.relative_hint", "Relative path will be resolved against the workspace root: {}");
+    en_us.insert("directory.add.prompt_relative", "This path is under the workspace root {}. Store it as a relative path (portable across machines)?");
     en_us.insert(
         "directory.add.success",
         "✓ Directory '{}' added successfully",
     );
     en_us.insert("directory.add.error", "✗ Addition failed: {}");
+    en_us.insert("directory.add.path_not_exists", "Path does not exist: {}");
     en_us.insert("directory.edit.prompt", "Select directory to edit");
     en_us.insert("directory.edit.success", "✓ Directory updated successfully");
     en_us.insert("directory.edit.error", "✗ Update failed: {}");
+    en_us.insert("directory.edit.no_changes", "No changes");
+    en_us.insert("directory.edit.header_field", "Field");
+    en_us.insert("directory.edit.header_old", "Old Value");
+    en_us.insert("directory.edit.header_new", "New Value");
+    en_us.insert("directory.edit.confirm", "Save the changes above?");
     en_us.insert("directory.delete.prompt", "Select directory to delete");
     en_us.insert(
         "directory.delete.confirm",
         "Are you sure you want to delete directory '{}'?",
     );
-    en_us.insert("directory.delete.warning", "(Only deletes database record, not actual files)");
+    en_us.insert("directory.delete.warning", "(Moves it to the recycle bin; actual files are kept)");
     en_us.insert(
         "directory.delete.success",
-        "✓ Directory deleted successfully",
+        "✓ Directory moved to the recycle bin",
     );
     en_us.insert("directory.delete.error", "✗ Deletion failed: {}");
+    en_us.insert("directory.trash.empty", "Recycle bin is empty");
+    en_us.insert("directory.trash.prompt", "Select a directory in the recycle bin");
+    en_us.insert("directory.trash.item", "{} - {} (deleted {})");
+    en_us.insert("directory.trash.action_prompt", "Choose an action");
+    en_us.insert("directory.trash.restore", "Restore");
+    en_us.insert("directory.trash.purge", "Delete permanently");
+    en_us.insert("directory.trash.restore_success", "✓ Directory restored");
+    en_us.insert("directory.trash.purge_confirm", "Permanently delete directory '{}'?");
+    en_us.insert("directory.trash.purge_confirm_again", "This cannot be undone and its associations will be removed too. Continue?");
+    en_us.insert("directory.trash.purge_success", "✓ Directory permanently deleted");
+    en_us.insert("directory.trash.error", "✗ Operation failed: {}");
+    en_us.insert("directory.reset.prompt", "Select directory to reset");
+    en_us.insert("directory.reset.select_steps", "Choose steps to run (space to toggle, enter to confirm)");
+    en_us.insert("directory.reset.step_env", "Remove Anthropic env keys");
+    en_us.insert("directory.reset.step_sandbox", "Remove IS_SANDBOX");
+    en_us.insert("directory.reset.step_claude_md", "Delete generated CLAUDE.local.md (only if unmodified)");
+    en_us.insert("directory.reset.step_claude_dir", "Remove empty .claude directory");
+    en_us.insert("directory.reset.confirm", "Reset directory '{}'?");
+    en_us.insert("directory.reset.removed_keys", "✓ Removed env keys: {}");
+    en_us.insert("directory.reset.settings_removed", "✓ settings.local.json was empty and has been deleted");
+    en_us.insert("directory.reset.claude_md_removed", "✓ Deleted CLAUDE.local.md");
+    en_us.insert("directory.reset.claude_md_kept", "⚠ CLAUDE.local.md was modified and has been kept");
+    en_us.insert("directory.reset.claude_dir_removed", "✓ Removed empty .claude directory");
+    en_us.insert("directory.quick_clear.title", "Clear Current Directory Config");
+    en_us.insert("directory.quick_clear.no_current", "No current directory recorded yet (no switch so far); please choose one");
+    en_us.insert("directory.quick_clear.prompt", "Select the directory to clear");
+    en_us.insert("directory.quick_clear.nothing", "Directory {} has no auth config to clear");
+    en_us.insert("directory.quick_clear.confirm", "Clear the token and Base URL of directory {} ({})?");
+    en_us.insert("directory.quick_clear.success", "✓ Cleared the following from directory {}:");
+    en_us.insert("directory.quick_clear.error", "✗ Failed to clear config: {}");
+    en_us.insert("directory.reset.nothing", "Nothing to clean up");
+    en_us.insert("directory.reset.error", "✗ Reset failed: {}");
+    en_us.insert("directory.merge_settings.prompt", "Select directory whose settings files to merge");
+    en_us.insert("directory.merge_settings.none", "No stale settings files to merge");
+    en_us.insert("directory.merge_settings.found", "These files will be merged into settings.local.json (existing values win) and renamed to .bak:");
+    en_us.insert("directory.merge_settings.confirm", "Merge now?");
+    en_us.insert("directory.merge_settings.success", "✓ Merged {} files");
+    en_us.insert("directory.merge_settings.error", "✗ Merge failed: {}");
+    en_us.insert("directory.history.prompt", "Select directory to view change history");
+    en_us.insert("directory.history.empty", "No config changes recorded for this directory");
+    en_us.insert("directory.history.title", "Config change history of {} (file hashes only):");
+    en_us.insert("directory.history.header_time", "Time");
+    en_us.insert("directory.history.header_action", "Action");
+    en_us.insert("directory.history.header_before", "Before");
+    en_us.insert("directory.history.header_after", "After");
+    en_us.insert("directory.history.header_actor", "User");
+    en_us.insert("directory.history.header_base_url", "Base URL");
+    en_us.insert("directory.history.action_apply", "Apply");
+    en_us.insert("directory.history.action_clear", "Clear");
+    en_us.insert("directory.history.action_reset", "Reset");
 
     // URL Management
     en_us.insert("url.menu.title", "URL Management");
@@ -543,8 +1686,10 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
         "No directory records, please add a directory first",
     );
     en_us.insert("switch.select_account", "Select Account");
+    en_us.insert("switch.recent_account", "[Recent] {}");
     en_us.insert("switch.select_directory", "Select Directory");
     en_us.insert("switch.prompt_skip_permissions", "Skip permission check? (Recommended: Yes)");
+    en_us.insert("switch.prompt_use_staging", "Use staging base URL {} for this switch?");
     en_us.insert("switch.prompt_use_proxy", "Use proxy? (Load proxy settings from Claude config)");
     en_us.insert("switch.switching", "Switching configuration...");
     en_us.insert("switch.success", "✓ Configuration switched successfully!");
@@ -556,6 +1701,23 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     en_us.insert("switch.directory", "  Directory: {}");
     en_us.insert("switch.path", "  Path: {}");
     en_us.insert("switch.sandbox", "  Sandbox Mode: Enabled");
+    en_us.insert("switch.template", "  Template: {}");
+    en_us.insert("switch.invalid_token", "✗ Account token is invalid: {}, please edit the account first");
+    en_us.insert("switch.not_expected_account", "⚠ Directory {} is pinned to {}; this account deviates from the pin");
+    en_us.insert("switch.batch.not_expected_account", "⚠ These directories are pinned to another account and will deviate: {}");
+    en_us.insert("token_command.timeout", "Token command did not finish within {} seconds");
+    en_us.insert("token_command.spawn_failed", "Failed to run token command: {}");
+    en_us.insert("token_command.failed", "Token command failed: {}");
+    en_us.insert("token_command.empty_output", "Token command printed nothing");
+    en_us.insert("hook.timeout", "Post-switch hook did not finish within {} seconds");
+    en_us.insert("hook.spawn_failed", "Failed to start post-switch hook command: {}");
+    en_us.insert("hook.failed", "Post-switch hook exited with status: {}");
+    en_us.insert("switch.preview.title", "About to apply:");
+    en_us.insert("switch.preview.claude_md_create", "will be created");
+    en_us.insert("switch.preview.claude_md_overwrite", "will be overwritten (differs from bundled template)");
+    en_us.insert("switch.preview.claude_md_skip_identical", "skipped (identical)");
+    en_us.insert("switch.preview.claude_md_skip_modified", "skipped (user-modified)");
+    en_us.insert("switch.preview.confirm", "Proceed with the switch?");
     en_us.insert("switch.permission", "  Permission Check: {}");
     en_us.insert("switch.permission_skipped", "Skipped");
     en_us.insert("switch.permission_required", "Required");
@@ -570,11 +1732,87 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
         "switch.warn_write_fail",
         "Warning: Failed to write Claude config: {}",
     );
+    en_us.insert("switch.gitignore_updated", "Appended to .gitignore: {}");
+    en_us.insert("switch.gitignore_failed", "⚠ Failed to update .gitignore: {}");
+    en_us.insert("switch.batch.symlink_escape", ".claude points outside the directory ({}), skipped");
+    en_us.insert("switch.hook_failed", "Post-switch hook failed: {} (see log for output)");
     en_us.insert(
         "switch.error_update",
         "✗ Configuration file update failed: {}",
     );
     en_us.insert("switch.error", "✗ Switch failed: {}");
+    en_us.insert("switch.unchanged", "No changes; nothing was written");
+    en_us.insert("switch.batch.title", "Batch Switch");
+    en_us.insert("switch.batch.last_record", "The last batch switch (account '{}', {}) has unfinished directories");
+    en_us.insert("switch.batch.retry_failed", "🔁 Retry failed entries ({} directories)");
+    en_us.insert("switch.batch.new", "📦 Start a new batch switch");
+    en_us.insert("switch.batch.locked_skipped", "Locked directories are excluded from batch switching: {}");
+    en_us.insert("switch.batch.select_directories", "Select directories to switch (Space to select, Enter to confirm)");
+    en_us.insert("switch.batch.filter_prompt", "Pre-select directories by name or path");
+    en_us.insert("switch.batch.filter_none", "No filter, pick manually");
+    en_us.insert("switch.batch.filter_glob", "Glob (e.g. ~/work/** or *-api)");
+    en_us.insert("switch.batch.filter_regex", "Regular expression (e.g. -api$)");
+    en_us.insert("switch.batch.filter_pattern", "Pattern matched against directory name or path");
+    en_us.insert("switch.batch.filter_invalid", "✗ Invalid pattern: {}");
+    en_us.insert("switch.batch.filter_no_match", "No directory matches {}, please try again");
+    en_us.insert("switch.batch.filter_matched", "Pre-selected {} of {} directories; adjust the selection in the list");
+    en_us.insert("switch.batch.confirm", "Apply account '{}' to {} directories?");
+    en_us.insert("switch.batch.running", "Switching {} directories...");
+    en_us.insert("switch.batch.progress", "Batch switch");
+    en_us.insert("switch.batch.directory_missing", "The directory has been deleted");
+    en_us.insert("switch.batch.account_missing", "✗ Account '{}' used by the batch switch no longer exists; cannot retry");
+    en_us.insert("switch.batch.template_missing", "The template used by the batch switch no longer exists; retrying without a template");
+    en_us.insert("switch.batch.header_error", "Error");
+    en_us.insert("switch.batch.status_succeeded", "Succeeded");
+    en_us.insert("switch.batch.status_unchanged", "Unchanged");
+    en_us.insert("switch.batch.status_failed", "Failed");
+    en_us.insert("switch.batch.status_pending", "Pending");
+    en_us.insert("switch.batch.summary", "{} succeeded, {} unchanged, {} to retry");
+    en_us.insert("switch.batch.retry_prompt", "Retry failed entries ({} directories)?");
+    en_us.insert("switch.batch.interrupted", "Interrupted: {} directories not processed yet. Choose to resume next time you open batch switch");
+    en_us.insert("interrupt.finishing", "Finishing the current write before stopping (press Ctrl-C again to quit immediately)");
+    en_us.insert("switch.worktree.found", "This directory is a git repository with {} other worktrees:");
+    en_us.insert("switch.worktree.confirm", "Apply the same account to all worktrees?");
+    en_us.insert("switch.worktree.locked", "{} (directory is locked, skipped)");
+    en_us.insert("switch.worktree.unchanged", "{} (unchanged)");
+    en_us.insert("switch.worktree.summary", "Worktrees: {} updated, {} unchanged, {} failed");
+    en_us.insert("switch.fallback.confirm", "Account {} has {} fallback accounts. Test connectivity first and fall back if it is down?");
+    en_us.insert("switch.fallback.testing", "Testing accounts in chain order...");
+    en_us.insert("switch.fallback.used", "⚠ Account {} is unavailable; using fallback account {} instead");
+    en_us.insert("switch.fallback.all_failed", "✗ Every account in the fallback chain is unavailable");
+    en_us.insert("switch.fallback.use_primary", "Continue the switch with the primary account {} anyway?");
+    en_us.insert("profile.menu.title", "Profiles");
+    en_us.insert("profile.menu.apply", "⚡ Apply Profile");
+    en_us.insert("profile.menu.list", "📝 List Profiles");
+    en_us.insert("profile.menu.add", "➕ New Profile");
+    en_us.insert("profile.menu.delete", "🗑️  Delete Profile");
+    en_us.insert("profile.list.no_records", "No profiles");
+    en_us.insert("profile.list.account_missing", "(account deleted)");
+    en_us.insert("profile.header.name", "Name");
+    en_us.insert("profile.header.account", "Account");
+    en_us.insert("profile.header.template", "Template");
+    en_us.insert("profile.header.skip_permissions", "Skip Permissions");
+    en_us.insert("profile.header.use_proxy", "Use Proxy");
+    en_us.insert("profile.add.title", "New Profile");
+    en_us.insert("profile.add.prompt_name", "Profile name");
+    en_us.insert("profile.add.success", "✓ Profile saved: {}");
+    en_us.insert("profile.add.error", "✗ Failed to save profile: {}");
+    en_us.insert("profile.apply.prompt", "Select a profile to apply");
+    en_us.insert("profile.apply.no_account", "The account referenced by profile {} was deleted; please recreate the profile");
+    en_us.insert("profile.apply.account_error", "✗ Failed to load the profile's account: {}");
+    en_us.insert("profile.apply.confirm", "Apply profile {} to directory {}?");
+    en_us.insert("profile.delete.prompt", "Select a profile to delete");
+    en_us.insert("profile.delete.confirm", "Delete profile '{}'?");
+    en_us.insert("profile.delete.success", "✓ Profile deleted");
+    en_us.insert("profile.delete.error", "✗ Failed to delete profile: {}");
+    en_us.insert("switch.unchanged_hint", "Start with --force to rewrite anyway");
+    en_us.insert("switch.env_override", "⚠ {} is set in the current environment; Claude Code will use it instead of the stored config");
+    en_us.insert("switch.terminal.prompt", "Open a terminal in this directory?");
+    en_us.insert("switch.terminal.shell_hint", "Started {} in this directory, type exit to return to the menu");
+    en_us.insert("switch.terminal.error", "⚠ Failed to open terminal: {}");
+    en_us.insert("switch.repeat.title", "Repeat Last Switch");
+    en_us.insert("switch.repeat.none", "No previous switch to repeat (the last account or directory may have been deleted)");
+    en_us.insert("switch.repeat.confirm", "Apply account '{}' to directory '{}' ({})?");
 
     // WebDAV Sync
     en_us.insert("webdav.menu.title", "WebDAV Sync");
@@ -583,9 +1821,17 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
     en_us.insert("webdav.menu.upload", "⬆️  Upload Configuration");
     en_us.insert("webdav.menu.download", "⬇️  Download Configuration");
     en_us.insert("webdav.menu.list", "📝 View Remote Files");
+    en_us.insert("webdav.menu.browse_remote", "🌐 Browse Remote");
+    en_us.insert("webdav.menu.namespace", "🏷️  Set Namespace");
     en_us.insert("webdav.menu.delete", "🗑️  Delete Configuration");
     en_us.insert("webdav.test.success", "✓ WebDAV connection test successful");
     en_us.insert("webdav.test.error", "✗ Connection test failed: {}");
+    en_us.insert("webdav.add.summary", "Configuration to save:");
+    en_us.insert("webdav.add.probe_auth_failed", "✗ Authentication failed (HTTP {}); check the username and password");
+    en_us.insert("webdav.add.probe_status", "✗ The server returned an unexpected status (HTTP {}); check the URL");
+    en_us.insert("webdav.add.probe_unreachable", "✗ Unable to reach the server: {}");
+    en_us.insert("webdav.add.save_anyway", "The connection test failed. Save anyway?");
+    en_us.insert("webdav.add.not_saved", "Configuration not saved");
     en_us.insert(
         "webdav.upload.clearing",
         "Clearing existing configuration...",
@@ -594,6 +1840,7 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
         "webdav.upload.cleared",
         "✓ Cleared existing accounts and Base URLs",
     );
+    en_us.insert("webdav.upload.prompt_note", "Note (optional)");
     en_us.insert("webdav.upload.importing_accounts", "Importing accounts...");
     en_us.insert(
         "webdav.upload.imported_accounts",
@@ -604,6 +1851,7 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
         "webdav.upload.imported_urls",
         "✓ Successfully imported {} Base URLs",
     );
+    en_us.insert("webdav.upload.imported_locks", "✓ Updated lock state of {} directories");
     en_us.insert(
         "webdav.upload.success",
         "✓ Configuration successfully uploaded to WebDAV: {}",
@@ -622,6 +1870,48 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
         "Successfully downloaded and imported configuration file: {}",
     );
     en_us.insert("webdav.download.error", "✗ Download failed: {}");
+    en_us.insert("webdav.download.checksum_mismatch", "✗ Bundle checksum mismatch, import aborted\n  Expected SHA-256: {}\n  Actual SHA-256:   {}");
+    en_us.insert("webdav.download.local_unchanged", "Local data was left unchanged");
+    en_us.insert("webdav.list.header_namespace", "Namespace");
+    en_us.insert("webdav.browse.select_namespace", "Select a namespace to browse");
+    en_us.insert("webdav.browse.this_machine", "(this machine)");
+    en_us.insert("webdav.browse.error", "Failed to read remote directory: {}");
+    en_us.insert("webdav.namespace.shared", "Shared path");
+    en_us.insert("webdav.namespace.current", "Current namespace: {}");
+    en_us.insert("webdav.namespace.use_hostname", "Use hostname {}");
+    en_us.insert("webdav.namespace.custom", "Custom name");
+    en_us.insert("webdav.namespace.use_shared", "Use the shared path (shared with other machines)");
+    en_us.insert("webdav.namespace.prompt", "Namespace (letters, digits, - _ .)");
+    en_us.insert("webdav.namespace.invalid", "Namespace cannot be empty");
+    en_us.insert("webdav.namespace.updated", "Bundles will be stored in {}");
+    en_us.insert("webdav.import.preview_title", "Import preview (matched by account / Base URL name):");
+    en_us.insert("webdav.import.meta_title", "Bundle info:");
+    en_us.insert("webdav.import.meta_exported_at", "  Exported at: {}");
+    en_us.insert("webdav.import.meta_app_version", "  App version: {}");
+    en_us.insert("webdav.import.meta_note", "  Note: {}");
+    en_us.insert("webdav.import.meta_unknown", "unknown");
+    en_us.insert("webdav.import.empty", "The bundle contains nothing to import");
+    en_us.insert("webdav.import.header_kind", "Type");
+    en_us.insert("webdav.import.header_name", "Name");
+    en_us.insert("webdav.import.header_action", "Action");
+    en_us.insert("webdav.import.kind_account", "Account");
+    en_us.insert("webdav.import.kind_base_url", "Base URL");
+    en_us.insert("webdav.import.action_add", "Add");
+    en_us.insert("webdav.import.action_update", "Update");
+    en_us.insert("webdav.import.action_skip", "Skip (identical)");
+    en_us.insert("webdav.import.action_local_only", "Local only (kept on merge / deleted on overwrite)");
+    en_us.insert("webdav.import.summary", "{} to add, {} to update, {} to skip, {} local only");
+    en_us.insert("webdav.import.select_mode", "Select import mode");
+    en_us.insert("webdav.import.rolled_back", "✗ Import failed and was rolled back; local data is unchanged: {}");
+    en_us.insert("bundle.import.account_failed", "Failed to import account '{}'");
+    en_us.insert("bundle.import.base_url_failed", "Failed to import Base URL '{}'");
+    en_us.insert("bundle.import.directory_lock_failed", "Failed to import lock state of directory '{}'");
+    en_us.insert("bundle.import.invalid_account_name", "Invalid account name '{}' in bundle: {}");
+    en_us.insert("bundle.import.duplicate_account", "Duplicate account name in bundle: {}");
+    en_us.insert("bundle.import.duplicate_base_url", "Duplicate Base URL name or address in bundle: {}");
+    en_us.insert("bundle.import.base_url_conflict", "Base URL '{}' in bundle has the same address as local '{}'");
+    en_us.insert("webdav.import.mode_merge", "Merge: add and update, keep local-only records");
+    en_us.insert("webdav.import.mode_overwrite", "Overwrite: clear local accounts and Base URLs, then import");
     en_us.insert("webdav.list.title", "Remote File List:");
     en_us.insert("webdav.list.error", "✗ Failed to get file list: {}");
     en_us.insert(
@@ -674,6 +1964,220 @@ static TRANSLATIONS: Lazy<HashMap<Language, HashMap<TransKey, &'static str>>> =
         "✗ Root check removal script not found: {}",
     );
 
+    // Templates
+    en_us.insert("template.menu.title", "Template Management");
+    en_us.insert("template.menu.list", "📝 View All Templates");
+    en_us.insert("template.menu.add", "➕ Add New Template");
+    en_us.insert("template.menu.edit", "✏️  Edit Template");
+    en_us.insert("template.menu.delete", "🗑️  Delete Template");
+    en_us.insert("template.list.no_records", "No template records");
+    en_us.insert("template.list.header_id", "ID");
+    en_us.insert("template.list.header_name", "Template Name");
+    en_us.insert("template.list.header_description", "Description");
+    en_us.insert("template.list.header_settings", "Settings");
+    en_us.insert("template.list.header_builtin", "Built-in");
+    en_us.insert("template.list.builtin_yes", "Yes");
+    en_us.insert("template.list.total", "Total {} templates");
+    en_us.insert("template.add.title", "Add New Template");
+    en_us.insert("template.add.prompt_name", "Template name");
+    en_us.insert("template.add.prompt_description", "Description (optional)");
+    en_us.insert("template.add.prompt_settings", "Settings (JSON)");
+    en_us.insert("template.add.json_hint", "Hint: enter a JSON object, e.g. {\"env\":{\"DISABLE_TELEMETRY\":\"1\"}}; do not include tokens");
+    en_us.insert("template.add.success", "✓ Template '{}' created successfully");
+    en_us.insert("template.add.error", "✗ Creation failed: {}");
+    en_us.insert("template.edit.prompt", "Select template to edit");
+    en_us.insert("template.edit.success", "✓ Template updated successfully");
+    en_us.insert("template.edit.error", "✗ Update failed: {}");
+    en_us.insert("template.delete.prompt", "Select template to delete");
+    en_us.insert("template.delete.confirm", "Are you sure you want to delete template '{}'?");
+    en_us.insert("template.delete.success", "✓ Template deleted successfully");
+    en_us.insert("template.delete.error", "✗ Deletion failed: {}");
+    en_us.insert("template.select.prompt", "Select settings template");
+    en_us.insert("template.select.none", "No template");
+    en_us.insert("template.error.invalid_json", "Invalid JSON: {}");
+    en_us.insert("template.error.not_object", "Template must be a JSON object");
+    en_us.insert("template.error.contains_secret", "Templates must not contain secrets: {} (tokens come from the account)");
+
+    // Watch mode
+    en_us.insert("watch.dir_not_found", "Directory with ID {} not found");
+    en_us.insert("dir_name.not_found", "Directory named {} not found");
+    en_us.insert("dir_name.ambiguous", "Multiple directories are named {}, use the id or name@path instead: {}");
+    en_us.insert("switch.cli.account_not_found", "Account with ID {} not found");
+    en_us.insert("switch.cli.prompt_register", "Directory {} is not registered yet. Register it as '{}' and apply?");
+    en_us.insert("switch.current_dir.title", "Configure Current Directory");
+    en_us.insert("switch.current_dir.matched", "Current directory: {} ({})");
+    en_us.insert("watch.no_account", "Directory {} has no associated account, run a switch first");
+    en_us.insert("watch.start", "👀 Watching {} (press Ctrl+C to stop)");
+    en_us.insert("watch.expected_account", "Expected account: {}");
+    en_us.insert("watch.mode_auto_fix", "Mode: auto-fix");
+    en_us.insert("watch.mode_warn", "Mode: warn only");
+    en_us.insert("watch.locked_no_fix", "Directory '{}' is locked; auto-fix disabled, warning only");
+    en_us.insert("watch.read_error", "Failed to read settings: {}");
+    en_us.insert("watch.drift", "⚠️  Settings changed externally, mismatched keys: {}");
+    en_us.insert("watch.fixed", "✓ Re-applied configuration for account {}");
+    en_us.insert("watch.fix_error", "Failed to re-apply configuration: {}");
+    en_us.insert("watch.stopped", "Stopped watching");
+    en_us.insert("migrate.success", "✓ {}: migrated {} into settings.local.json, original renamed to {}");
+    en_us.insert("migrate.error", "✗ {}: migration failed: {}");
+    en_us.insert("migrate.nothing", "No directory is using a legacy config file; nothing to migrate");
+    en_us.insert("migrate.summary", "Migrated {} directories");
+    en_us.insert("setting.not_found", "{} is not set");
+    en_us.insert("setting.updated", "✓ Updated {} (directory {})");
+    en_us.insert("app_settings.export.success", "✓ App settings exported to {}");
+    en_us.insert("app_settings.export.no_secrets", "WebDAV passwords are not included (use --include-secrets to export them)");
+    en_us.insert("app_settings.import.success", "✓ App settings restored from {}");
+    en_us.insert("app_settings.import.webdav", "WebDAV configs: {} created, {} updated");
+    en_us.insert("app_settings.import.missing_passwords", "⚠ These WebDAV configs have no password; restore from a backup with secrets or re-add them before connecting: {}");
+    en_us.insert("app_settings.error", "✗ Operation failed: {}");
+    en_us.insert("doctor.title", "Environment check");
+    en_us.insert("self_test.title", "Self-test: verifying config read/write in a temporary directory");
+    en_us.insert("self_test.step.create_dir", "Create temporary directory");
+    en_us.insert("self_test.step.write", "Write test config");
+    en_us.insert("self_test.step.read", "Read back and verify config");
+    en_us.insert("self_test.step.clear", "Clear config");
+    en_us.insert("self_test.step.cleanup", "Remove temporary directory");
+    en_us.insert("self_test.mismatch", "Values read back do not match what was written: {}");
+    en_us.insert("self_test.not_cleared", "Still present after clearing: {}");
+    en_us.insert("self_test.passed", "✓ Self-test passed");
+    en_us.insert("self_test.failed", "✗ Self-test failed. Please include the output above when reporting the issue");
+    en_us.insert("doctor.ok", "OK");
+    en_us.insert("doctor.hint", "Hint: {}");
+    en_us.insert("doctor.check.database", "Database");
+    en_us.insert("doctor.check.config_dir", "App data directory writable");
+    en_us.insert("doctor.check.accounts", "Accounts");
+    en_us.insert("doctor.check.directories", "Directories");
+    en_us.insert("doctor.check.directory", "Directory {}");
+    en_us.insert("doctor.check.claude_local_md", "CLAUDE.local.md template");
+    en_us.insert("doctor.check.webdav", "WebDAV {}");
+    en_us.insert("doctor.accounts_count", "{} accounts configured");
+    en_us.insert("doctor.no_accounts", "No accounts configured");
+    en_us.insert("doctor.no_directories", "No directories added");
+    en_us.insert("doctor.not_a_directory", "{} is not a directory");
+    en_us.insert("doctor.directory_missing", "{} does not exist");
+    en_us.insert("doctor.claude_local_md_bundled", "Bundled");
+    en_us.insert("doctor.claude_local_md_empty", "Bundled template is empty");
+    en_us.insert("doctor.hint.database", "Check the database file permissions, or remove a corrupted database and restart");
+    en_us.insert("doctor.hint.config_dir", "Check the app data directory permissions, or point CLAUDE_CONFIG_HOME elsewhere");
+    en_us.insert("doctor.hint.accounts", "Add an account under Account Management");
+    en_us.insert("doctor.hint.add_directory", "Add a project directory under Directory Management");
+    en_us.insert("doctor.hint.fix_directory", "Edit the path or delete the directory under Directory Management");
+    en_us.insert("doctor.hint.reinstall", "Reinstall or rebuild the program");
+    en_us.insert("doctor.hint.offline", "Disable offline mode and check again");
+    en_us.insert("doctor.hint.webdav", "Check the WebDAV URL, username and password");
+    en_us.insert("doctor.summary_ok", "✓ All checks passed");
+    en_us.insert("doctor.summary_warned", "All required checks passed with {} warnings");
+    en_us.insert("doctor.summary_failed", "✗ {} checks failed, {} warnings");
+
+    // Edit settings file
+    en_us.insert("directory.edit_settings.prompt", "Select directory whose settings file to edit");
+    en_us.insert("directory.edit_settings.candidates", "Candidate settings files (by priority):");
+    en_us.insert("directory.edit_settings.active", "← active");
+    en_us.insert("directory.edit_settings.exists", "(exists)");
+    en_us.insert("directory.edit_settings.missing", "(missing)");
+    en_us.insert("directory.edit_settings.created", "Settings file did not exist, created: {}");
+    en_us.insert("directory.edit_settings.opening", "Opening editor: {}");
+    en_us.insert("directory.edit_settings.editor_error", "Failed to open editor: {}");
+    en_us.insert("directory.edit_settings.valid", "✓ Settings file is valid");
+    en_us.insert("directory.edit_settings.invalid", "✗ Settings file has a syntax error: {}");
+    en_us.insert("directory.repair_settings.prompt", "Select directory whose settings to repair");
+    en_us.insert("directory.repair_settings.repaired", "✓ Rewrote env into canonical object form");
+    en_us.insert("directory.repair_settings.nothing", "Settings do not need repair");
+    en_us.insert("directory.repair_settings.error", "✗ Repair failed: {}");
+    en_us.insert("directory.validate_settings.prompt", "Select directory whose settings to validate");
+    en_us.insert("directory.validate_settings.file", "Settings file: {}");
+    en_us.insert("directory.validate_settings.valid", "✓ Settings are valid");
+    en_us.insert("directory.validate_settings.issues", "Found {} issues:");
+    en_us.insert("directory.validate_settings.error", "✗ Validation failed: {}");
+    en_us.insert("directory.effective_env.prompt", "Select directory whose effective environment to view");
+    en_us.insert("directory.effective_env.empty", "No environment variables found");
+    en_us.insert("directory.effective_env.key", "Variable");
+    en_us.insert("directory.effective_env.value", "Value");
+    en_us.insert("directory.effective_env.source", "Source");
+    en_us.insert("directory.effective_env.global", "Global");
+    en_us.insert("directory.effective_env.global_note", "{} variable(s) are not set by the project and come from the global config (manage them in the Global Config menu)");
+    en_us.insert("directory.effective_env.project", "Project");
+    en_us.insert("directory.effective_env.process", "Environment");
+    en_us.insert("directory.effective_env.masked_note", "Process environment variables override the stored {}");
+    en_us.insert("directory.effective_env.error", "✗ Failed to read settings: {}");
+
+    // Config audit
+    en_us.insert("audit.header.directory", "Directory");
+    en_us.insert("audit.header.account", "Account");
+    en_us.insert("audit.header.base_url", "Base URL");
+    en_us.insert("audit.header.token", "Token Fingerprint");
+    en_us.insert("audit.status.deviated", "Deviates from expected");
+    en_us.insert("audit.pinned", "(expected)");
+    en_us.insert("audit.token_from_command", "(via command)");
+    en_us.insert("audit.header.result", "Result");
+    en_us.insert("audit.status.match", "✓ Match");
+    en_us.insert("audit.status.mismatch", "✗ Mismatch");
+    en_us.insert("audit.status.not_configured", "Not configured");
+    en_us.insert("audit.status.no_account", "No account");
+    en_us.insert("audit.summary_mismatch", "{} directories have config that does not match their account");
+    en_us.insert("audit.summary_ok", "All configured directories match their accounts");
+    en_us.insert("status.missing", "✗ Directory missing");
+    en_us.insert("audit.progress", "Auditing directories");
+
+    // Relative time
+    en_us.insert("time.never", "Never");
+    en_us.insert("time.just_now", "Just now");
+    en_us.insert("time.minutes_ago", "{}m ago");
+    en_us.insert("time.hours_ago", "{}h ago");
+    en_us.insert("time.days_ago", "{}d ago");
+    en_us.insert("time.months_ago", "{}mo ago");
+    en_us.insert("time.years_ago", "{}y ago");
+
+    // Progress
+    en_us.insert("progress.summary", "{} finished: {} items in {}s");
+
+    // First-run setup wizard
+    en_us.insert("setup.title", "Welcome! First-run setup");
+    en_us.insert("setup.intro", "This will walk you through adding your first account and directory, and optionally applying it.");
+    en_us.insert("setup.step", "Step {}/{}: {}");
+    en_us.insert("setup.step_account", "Add an account");
+    en_us.insert("setup.step_directory", "Add a directory");
+    en_us.insert("setup.step_apply", "Apply configuration");
+    en_us.insert("setup.prompt_apply", "Apply the account to the directory now?");
+    en_us.insert("setup.aborted", "Setup wizard exited; run with --setup to start it again");
+    en_us.insert("setup.summary_title", "Setup complete");
+    en_us.insert("setup.summary_accounts", "Accounts: {}");
+    en_us.insert("setup.summary_directories", "Directories: {}");
+    en_us.insert("setup.summary_hint", "Run with --setup any time to start the wizard again");
+
+    // Global config
+    en_us.insert("global.menu.title", "Global Config (~/.claude/settings.json)");
+    en_us.insert("global.menu.list", "📋 List global env vars");
+    en_us.insert("global.menu.set", "✏️  Set env var");
+    en_us.insert("global.menu.remove", "🗑️  Remove env var");
+    en_us.insert("global.menu.clear", "🧹 Clear Anthropic auth vars");
+    en_us.insert("global.file", "Global settings file: {}");
+    en_us.insert("global.no_home", "Cannot determine the home directory; global config is unavailable");
+    en_us.insert("global.error", "✗ Global config operation failed: {}");
+    en_us.insert("global.list.empty", "No env vars in the global config");
+    en_us.insert("global.set.prompt_key", "Variable name");
+    en_us.insert("global.set.prompt_value", "Value");
+    en_us.insert("global.set.success", "✓ Global variable {} set");
+    en_us.insert("global.remove.prompt", "Select variable to remove");
+    en_us.insert("global.remove.success", "✓ Global variable {} removed");
+    en_us.insert("global.clear.confirm", "Remove ANTHROPIC_API_KEY / ANTHROPIC_AUTH_TOKEN / ANTHROPIC_BASE_URL from the global config?");
+    en_us.insert("global.clear.success", "✓ Global auth vars cleared");
+
+    // Offline mode
+    en_us.insert("offline.enabled", "Offline mode is enabled");
+    en_us.insert("output.ascii.active", "[ACTIVE]");
+    en_us.insert("output.ascii.inactive", "[INACTIVE]");
+    en_us.insert("output.ascii.exists", "[OK]");
+    en_us.insert("output.ascii.missing", "[MISSING]");
+
+    // Pagination
+    en_us.insert("pager.info", "Page {}/{}, {} items in total");
+    en_us.insert("pager.prompt", "Page");
+    en_us.insert("pager.next", "Next page");
+    en_us.insert("pager.prev", "Previous page");
+    en_us.insert("pager.quit", "Quit");
+    en_us.insert("bulk.type_count", "Type the count {} to confirm (press Enter to cancel)");
+    en_us.insert("bulk.count_mismatch", "The count does not match, cancelled");
+
     translations.insert(Language::EnUS, en_us);
 
     translations
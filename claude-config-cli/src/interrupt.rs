@@ -0,0 +1,53 @@
+//! Ctrl-C 处理：没有正在进行的修改时立即退出；修改配置期间只记录中断请求，
+//! 等当前目录写入完成后再停止，避免留下写了一半的配置。再次按 Ctrl-C 立即退出。
+
+use crate::{print_warning, t};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+/// 中断时的退出码（128 + SIGINT）
+pub const EXIT_INTERRUPTED: i32 = 130;
+
+static ACTIVE_MUTATIONS: AtomicUsize = AtomicUsize::new(0);
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+
+/// 安装 Ctrl-C 处理，只在交互式菜单中使用
+pub fn install() {
+    tokio::spawn(async {
+        while tokio::signal::ctrl_c().await.is_ok() {
+            if ACTIVE_MUTATIONS.load(Ordering::SeqCst) == 0 || INTERRUPTED.swap(true, Ordering::SeqCst) {
+                exit();
+            }
+            tracing::info!("收到 Ctrl-C，等待当前写入完成后停止");
+            print_warning!("\n{}", t!("interrupt.finishing"));
+        }
+    });
+}
+
+/// 是否已请求中断；批量操作在处理下一个目录前检查
+pub fn requested() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+fn exit() -> ! {
+    let _ = console::Term::stdout().show_cursor();
+    std::process::exit(EXIT_INTERRUPTED);
+}
+
+/// 修改配置期间持有；最外层的守卫释放时如果已请求中断则退出程序
+pub struct MutationGuard(());
+
+impl MutationGuard {
+    pub fn begin() -> Self {
+        ACTIVE_MUTATIONS.fetch_add(1, Ordering::SeqCst);
+        MutationGuard(())
+    }
+}
+
+impl Drop for MutationGuard {
+    fn drop(&mut self) {
+        if ACTIVE_MUTATIONS.fetch_sub(1, Ordering::SeqCst) == 1 && requested() {
+            tracing::info!("当前写入已完成，按中断请求退出");
+            exit();
+        }
+    }
+}
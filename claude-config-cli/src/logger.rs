@@ -1,8 +1,15 @@
+use crate::config_manager::ConfigManager;
 use anyhow::Result;
+use claude_config_core::redact::RedactingMakeWriter;
 use std::fs;
 use std::path::PathBuf;
 use tracing_appender::{non_blocking, rolling};
-use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+use tracing_subscriber::{
+    fmt,
+    layer::SubscriberExt,
+    util::SubscriberInitExt,
+    EnvFilter,
+};
 
 pub struct Logger;
 
@@ -10,10 +17,8 @@ impl Logger {
     /// 初始化日志系统
     pub fn init() -> Result<()> {
         // 获取可执行文件目录
-        let exe_dir = get_exe_dir()?;
-
-        // 创建logs目录
-        let logs_dir = exe_dir.join("logs");
+        // 创建logs目录（位于应用数据目录下）
+        let logs_dir = Self::get_log_directory()?;
         fs::create_dir_all(&logs_dir)?;
 
         // 创建日志文件appender（每天滚动）
@@ -29,7 +34,7 @@ impl Logger {
             .with(env_filter)
             .with(
                 fmt::Layer::new()
-                    .with_writer(non_blocking_file)
+                    .with_writer(RedactingMakeWriter::new(non_blocking_file))
                     .with_ansi(false)
                     .with_target(true)
                     .with_thread_ids(true)
@@ -49,8 +54,7 @@ impl Logger {
 
     /// 获取日志目录路径
     pub fn get_log_directory() -> Result<PathBuf> {
-        let exe_dir = get_exe_dir()?;
-        Ok(exe_dir.join("logs"))
+        Ok(ConfigManager::get_app_data_dir().join("logs"))
     }
 
     /// 获取日志信息
@@ -72,7 +76,7 @@ impl Logger {
             .filter_map(|entry| {
                 let entry = entry.ok()?;
                 let path = entry.path();
-                if path.is_file() && path.extension().map_or(false, |ext| ext == "log") {
+                if path.is_file() && path.extension().is_some_and(|ext| ext == "log") {
                     Some(path.file_name()?.to_string_lossy().to_string())
                 } else {
                     None
@@ -121,25 +125,17 @@ impl Logger {
     }
 }
 
-/// 获取可执行文件所在目录
-fn get_exe_dir() -> Result<PathBuf> {
-    let exe_path = std::env::current_exe()?;
-    let exe_dir = exe_path
-        .parent()
-        .ok_or_else(|| anyhow::anyhow!("Failed to get executable directory"))?;
-    Ok(exe_dir.to_path_buf())
-}
-
 /// 测试日志功能
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
-    fn test_get_exe_dir() {
-        let dir = get_exe_dir().unwrap();
-        println!("Executable directory: {}", dir.display());
-        assert!(dir.exists());
+    fn test_get_log_directory() {
+        let dir = Logger::get_log_directory().unwrap();
+        println!("Log directory: {}", dir.display());
+        assert!(dir.starts_with(ConfigManager::get_app_data_dir()));
+        assert!(dir.ends_with("logs"));
     }
 
     #[tokio::test]
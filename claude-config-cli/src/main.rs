@@ -1,17 +1,40 @@
-mod claude_config;
+mod account_snippet;
+mod batch_switch;
+mod bundle;
+mod change_log;
+mod cli;
+mod clipboard;
+mod commands;
 mod config_manager;
+mod connectivity;
 mod database;
+mod db_recovery;
+mod dir_filter;
+mod gitignore;
+mod health;
+mod hooks;
 mod i18n;
+mod interrupt;
 mod logger;
 mod menu;
 mod models;
+mod names;
+mod offline;
+mod output;
+mod progress;
+mod settings;
+mod token_source;
+mod undo;
 mod webdav;
+mod worktree;
 
 use anyhow::Result;
+use claude_config_core::claude_config;
+use clap::Parser;
 use colored::Colorize;
 use console::Term;
 use database::Database;
-use dialoguer::Select;
+use dialoguer::{Input, Select};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -24,6 +47,87 @@ async fn main() -> Result<()> {
         eprintln!("日志系统初始化失败: {}", e);
     }
 
+    let cli = cli::Cli::parse();
+    let app_settings = settings::load_or_default().await;
+    offline::init(cli.offline, app_settings.offline);
+    names::init(app_settings.max_name_length);
+    output::init(cli.no_emoji, cli.no_color, app_settings.plain_output);
+    output::set_console_timestamps(app_settings.console_timestamps);
+    menu::switch::set_force_rewrite(cli.force);
+
+    // 子命令模式：不显示欢迎信息，直接执行
+    if let Some(command) = cli.command {
+        // 自检只使用临时目录，不需要数据库
+        if matches!(command, cli::Command::SelfTest) {
+            if !commands::self_test::run() {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        // status --quiet 只通过退出码表示结果，debug-dump 只输出 JSON，都不输出数据库初始化信息
+        let quiet = matches!(
+            command,
+            cli::Command::Status { quiet: true, .. } | cli::Command::DebugDump { .. }
+        );
+        let db = init_database(quiet, cli.ephemeral).await?;
+        return match command {
+            cli::Command::Watch {
+                target,
+                auto_fix,
+                debounce_ms,
+            } => {
+                let dir = commands::dir_name::resolve(&db, &target).await?;
+                commands::watch::run(&db, dir, auto_fix, debounce_ms).await
+            }
+            cli::Command::Audit => commands::audit::run(&db, false).await,
+            cli::Command::Status { target, quiet } => {
+                let dir = commands::dir_name::resolve(&db, &target).await?;
+                std::process::exit(commands::status::run(&db, dir, quiet).await?);
+            }
+            cli::Command::DebugDump { target, show_secrets } => {
+                let dir = commands::dir_name::resolve(&db, &target).await?;
+                commands::debug_dump::run(&db, dir, show_secrets).await
+            }
+            cli::Command::Switch { path, account, yes } => {
+                commands::switch::run(&db, &path, account, yes).await
+            }
+            cli::Command::Doctor => {
+                if !commands::doctor::run(&db).await? {
+                    std::process::exit(1);
+                }
+                Ok(())
+            }
+            cli::Command::SelfTest => unreachable!(),
+            cli::Command::Migrate { dir, dir_name } => {
+                let dir = match dir_name {
+                    Some(dir_name) => {
+                        let target = cli::DirTarget {
+                            dir: None,
+                            dir_name: Some(dir_name),
+                        };
+                        Some(commands::dir_name::resolve(&db, &target).await?)
+                    }
+                    None => dir,
+                };
+                commands::migrate::run(&db, dir).await
+            }
+            cli::Command::Setting {
+                target,
+                pointer,
+                value,
+                create,
+            } => {
+                let dir = commands::dir_name::resolve(&db, &target).await?;
+                commands::setting::run(&db, dir, &pointer, value, create).await
+            }
+            cli::Command::ExportSettings {
+                path,
+                include_secrets,
+            } => commands::app_settings::export(&db, &path, include_secrets).await,
+            cli::Command::ImportSettings { path } => commands::app_settings::import(&db, &path).await,
+        };
+    }
+
     // 清屏
     let term = Term::stdout();
     let _ = term.clear_screen();
@@ -31,40 +135,25 @@ async fn main() -> Result<()> {
     // 显示欢迎信息
     print_banner();
 
-    // 初始化数据库
-    println!("{}", i18n::translate("db.init").cyan());
-    let db = match Database::new().await {
-        Ok(database) => {
-            println!("{}", i18n::translate("db.init_success").green());
-            Arc::new(Mutex::new(database))
-        }
-        Err(e) => {
-            eprintln!(
-                "{}",
-                format!("{}: {}", i18n::translate("db.init_error"), e).red()
-            );
-            println!("\n{}", i18n::translate("db.fallback"));
-            match Database::create_with_fallback().await {
-                Ok(database) => {
-                    println!("{}", i18n::translate("db.fallback_success").green());
-                    Arc::new(Mutex::new(database))
-                }
-                Err(e) => {
-                    eprintln!(
-                        "{}",
-                        format!("{}: {}", i18n::translate("db.fallback_error"), e).red()
-                    );
-                    return Err(e.into());
-                }
-            }
-        }
-    };
+    let db = init_database(false, cli.ephemeral).await?;
+    health::spawn_refresher(db.clone());
+    interrupt::install();
 
     println!();
 
+    // 首次运行（或指定 --setup）时进入向导
+    if cli.setup || menu::setup::needs_setup(&db).await? {
+        menu::setup::run_setup_wizard(&db).await?;
+    }
+
     // 主菜单循环
     loop {
-        let selection = match show_main_menu()? {
+        let menu_selection = if settings::load_or_default().await.hotkey_menu {
+            show_hotkey_menu(&term)?
+        } else {
+            show_main_menu()?
+        };
+        let selection = match menu_selection {
             Some(sel) => sel,
             None => {
                 // 用户按了ESC，退出程序
@@ -84,21 +173,55 @@ async fn main() -> Result<()> {
                 menu::base_url::base_url_menu(&db).await?;
             }
             3 => {
-                menu::switch::switch_menu(&db).await?;
+                menu::template::template_menu(&db).await?;
             }
             4 => {
-                menu::webdav::webdav_menu(&db).await?;
+                menu::switch::switch_menu(&db).await?;
             }
             5 => {
-                menu::logs::logs_menu().await?;
+                menu::switch::switch_current_directory(&db).await?;
             }
             6 => {
-                remove_root_check()?;
+                menu::switch::repeat_last_switch(&db).await?;
             }
             7 => {
-                menu::settings::settings_menu().await?;
+                menu::directory::quick_clear(&db).await?;
             }
             8 => {
+                menu::directory::undo_last_change().await?;
+            }
+            9 => {
+                menu::switch::batch_switch_menu(&db).await?;
+            }
+            10 => {
+                menu::model::model_menu(&db).await?;
+            }
+            11 => {
+                menu::profile::profile_menu(&db).await?;
+            }
+            12 => {
+                menu::webdav::webdav_menu(&db).await?;
+            }
+            13 => {
+                menu::logs::logs_menu().await?;
+            }
+            14 => {
+                remove_root_check()?;
+            }
+            15 => {
+                menu::global::global_menu().await?;
+            }
+            16 => {
+                commands::doctor::run(&db).await?;
+                let _ = Input::<String>::new()
+                    .with_prompt(i18n::translate("common.continue"))
+                    .allow_empty(true)
+                    .interact()?;
+            }
+            17 => {
+                menu::settings::settings_menu(&db).await?;
+            }
+            18 => {
                 // 切换语言
                 let new_lang = match i18n::current_language() {
                     i18n::Language::ZhCN => i18n::Language::EnUS,
@@ -110,7 +233,7 @@ async fn main() -> Result<()> {
                 let _ = term.clear_screen();
                 print_banner();
             }
-            9 => {
+            19 => {
                 println!("\n{}", i18n::translate("app.exit_message").green().bold());
                 break;
             }
@@ -121,6 +244,64 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+/// 初始化数据库，失败时回退到备用位置；`quiet` 时只输出错误信息，`ephemeral` 时使用内存数据库
+async fn init_database(quiet: bool, ephemeral: bool) -> Result<DbState> {
+    if ephemeral {
+        let database = Database::new_in_memory().await?;
+        if !quiet {
+            println!("{}", i18n::translate("db.ephemeral").yellow());
+        }
+        return Ok(Arc::new(Mutex::new(database)));
+    }
+
+    // 数据库文件损坏时进入引导恢复，而不是直接报出 SQLite 错误
+    if let Some(path) = Database::default_database_path() {
+        if let Some(detail) = db_recovery::detect_corruption(&path).await {
+            let database = db_recovery::recover(&path, &detail).await?;
+            return Ok(Arc::new(Mutex::new(database)));
+        }
+    }
+
+    // 初始化数据库
+    if !quiet {
+        println!("{}", i18n::translate("db.init").cyan());
+    }
+    let db = match Database::new().await {
+        Ok(database) => {
+            if !quiet {
+                println!("{}", i18n::translate("db.init_success").green());
+            }
+            Arc::new(Mutex::new(database))
+        }
+        Err(e) => {
+            eprintln!(
+                "{}",
+                format!("{}: {}", i18n::translate("db.init_error"), e).red()
+            );
+            if !quiet {
+                println!("\n{}", i18n::translate("db.fallback"));
+            }
+            match Database::create_with_fallback().await {
+                Ok(database) => {
+                    if !quiet {
+                        println!("{}", i18n::translate("db.fallback_success").green());
+                    }
+                    Arc::new(Mutex::new(database))
+                }
+                Err(e) => {
+                    eprintln!(
+                        "{}",
+                        format!("{}: {}", i18n::translate("db.fallback_error"), e).red()
+                    );
+                    return Err(e.into());
+                }
+            }
+        }
+    };
+
+    Ok(db)
+}
+
 fn print_banner() {
     println!(
         "{}",
@@ -161,10 +342,20 @@ fn show_main_menu() -> Result<Option<usize>> {
         i18n::translate("menu.main.account"),
         i18n::translate("menu.main.directory"),
         i18n::translate("menu.main.url"),
+        i18n::translate("menu.main.template"),
         i18n::translate("menu.main.switch"),
+        i18n::translate("menu.main.current_dir"),
+        i18n::translate("menu.main.repeat_switch"),
+        i18n::translate("menu.main.quick_clear"),
+        i18n::translate("menu.main.undo"),
+        i18n::translate("menu.main.batch_switch"),
+        i18n::translate("menu.main.model"),
+        i18n::translate("menu.main.profile"),
         i18n::translate("menu.main.webdav"),
         i18n::translate("menu.main.logs"),
         i18n::translate("menu.main.remove_root"),
+        i18n::translate("menu.main.global"),
+        i18n::translate("menu.main.doctor"),
         i18n::translate("menu.main.settings"),
         i18n::translate("menu.main.language"),
         i18n::translate("menu.main.exit"),
@@ -179,6 +370,37 @@ fn show_main_menu() -> Result<Option<usize>> {
     Ok(selection)
 }
 
+/// 单键快捷菜单：返回与 show_main_menu 相同的序号，q 退出（返回 None），m 打开完整菜单
+fn show_hotkey_menu(term: &Term) -> Result<Option<usize>> {
+    println!("\n{}", i18n::translate("menu.hotkey.prompt").cyan());
+
+    loop {
+        let key = term.read_char()?.to_ascii_lowercase();
+        let index = match key {
+            'a' => 0,
+            'd' => 1,
+            's' => 4,
+            'h' => 5,
+            'c' => 7,
+            'u' => 8,
+            'w' => 12,
+            'l' => 13,
+            'm' => return show_main_menu(),
+            'q' => return Ok(None),
+            _ => {
+                println!(
+                    "{}",
+                    i18n::translate("menu.hotkey.invalid")
+                        .replace("{}", &key.to_string())
+                        .yellow()
+                );
+                continue;
+            }
+        };
+        return Ok(Some(index));
+    }
+}
+
 fn remove_root_check() -> Result<()> {
     use dialoguer::{Confirm, Input};
     use std::io::Write;
@@ -1,8 +1,19 @@
-use crate::{models::*, t, DbState};
+use crate::{
+    account_snippet,
+    claude_config::{self, SwapSuspicion, TokenError},
+    connectivity,
+    health::HealthLevel,
+    models::*,
+    offline, output,
+    print_error, print_success, print_warning,
+    progress::Progress,
+    settings, t, DbState,
+};
 use anyhow::Result;
 use colored::Colorize;
 use comfy_table::{Attribute, Cell, Color};
-use dialoguer::{Confirm, Input, Select};
+use dialoguer::{Confirm, FuzzySelect, Input, MultiSelect, Select};
+use std::collections::HashMap;
 
 pub async fn account_menu(db: &DbState) -> Result<()> {
     let mut last_selection = 0;
@@ -13,7 +24,15 @@ pub async fn account_menu(db: &DbState) -> Result<()> {
             t!("account.menu.list"),
             t!("account.menu.add"),
             t!("account.menu.edit"),
+            t!("account.menu.rename"),
+            t!("account.menu.clone"),
             t!("account.menu.delete"),
+            t!("account.menu.batch_delete"),
+            t!("account.menu.test_all"),
+            t!("account.menu.claude_md"),
+            t!("account.menu.export_snippet"),
+            t!("account.menu.import_snippet"),
+            t!("account.menu.fallbacks"),
         ];
 
         let selection = match Select::new()
@@ -32,7 +51,15 @@ pub async fn account_menu(db: &DbState) -> Result<()> {
             1 => list_accounts(db).await?,
             2 => add_account(db).await?,
             3 => edit_account(db).await?,
-            4 => delete_account(db).await?,
+            4 => rename_account(db).await?,
+            5 => clone_account(db).await?,
+            6 => delete_account(db).await?,
+            7 => batch_delete_accounts(db).await?,
+            8 => test_all_accounts(db).await?,
+            9 => edit_claude_md_template(db).await?,
+            10 => export_account_snippet(db).await?,
+            11 => import_account_snippet(db).await?,
+            12 => edit_fallback_chain(db).await?,
             _ => unreachable!(),
         }
     }
@@ -40,62 +67,438 @@ pub async fn account_menu(db: &DbState) -> Result<()> {
     Ok(())
 }
 
+pub(super) fn token_error_message(error: &TokenError) -> &'static str {
+    match error {
+        TokenError::Empty => t!("account.token.empty"),
+        TokenError::ContainsWhitespace => t!("account.token.whitespace"),
+    }
+}
+
+/// Token 输入框校验（空输入用于取消，放行）
+fn validate_token_input(input: &str) -> Result<(), String> {
+    if input.trim().is_empty() {
+        return Ok(());
+    }
+    claude_config::validate_token(input)
+        .map(|_| ())
+        .map_err(|e| token_error_message(&e).to_string())
+}
+
+/// 选择 Token 来源，再输入 Token 或获取 Token 的命令（如 `pass show claude/work`）；
+/// `current` 为编辑或克隆的源账号，用作默认值。输入为空时返回 None
+fn prompt_token_source(current: Option<&Account>) -> Result<Option<TokenSource>> {
+    let current = current.map(Account::token_source);
+    let items = [t!("account.token_source.inline"), t!("account.token_source.command")];
+    let selection = Select::new()
+        .with_prompt(t!("account.token_source.prompt"))
+        .items(&items)
+        .default(usize::from(matches!(current, Some(TokenSource::Command(_)))))
+        .interact()?;
+
+    if selection == 1 {
+        let mut input = Input::<String>::new()
+            .with_prompt(t!("account.token_source.prompt_command"))
+            .allow_empty(true);
+        if let Some(TokenSource::Command(command)) = &current {
+            input = input.default(command.clone());
+        }
+        let command = input.interact_text()?;
+        let command = command.trim();
+        return Ok((!command.is_empty()).then(|| TokenSource::Command(command.to_string())));
+    }
+
+    let mut input = Input::<String>::new()
+        .with_prompt(t!("account.add.prompt_token"))
+        .allow_empty(true)
+        .validate_with(|input: &String| validate_token_input(input));
+    if let Some(TokenSource::Inline(token)) = &current {
+        input = input.default(token.clone());
+    }
+    let token = input.interact_text()?;
+    if token.trim().is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(TokenSource::Inline(finalize_token(&token)?)))
+}
+
+/// 拆分为请求中的 (token, token_command)；使用命令时不保存 Token
+fn token_fields(source: TokenSource) -> (String, Option<String>) {
+    match source {
+        TokenSource::Inline(token) => (token, None),
+        TokenSource::Command(command) => (String::new(), Some(command)),
+    }
+}
+
+/// 直接保存的 Token 检查是否与 Base URL 填反；通过命令获取的 Token 在切换时再检查
+fn confirm_token_source_not_swapped(base_url: &str, source: &TokenSource) -> Result<bool> {
+    match source {
+        TokenSource::Inline(token) => confirm_credentials_not_swapped(base_url, token),
+        TokenSource::Command(_) => Ok(true),
+    }
+}
+
+/// 返回规范化后的 Token，前缀不常见时给出提示
+fn finalize_token(input: &str) -> Result<String> {
+    let token = claude_config::validate_token(input)
+        .map_err(|e| anyhow::anyhow!(token_error_message(&e)))?;
+    if !claude_config::has_expected_token_prefix(&token) {
+        print_warning!("{}", t!("account.token.unexpected_prefix"));
+    }
+    Ok(token)
+}
+
+/// Base URL 与 Token 疑似填反时给出警告，返回用户是否仍要继续
+pub(super) fn confirm_credentials_not_swapped(base_url: &str, token: &str) -> Result<bool> {
+    let message = match claude_config::detect_swapped_credentials(base_url, token) {
+        None => return Ok(true),
+        Some(SwapSuspicion::BaseUrlLooksLikeToken) => t!("account.swap.base_url_looks_like_token"),
+        Some(SwapSuspicion::TokenLooksLikeUrl) => t!("account.swap.token_looks_like_url"),
+    };
+    print_warning!("\n{}", message);
+    Ok(Confirm::new()
+        .with_prompt(t!("account.swap.confirm"))
+        .default(false)
+        .interact()?)
+}
+
+fn auth_style_label(auth_style: AuthStyle) -> &'static str {
+    match auth_style {
+        AuthStyle::ApiKey => t!("account.auth_style.api_key"),
+        AuthStyle::BearerToken => t!("account.auth_style.bearer_token"),
+        AuthStyle::Both => t!("account.auth_style.both"),
+    }
+}
+
+/// 选择认证方式（不同代理只认 x-api-key 或 Authorization: Bearer 其中之一）
+fn select_auth_style(current: AuthStyle) -> Result<AuthStyle> {
+    let items: Vec<&str> = AuthStyle::ALL.iter().map(|style| auth_style_label(*style)).collect();
+    let default_index = AuthStyle::ALL
+        .iter()
+        .position(|style| *style == current)
+        .unwrap_or(0);
+
+    let selection = Select::new()
+        .with_prompt(t!("account.add.select_auth_style"))
+        .items(&items)
+        .default(default_index)
+        .interact()?;
+
+    Ok(AuthStyle::ALL[selection])
+}
+
+/// 输入额外写入 Token 的环境变量名，直接回车保留当前值，清空表示不额外写入
+fn prompt_extra_auth_env_key(current: Option<&str>) -> Result<Option<String>> {
+    let key: String = Input::new()
+        .with_prompt(t!("account.add.prompt_extra_auth_env_key"))
+        .default(current.unwrap_or_default().to_string())
+        .allow_empty(true)
+        .validate_with(|input: &String| -> Result<(), String> {
+            let input = input.trim();
+            if input.is_empty() || claude_config::is_valid_env_key(input) {
+                Ok(())
+            } else {
+                Err(t!("account.add.invalid_env_key").to_string())
+            }
+        })
+        .interact_text()?;
+    Ok(Some(key.trim().to_string()).filter(|key| !key.is_empty()))
+}
+
+/// 输入备用 Base URL，直接回车保留当前值，清空表示不使用备用地址
+fn prompt_staging_base_url(current: Option<&str>) -> Result<Option<String>> {
+    let url: String = Input::new()
+        .with_prompt(t!("account.add.prompt_staging_base_url"))
+        .default(current.unwrap_or_default().to_string())
+        .allow_empty(true)
+        .validate_with(|input: &String| -> Result<(), String> {
+            let input = input.trim();
+            if input.is_empty() || reqwest::Url::parse(input).is_ok() {
+                Ok(())
+            } else {
+                Err(t!("account.add.invalid_staging_base_url").to_string())
+            }
+        })
+        .interact_text()?;
+    Ok(Some(url.trim().to_string()).filter(|url| !url.is_empty()))
+}
+
+/// 代理地址输入框校验（空输入表示不设置）
+fn validate_proxy_input(input: &str) -> Result<(), String> {
+    if input.trim().is_empty() || reqwest::Proxy::all(input.trim()).is_ok() {
+        Ok(())
+    } else {
+        Err(t!("account.add.invalid_proxy").to_string())
+    }
+}
+
+/// 输入账号的代理配置；选择不配置时返回空配置（即清除已有代理）
+fn prompt_proxy(current: &ProxyConfig) -> Result<ProxyConfig> {
+    if !Confirm::new()
+        .with_prompt(t!("account.add.prompt_configure_proxy"))
+        .default(!current.is_empty())
+        .interact()?
+    {
+        return Ok(ProxyConfig::default());
+    }
+
+    let http_proxy: String = Input::new()
+        .with_prompt(t!("account.add.prompt_http_proxy"))
+        .default(current.http_proxy.clone())
+        .allow_empty(true)
+        .validate_with(|input: &String| validate_proxy_input(input))
+        .interact_text()?;
+    let https_proxy: String = Input::new()
+        .with_prompt(t!("account.add.prompt_https_proxy"))
+        .default(current.https_proxy.clone())
+        .allow_empty(true)
+        .validate_with(|input: &String| validate_proxy_input(input))
+        .interact_text()?;
+    let no_proxy: String = Input::new()
+        .with_prompt(t!("account.add.prompt_no_proxy"))
+        .default(current.no_proxy.clone())
+        .allow_empty(true)
+        .interact_text()?;
+
+    Ok(ProxyConfig {
+        http_proxy: http_proxy.trim().to_string(),
+        https_proxy: https_proxy.trim().to_string(),
+        no_proxy: no_proxy.trim().to_string(),
+    })
+}
+
+/// 账号备注在表格和选择列表中显示的最大字符数
+const NOTE_DISPLAY_CHARS: usize = 24;
+
+/// 截断过长的备注，超出部分以省略号表示
+pub(super) fn truncate_note(note: &str) -> String {
+    if note.chars().count() <= NOTE_DISPLAY_CHARS {
+        return note.to_string();
+    }
+    let head: String = note.chars().take(NOTE_DISPLAY_CHARS - 1).collect();
+    format!("{}…", head)
+}
+
+/// 输入账号备注，直接回车保留当前值，清空内容表示删除备注
+fn prompt_note(current: Option<&str>) -> Result<Option<String>> {
+    let note: String = Input::new()
+        .with_prompt(t!("account.add.prompt_note"))
+        .default(current.unwrap_or_default().to_string())
+        .allow_empty(true)
+        .interact_text()?;
+    Ok(Some(note.trim().to_string()).filter(|note| !note.is_empty()))
+}
+
 async fn list_accounts(db: &DbState) -> Result<()> {
-    let db_lock = db.lock().await;
-    let request = GetAccountsRequest {
-        page: Some(1),
-        per_page: Some(100),
-        search: None,
-        base_url: None,
+    let page_size = settings::load_page_size().await;
+    let mut page = 0;
+    let pages = loop {
+        // 按页从数据库读取，账号很多时也不会一次加载全部
+        let db_lock = db.lock().await;
+        let request = GetAccountsRequest {
+            page: Some(page as i64 + 1),
+            per_page: Some(page_size as i64),
+            search: None,
+            base_url: None,
+        };
+
+        let response = db_lock.get_accounts(request).await?;
+        let health: HashMap<i64, AccountHealth> = db_lock
+            .get_account_health()
+            .await?
+            .into_iter()
+            .map(|health| (health.account_id, health))
+            .collect();
+        drop(db_lock);
+
+        if response.accounts.is_empty() && page == 0 {
+            print_warning!("\n{}", t!("account.list.no_records"));
+            return Ok(());
+        }
+
+        let mut table = super::create_table();
+        table.set_header(vec![
+            Cell::new(t!("account.list.header_id"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("account.list.header_name"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("account.list.header_base_url"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("account.list.header_model"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("account.list.header_auth_style"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("account.list.header_status"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("account.list.header_health"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("account.list.header_note"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+        ]);
+
+        for account in &response.accounts {
+            let status = output::status_label(account.is_active);
+            let (badge, level) = crate::health::badge(health.get(&account.id));
+            let badge_color = match level {
+                HealthLevel::Ok => Color::Green,
+                HealthLevel::RateLimited => Color::Yellow,
+                HealthLevel::Failed => Color::Red,
+                HealthLevel::Unknown => Color::DarkGrey,
+            };
+            table.add_row(vec![
+                Cell::new(account.id),
+                Cell::new(crate::names::display_name(&account.name)),
+                Cell::new(&account.base_url),
+                Cell::new(&account.model),
+                Cell::new(match account.token_source() {
+                    TokenSource::Inline(_) => auth_style_label(account.auth_style).to_string(),
+                    TokenSource::Command(_) => format!(
+                        "{} ({})",
+                        auth_style_label(account.auth_style),
+                        t!("account.list.token_from_command")
+                    ),
+                }),
+                Cell::new(status),
+                Cell::new(badge).fg(badge_color),
+                Cell::new(account.note.as_deref().map(truncate_note).unwrap_or_default()),
+            ]);
+        }
+
+        println!("\n{}", table);
+        let pages = response.pagination.pages.max(1) as usize;
+        match super::prompt_page(page, pages, response.pagination.total as usize)? {
+            Some(next) => page = next,
+            None => break pages,
+        }
     };
 
-    let response = db_lock.get_accounts(request).await?;
+    // 多页时翻页菜单中的"退出"即结束浏览
+    if pages > 1 {
+        return Ok(());
+    }
+
+    let _ = Input::<String>::new()
+        .with_prompt(t!("common.continue"))
+        .allow_empty(true)
+        .interact()?;
+
+    Ok(())
+}
+
+/// 并发测试所有账号的连通性，失败的排在前面
+async fn test_all_accounts(db: &DbState) -> Result<()> {
+    if offline::refuse() {
+        return Ok(());
+    }
+
+    let db_lock = db.lock().await;
+    let response = db_lock
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(100),
+            search: None,
+            base_url: None,
+        })
+        .await?;
     drop(db_lock);
 
     if response.accounts.is_empty() {
-        println!("\n{}", t!("account.list.no_records").yellow());
+        print_warning!("\n{}", t!("account.list.no_records"));
         return Ok(());
     }
 
+    println!(
+        "\n{}",
+        t!("account.test_all.running")
+            .replace("{}", &response.accounts.len().to_string())
+            .cyan()
+    );
+
+    let app_settings = settings::load_or_default().await;
+    let timeout = std::time::Duration::from_secs(app_settings.http_timeout_secs);
+    let mut progress = Progress::new(
+        t!("account.test_all.progress"),
+        response.accounts.len(),
+        true,
+    );
+    let results = connectivity::test_accounts(response.accounts, timeout, &mut progress).await;
+    progress.finish();
+
+    // 手动测试的结果同样刷新账号列表中的可用性
+    let db_lock = db.lock().await;
+    for result in &results {
+        crate::health::save_result(&db_lock, result).await;
+    }
+    drop(db_lock);
+
     let mut table = super::create_table();
     table.set_header(vec![
-        Cell::new(t!("account.list.header_id"))
+        Cell::new(t!("account.list.header_name"))
             .add_attribute(Attribute::Bold)
             .fg(Color::Cyan),
-        Cell::new(t!("account.list.header_name"))
+        Cell::new(t!("account.test_all.header_token"))
             .add_attribute(Attribute::Bold)
             .fg(Color::Cyan),
         Cell::new(t!("account.list.header_base_url"))
             .add_attribute(Attribute::Bold)
             .fg(Color::Cyan),
-        Cell::new(t!("account.list.header_model"))
+        Cell::new(t!("account.test_all.header_status"))
             .add_attribute(Attribute::Bold)
             .fg(Color::Cyan),
-        Cell::new(t!("account.list.header_status"))
+        Cell::new(t!("account.test_all.header_latency"))
             .add_attribute(Attribute::Bold)
             .fg(Color::Cyan),
     ]);
 
-    for account in &response.accounts {
-        let status = if account.is_active {
-            t!("account.list.status_active")
+    for result in &results {
+        let status = match (result.status, &result.error) {
+            (Some(code), _) if result.is_rate_limited() => {
+                format!("{} {}", code, t!("account.test_all.rate_limited"))
+            }
+            (Some(code), _) => code.to_string(),
+            (None, Some(error)) => error.clone(),
+            (None, None) => "-".to_string(),
+        };
+        let color = if result.is_success() {
+            Color::Green
+        } else if result.is_rate_limited() {
+            Color::Yellow
         } else {
-            t!("account.list.status_inactive")
+            Color::Red
         };
         table.add_row(vec![
-            account.id.to_string(),
-            account.name.clone(),
-            account.base_url.clone(),
-            account.model.clone(),
-            status.to_string(),
+            Cell::new(&result.name),
+            Cell::new(&result.masked_token),
+            Cell::new(&result.base_url),
+            Cell::new(status).fg(color),
+            Cell::new(format!("{} ms", result.latency.as_millis())),
         ]);
     }
 
     println!("\n{}", table);
+
+    let succeeded = results.iter().filter(|r| r.is_success()).count();
+    let rate_limited = results.iter().filter(|r| r.is_rate_limited()).count();
     println!(
         "{}",
-        t!("account.list.total").replace("{}", &response.accounts.len().to_string())
+        t!("account.test_all.summary")
+            .replacen("{}", &succeeded.to_string(), 1)
+            .replacen("{}", &(results.len() - succeeded - rate_limited).to_string(), 1)
     );
+    if results.iter().any(|r| r.throttled) {
+        print_warning!(
+            "{}",
+            t!("account.test_all.rate_limited_summary")
+                .replace("{}", &rate_limited.to_string())
+        );
+    }
 
     let _ = Input::<String>::new()
         .with_prompt(t!("common.continue"))
@@ -105,9 +508,9 @@ async fn list_accounts(db: &DbState) -> Result<()> {
     Ok(())
 }
 
-async fn add_account(db: &DbState) -> Result<()> {
+pub(super) async fn add_account(db: &DbState) -> Result<()> {
     println!("\n{}", t!("account.add.title").green().bold());
-    println!("{}", t!("common.input_cancel_hint").yellow());
+    print_warning!("{}", t!("common.input_cancel_hint"));
 
     let name: String = Input::new()
         .with_prompt(t!("account.add.prompt_name"))
@@ -115,19 +518,14 @@ async fn add_account(db: &DbState) -> Result<()> {
         .interact_text()?;
 
     if name.trim().is_empty() {
-        println!("\n{}", t!("common.cancel").yellow());
+        print_warning!("\n{}", t!("common.cancel"));
         return Ok(());
     }
 
-    let token: String = Input::new()
-        .with_prompt(t!("account.add.prompt_token"))
-        .allow_empty(true)
-        .interact_text()?;
-
-    if token.trim().is_empty() {
-        println!("\n{}", t!("common.cancel").yellow());
+    let Some(token_source) = prompt_token_source(None)? else {
+        print_warning!("\n{}", t!("common.cancel"));
         return Ok(());
-    }
+    };
 
     // 获取所有 Base URL
     let db_lock = db.lock().await;
@@ -136,7 +534,7 @@ async fn add_account(db: &DbState) -> Result<()> {
 
     let base_url: String = if base_urls.is_empty() {
         // 如果没有 Base URL，让用户手动输入
-        println!("\n{}", t!("account.add.no_base_url").yellow());
+        print_warning!("\n{}", t!("account.add.no_base_url"));
         Input::new()
             .with_prompt(t!("account.add.prompt_base_url"))
             .default("https://api.anthropic.com".to_string())
@@ -163,31 +561,43 @@ async fn add_account(db: &DbState) -> Result<()> {
         base_urls[selection].url.clone()
     };
 
+    if !confirm_token_source_not_swapped(&base_url, &token_source)? {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
     let model: String = Input::new()
         .with_prompt(t!("account.add.prompt_model"))
         .allow_empty(true)
         .interact_text()?;
 
+    let auth_style = select_auth_style(AuthStyle::default())?;
+    let extra_auth_env_key = prompt_extra_auth_env_key(None)?;
+    let staging_base_url = prompt_staging_base_url(None)?;
+    let proxy = prompt_proxy(&ProxyConfig::default())?;
+    let note = prompt_note(None)?;
+
+    let (token, token_command) = token_fields(token_source);
     let db_lock = db.lock().await;
     let request = CreateAccountRequest {
         name: name.clone(),
         token,
         base_url,
         model,
+        auth_style,
+        proxy,
+        note,
+        extra_auth_env_key,
+        staging_base_url,
+        token_command,
     };
 
     match db_lock.create_account(request).await {
         Ok(_) => {
-            println!(
-                "\n{}",
-                t!("account.add.success").replace("{}", &name).green()
-            );
+            print_success!("\n{}", t!("account.add.success").replace("{}", &name));
         }
         Err(e) => {
-            println!(
-                "\n{}",
-                t!("account.add.error").replace("{}", &e.to_string()).red()
-            );
+            print_error!("\n{}", t!("account.add.error").replace("{}", &e.to_string()));
         }
     }
 
@@ -207,7 +617,7 @@ async fn edit_account(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if response.accounts.is_empty() {
-        println!("\n{}", t!("account.list.no_records").yellow());
+        print_warning!("\n{}", t!("account.list.no_records"));
         return Ok(());
     }
 
@@ -219,7 +629,7 @@ async fn edit_account(db: &DbState) -> Result<()> {
             .map(|a| format!("{} - {}", a.name, a.base_url)),
     );
 
-    let selection = Select::new()
+    let selection = FuzzySelect::new()
         .with_prompt(t!("account.edit.prompt"))
         .items(&items)
         .interact_opt()?;
@@ -231,7 +641,7 @@ async fn edit_account(db: &DbState) -> Result<()> {
         let idx = idx - 1;
         let account = &response.accounts[idx];
 
-        println!("{}", t!("common.input_cancel_hint").yellow());
+        print_warning!("{}", t!("common.input_cancel_hint"));
 
         let name: String = Input::new()
             .with_prompt(t!("account.add.prompt_name"))
@@ -245,16 +655,9 @@ async fn edit_account(db: &DbState) -> Result<()> {
             name
         };
 
-        let token: String = Input::new()
-            .with_prompt(t!("account.add.prompt_token"))
-            .default(account.token.clone())
-            .allow_empty(true)
-            .interact_text()?;
-
-        let token = if token.trim().is_empty() {
-            account.token.clone()
-        } else {
-            token
+        let token_source = match prompt_token_source(Some(account))? {
+            Some(token_source) => token_source,
+            None => account.token_source(),
         };
 
         // 获取所有 Base URL
@@ -264,7 +667,7 @@ async fn edit_account(db: &DbState) -> Result<()> {
 
         let base_url: String = if base_urls.is_empty() {
             // 如果没有 Base URL，让用户手动输入
-            println!("\n{}", t!("account.add.no_base_url").yellow());
+            print_warning!("\n{}", t!("account.add.no_base_url"));
             let input_url: String = Input::new()
                 .with_prompt(t!("account.add.prompt_base_url"))
                 .default(account.base_url.clone())
@@ -304,6 +707,11 @@ async fn edit_account(db: &DbState) -> Result<()> {
             base_urls[selection].url.clone()
         };
 
+        if !confirm_token_source_not_swapped(&base_url, &token_source)? {
+            print_warning!("\n{}", t!("common.cancel"));
+            return Ok(());
+        }
+
         let model: String = Input::new()
             .with_prompt(t!("account.add.prompt_model"))
             .default(account.model.clone())
@@ -316,30 +724,262 @@ async fn edit_account(db: &DbState) -> Result<()> {
             model
         };
 
+        let auth_style = select_auth_style(account.auth_style)?;
+        let extra_auth_env_key = prompt_extra_auth_env_key(account.extra_auth_env_key.as_deref())?;
+        let staging_base_url = prompt_staging_base_url(account.staging_base_url.as_deref())?;
+        let proxy = prompt_proxy(&account.proxy_config())?;
+        let note = prompt_note(account.note.as_deref())?;
+
+        let (token, token_command) = token_fields(token_source);
         let db_lock = db.lock().await;
         let request = UpdateAccountRequest {
             name: Some(name),
             token: Some(token),
             base_url: Some(base_url),
             model: Some(model),
+            auth_style: Some(auth_style),
+            proxy: Some(proxy),
+            note: Some(note.unwrap_or_default()),
+            extra_auth_env_key: Some(extra_auth_env_key.unwrap_or_default()),
+            staging_base_url: Some(staging_base_url.unwrap_or_default()),
+            token_command: Some(token_command.unwrap_or_default()),
         };
 
         match db_lock.update_account(account.id, request).await {
             Ok(_) => {
-                println!("\n{}", t!("account.edit.success").green());
+                print_success!("\n{}", t!("account.edit.success"));
             }
             Err(e) => {
-                println!(
-                    "\n{}",
-                    t!("account.edit.error").replace("{}", &e.to_string()).red()
-                );
+                print_error!("\n{}", t!("account.edit.error").replace("{}", &e.to_string()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 只修改账号名称；档案、目录关联、备用账号链和上次切换记录都按 ID 引用账号，改名后无需更新
+async fn rename_account(db: &DbState) -> Result<()> {
+    let accounts = db
+        .lock()
+        .await
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(100),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts;
+    if accounts.is_empty() {
+        print_warning!("\n{}", t!("account.list.no_records"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(accounts.iter().map(|a| format!("{} - {}", a.name, a.base_url)));
+    let account = match FuzzySelect::new()
+        .with_prompt(t!("account.rename.prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &accounts[idx - 1],
+        _ => return Ok(()),
+    };
+
+    print_warning!("{}", t!("common.input_cancel_hint"));
+    let name: String = Input::new()
+        .with_prompt(t!("account.rename.prompt_name"))
+        .default(account.name.clone())
+        .allow_empty(true)
+        .interact_text()?;
+    let name = name.trim();
+    if name.is_empty() || name == account.name {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+    if accounts.iter().any(|a| a.id != account.id && a.name == name) {
+        print_error!("\n{}", t!("account.rename.duplicate").replace("{}", name));
+        return Ok(());
+    }
+
+    match db.lock().await.rename_account(account.id, name).await {
+        Ok(_) => {
+            print_success!(
+                "\n{}",
+                t!("account.rename.success")
+                    .replacen("{}", &account.name, 1)
+                    .replacen("{}", name, 1)
+            );
+            println!("{}", t!("account.rename.user_name_hint"));
+        }
+        Err(e) => print_error!("\n{}", t!("account.edit.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 以选中账号为模板预填表单，保存为新账号
+async fn clone_account(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let request = GetAccountsRequest {
+        page: Some(1),
+        per_page: Some(100),
+        search: None,
+        base_url: None,
+    };
+    let response = db_lock.get_accounts(request).await?;
+    drop(db_lock);
+
+    if response.accounts.is_empty() {
+        print_warning!("\n{}", t!("account.list.no_records"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        response
+            .accounts
+            .iter()
+            .map(|a| format!("{} - {}", a.name, a.base_url)),
+    );
+
+    let selection = FuzzySelect::new()
+        .with_prompt(t!("account.clone.prompt"))
+        .items(&items)
+        .interact_opt()?;
+
+    let account = match selection {
+        Some(idx) if idx > 0 => &response.accounts[idx - 1],
+        _ => return Ok(()),
+    };
+
+    println!("\n{}", t!("account.clone.title").green().bold());
+    print_warning!("{}", t!("common.input_cancel_hint"));
+
+    let name: String = Input::new()
+        .with_prompt(t!("account.add.prompt_name"))
+        .default(t!("account.clone.name_suffix").replace("{}", &account.name))
+        .allow_empty(true)
+        .interact_text()?;
+
+    if name.trim().is_empty() {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let Some(token_source) = prompt_token_source(Some(account))? else {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    };
+
+    // 获取所有 Base URL
+    let db_lock = db.lock().await;
+    let base_urls = db_lock.get_base_urls().await?;
+    drop(db_lock);
+
+    let base_url: String = if base_urls.is_empty() {
+        print_warning!("\n{}", t!("account.add.no_base_url"));
+        Input::new()
+            .with_prompt(t!("account.add.prompt_base_url"))
+            .default(account.base_url.clone())
+            .interact()?
+    } else {
+        let mut items: Vec<String> = base_urls
+            .iter()
+            .map(|u| {
+                if u.is_default {
+                    format!("{} - {} {}", u.name, u.url, t!("account.default_indicator"))
+                } else {
+                    format!("{} - {}", u.name, u.url)
+                }
+            })
+            .collect();
+
+        // 默认选中源账号使用的 Base URL；不在列表中时追加“保留源地址”选项并默认选中
+        let existing_index = base_urls.iter().position(|u| u.url == account.base_url);
+        if existing_index.is_none() {
+            items.push(t!("account.clone.keep_base_url").replace("{}", &account.base_url));
+        }
+
+        let selection = Select::new()
+            .with_prompt(t!("account.add.select_base_url"))
+            .items(&items)
+            .default(existing_index.unwrap_or(base_urls.len()))
+            .interact()?;
+
+        base_urls
+            .get(selection)
+            .map_or_else(|| account.base_url.clone(), |u| u.url.clone())
+    };
+
+    if !confirm_token_source_not_swapped(&base_url, &token_source)? {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let model: String = Input::new()
+        .with_prompt(t!("account.add.prompt_model"))
+        .default(account.model.clone())
+        .allow_empty(true)
+        .interact_text()?;
+
+    let auth_style = select_auth_style(account.auth_style)?;
+    let extra_auth_env_key = prompt_extra_auth_env_key(account.extra_auth_env_key.as_deref())?;
+    let staging_base_url = prompt_staging_base_url(account.staging_base_url.as_deref())?;
+    let proxy = prompt_proxy(&account.proxy_config())?;
+    let note = prompt_note(account.note.as_deref())?;
+
+    let (token, token_command) = token_fields(token_source);
+    let db_lock = db.lock().await;
+    let request = CreateAccountRequest {
+        name: name.clone(),
+        token,
+        base_url,
+        model,
+        auth_style,
+        proxy,
+        note,
+        extra_auth_env_key,
+        staging_base_url,
+        token_command,
+    };
+
+    match db_lock.create_account(request).await {
+        Ok(created) => {
+            // 克隆时一并复制 CLAUDE.local.md 模板
+            if let Some(template) = &account.claude_md_template {
+                if let Err(e) = db_lock.set_account_claude_md(created.id, Some(template)).await {
+                    tracing::warn!("复制账号 CLAUDE.local.md 模板失败: {}", e);
+                }
             }
+            print_success!("\n{}", t!("account.add.success").replace("{}", &name));
+        }
+        Err(e) => {
+            print_error!("\n{}", t!("account.add.error").replace("{}", &e.to_string()));
         }
     }
 
     Ok(())
 }
 
+/// 删除账号前提示引用这些账号的配置档案，删除后档案中的账号引用会被清空
+async fn warn_referencing_profiles(db: &DbState, account_ids: &[i64]) -> Result<()> {
+    let names: Vec<String> = db
+        .lock()
+        .await
+        .get_profiles()
+        .await?
+        .into_iter()
+        .filter(|profile| profile.account_id.is_some_and(|id| account_ids.contains(&id)))
+        .map(|profile| profile.name)
+        .collect();
+    if !names.is_empty() {
+        print_warning!("{}", t!("account.delete.profile_warning").replace("{}", &names.join(", ")));
+    }
+    Ok(())
+}
+
 async fn delete_account(db: &DbState) -> Result<()> {
     let db_lock = db.lock().await;
     let request = GetAccountsRequest {
@@ -352,7 +992,7 @@ async fn delete_account(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if response.accounts.is_empty() {
-        println!("\n{}", t!("account.list.no_records").yellow());
+        print_warning!("\n{}", t!("account.list.no_records"));
         return Ok(());
     }
 
@@ -364,7 +1004,7 @@ async fn delete_account(db: &DbState) -> Result<()> {
             .map(|a| format!("{} - {}", a.name, a.base_url)),
     );
 
-    let selection = Select::new()
+    let selection = FuzzySelect::new()
         .with_prompt(t!("account.delete.prompt"))
         .items(&items)
         .interact_opt()?;
@@ -375,6 +1015,7 @@ async fn delete_account(db: &DbState) -> Result<()> {
         }
         let idx = idx - 1;
         let account = &response.accounts[idx];
+        warn_referencing_profiles(db, &[account.id]).await?;
 
         if Confirm::new()
             .with_prompt(t!("account.delete.confirm").replace("{}", &account.name))
@@ -384,15 +1025,10 @@ async fn delete_account(db: &DbState) -> Result<()> {
             let db_lock = db.lock().await;
             match db_lock.delete_account(account.id).await {
                 Ok(_) => {
-                    println!("\n{}", t!("account.delete.success").green());
+                    print_success!("\n{}", t!("account.delete.success"));
                 }
                 Err(e) => {
-                    println!(
-                        "\n{}",
-                        t!("account.delete.error")
-                            .replace("{}", &e.to_string())
-                            .red()
-                    );
+                    print_error!("\n{}", t!("account.delete.error").replace("{}", &e.to_string()));
                 }
             }
         }
@@ -400,3 +1036,419 @@ async fn delete_account(db: &DbState) -> Result<()> {
 
     Ok(())
 }
+
+/// 多选批量删除账号，确认一次后在同一个事务中删除
+async fn batch_delete_accounts(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let accounts = db_lock
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(1000),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts;
+
+    // 每个目录当前使用的账号，删除前提示
+    let mut in_use: HashMap<i64, Vec<String>> = HashMap::new();
+    for directory in db_lock.get_directories().await? {
+        if let Some(account) = db_lock.get_directory_account(directory.id).await? {
+            in_use.entry(account.id).or_default().push(directory.name);
+        }
+    }
+    drop(db_lock);
+
+    if accounts.is_empty() {
+        print_warning!("\n{}", t!("account.list.no_records"));
+        return Ok(());
+    }
+
+    let items: Vec<String> = accounts
+        .iter()
+        .map(|a| format!("{} - {}", a.name, a.base_url))
+        .collect();
+
+    let Some(chosen) = MultiSelect::new()
+        .with_prompt(t!("account.batch_delete.prompt"))
+        .items(&items)
+        .interact_opt()?
+    else {
+        return Ok(());
+    };
+    if chosen.is_empty() {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let selected: Vec<&Account> = chosen.iter().map(|&idx| &accounts[idx]).collect();
+
+    let mut table = super::create_table();
+    table.set_header(vec![
+        Cell::new(t!("account.list.header_name"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("account.list.header_base_url"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("account.batch_delete.header_in_use"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+    ]);
+
+    let mut referenced = 0;
+    for account in &selected {
+        let in_use_cell = match in_use.get(&account.id) {
+            Some(directories) => {
+                referenced += 1;
+                Cell::new(directories.join(", ")).fg(Color::Yellow)
+            }
+            None => Cell::new("-"),
+        };
+        table.add_row(vec![
+            Cell::new(&account.name),
+            Cell::new(&account.base_url),
+            in_use_cell,
+        ]);
+    }
+
+    println!("\n{}", table);
+    if referenced > 0 {
+        print_warning!(
+            "{}",
+            t!("account.batch_delete.in_use_warning")
+                .replace("{}", &referenced.to_string())
+        );
+    }
+
+    let ids: Vec<i64> = selected.iter().map(|a| a.id).collect();
+    warn_referencing_profiles(db, &ids).await?;
+
+    let prompt = t!("account.batch_delete.confirm").replace("{}", &selected.len().to_string());
+    if !super::confirm_bulk(&prompt, selected.len(), false).await? {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    match db.lock().await.delete_accounts(&ids).await {
+        Ok(deleted) => print_success!(
+            "\n{}",
+            t!("account.batch_delete.success")
+                .replace("{}", &deleted.to_string())
+        ),
+        Err(e) => print_error!("\n{}", t!("account.delete.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 编辑账号的 CLAUDE.local.md 模板：用编辑器修改，内容与内置模板相同或为空时恢复使用内置模板
+/// 设置账号的备用账号链：依次选择备用账号，顺序即切换时的尝试顺序
+async fn edit_fallback_chain(db: &DbState) -> Result<()> {
+    let accounts = db
+        .lock()
+        .await
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(100),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts;
+    if accounts.len() < 2 {
+        print_warning!("\n{}", t!("account.fallbacks.need_two"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(accounts.iter().map(|a| format!("{} - {}", a.name, a.base_url)));
+    let account = match FuzzySelect::new()
+        .with_prompt(t!("account.fallbacks.select_account"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &accounts[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let names: HashMap<i64, &str> = accounts.iter().map(|a| (a.id, a.name.as_str())).collect();
+    let current = db.lock().await.get_account_fallbacks(account.id).await?;
+    if current.is_empty() {
+        println!("\n{}", t!("account.fallbacks.none").replace("{}", &account.name));
+    } else {
+        let chain: Vec<&str> = current.iter().filter_map(|id| names.get(id).copied()).collect();
+        println!(
+            "\n{}",
+            t!("account.fallbacks.current")
+                .replacen("{}", &account.name, 1)
+                .replacen("{}", &chain.join(" → "), 1)
+        );
+    }
+    println!("{}", t!("account.fallbacks.hint").cyan());
+
+    // 逐个选择，已选的账号和主账号不再出现在候选中；ESC 放弃修改
+    let mut chain: Vec<&Account> = Vec::new();
+    loop {
+        let candidates: Vec<&Account> = accounts
+            .iter()
+            .filter(|a| a.id != account.id && !chain.iter().any(|c| c.id == a.id))
+            .collect();
+        if candidates.is_empty() {
+            break;
+        }
+        let mut items: Vec<String> = vec![t!("account.fallbacks.done").to_string()];
+        items.extend(candidates.iter().map(|a| format!("{} - {}", a.name, a.base_url)));
+        match FuzzySelect::new()
+            .with_prompt(t!("account.fallbacks.select_next").replace("{}", &(chain.len() + 1).to_string()))
+            .items(&items)
+            .interact_opt()?
+        {
+            Some(0) => break,
+            Some(idx) => chain.push(candidates[idx - 1]),
+            None => {
+                print_warning!("\n{}", t!("common.cancel"));
+                return Ok(());
+            }
+        }
+    }
+
+    let ids: Vec<i64> = chain.iter().map(|a| a.id).collect();
+    if let Err(e) = db.lock().await.set_account_fallbacks(account.id, &ids).await {
+        print_error!("\n{}", t!("account.fallbacks.error").replace("{}", &e.to_string()));
+        return Ok(());
+    }
+    if chain.is_empty() {
+        print_success!("\n{}", t!("account.fallbacks.cleared").replace("{}", &account.name));
+    } else {
+        let names: Vec<&str> = chain.iter().map(|a| a.name.as_str()).collect();
+        print_success!(
+            "\n{}",
+            t!("account.fallbacks.saved")
+                .replacen("{}", &account.name, 1)
+                .replacen("{}", &names.join(" → "), 1)
+        );
+    }
+
+    Ok(())
+}
+
+async fn edit_claude_md_template(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let accounts = db_lock
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(100),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts;
+    drop(db_lock);
+
+    if accounts.is_empty() {
+        print_warning!("\n{}", t!("account.list.no_records"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(accounts.iter().map(|a| {
+        let marker = if a.claude_md_template.is_some() {
+            t!("account.claude_md.custom")
+        } else {
+            t!("account.claude_md.default")
+        };
+        format!("{} - {}", a.name, marker)
+    }));
+
+    let account = match FuzzySelect::new()
+        .with_prompt(t!("account.claude_md.prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &accounts[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let mut actions = vec![t!("common.cancel"), t!("account.claude_md.edit")];
+    if account.claude_md_template.is_some() {
+        actions.push(t!("account.claude_md.restore_default"));
+    }
+    let content = match Select::new()
+        .with_prompt(t!("account.claude_md.action"))
+        .items(&actions)
+        .default(1)
+        .interact_opt()?
+    {
+        Some(1) => {
+            let default = claude_config::claude_local_md_template();
+            let path = std::env::temp_dir().join(format!("claude-md-account-{}.md", account.id));
+            std::fs::write(&path, account.claude_md_template.as_deref().unwrap_or(default))?;
+            let edited = super::directory::open_in_editor(&path)
+                .and_then(|_| Ok(std::fs::read_to_string(&path)?));
+            let _ = std::fs::remove_file(&path);
+            let edited = edited?;
+
+            if edited.trim().is_empty() || edited == default {
+                None
+            } else {
+                Some(edited)
+            }
+        }
+        Some(2) => None,
+        _ => return Ok(()),
+    };
+
+    match db.lock().await.set_account_claude_md(account.id, content.as_deref()).await {
+        Ok(()) => print_success!(
+            "\n{}",
+            if content.is_some() {
+                t!("account.claude_md.saved")
+            } else {
+                t!("account.claude_md.using_default")
+            }
+            .replace("{}", &account.name)
+        ),
+        Err(e) => print_error!("\n{}", t!("account.claude_md.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 导出账号的分享片段，Token 替换为占位符
+async fn export_account_snippet(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let accounts = db_lock
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(100),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts;
+    drop(db_lock);
+
+    if accounts.is_empty() {
+        print_warning!("\n{}", t!("account.list.no_records"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(accounts.iter().map(|a| format!("{} - {}", a.name, a.base_url)));
+
+    let account = match FuzzySelect::new()
+        .with_prompt(t!("account.snippet.export_prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &accounts[idx - 1],
+        _ => return Ok(()),
+    };
+
+    // 文件名只保留字母、数字和连字符
+    let file_stem: String = account
+        .name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' { c } else { '-' })
+        .collect();
+    print_warning!("{}", t!("account.snippet.format_hint"));
+    let path: String = Input::new()
+        .with_prompt(t!("account.snippet.path"))
+        .default(format!("{}.json", file_stem))
+        .interact_text()?;
+    let path = std::path::Path::new(path.trim());
+
+    let snippet = account_snippet::AccountSnippet::from_account(account);
+    let result = snippet
+        .render(account_snippet::SnippetFormat::from_path(path))
+        .and_then(|content| Ok(std::fs::write(path, content)?));
+    match result {
+        Ok(()) => {
+            tracing::info!("已导出账号 {} 的分享片段到 {}", account.name, path.display());
+            print_success!("\n{}", t!("account.snippet.exported").replace("{}", &path.display().to_string()));
+            print_warning!(
+                "{}",
+                t!("account.snippet.placeholder_note")
+                    .replace("{}", account_snippet::TOKEN_PLACEHOLDER)
+            );
+        }
+        Err(e) => print_error!("\n{}", t!("account.snippet.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 从分享片段创建账号，Token 由导入方填写
+async fn import_account_snippet(db: &DbState) -> Result<()> {
+    print_warning!("{}", t!("common.input_cancel_hint"));
+    let path: String = Input::new()
+        .with_prompt(t!("account.snippet.path"))
+        .allow_empty(true)
+        .interact_text()?;
+    if path.trim().is_empty() {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+    let path = std::path::Path::new(path.trim());
+
+    let snippet = match std::fs::read_to_string(path)
+        .map_err(anyhow::Error::from)
+        .and_then(|content| {
+            account_snippet::AccountSnippet::parse(&content, account_snippet::SnippetFormat::from_path(path))
+        }) {
+        Ok(snippet) => snippet,
+        Err(e) => {
+            print_error!("\n{}", t!("account.snippet.error").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+
+    println!("\n{}", t!("account.snippet.import_title").green().bold());
+    println!("  {}: {}", t!("account.list.header_base_url"), snippet.base_url);
+    if !snippet.model.is_empty() {
+        println!("  {}: {}", t!("account.list.header_model"), snippet.model);
+    }
+    println!("  {}: {}", t!("account.list.header_auth_style"), auth_style_label(snippet.auth_style));
+
+    let name: String = Input::new()
+        .with_prompt(t!("account.add.prompt_name"))
+        .default(snippet.name.clone())
+        .allow_empty(true)
+        .interact_text()?;
+    if name.trim().is_empty() {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let Some(token_source) = prompt_token_source(None)? else {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    };
+    if !confirm_token_source_not_swapped(&snippet.base_url, &token_source)? {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+    let (token, token_command) = token_fields(token_source);
+
+    let request = CreateAccountRequest {
+        name: name.trim().to_string(),
+        token,
+        base_url: snippet.base_url,
+        model: snippet.model,
+        auth_style: snippet.auth_style,
+        proxy: snippet.proxy,
+        note: snippet.note,
+        extra_auth_env_key: None,
+        staging_base_url: None,
+        token_command,
+    };
+
+    let db_lock = db.lock().await;
+    match db_lock.create_account(request).await {
+        Ok(_) => print_success!("\n{}", t!("account.add.success").replace("{}", name.trim())),
+        Err(e) => print_error!("\n{}", t!("account.add.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
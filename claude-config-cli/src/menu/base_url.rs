@@ -1,4 +1,4 @@
-use crate::{models::*, t, DbState};
+use crate::{models::*, print_error, print_success, print_warning, t, DbState};
 use anyhow::Result;
 use colored::Colorize;
 use comfy_table::{Attribute, Cell, Color};
@@ -46,7 +46,7 @@ async fn list_base_urls(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if base_urls.is_empty() {
-        println!("\n{}", t!("url.list.no_records").yellow());
+        print_warning!("\n{}", t!("url.list.no_records"));
         return Ok(());
     }
 
@@ -74,11 +74,7 @@ async fn list_base_urls(db: &DbState) -> Result<()> {
 
     for base_url in &base_urls {
         let is_default = if base_url.is_default { t!("url.list.default_yes") } else { "" };
-        let description = base_url
-            .description
-            .as_ref()
-            .map(|s| s.as_str())
-            .unwrap_or("");
+        let description = base_url.description.as_deref().unwrap_or("");
         table.add_row(vec![
             base_url.id.to_string(),
             base_url.name.clone(),
@@ -102,7 +98,7 @@ async fn list_base_urls(db: &DbState) -> Result<()> {
 
 async fn add_base_url(db: &DbState) -> Result<()> {
     println!("\n{}", t!("url.add.title").green().bold());
-    println!("{}", t!("common.input_cancel_hint").yellow());
+    print_warning!("{}", t!("common.input_cancel_hint"));
 
     let name: String = Input::new()
         .with_prompt(t!("url.add.prompt_name"))
@@ -110,7 +106,7 @@ async fn add_base_url(db: &DbState) -> Result<()> {
         .interact_text()?;
 
     if name.trim().is_empty() || name.trim().eq_ignore_ascii_case("q") {
-        println!("\n{}", t!("common.cancel").yellow());
+        print_warning!("\n{}", t!("common.cancel"));
         return Ok(());
     }
 
@@ -121,7 +117,7 @@ async fn add_base_url(db: &DbState) -> Result<()> {
         .interact_text()?;
 
     if url.trim().is_empty() || url.trim().eq_ignore_ascii_case("q") {
-        println!("\n{}", t!("common.cancel").yellow());
+        print_warning!("\n{}", t!("common.cancel"));
         return Ok(());
     }
 
@@ -162,10 +158,10 @@ async fn add_base_url(db: &DbState) -> Result<()> {
 
     match db_lock.create_base_url(request).await {
         Ok(_) => {
-            println!("\n{}", t!("url.add.success").replace("{}", &name).green());
+            print_success!("\n{}", t!("url.add.success").replace("{}", &name));
         }
         Err(e) => {
-            println!("\n{}", t!("url.add.error").replace("{}", &e.to_string()).red());
+            print_error!("\n{}", t!("url.add.error").replace("{}", &e.to_string()));
         }
     }
 
@@ -178,7 +174,7 @@ async fn edit_base_url(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if base_urls.is_empty() {
-        println!("\n{}", t!("url.list.no_records").yellow());
+        print_warning!("\n{}", t!("url.list.no_records"));
         return Ok(());
     }
 
@@ -197,7 +193,7 @@ async fn edit_base_url(db: &DbState) -> Result<()> {
         let idx = idx - 1;
         let base_url = &base_urls[idx];
 
-        println!("{}", t!("common.input_cancel_hint").yellow());
+        print_warning!("{}", t!("common.input_cancel_hint"));
 
         let name: String = Input::new()
             .with_prompt(t!("url.add.prompt_name"))
@@ -261,10 +257,10 @@ async fn edit_base_url(db: &DbState) -> Result<()> {
 
         match db_lock.update_base_url(base_url.id, request).await {
             Ok(_) => {
-                println!("\n{}", t!("url.edit.success").green());
+                print_success!("\n{}", t!("url.edit.success"));
             }
             Err(e) => {
-                println!("\n{}", t!("url.edit.error").replace("{}", &e.to_string()).red());
+                print_error!("\n{}", t!("url.edit.error").replace("{}", &e.to_string()));
             }
         }
     }
@@ -278,7 +274,7 @@ async fn delete_base_url(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if base_urls.is_empty() {
-        println!("\n{}", t!("url.list.no_records").yellow());
+        print_warning!("\n{}", t!("url.list.no_records"));
         return Ok(());
     }
 
@@ -309,10 +305,10 @@ async fn delete_base_url(db: &DbState) -> Result<()> {
             let db_lock = db.lock().await;
             match db_lock.delete_base_url(base_url.id).await {
                 Ok(_) => {
-                    println!("\n{}", t!("url.delete.success").green());
+                    print_success!("\n{}", t!("url.delete.success"));
                 }
                 Err(e) => {
-                    println!("\n{}", t!("url.delete.error").replace("{}", &e.to_string()).red());
+                    print_error!("\n{}", t!("url.delete.error").replace("{}", &e.to_string()));
                 }
             }
         }
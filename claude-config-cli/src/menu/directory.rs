@@ -1,8 +1,16 @@
-use crate::{models::*, t, DbState};
-use anyhow::Result;
+use crate::{
+    change_log::ChangeRecorder,
+    undo::UndoPoint,
+    claude_config::{self, ClaudeConfigManager, ClaudeMdStatus, ResetOptions, ResetReport},
+    models::*,
+    output, settings, t, DbState,
+    print_error, print_success, print_warning,
+};
+use anyhow::{Context, Result};
+use claude_config_core::audit::ConfigAction;
 use colored::Colorize;
 use comfy_table::{Attribute, Cell, Color};
-use dialoguer::{Confirm, Input, Select};
+use dialoguer::{Confirm, FuzzySelect, Input, MultiSelect, Select};
 
 pub async fn directory_menu(db: &DbState) -> Result<()> {
     let mut last_selection = 0;
@@ -14,6 +22,25 @@ pub async fn directory_menu(db: &DbState) -> Result<()> {
             t!("directory.menu.add"),
             t!("directory.menu.edit"),
             t!("directory.menu.delete"),
+            t!("directory.menu.edit_settings"),
+            t!("directory.menu.repair_settings"),
+            t!("directory.menu.audit"),
+            t!("directory.menu.validate_settings"),
+            t!("directory.menu.effective_env"),
+            t!("directory.menu.trash"),
+            t!("directory.menu.reset"),
+            t!("directory.menu.merge_settings"),
+            t!("directory.menu.history"),
+            t!("directory.menu.migrate_legacy"),
+            t!("directory.menu.lock"),
+            t!("directory.menu.copy_config"),
+            t!("directory.menu.mcp"),
+            t!("directory.menu.external_changes"),
+            t!("directory.menu.clipboard_env"),
+            t!("directory.menu.claude_md_report"),
+            t!("directory.menu.deactivate_all"),
+            t!("directory.menu.activate_selected"),
+            t!("directory.menu.expected_account"),
         ];
 
         let selection = match Select::new()
@@ -33,6 +60,37 @@ pub async fn directory_menu(db: &DbState) -> Result<()> {
             2 => add_directory(db).await?,
             3 => edit_directory(db).await?,
             4 => delete_directory(db).await?,
+            5 => edit_settings_file(db).await?,
+            6 => repair_settings(db).await?,
+            7 => {
+                crate::commands::audit::run(db, true).await?;
+                let _ = Input::<String>::new()
+                    .with_prompt(t!("common.continue"))
+                    .allow_empty(true)
+                    .interact()?;
+            }
+            8 => validate_settings(db).await?,
+            9 => show_effective_env(db).await?,
+            10 => trash_menu(db).await?,
+            11 => reset_directory(db).await?,
+            12 => merge_settings_files(db).await?,
+            13 => show_change_history(db).await?,
+            14 => {
+                crate::commands::migrate::run(db, None).await?;
+                let _ = Input::<String>::new()
+                    .with_prompt(t!("common.continue"))
+                    .allow_empty(true)
+                    .interact()?;
+            }
+            15 => toggle_directory_lock(db).await?,
+            16 => copy_config_from_directory(db).await?,
+            17 => super::mcp::mcp_menu(db).await?,
+            18 => super::external_changes::detect_external_changes(db).await?,
+            19 => merge_env_from_clipboard(db).await?,
+            20 => claude_md_report(db).await?,
+            21 => deactivate_all_directories(db).await?,
+            22 => activate_selected_directories(db).await?,
+            23 => set_expected_account(db).await?,
             _ => unreachable!(),
         }
     }
@@ -46,52 +104,97 @@ async fn list_directories(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if directories.is_empty() {
-        println!("\n{}", t!("directory.list.no_records").yellow());
+        print_warning!("\n{}", t!("directory.list.no_records"));
         return Ok(());
     }
 
-    let mut table = super::create_table();
-    table.set_header(vec![
-        Cell::new(t!("directory.list.header_id"))
-            .add_attribute(Attribute::Bold)
-            .fg(Color::Cyan),
-        Cell::new(t!("directory.list.header_name"))
-            .add_attribute(Attribute::Bold)
-            .fg(Color::Cyan),
-        Cell::new(t!("directory.list.header_path"))
-            .add_attribute(Attribute::Bold)
-            .fg(Color::Cyan),
-        Cell::new(t!("account.list.header_status"))
-            .add_attribute(Attribute::Bold)
-            .fg(Color::Cyan),
-        Cell::new(t!("directory.list.header_exists"))
-            .add_attribute(Attribute::Bold)
-            .fg(Color::Cyan),
-    ]);
+    let workspace_root = settings::load_workspace_root().await;
+    let show_env_summary = settings::load_or_default().await.directory_env_summary;
 
-    for directory in &directories {
-        let status = if directory.is_active {
-            t!("account.list.status_active")
-        } else {
-            t!("account.list.status_inactive")
-        };
-        let exists = if std::path::Path::new(&directory.path).exists() {
-            t!("directory.list.exists")
+    let page_size = settings::load_page_size().await;
+    let pages = super::page_count(directories.len(), page_size);
+    let mut page = 0;
+    loop {
+        let page_directories = &directories[super::page_range(page, page_size, directories.len())];
+        // 只读取当前页的目录配置
+        let summaries = if show_env_summary {
+            load_env_summaries(page_directories.iter().map(|d| d.resolved_path(&workspace_root)).collect()).await
         } else {
-            t!("directory.list.not_exists")
+            Vec::new()
         };
 
-        table.add_row(vec![
-            directory.id.to_string(),
-            directory.name.clone(),
-            directory.path.clone(),
-            status.to_string(),
-            exists.to_string(),
-        ]);
+        let mut table = super::create_table();
+        let mut header = vec![
+            Cell::new(t!("directory.list.header_id"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("directory.list.header_name"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("directory.list.header_path"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("account.list.header_status"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("directory.list.header_exists"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("directory.list.header_last_switched"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+        ];
+        if show_env_summary {
+            for title in [t!("directory.list.header_base_url"), t!("directory.list.header_token")] {
+                header.push(Cell::new(title).add_attribute(Attribute::Bold).fg(Color::Cyan));
+            }
+        }
+        table.set_header(header);
+
+        for (index, directory) in page_directories.iter().enumerate() {
+            let path = directory.resolved_path(&workspace_root);
+            let status = output::status_label(directory.is_active);
+            let exists = output::exists_label(std::path::Path::new(&path).exists());
+
+            let mut row = vec![
+                directory.id.to_string(),
+                if directory.is_locked {
+                    format!("{} {}", t!("directory.lock.marker"), crate::names::display_name(&directory.name))
+                } else {
+                    crate::names::display_name(&directory.name)
+                },
+                if directory.is_relative {
+                    format!("{} {}", path, t!("directory.list.relative"))
+                } else {
+                    path
+                },
+                status.to_string(),
+                exists.to_string(),
+                super::humanize_since(directory.last_switched_at),
+            ];
+            if show_env_summary {
+                match summaries.get(index).and_then(Option::as_ref) {
+                    Some(summary) => {
+                        row.push(summary.host.clone().unwrap_or_else(|| "-".to_string()));
+                        row.push(output::symbol(if summary.has_token { "✓" } else { "✗" }).to_string());
+                    }
+                    None => row.extend(["-".to_string(), "-".to_string()]),
+                }
+            }
+            table.add_row(row);
+        }
+
+        println!("\n{}", table);
+        match super::prompt_page(page, pages, directories.len())? {
+            Some(next) => page = next,
+            None => break,
+        }
     }
 
-    println!("\n{}", table);
-    println!("{}", t!("directory.list.total").replace("{}", &directories.len().to_string()));
+    // 多页时翻页菜单中的"退出"即结束浏览
+    if pages > 1 {
+        return Ok(());
+    }
 
     let _ = Input::<String>::new()
         .with_prompt(t!("common.continue"))
@@ -101,9 +204,51 @@ async fn list_directories(db: &DbState) -> Result<()> {
     Ok(())
 }
 
-async fn add_directory(db: &DbState) -> Result<()> {
+/// 目录列表中显示的配置摘要
+struct EnvSummary {
+    /// Base URL 的主机部分，不显示完整地址
+    host: Option<String>,
+    has_token: bool,
+}
+
+/// 并发读取各目录的认证配置，结果与输入顺序一致；路径不存在或读取失败时为 None
+async fn load_env_summaries(paths: Vec<String>) -> Vec<Option<EnvSummary>> {
+    let mut tasks = tokio::task::JoinSet::new();
+    for (index, path) in paths.into_iter().enumerate() {
+        if !std::path::Path::new(&path).is_dir() {
+            continue;
+        }
+        tasks.spawn_blocking(move || {
+            let env = ClaudeConfigManager::new(path).get_env_config().ok()?;
+            let host = env.get("ANTHROPIC_BASE_URL").map(|url| {
+                reqwest::Url::parse(url)
+                    .ok()
+                    .and_then(|url| url.host_str().map(str::to_string))
+                    // 无法解析时可能是误填的 Token，只显示首尾几位
+                    .unwrap_or_else(|| crate::connectivity::mask_token(url))
+            });
+            let has_token = ["ANTHROPIC_API_KEY", "ANTHROPIC_AUTH_TOKEN"]
+                .iter()
+                .any(|key| env.get(*key).is_some_and(|token| !token.is_empty()));
+            Some((index, EnvSummary { host, has_token }))
+        });
+    }
+
+    let mut summaries: Vec<Option<EnvSummary>> = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        if let Ok(Some((index, summary))) = result {
+            if summaries.len() <= index {
+                summaries.resize_with(index + 1, || None);
+            }
+            summaries[index] = Some(summary);
+        }
+    }
+    summaries
+}
+
+pub(super) async fn add_directory(db: &DbState) -> Result<()> {
     println!("\n{}", t!("directory.add.title").green().bold());
-    println!("{}", t!("common.input_cancel_hint").yellow());
+    print_warning!("{}", t!("common.input_cancel_hint"));
 
     let path: String = Input::new()
         .with_prompt(t!("directory.add.prompt_path"))
@@ -111,21 +256,14 @@ async fn add_directory(db: &DbState) -> Result<()> {
         .interact_text()?;
 
     if path.trim().is_empty() || path.trim().eq_ignore_ascii_case("q") {
-        println!("\n{}", t!("common.cancel").yellow());
+        print_warning!("\n{}", t!("common.cancel"));
         return Ok(());
     }
 
-    // 检查路径是否存在
-    if !std::path::Path::new(&path).exists() {
-        println!("{}", t!("directory.add.warn_path_not_exists").yellow());
-        if !Confirm::new()
-            .with_prompt(t!("common.confirm"))
-            .default(false)
-            .interact()?
-        {
-            return Ok(());
-        }
-    }
+    let workspace_root = settings::load_workspace_root().await;
+    let Some((path, is_relative)) = prepare_directory_path(&path, &workspace_root, false)? else {
+        return Ok(());
+    };
 
     let name: String = Input::new()
         .with_prompt(t!("directory.add.prompt_name"))
@@ -133,7 +271,7 @@ async fn add_directory(db: &DbState) -> Result<()> {
         .interact_text()?;
 
     if name.trim().is_empty() || name.trim().eq_ignore_ascii_case("q") {
-        println!("\n{}", t!("common.cancel").yellow());
+        print_warning!("\n{}", t!("common.cancel"));
         return Ok(());
     }
 
@@ -141,14 +279,15 @@ async fn add_directory(db: &DbState) -> Result<()> {
     let request = CreateDirectoryRequest {
         path: path.clone(),
         name: name.clone(),
+        is_relative,
     };
 
     match db_lock.create_directory(request).await {
         Ok(_) => {
-            println!("\n{}", t!("directory.add.success").replace("{}", &name).green());
+            print_success!("\n{}", t!("directory.add.success").replace("{}", &name));
         }
         Err(e) => {
-            println!("\n{}", t!("directory.add.error").replace("{}", &e.to_string()).red());
+            print_error!("\n{}", t!("directory.add.error").replace("{}", &e.to_string()));
         }
     }
 
@@ -161,18 +300,19 @@ async fn edit_directory(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if directories.is_empty() {
-        println!("\n{}", t!("directory.list.no_records").yellow());
+        print_warning!("\n{}", t!("directory.list.no_records"));
         return Ok(());
     }
 
+    let workspace_root = settings::load_workspace_root().await;
     let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
     items.extend(
         directories
             .iter()
-            .map(|d| format!("{} - {}", d.name, d.path)),
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
     );
 
-    let selection = Select::new()
+    let selection = FuzzySelect::new()
         .with_prompt(t!("directory.edit.prompt"))
         .items(&items)
         .interact_opt()?;
@@ -184,7 +324,7 @@ async fn edit_directory(db: &DbState) -> Result<()> {
         let idx = idx - 1;
         let directory = &directories[idx];
 
-        println!("{}", t!("common.input_cancel_hint").yellow());
+        print_warning!("{}", t!("common.input_cancel_hint"));
 
         let name: String = Input::new()
             .with_prompt(t!("directory.add.prompt_name"))
@@ -204,24 +344,84 @@ async fn edit_directory(db: &DbState) -> Result<()> {
             .allow_empty(true)
             .interact_text()?;
 
-        let path = if path.trim().is_empty() {
-            directory.path.clone()
+        let (path, is_relative) = if path.trim().is_empty() || path == directory.path {
+            (directory.path.clone(), directory.is_relative)
         } else {
-            path
+            choose_path_storage(&path, &workspace_root, false)?
+        };
+
+        let path_label = |path: &str, is_relative: bool| {
+            if is_relative {
+                format!("{} {}", path, t!("directory.list.relative"))
+            } else {
+                path.to_string()
+            }
         };
+        let changes = [
+            (
+                t!("directory.list.header_name"),
+                directory.name.clone(),
+                name.clone(),
+            ),
+            (
+                t!("directory.list.header_path"),
+                path_label(&directory.path, directory.is_relative),
+                path_label(&path, is_relative),
+            ),
+        ];
+
+        if changes.iter().all(|(_, old, new)| old == new) {
+            print_warning!("\n{}", t!("directory.edit.no_changes"));
+            return Ok(());
+        }
+
+        let mut table = super::create_table();
+        table.set_header(vec![
+            Cell::new(t!("directory.edit.header_field"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("directory.edit.header_old"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+            Cell::new(t!("directory.edit.header_new"))
+                .add_attribute(Attribute::Bold)
+                .fg(Color::Cyan),
+        ]);
+        for (field, old, new) in &changes {
+            if old == new {
+                table.add_row(vec![Cell::new(field), Cell::new(old), Cell::new(new)]);
+            } else {
+                table.add_row(vec![
+                    Cell::new(field).add_attribute(Attribute::Bold),
+                    Cell::new(old).fg(Color::Red),
+                    Cell::new(new).fg(Color::Green).add_attribute(Attribute::Bold),
+                ]);
+            }
+        }
+        println!("\n{}", table);
+
+        if !Confirm::new()
+            .with_prompt(t!("directory.edit.confirm"))
+            .default(true)
+            .interact()?
+        {
+            print_warning!("\n{}", t!("common.cancel"));
+            return Ok(());
+        }
 
         let db_lock = db.lock().await;
         let request = UpdateDirectoryRequest {
             name: Some(name),
             path: Some(path),
+            is_relative: Some(is_relative),
         };
 
         match db_lock.update_directory(directory.id, request).await {
             Ok(_) => {
-                println!("\n{}", t!("directory.edit.success").green());
+                print_success!("\n{}", t!("directory.edit.success"));
             }
             Err(e) => {
-                println!("\n{}", t!("directory.edit.error").replace("{}", &e.to_string()).red());
+                print_error!("\n{}", t!("directory.edit.error").replace("{}", &e.to_string()));
             }
         }
     }
@@ -235,18 +435,19 @@ async fn delete_directory(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if directories.is_empty() {
-        println!("\n{}", t!("directory.list.no_records").yellow());
+        print_warning!("\n{}", t!("directory.list.no_records"));
         return Ok(());
     }
 
+    let workspace_root = settings::load_workspace_root().await;
     let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
     items.extend(
         directories
             .iter()
-            .map(|d| format!("{} - {}", d.name, d.path)),
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
     );
 
-    let selection = Select::new()
+    let selection = FuzzySelect::new()
         .with_prompt(t!("directory.delete.prompt"))
         .items(&items)
         .interact_opt()?;
@@ -270,10 +471,10 @@ async fn delete_directory(db: &DbState) -> Result<()> {
             let db_lock = db.lock().await;
             match db_lock.delete_directory(directory.id).await {
                 Ok(_) => {
-                    println!("\n{}", t!("directory.delete.success").green());
+                    print_success!("\n{}", t!("directory.delete.success"));
                 }
                 Err(e) => {
-                    println!("\n{}", t!("directory.delete.error").replace("{}", &e.to_string()).red());
+                    print_error!("\n{}", t!("directory.delete.error").replace("{}", &e.to_string()));
                 }
             }
         }
@@ -281,3 +482,1433 @@ async fn delete_directory(db: &DbState) -> Result<()> {
 
     Ok(())
 }
+
+/// 重置目录：移除本工具写入的配置，需确认后执行
+async fn reset_directory(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        directories
+            .iter()
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+    );
+
+    let selection = FuzzySelect::new()
+        .with_prompt(t!("directory.reset.prompt"))
+        .items(&items)
+        .interact_opt()?;
+
+    let directory = match selection {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+    if refuse_if_locked(directory) {
+        return Ok(());
+    }
+    if !confirm_claude_dir_target(&directory.resolved_path(&workspace_root)).await? {
+        return Ok(());
+    }
+
+    let steps = [
+        t!("directory.reset.step_env"),
+        t!("directory.reset.step_sandbox"),
+        t!("directory.reset.step_claude_md"),
+        t!("directory.reset.step_claude_dir"),
+    ];
+    let Some(chosen) = MultiSelect::new()
+        .with_prompt(t!("directory.reset.select_steps"))
+        .items(&steps)
+        .defaults(&[true; 4])
+        .interact_opt()?
+    else {
+        return Ok(());
+    };
+    if chosen.is_empty() {
+        return Ok(());
+    }
+
+    if !Confirm::new()
+        .with_prompt(t!("directory.reset.confirm").replace("{}", &directory.name))
+        .default(false)
+        .interact()?
+    {
+        return Ok(());
+    }
+
+    let options = ResetOptions {
+        remove_env_keys: chosen.contains(&0),
+        remove_sandbox: chosen.contains(&1),
+        remove_claude_local_md: chosen.contains(&2),
+        remove_empty_claude_dir: chosen.contains(&3),
+    };
+    let recorder = ChangeRecorder::new(directory.id);
+    let manager = ClaudeConfigManager::new(directory.resolved_path(&workspace_root))
+        .with_change_hook(recorder.hook())
+        .with_write_format(settings::load_write_format().await)
+        .with_indent(settings::load_indent().await);
+    let undo_point = UndoPoint::capture(ConfigAction::Reset, &directory.name, &manager.write_target_path());
+    let result = manager.reset_directory(options);
+    recorder.flush(&*db.lock().await).await;
+    let report = match result {
+        Ok(report) => {
+            undo_point.commit();
+            report
+        }
+        Err(e) => {
+            print_error!("\n{}", t!("directory.reset.error").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+
+    println!();
+    if report == ResetReport::default() {
+        print_warning!("{}", t!("directory.reset.nothing"));
+    }
+    if !report.removed_keys.is_empty() {
+        print_success!(
+            "{}",
+            t!("directory.reset.removed_keys")
+                .replace("{}", &report.removed_keys.join(", "))
+        );
+    }
+    if report.settings_file_removed {
+        print_success!("{}", t!("directory.reset.settings_removed"));
+    }
+    if report.claude_local_md_removed {
+        print_success!("{}", t!("directory.reset.claude_md_removed"));
+    }
+    if report.claude_local_md_kept {
+        print_warning!("{}", t!("directory.reset.claude_md_kept"));
+    }
+    if report.claude_dir_removed {
+        print_success!("{}", t!("directory.reset.claude_dir_removed"));
+    }
+
+    Ok(())
+}
+
+/// clear_env_config 会移除的环境变量
+const CLEARED_ENV_KEYS: [&str; 3] = ["ANTHROPIC_API_KEY", "ANTHROPIC_AUTH_TOKEN", "ANTHROPIC_BASE_URL"];
+
+/// 快捷操作：清除当前目录（上次切换的目录）的认证配置；没有记录时让用户选择目录
+pub async fn quick_clear(db: &DbState) -> Result<()> {
+    println!("\n{}", t!("directory.quick_clear.title").green().bold());
+
+    let directories = db.lock().await.get_directories().await?;
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let last_directory_id = settings::load_or_default().await.last_directory_id;
+    let directory = match directories.iter().find(|d| Some(d.id) == last_directory_id) {
+        Some(directory) => directory,
+        None => {
+            print_warning!("{}", t!("directory.quick_clear.no_current"));
+            let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+            items.extend(
+                directories
+                    .iter()
+                    .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+            );
+            match FuzzySelect::new()
+                .with_prompt(t!("directory.quick_clear.prompt"))
+                .items(&items)
+                .interact_opt()?
+            {
+                Some(idx) if idx > 0 => &directories[idx - 1],
+                _ => return Ok(()),
+            }
+        }
+    };
+    if refuse_if_locked(directory) {
+        return Ok(());
+    }
+    if !confirm_claude_dir_target(&directory.resolved_path(&workspace_root)).await? {
+        return Ok(());
+    }
+
+    // 关联账号配置了额外认证变量时一并清除
+    let extra_auth_env_key = db
+        .lock()
+        .await
+        .get_directory_account(directory.id)
+        .await?
+        .and_then(|account| account.extra_auth_env_key);
+
+    let directory_path = directory.resolved_path(&workspace_root);
+    let recorder = ChangeRecorder::new(directory.id);
+    let manager = ClaudeConfigManager::new(directory_path.clone())
+        .with_change_hook(recorder.hook())
+        .with_extra_auth_env_key(extra_auth_env_key.clone())
+        .with_write_format(settings::load_write_format().await)
+        .with_indent(settings::load_indent().await);
+
+    // 先读出将被清除的变量，清除后用于展示
+    let env = match manager.get_env_config() {
+        Ok(env) => env,
+        Err(e) => {
+            print_error!("\n{}", t!("directory.quick_clear.error").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+    let cleared: Vec<(&str, &String)> = CLEARED_ENV_KEYS
+        .iter()
+        .copied()
+        .chain(extra_auth_env_key.as_deref())
+        .filter_map(|key| env.get(key).map(|value| (key, value)))
+        .collect();
+    if cleared.is_empty() {
+        print_warning!("\n{}", t!("directory.quick_clear.nothing").replace("{}", &directory.name));
+        return Ok(());
+    }
+
+    if !Confirm::new()
+        .with_prompt(
+            t!("directory.quick_clear.confirm")
+                .replacen("{}", &directory.name, 1)
+                .replacen("{}", &directory_path, 1),
+        )
+        .default(false)
+        .interact()?
+    {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let undo_point = UndoPoint::capture(ConfigAction::Clear, &directory.name, &manager.write_target_path());
+    let result = manager.clear_env_config();
+    recorder.flush(&*db.lock().await).await;
+    if let Err(e) = result {
+        print_error!("\n{}", t!("directory.quick_clear.error").replace("{}", &e.to_string()));
+        return Ok(());
+    }
+    undo_point.commit();
+
+    print_success!("\n{}", t!("directory.quick_clear.success").replace("{}", &directory.name));
+    for (key, value) in cleared {
+        // Token 只显示首尾几位
+        let shown = if key == "ANTHROPIC_BASE_URL" {
+            value.clone()
+        } else {
+            crate::connectivity::mask_token(value)
+        };
+        println!("  {} {}={}", output::symbol("✓").green(), key, shown);
+    }
+
+    Ok(())
+}
+
+/// 撤销本次会话中最近一次切换、清除或重置，恢复修改前的配置文件
+pub async fn undo_last_change() -> Result<()> {
+    let Some(entry) = crate::undo::peek() else {
+        print_warning!("\n{}", t!("undo.empty"));
+        return Ok(());
+    };
+
+    println!(
+        "\n{}",
+        t!("undo.summary")
+            .replacen("{}", &entry.at.format("%H:%M:%S").to_string(), 1)
+            .replacen("{}", entry.action.as_str(), 1)
+            .replacen("{}", &entry.directory_name, 1)
+            .cyan()
+    );
+    println!("{}", t!("undo.path").replace("{}", &entry.path.display().to_string()));
+    println!("{}", t!("undo.remaining").replace("{}", &crate::undo::len().to_string()));
+    match &entry.before {
+        Some(before) => {
+            let current = std::fs::read_to_string(&entry.path).unwrap_or_default();
+            super::external_changes::print_masked_diff(&current, &String::from_utf8_lossy(before));
+        }
+        None => print_warning!("{}", t!("undo.will_delete")),
+    }
+
+    if !Confirm::new()
+        .with_prompt(t!("undo.confirm"))
+        .default(false)
+        .interact()?
+    {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let Some(entry) = crate::undo::pop() else {
+        return Ok(());
+    };
+    match entry.restore() {
+        Ok(()) => print_success!("\n{}", t!("undo.success").replace("{}", &entry.directory_name)),
+        Err(e) => print_error!("\n{}", t!("undo.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 目录的 `.claude` 实际位于目录之外且设置未允许符号链接时，返回其真实路径
+pub async fn claude_dir_escape(directory_path: &str) -> Option<std::path::PathBuf> {
+    if settings::load_or_default().await.allow_symlinked_claude_dir {
+        return None;
+    }
+    ClaudeConfigManager::new(directory_path.to_string()).claude_dir_outside_directory()
+}
+
+/// `.claude` 指向目录之外时警告并确认是否继续写入，返回是否继续
+pub async fn confirm_claude_dir_target(directory_path: &str) -> Result<bool> {
+    let Some(target) = claude_dir_escape(directory_path).await else {
+        return Ok(true);
+    };
+    print_warning!(
+        "\n{}",
+        t!("directory.symlink_escape.warning")
+            .replacen("{}", directory_path, 1)
+            .replacen("{}", &target.display().to_string(), 1)
+    );
+    println!("{}", t!("directory.symlink_escape.hint"));
+    Ok(Confirm::new()
+        .with_prompt(t!("directory.symlink_escape.confirm"))
+        .default(false)
+        .interact()?)
+}
+
+/// 目录已锁定时打印提示并返回 true，修改配置的流程应直接放弃
+pub fn refuse_if_locked(directory: &Directory) -> bool {
+    if directory.is_locked {
+        print_warning!("\n{}", t!("directory.lock.refused").replace("{}", &directory.name));
+    }
+    directory.is_locked
+}
+
+/// 锁定 / 解锁目录，需要确认
+async fn toggle_directory_lock(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(directories.iter().map(|d| {
+        if d.is_locked {
+            format!("{} {}", t!("directory.lock.marker"), d.name)
+        } else {
+            d.name.clone()
+        }
+    }));
+
+    let directory = match FuzzySelect::new()
+        .with_prompt(t!("directory.lock.prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let locked = !directory.is_locked;
+    let confirm_key = if locked {
+        "directory.lock.confirm_lock"
+    } else {
+        "directory.lock.confirm_unlock"
+    };
+    if !Confirm::new()
+        .with_prompt(crate::i18n::translate(confirm_key).replace("{}", &directory.name))
+        .default(false)
+        .interact()?
+    {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    match db.lock().await.set_directory_locked(directory.id, locked).await {
+        Ok(()) => {
+            let key = if locked {
+                "directory.lock.locked"
+            } else {
+                "directory.lock.unlocked"
+            };
+            print_success!("\n{}", crate::i18n::translate(key).replace("{}", &directory.name));
+        }
+        Err(e) => print_error!("\n{}", t!("directory.lock.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 设置目录的预期账号；核对和状态检查按预期账号判断是否偏离，应用其他账号时给出警告
+async fn set_expected_account(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    let accounts = db_lock
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(100),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+    if accounts.is_empty() {
+        print_warning!("\n{}", t!("account.list.no_records"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(directories.iter().map(|d| {
+        match d.expected_account_id.and_then(|id| accounts.iter().find(|a| a.id == id)) {
+            Some(account) => format!("{} ({})", d.name, t!("directory.expected.current").replace("{}", &account.name)),
+            None => d.name.clone(),
+        }
+    }));
+    let directory = match FuzzySelect::new()
+        .with_prompt(t!("directory.expected.select_directory"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let mut account_items: Vec<String> = vec![t!("directory.expected.none").to_string()];
+    account_items.extend(accounts.iter().map(|a| format!("{} - {}", a.name, a.base_url)));
+    let default = directory
+        .expected_account_id
+        .and_then(|id| accounts.iter().position(|a| a.id == id))
+        .map_or(0, |pos| pos + 1);
+    let Some(selection) = FuzzySelect::new()
+        .with_prompt(t!("directory.expected.select_account"))
+        .items(&account_items)
+        .default(default)
+        .interact_opt()?
+    else {
+        return Ok(());
+    };
+    let account = selection.checked_sub(1).map(|idx| &accounts[idx]);
+
+    match db
+        .lock()
+        .await
+        .set_directory_expected_account(directory.id, account.map(|a| a.id))
+        .await
+    {
+        Ok(()) => match account {
+            Some(account) => print_success!(
+                "\n{}",
+                t!("directory.expected.set")
+                    .replacen("{}", &directory.name, 1)
+                    .replacen("{}", &account.name, 1)
+            ),
+            None => print_success!("\n{}", t!("directory.expected.cleared").replace("{}", &directory.name)),
+        },
+        Err(e) => print_error!("\n{}", t!("directory.expected.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 从其他目录复制 env（原样复制 Token），可选复制 CLAUDE.local.md
+async fn copy_config_from_directory(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.len() < 2 {
+        print_warning!("\n{}", t!("directory.copy_config.need_two"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        directories
+            .iter()
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+    );
+
+    let source = match FuzzySelect::new()
+        .with_prompt(t!("directory.copy_config.source_prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+    let target = match FuzzySelect::new()
+        .with_prompt(t!("directory.copy_config.target_prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+    if source.id == target.id {
+        print_warning!("\n{}", t!("directory.copy_config.same"));
+        return Ok(());
+    }
+    if refuse_if_locked(target) {
+        return Ok(());
+    }
+    if !confirm_claude_dir_target(&target.resolved_path(&workspace_root)).await? {
+        return Ok(());
+    }
+
+    let source_manager = ClaudeConfigManager::new(source.resolved_path(&workspace_root));
+    let recorder = ChangeRecorder::new(target.id);
+    let target_manager = ClaudeConfigManager::new(target.resolved_path(&workspace_root))
+        .with_change_hook(recorder.hook())
+        .with_write_format(settings::load_write_format().await)
+        .with_indent(settings::load_indent().await);
+
+    // 目标目录已有配置时需要确认覆盖
+    if target_manager.resolve_active_settings_path().exists()
+        && !Confirm::new()
+            .with_prompt(t!("directory.copy_config.confirm_overwrite").replace("{}", &target.name))
+            .default(false)
+            .interact()?
+    {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let include_claude_md = Confirm::new()
+        .with_prompt(t!("directory.copy_config.include_claude_md"))
+        .default(false)
+        .interact()?;
+
+    let result = target_manager.copy_env_from(&source_manager, include_claude_md);
+    recorder.flush(&*db.lock().await).await;
+    match result {
+        Ok(report) => {
+            print_success!(
+                "\n{}",
+                t!("directory.copy_config.success")
+                    .replacen("{}", &source.name, 1)
+                    .replacen("{}", &target.name, 1)
+            );
+            if report.env_keys.is_empty() {
+                print_warning!("{}", t!("directory.copy_config.no_env"));
+            } else {
+                println!(
+                    "{}",
+                    t!("directory.copy_config.env_keys").replace("{}", &report.env_keys.join(", "))
+                );
+            }
+            if report.claude_local_md_copied {
+                println!("{}", t!("directory.copy_config.claude_md_copied"));
+            } else if include_claude_md {
+                print_warning!("{}", t!("directory.copy_config.claude_md_missing"));
+            }
+        }
+        Err(e) => print_error!("\n{}", t!("directory.copy_config.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 将旧的配置文件合并进 settings.local.json，避免读取与写入的不是同一个文件
+async fn merge_settings_files(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        directories
+            .iter()
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+    );
+
+    let selection = FuzzySelect::new()
+        .with_prompt(t!("directory.merge_settings.prompt"))
+        .items(&items)
+        .interact_opt()?;
+
+    let directory = match selection {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+    if refuse_if_locked(directory) {
+        return Ok(());
+    }
+    if !confirm_claude_dir_target(&directory.resolved_path(&workspace_root)).await? {
+        return Ok(());
+    }
+
+    let manager = ClaudeConfigManager::new(directory.resolved_path(&workspace_root))
+        .with_indent(settings::load_indent().await);
+    let stale = manager.stale_settings_files();
+    if stale.is_empty() {
+        print_warning!("\n{}", t!("directory.merge_settings.none"));
+        return Ok(());
+    }
+
+    println!("\n{}", t!("directory.merge_settings.found").cyan());
+    for path in &stale {
+        println!("  {}", path.display());
+    }
+
+    if !Confirm::new()
+        .with_prompt(t!("directory.merge_settings.confirm"))
+        .default(false)
+        .interact()?
+    {
+        return Ok(());
+    }
+
+    match manager.merge_stale_settings() {
+        Ok(merged) => print_success!(
+            "\n{}",
+            t!("directory.merge_settings.success")
+                .replace("{}", &merged.len().to_string())
+        ),
+        Err(e) => print_error!("\n{}", t!("directory.merge_settings.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 从剪贴板读取 `KEY=VALUE` 行，预览（值已遮盖）并确认后合并到所选目录的 env
+async fn merge_env_from_clipboard(db: &DbState) -> Result<()> {
+    let text = match crate::clipboard::read_text() {
+        Ok(text) => text,
+        Err(e) => {
+            print_error!("\n{}", t!("directory.clipboard_env.read_failed").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+    let vars = match claude_config::parse_env_block(&text) {
+        Ok(vars) if !vars.is_empty() => vars,
+        Ok(_) => {
+            print_warning!("\n{}", t!("directory.clipboard_env.empty"));
+            return Ok(());
+        }
+        Err(line) => {
+            print_error!("\n{}", t!("directory.clipboard_env.invalid").replace("{}", &line.to_string()));
+            return Ok(());
+        }
+    };
+
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    println!("\n{}", t!("directory.clipboard_env.found").replace("{}", &vars.len().to_string()).cyan());
+    for (key, value) in &vars {
+        println!("  {}={}", key, crate::connectivity::mask_token(value.as_str().unwrap_or_default()));
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        directories
+            .iter()
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+    );
+
+    let directory = match FuzzySelect::new()
+        .with_prompt(t!("directory.clipboard_env.prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+    if refuse_if_locked(directory) {
+        return Ok(());
+    }
+    if !confirm_claude_dir_target(&directory.resolved_path(&workspace_root)).await? {
+        return Ok(());
+    }
+
+    if !Confirm::new()
+        .with_prompt(t!("directory.clipboard_env.confirm").replace("{}", &directory.name))
+        .default(false)
+        .interact()?
+    {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let recorder = ChangeRecorder::new(directory.id);
+    let manager = ClaudeConfigManager::new(directory.resolved_path(&workspace_root))
+        .with_change_hook(recorder.hook())
+        .with_write_format(settings::load_write_format().await)
+        .with_indent(settings::load_indent().await)
+        .with_omit_empty_env(settings::load_omit_empty_env().await);
+    let result = manager.merge_env_vars(&vars);
+    recorder.flush(&*db.lock().await).await;
+    match result {
+        Ok(()) => print_success!(
+            "\n{}",
+            t!("directory.clipboard_env.success")
+                .replacen("{}", &vars.len().to_string(), 1)
+                .replacen("{}", &directory.name, 1)
+        ),
+        Err(e) => print_error!("\n{}", t!("directory.clipboard_env.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 对比各目录的 CLAUDE.local.md 与模板，可选择刷新过时（未修改过）的文件；修改过的文件不会被覆盖
+async fn claude_md_report(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    // 账号设置了自定义模板时，以该模板作为比较基准
+    let workspace_root = settings::load_workspace_root().await;
+    let mut managers = Vec::with_capacity(directories.len());
+    for directory in &directories {
+        let template = db_lock
+            .get_directory_account(directory.id)
+            .await?
+            .and_then(|account| account.claude_md_template);
+        let manager = ClaudeConfigManager::new(directory.resolved_path(&workspace_root)).with_claude_local_md(template);
+        managers.push((directory, manager));
+    }
+    drop(db_lock);
+
+    let mut table = super::create_table();
+    table.set_header(vec![
+        Cell::new(t!("directory.list.header_name")).add_attribute(Attribute::Bold).fg(Color::Cyan),
+        Cell::new(t!("directory.list.header_path")).add_attribute(Attribute::Bold).fg(Color::Cyan),
+        Cell::new(t!("directory.claude_md_report.header_status")).add_attribute(Attribute::Bold).fg(Color::Cyan),
+    ]);
+    let mut outdated = Vec::new();
+    for (directory, manager) in &managers {
+        let (label, color) = match manager.claude_local_md_status() {
+            Ok(ClaudeMdStatus::Identical) => (t!("directory.claude_md_report.identical").to_string(), Color::Green),
+            Ok(ClaudeMdStatus::Outdated) => {
+                outdated.push((*directory, manager));
+                (t!("directory.claude_md_report.outdated").to_string(), Color::Yellow)
+            }
+            Ok(ClaudeMdStatus::Modified) => (t!("directory.claude_md_report.modified").to_string(), Color::Magenta),
+            Ok(ClaudeMdStatus::Missing) => (t!("directory.claude_md_report.missing").to_string(), Color::DarkGrey),
+            Err(e) => (t!("directory.claude_md_report.error").replace("{}", &e.to_string()), Color::Red),
+        };
+        table.add_row(vec![
+            Cell::new(&directory.name),
+            Cell::new(directory.resolved_path(&workspace_root)),
+            Cell::new(label).fg(color),
+        ]);
+    }
+    println!("\n{}", table);
+
+    if outdated.is_empty() {
+        return Ok(());
+    }
+    let refresh = Confirm::new()
+        .with_prompt(t!("directory.claude_md_report.confirm_refresh").replace("{}", &outdated.len().to_string()))
+        .default(false)
+        .interact()?;
+    if !refresh {
+        return Ok(());
+    }
+
+    for (directory, manager) in outdated {
+        if directory.is_locked {
+            print_warning!("{}", t!("directory.lock.refused").replace("{}", &directory.name));
+            continue;
+        }
+        match manager.refresh_outdated_claude_local_md() {
+            Ok(true) => println!("  {} {}", output::symbol("✓").green(), directory.name),
+            Ok(false) => {}
+            Err(e) => print_error!(
+                "{}",
+                t!("directory.claude_md_report.refresh_error")
+                    .replacen("{}", &directory.name, 1)
+                    .replacen("{}", &e.to_string(), 1)
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// 提示被跳过的锁定目录
+fn note_locked_skipped(locked: &[&Directory]) {
+    if locked.is_empty() {
+        return;
+    }
+    let names: Vec<&str> = locked.iter().map(|d| d.name.as_str()).collect();
+    print_warning!("{}", t!("directory.active.locked_skipped").replace("{}", &names.join(", ")));
+}
+
+/// 一次取消所有目录的激活状态，锁定的目录保持不变
+async fn deactivate_all_directories(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    let (locked, unlocked): (Vec<&Directory>, Vec<&Directory>) = directories
+        .iter()
+        .filter(|d| d.is_active)
+        .partition(|d| d.is_locked);
+    if unlocked.is_empty() && locked.is_empty() {
+        drop(db_lock);
+        print_warning!("\n{}", t!("directory.active.none_active"));
+        return Ok(());
+    }
+
+    let ids: Vec<i64> = unlocked.iter().map(|d| d.id).collect();
+    let result = db_lock.set_directories_active(&ids, false).await;
+    drop(db_lock);
+    match result {
+        Ok(changed) => {
+            print_success!("\n{}", t!("directory.active.deactivated").replace("{}", &changed.to_string()));
+            note_locked_skipped(&locked);
+        }
+        Err(e) => print_error!("\n{}", t!("directory.active.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 多选目录并设为激活，锁定的目录跳过
+async fn activate_selected_directories(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let items: Vec<String> = directories
+        .iter()
+        .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root)))
+        .collect();
+    let defaults: Vec<bool> = directories.iter().map(|d| d.is_active).collect();
+    let Some(selected) = MultiSelect::new()
+        .with_prompt(t!("directory.active.select_prompt"))
+        .items(&items)
+        .defaults(&defaults)
+        .interact_opt()?
+    else {
+        return Ok(());
+    };
+    if selected.is_empty() {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let (locked, unlocked): (Vec<&Directory>, Vec<&Directory>) =
+        selected.iter().map(|&idx| &directories[idx]).partition(|d| d.is_locked);
+    let ids: Vec<i64> = unlocked.iter().map(|d| d.id).collect();
+    match db.lock().await.set_directories_active(&ids, true).await {
+        Ok(changed) => {
+            print_success!("\n{}", t!("directory.active.activated").replace("{}", &changed.to_string()));
+            note_locked_skipped(&locked);
+        }
+        Err(e) => print_error!("\n{}", t!("directory.active.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 回收站：恢复或永久删除已删除的目录
+async fn trash_menu(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_trashed_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.trash.empty"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(directories.iter().map(|d| {
+        t!("directory.trash.item")
+            .replacen("{}", &d.name, 1)
+            .replacen("{}", &d.path, 1)
+            .replacen("{}", &super::humanize_since(d.deleted_at), 1)
+    }));
+
+    let selection = FuzzySelect::new()
+        .with_prompt(t!("directory.trash.prompt"))
+        .items(&items)
+        .interact_opt()?;
+
+    let directory = match selection {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let actions = vec![
+        t!("common.cancel"),
+        t!("directory.trash.restore"),
+        t!("directory.trash.purge"),
+    ];
+    let action = Select::new()
+        .with_prompt(t!("directory.trash.action_prompt"))
+        .items(&actions)
+        .interact_opt()?;
+
+    let result = match action {
+        Some(1) => db
+            .lock()
+            .await
+            .restore_directory(directory.id)
+            .await
+            .map(|_| t!("directory.trash.restore_success")),
+        Some(2) => {
+            // 永久删除需要二次确认
+            let confirmed = Confirm::new()
+                .with_prompt(t!("directory.trash.purge_confirm").replace("{}", &directory.name))
+                .default(false)
+                .interact()?
+                && Confirm::new()
+                    .with_prompt(t!("directory.trash.purge_confirm_again"))
+                    .default(false)
+                    .interact()?;
+            if !confirmed {
+                return Ok(());
+            }
+            db.lock()
+                .await
+                .purge_directory(directory.id)
+                .await
+                .map(|_| t!("directory.trash.purge_success"))
+        }
+        _ => return Ok(()),
+    };
+
+    match result {
+        Ok(message) => print_success!("\n{}", message),
+        Err(e) => print_error!("\n{}", t!("directory.trash.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 添加目录前的路径检查：决定存储方式并确认路径存在，返回 (保存的路径, 是否相对路径)；
+/// 用户取消时返回 None。`assume_yes` 时不询问：按输入的形式保存，路径不存在直接报错
+pub(crate) fn prepare_directory_path(
+    path: &str,
+    workspace_root: &std::path::Path,
+    assume_yes: bool,
+) -> Result<Option<(String, bool)>> {
+    let (path, is_relative) = choose_path_storage(path, workspace_root, assume_yes)?;
+
+    // 检查路径是否存在
+    let resolved_path = if is_relative {
+        workspace_root.join(&path)
+    } else {
+        std::path::PathBuf::from(&path)
+    };
+    if !resolved_path.exists() {
+        if assume_yes {
+            anyhow::bail!(t!("directory.add.path_not_exists").replace("{}", &resolved_path.display().to_string()));
+        }
+        print_warning!("{}", t!("directory.add.warn_path_not_exists"));
+        if !Confirm::new()
+            .with_prompt(t!("common.confirm"))
+            .default(false)
+            .interact()?
+        {
+            return Ok(None);
+        }
+    }
+
+    Ok(Some((path, is_relative)))
+}
+
+/// 决定目录路径的存储方式：相对路径按工作区根目录保存；位于工作区内的绝对路径可选择转为相对路径
+fn choose_path_storage(path: &str, workspace_root: &std::path::Path, assume_yes: bool) -> Result<(String, bool)> {
+    let path = path.trim();
+    let input = std::path::Path::new(path);
+
+    if input.is_relative() {
+        println!(
+            "{}",
+            t!("directory.add.relative_hint")
+                .replace("{}", &workspace_root.join(input).display().to_string())
+                .cyan()
+        );
+        return Ok((path.to_string(), true));
+    }
+
+    if let Ok(relative) = input.strip_prefix(workspace_root) {
+        if !relative.as_os_str().is_empty()
+            && !assume_yes
+            && Confirm::new()
+                .with_prompt(
+                    t!("directory.add.prompt_relative")
+                        .replace("{}", &workspace_root.display().to_string()),
+                )
+                .default(false)
+                .interact()?
+        {
+            return Ok((relative.to_string_lossy().to_string(), true));
+        }
+    }
+
+    Ok((path.to_string(), false))
+}
+
+/// 在编辑器中打开目录当前生效的配置文件，退出后校验 JSON
+async fn edit_settings_file(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        directories
+            .iter()
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+    );
+
+    let selection = FuzzySelect::new()
+        .with_prompt(t!("directory.edit_settings.prompt"))
+        .items(&items)
+        .interact_opt()?;
+
+    let directory = match selection {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let manager = ClaudeConfigManager::new(directory.resolved_path(&workspace_root));
+    let settings_path = manager.resolve_active_settings_path();
+
+    println!("\n{}", t!("directory.edit_settings.candidates").cyan());
+    for candidate in manager.settings_file_candidates() {
+        let marker = if candidate == settings_path {
+            t!("directory.edit_settings.active").green().to_string()
+        } else if candidate.exists() {
+            t!("directory.edit_settings.exists").to_string()
+        } else {
+            t!("directory.edit_settings.missing").dimmed().to_string()
+        };
+        println!("  {} {}", candidate.display(), marker);
+    }
+
+    if !settings_path.exists() {
+        if let Some(parent) = settings_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&settings_path, "{}")?;
+        print_warning!(
+            "{}",
+            t!("directory.edit_settings.created")
+                .replace("{}", &settings_path.display().to_string())
+        );
+    }
+
+    println!(
+        "{}",
+        t!("directory.edit_settings.opening").replace("{}", &settings_path.display().to_string())
+    );
+
+    if let Err(e) = open_in_editor(&settings_path) {
+        print_error!("\n{}", t!("directory.edit_settings.editor_error").replace("{}", &e.to_string()));
+        return Ok(());
+    }
+
+    // CLAUDE.md 是 Markdown 格式，无需校验；其余按扩展名以 JSON / YAML / TOML 解析
+    let is_settings = settings_path.file_name().is_some_and(|name| name != "CLAUDE.md");
+    if is_settings {
+        let content = std::fs::read_to_string(&settings_path)?;
+        match claude_config_core::settings_format::parse_settings(&settings_path, &content) {
+            Ok(_) => print_success!("\n{}", t!("directory.edit_settings.valid")),
+            Err(e) => print_error!(
+                "\n{}",
+                t!("directory.edit_settings.invalid")
+                    .replace("{}", &e.to_string())
+            ),
+        }
+    }
+
+    Ok(())
+}
+
+/// 将目录配置中字符串形式的 env 改写为对象形式
+async fn repair_settings(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        directories
+            .iter()
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+    );
+
+    let selection = FuzzySelect::new()
+        .with_prompt(t!("directory.repair_settings.prompt"))
+        .items(&items)
+        .interact_opt()?;
+
+    let directory = match selection {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let manager = ClaudeConfigManager::new(directory.resolved_path(&workspace_root))
+        .with_indent(settings::load_indent().await);
+    match manager.repair_settings() {
+        Ok(true) => print_success!("\n{}", t!("directory.repair_settings.repaired")),
+        Ok(false) => print_warning!("\n{}", t!("directory.repair_settings.nothing")),
+        Err(e) => print_error!("\n{}", t!("directory.repair_settings.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 按内置 schema 校验目录当前生效的配置文件
+async fn validate_settings(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        directories
+            .iter()
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+    );
+
+    let selection = FuzzySelect::new()
+        .with_prompt(t!("directory.validate_settings.prompt"))
+        .items(&items)
+        .interact_opt()?;
+
+    let directory = match selection {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let manager = ClaudeConfigManager::new(directory.resolved_path(&workspace_root));
+    println!(
+        "\n{}",
+        t!("directory.validate_settings.file")
+            .replace("{}", &manager.resolve_active_settings_path().display().to_string())
+    );
+
+    match manager.validate_settings() {
+        Ok(issues) if issues.is_empty() => {
+            print_success!("{}", t!("directory.validate_settings.valid"));
+        }
+        Ok(issues) => {
+            print_error!(
+                "{}",
+                t!("directory.validate_settings.issues")
+                    .replace("{}", &issues.len().to_string())
+            );
+            for issue in &issues {
+                println!("  {} {}", output::symbol("✗").red(), issue.message);
+            }
+        }
+        Err(e) => print_error!("{}", t!("directory.validate_settings.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 展示目录会话实际看到的环境变量（全局配置与项目配置叠加后），只读
+async fn show_effective_env(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        directories
+            .iter()
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+    );
+
+    let selection = FuzzySelect::new()
+        .with_prompt(t!("directory.effective_env.prompt"))
+        .items(&items)
+        .interact_opt()?;
+
+    let directory = match selection {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let manager = ClaudeConfigManager::new(directory.resolved_path(&workspace_root));
+    let entries = match manager.effective_env() {
+        Ok(entries) => entries,
+        Err(e) => {
+            print_error!("\n{}", t!("directory.effective_env.error").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+
+    if entries.is_empty() {
+        print_warning!("\n{}", t!("directory.effective_env.empty"));
+        return Ok(());
+    }
+
+    let project_dir = manager.claude_dir_path();
+    let mut table = super::create_table();
+    table.set_header(vec![
+        Cell::new(t!("directory.effective_env.key"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("directory.effective_env.value"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("directory.effective_env.source"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+    ]);
+
+    // 项目配置中未设置、由全局配置提供的变量
+    let from_global = entries
+        .iter()
+        .filter(|entry| entry.source.file().is_some_and(|file| !file.starts_with(&project_dir)))
+        .count();
+    // 被进程环境变量覆盖的配置项
+    let masked: Vec<&str> = entries
+        .iter()
+        .filter(|entry| entry.masked_value.is_some())
+        .map(|entry| entry.key.as_str())
+        .collect();
+
+    for entry in &entries {
+        // 敏感变量只显示指纹
+        let value = if claude_config::SECRET_ENV_KEYS.contains(&entry.key.as_str()) {
+            claude_config::token_fingerprint(&entry.value)
+        } else {
+            entry.value.clone()
+        };
+        let source = match entry.source.file() {
+            Some(file) if file.starts_with(&project_dir) => {
+                format!("{} ({})", t!("directory.effective_env.project"), file.display())
+            }
+            Some(file) => format!("{} ({})", t!("directory.effective_env.global"), file.display()),
+            None => t!("directory.effective_env.process").to_string(),
+        };
+        let color = match entry.source.file() {
+            Some(file) if file.starts_with(&project_dir) => Color::Green,
+            Some(_) => Color::Yellow,
+            None => Color::Magenta,
+        };
+
+        table.add_row(vec![
+            Cell::new(&entry.key),
+            Cell::new(value),
+            Cell::new(source).fg(color),
+        ]);
+    }
+
+    println!("\n{}", table);
+    if !masked.is_empty() {
+        println!(
+            "{}",
+            t!("directory.effective_env.masked_note")
+                .replace("{}", &masked.join(", "))
+                .magenta()
+        );
+    }
+    if from_global > 0 {
+        print_warning!(
+            "{}",
+            t!("directory.effective_env.global_note")
+                .replace("{}", &from_global.to_string())
+        );
+    }
+
+    let _ = Input::<String>::new()
+        .with_prompt(t!("common.continue"))
+        .allow_empty(true)
+        .interact()?;
+
+    Ok(())
+}
+
+/// 查看目录的配置变更历史（只显示配置文件哈希）
+async fn show_change_history(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        directories
+            .iter()
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+    );
+
+    let directory = match FuzzySelect::new()
+        .with_prompt(t!("directory.history.prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let entries = db.lock().await.get_config_audit(directory.id).await?;
+    if entries.is_empty() {
+        print_warning!("\n{}", t!("directory.history.empty"));
+        return Ok(());
+    }
+
+    // 哈希只显示前 12 位，文件不存在时显示 -
+    let short_hash = |hash: &Option<String>| match hash {
+        Some(hash) => hash.chars().take(12).collect(),
+        None => "-".to_string(),
+    };
+
+    let mut table = super::create_table();
+    table.set_header(vec![
+        Cell::new(t!("directory.history.header_time"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("directory.history.header_action"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("directory.history.header_before"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("directory.history.header_after"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("directory.history.header_base_url"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("directory.history.header_actor"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+    ]);
+
+    for entry in &entries {
+        let (action, color) = match entry.action.parse::<ConfigAction>() {
+            Ok(ConfigAction::Apply) => (t!("directory.history.action_apply"), Color::Green),
+            Ok(ConfigAction::Clear) => (t!("directory.history.action_clear"), Color::Yellow),
+            Ok(ConfigAction::Reset) => (t!("directory.history.action_reset"), Color::Red),
+            Err(_) => (entry.action.as_str(), Color::White),
+        };
+        table.add_row(vec![
+            Cell::new(
+                entry
+                    .created_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S"),
+            ),
+            Cell::new(action).fg(color),
+            Cell::new(short_hash(&entry.before_hash)),
+            Cell::new(short_hash(&entry.after_hash)),
+            Cell::new(entry.base_url.as_deref().unwrap_or("-")),
+            Cell::new(&entry.actor),
+        ]);
+    }
+
+    println!(
+        "\n{}",
+        t!("directory.history.title").replace("{}", &directory.name).cyan()
+    );
+    println!("{}", table);
+
+    let _ = Input::<String>::new()
+        .with_prompt(t!("common.continue"))
+        .allow_empty(true)
+        .interact()?;
+
+    Ok(())
+}
+
+/// 使用 $VISUAL / $EDITOR 打开文件，未设置时使用平台默认编辑器，并等待编辑器退出
+pub(super) fn open_in_editor(path: &std::path::Path) -> Result<()> {
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .ok()
+        .filter(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| default_editor().to_string());
+
+    // 支持带参数的编辑器命令，例如 "code --wait"
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or_default();
+
+    let status = std::process::Command::new(program)
+        .args(parts)
+        .arg(path)
+        .status()
+        .with_context(|| format!("无法启动编辑器: {}", program))?;
+
+    if !status.success() {
+        anyhow::bail!("编辑器退出状态: {}", status);
+    }
+
+    Ok(())
+}
+
+fn default_editor() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "notepad"
+    } else if cfg!(target_os = "macos") {
+        "open -W -t"
+    } else {
+        "vi"
+    }
+}
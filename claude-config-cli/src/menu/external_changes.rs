@@ -0,0 +1,219 @@
+use crate::{connectivity::mask_token, models::AuthStyle, print_success, print_warning, t, DbState};
+use anyhow::Result;
+use claude_config_core::audit::file_hash;
+use claude_config_core::diff::{line_diff, DiffLine};
+use colored::Colorize;
+use comfy_table::{Attribute, Cell, Color};
+use dialoguer::{Input, Select};
+use std::collections::HashMap;
+
+/// 配置文件与本工具最后一次写入时的对比结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DiskState {
+    Unchanged,
+    Modified,
+    /// 本工具写入后文件被删除
+    Deleted,
+    /// 本工具最后一次操作删除了文件，之后又被重新创建
+    Created,
+    /// 从未通过本工具写入
+    NoRecord,
+}
+
+impl DiskState {
+    fn classify(recorded: Option<&str>, current: Option<&str>) -> Self {
+        match (recorded, current) {
+            (Some(recorded), Some(current)) if recorded == current => DiskState::Unchanged,
+            (Some(_), Some(_)) => DiskState::Modified,
+            (Some(_), None) => DiskState::Deleted,
+            (None, Some(_)) => DiskState::Created,
+            (None, None) => DiskState::Unchanged,
+        }
+    }
+
+    fn label(&self) -> (&'static str, Color) {
+        match self {
+            DiskState::Unchanged => (t!("external.state.unchanged"), Color::Green),
+            DiskState::Modified => (t!("external.state.modified"), Color::Red),
+            DiskState::Deleted => (t!("external.state.deleted"), Color::Red),
+            DiskState::Created => (t!("external.state.created"), Color::Yellow),
+            DiskState::NoRecord => (t!("external.state.no_record"), Color::DarkGrey),
+        }
+    }
+
+    fn is_changed(&self) -> bool {
+        matches!(self, DiskState::Modified | DiskState::Deleted | DiskState::Created)
+    }
+}
+
+/// 外部修改的目录，保存查看差异需要的内容
+struct ChangedDirectory {
+    name: String,
+    settings_path: String,
+    /// 最后一次写入的快照，文件过大或写入后文件不存在时为 None
+    snapshot: Option<String>,
+    recorded: bool,
+}
+
+/// 检测外部修改：重新计算每个目录配置文件的哈希，与本工具最后一次写入时记录的哈希比较
+pub async fn detect_external_changes(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    let snapshots: HashMap<i64, _> = db_lock
+        .get_settings_snapshots()
+        .await?
+        .into_iter()
+        .map(|snapshot| (snapshot.directory_id, snapshot))
+        .collect();
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let mut table = super::create_table();
+    table.set_header(
+        [
+            t!("external.header.name"),
+            t!("external.header.state"),
+            t!("external.header.written_at"),
+            t!("external.header.path"),
+        ]
+        .into_iter()
+        .map(|header| Cell::new(header).add_attribute(Attribute::Bold).fg(Color::Cyan)),
+    );
+
+    let mut changed = Vec::new();
+    for directory in &directories {
+        let Some(snapshot) = snapshots.get(&directory.id) else {
+            let (label, color) = DiskState::NoRecord.label();
+            table.add_row(vec![
+                Cell::new(&directory.name),
+                Cell::new(label).fg(color),
+                Cell::new("-"),
+                Cell::new("-"),
+            ]);
+            continue;
+        };
+
+        let current = file_hash(&snapshot.settings_path);
+        let state = DiskState::classify(snapshot.content_hash.as_deref(), current.as_deref());
+        let (label, color) = state.label();
+        table.add_row(vec![
+            Cell::new(&directory.name),
+            Cell::new(label).fg(color),
+            Cell::new(
+                snapshot
+                    .updated_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S"),
+            ),
+            Cell::new(&snapshot.settings_path),
+        ]);
+
+        if state.is_changed() {
+            changed.push(ChangedDirectory {
+                name: directory.name.clone(),
+                settings_path: snapshot.settings_path.clone(),
+                snapshot: snapshot.content.clone(),
+                recorded: snapshot.content_hash.is_some(),
+            });
+        }
+    }
+
+    println!("\n{}", table);
+
+    if changed.is_empty() {
+        print_success!("\n{}", t!("external.none"));
+        let _ = Input::<String>::new()
+            .with_prompt(t!("common.continue"))
+            .allow_empty(true)
+            .interact()?;
+        return Ok(());
+    }
+
+    print_warning!("\n{}", t!("external.summary").replace("{}", &changed.len().to_string()));
+
+    let mut items: Vec<String> = vec![t!("common.back").to_string()];
+    items.extend(changed.iter().map(|c| c.name.clone()));
+    loop {
+        let selection = match Select::new()
+            .with_prompt(t!("external.diff_prompt"))
+            .items(&items)
+            .default(0)
+            .interact_opt()?
+        {
+            Some(idx) if idx > 0 => &changed[idx - 1],
+            _ => break,
+        };
+        print_diff(selection);
+    }
+
+    Ok(())
+}
+
+fn print_diff(changed: &ChangedDirectory) {
+    // 写入后文件存在但快照缺失，说明文件超过大小上限或含有命令获取的 Token，无法比较内容
+    let old = match (&changed.snapshot, changed.recorded) {
+        (Some(snapshot), _) => snapshot.as_str(),
+        (None, false) => "",
+        (None, true) => {
+            print_warning!("\n{}", t!("external.no_snapshot"));
+            return;
+        }
+    };
+    let new = std::fs::read_to_string(&changed.settings_path).unwrap_or_default();
+
+    println!(
+        "\n{}",
+        t!("external.diff_title")
+            .replace("{}", &changed.settings_path)
+            .cyan()
+    );
+    print_masked_diff(old, &new);
+}
+
+/// 输出两段配置内容的行级差异，Token 值已遮盖
+pub(crate) fn print_masked_diff(old: &str, new: &str) {
+    for line in line_diff(old, new) {
+        match line {
+            DiffLine::Same(text) => println!("  {}", mask_secret_line(text)),
+            DiffLine::Removed(text) => println!("{}", format!("- {}", mask_secret_line(text)).red()),
+            DiffLine::Added(text) => println!("{}", format!("+ {}", mask_secret_line(text)).green()),
+        }
+    }
+}
+
+/// 遮盖包含 Token 变量的行中的值，差异输出可能会被截图或分享
+fn mask_secret_line(line: &str) -> String {
+    if !AuthStyle::Both.env_keys().iter().any(|key| line.contains(key)) {
+        return line.to_string();
+    }
+    let Some(index) = line.find([':', '=']) else {
+        return line.to_string();
+    };
+    let (head, value) = line.split_at(index + 1);
+    let trailing = if value.trim_end().ends_with(',') { "," } else { "" };
+    let token = value.trim().trim_end_matches(',').trim_matches(|c| c == '"' || c == '\'');
+    format!("{} \"{}\"{}", head, mask_token(token), trailing)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_secret_line_hides_tokens() {
+        assert_eq!(
+            mask_secret_line(r#"    "ANTHROPIC_AUTH_TOKEN": "sk-ant-1234567890abcd","#),
+            r#"    "ANTHROPIC_AUTH_TOKEN": "sk-ant****abcd","#
+        );
+        assert_eq!(
+            mask_secret_line("  ANTHROPIC_API_KEY = 'sk-ant-1234567890abcd'"),
+            r#"  ANTHROPIC_API_KEY = "sk-ant****abcd""#
+        );
+        let plain = r#"    "ANTHROPIC_BASE_URL": "https://api.anthropic.com","#;
+        assert_eq!(mask_secret_line(plain), plain);
+    }
+}
@@ -0,0 +1,184 @@
+use crate::{
+    claude_config::{self, ClaudeConfigManager},
+    print_error, print_success, print_warning,
+    t,
+};
+use anyhow::Result;
+use colored::Colorize;
+use comfy_table::{Attribute, Cell, Color};
+use dialoguer::{Confirm, FuzzySelect, Input, Select};
+
+/// 全局配置菜单：管理 ~/.claude/settings.json 中的环境变量（项目未设置的变量由这里提供）
+pub async fn global_menu() -> Result<()> {
+    let Some(manager) = ClaudeConfigManager::global() else {
+        print_error!("\n{}", t!("global.no_home"));
+        return Ok(());
+    };
+    let manager = manager
+        .with_indent(crate::settings::load_indent().await)
+        .with_omit_empty_env(crate::settings::load_omit_empty_env().await);
+
+    let mut last_selection = 0;
+
+    loop {
+        println!(
+            "\n{}",
+            t!("global.file")
+                .replace("{}", &manager.settings_file_path().display().to_string())
+                .cyan()
+        );
+
+        let items = vec![
+            t!("common.back"),
+            t!("global.menu.list"),
+            t!("global.menu.set"),
+            t!("global.menu.remove"),
+            t!("global.menu.clear"),
+        ];
+
+        let selection = match Select::new()
+            .with_prompt(format!("\n{} (ESC {})", t!("global.menu.title"), t!("common.to_back")))
+            .items(&items)
+            .default(last_selection)
+            .interact_opt()? {
+                Some(sel) => sel,
+                None => break, // 用户按了ESC，返回上一级
+            };
+
+        last_selection = selection;
+
+        match selection {
+            0 => break,
+            1 => list_global_env(&manager)?,
+            2 => set_global_env(&manager)?,
+            3 => remove_global_env(&manager)?,
+            4 => clear_global_env(&manager)?,
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
+
+/// 读取全局 env，按变量名排序
+fn sorted_env(manager: &ClaudeConfigManager) -> Option<Vec<(String, String)>> {
+    match manager.get_env_config() {
+        Ok(env) => {
+            let mut entries: Vec<(String, String)> = env.into_iter().collect();
+            entries.sort();
+            Some(entries)
+        }
+        Err(e) => {
+            print_error!("\n{}", t!("global.error").replace("{}", &e.to_string()));
+            None
+        }
+    }
+}
+
+fn list_global_env(manager: &ClaudeConfigManager) -> Result<()> {
+    let Some(entries) = sorted_env(manager) else {
+        return Ok(());
+    };
+
+    if entries.is_empty() {
+        print_warning!("\n{}", t!("global.list.empty"));
+        return Ok(());
+    }
+
+    let mut table = super::create_table();
+    table.set_header(vec![
+        Cell::new(t!("directory.effective_env.key"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("directory.effective_env.value"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+    ]);
+
+    for (key, value) in &entries {
+        // 敏感变量只显示指纹
+        let value = if claude_config::SECRET_ENV_KEYS.contains(&key.as_str()) {
+            claude_config::token_fingerprint(value)
+        } else {
+            value.clone()
+        };
+        table.add_row(vec![Cell::new(key), Cell::new(value)]);
+    }
+
+    println!("\n{}", table);
+    Ok(())
+}
+
+fn set_global_env(manager: &ClaudeConfigManager) -> Result<()> {
+    print_warning!("{}", t!("common.input_cancel_hint"));
+
+    let key: String = Input::new()
+        .with_prompt(t!("global.set.prompt_key"))
+        .allow_empty(true)
+        .interact_text()?;
+    let key = key.trim();
+    if key.is_empty() {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let value: String = Input::new()
+        .with_prompt(t!("global.set.prompt_value"))
+        .allow_empty(true)
+        .interact_text()?;
+
+    match manager.set_env_var(key, value.trim()) {
+        Ok(()) => print_success!("\n{}", t!("global.set.success").replace("{}", key)),
+        Err(e) => print_error!("\n{}", t!("global.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+fn remove_global_env(manager: &ClaudeConfigManager) -> Result<()> {
+    let Some(entries) = sorted_env(manager) else {
+        return Ok(());
+    };
+
+    if entries.is_empty() {
+        print_warning!("\n{}", t!("global.list.empty"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(entries.iter().map(|(key, _)| key.clone()));
+
+    let key = match FuzzySelect::new()
+        .with_prompt(t!("global.remove.prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &entries[idx - 1].0,
+        _ => return Ok(()),
+    };
+
+    match manager.remove_env_var(key) {
+        Ok(_) => print_success!("\n{}", t!("global.remove.success").replace("{}", key)),
+        Err(e) => print_error!("\n{}", t!("global.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+fn clear_global_env(manager: &ClaudeConfigManager) -> Result<()> {
+    if !Confirm::new()
+        .with_prompt(t!("global.clear.confirm"))
+        .default(false)
+        .interact()?
+    {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    match manager.clear_env_config() {
+        Ok(_) => print_success!("\n{}", t!("global.clear.success")),
+        Err(e) => print_error!("\n{}", t!("global.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::{logger::Logger, t};
+use crate::{logger::Logger, print_error, print_success, print_warning, settings, t};
 use anyhow::Result;
 use colored::Colorize;
 use dialoguer::{Input, Select};
@@ -46,16 +46,29 @@ async fn view_recent_logs() -> Result<()> {
     match Logger::get_recent_logs(Some(lines)) {
         Ok(logs) => {
             if logs.is_empty() {
-                println!("\n{}", t!("logs.no_records").yellow());
+                print_warning!("\n{}", t!("logs.no_records"));
             } else {
-                println!("\n{}", t!("logs.title").green().bold());
-                for log in logs {
-                    println!("{}", log);
+                let page_size = settings::load_page_size().await;
+                let pages = super::page_count(logs.len(), page_size);
+                let mut page = 0;
+                loop {
+                    println!("\n{}", t!("logs.title").green().bold());
+                    for log in &logs[super::page_range(page, page_size, logs.len())] {
+                        println!("{}", log);
+                    }
+                    match super::prompt_page(page, pages, logs.len())? {
+                        Some(next) => page = next,
+                        None => break,
+                    }
+                }
+                // 多页时翻页菜单中的"退出"即结束浏览
+                if pages > 1 {
+                    return Ok(());
                 }
             }
         }
         Err(e) => {
-            println!("{}", t!("logs.read.error").replace("{}", &e.to_string()).red());
+            print_error!("{}", t!("logs.read.error").replace("{}", &e.to_string()));
         }
     }
 
@@ -82,7 +95,7 @@ async fn show_log_info() -> Result<()> {
             }
         }
         Err(e) => {
-            println!("{}", t!("logs.info.error").replace("{}", &e.to_string()).red());
+            print_error!("{}", t!("logs.info.error").replace("{}", &e.to_string()));
         }
     }
 
@@ -103,29 +116,29 @@ async fn open_log_directory() -> Result<()> {
             #[cfg(target_os = "linux")]
             {
                 match std::process::Command::new("xdg-open").arg(&log_dir).spawn() {
-                    Ok(_) => println!("{}", t!("logs.directory_opened").green()),
-                    Err(e) => println!("{}", t!("logs.open_dir.error").replace("{}", &e.to_string()).red()),
+                    Ok(_) => print_success!("{}", t!("logs.directory_opened")),
+                    Err(e) => print_error!("{}", t!("logs.open_dir.error").replace("{}", &e.to_string())),
                 }
             }
 
             #[cfg(target_os = "windows")]
             {
                 match std::process::Command::new("explorer").arg(&log_dir).spawn() {
-                    Ok(_) => println!("{}", t!("logs.directory_opened").green()),
-                    Err(e) => println!("{}", t!("logs.open_dir.error").replace("{}", &e.to_string()).red()),
+                    Ok(_) => print_success!("{}", t!("logs.directory_opened")),
+                    Err(e) => print_error!("{}", t!("logs.open_dir.error").replace("{}", &e.to_string())),
                 }
             }
 
             #[cfg(target_os = "macos")]
             {
                 match std::process::Command::new("open").arg(&log_dir).spawn() {
-                    Ok(_) => println!("{}", t!("logs.directory_opened").green()),
-                    Err(e) => println!("{}", t!("logs.open_dir.error").replace("{}", &e.to_string()).red()),
+                    Ok(_) => print_success!("{}", t!("logs.directory_opened")),
+                    Err(e) => print_error!("{}", t!("logs.open_dir.error").replace("{}", &e.to_string())),
                 }
             }
         }
         Err(e) => {
-            println!("{}", t!("logs.directory.error").replace("{}", &e.to_string()).red());
+            print_error!("{}", t!("logs.directory.error").replace("{}", &e.to_string()));
         }
     }
 
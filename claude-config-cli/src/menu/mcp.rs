@@ -0,0 +1,260 @@
+use crate::{
+    change_log::ChangeRecorder,
+    claude_config::{ClaudeConfigManager, McpServer},
+    models::Directory,
+    print_error, print_success, print_warning,
+    settings, t, DbState,
+};
+use anyhow::Result;
+use colored::Colorize;
+use comfy_table::{Attribute, Cell, Color};
+use dialoguer::{Confirm, FuzzySelect, Input, Select};
+
+/// MCP 服务器菜单：管理目录配置中 mcpServers 下的服务器定义
+pub async fn mcp_menu(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        directories
+            .iter()
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+    );
+
+    let directory = match FuzzySelect::new()
+        .with_prompt(t!("mcp.select_directory"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let recorder = ChangeRecorder::new(directory.id);
+    let manager = ClaudeConfigManager::new(directory.resolved_path(&workspace_root))
+        .with_change_hook(recorder.hook())
+        .with_write_format(settings::load_write_format().await)
+        .with_indent(settings::load_indent().await);
+
+    let mut last_selection = 0;
+    loop {
+        let items = vec![
+            t!("common.back"),
+            t!("mcp.menu.list"),
+            t!("mcp.menu.set"),
+            t!("mcp.menu.remove"),
+        ];
+
+        let selection = match Select::new()
+            .with_prompt(format!(
+                "\n{} - {} (ESC {})",
+                t!("mcp.menu.title"),
+                directory.name,
+                t!("common.to_back")
+            ))
+            .items(&items)
+            .default(last_selection)
+            .interact_opt()?
+        {
+            Some(sel) => sel,
+            None => break,
+        };
+
+        last_selection = selection;
+
+        match selection {
+            0 => break,
+            1 => list_servers(&manager)?,
+            2 => set_server(&manager, directory)?,
+            3 => remove_server(&manager, directory)?,
+            _ => unreachable!(),
+        }
+        recorder.flush(&*db.lock().await).await;
+    }
+
+    Ok(())
+}
+
+fn list_servers(manager: &ClaudeConfigManager) -> Result<()> {
+    let servers = match manager.get_mcp_servers() {
+        Ok(servers) => servers,
+        Err(e) => {
+            print_error!("\n{}", t!("mcp.error").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+
+    if servers.is_empty() {
+        print_warning!("\n{}", t!("mcp.list.empty"));
+        return Ok(());
+    }
+
+    let mut table = super::create_table();
+    table.set_header(
+        [
+            t!("mcp.header.name"),
+            t!("mcp.header.command"),
+            t!("mcp.header.args"),
+            t!("mcp.header.env"),
+            t!("mcp.header.other"),
+        ]
+        .into_iter()
+        .map(|header| Cell::new(header).add_attribute(Attribute::Bold).fg(Color::Cyan)),
+    );
+
+    for (name, server) in &servers {
+        // env 中可能包含密钥，只显示变量名
+        let env_keys: Vec<&str> = server.env.keys().map(String::as_str).collect();
+        let other_keys: Vec<&str> = server.extra.keys().map(String::as_str).collect();
+        table.add_row(vec![
+            name.clone(),
+            server.command.clone(),
+            server.args.join(" "),
+            env_keys.join("\n"),
+            other_keys.join(", "),
+        ]);
+    }
+
+    println!("\n{}", table);
+    Ok(())
+}
+
+/// 新增 MCP 服务器；名称已存在时编辑，未编辑的字段保持不变
+fn set_server(manager: &ClaudeConfigManager, directory: &Directory) -> Result<()> {
+    if super::directory::refuse_if_locked(directory) {
+        return Ok(());
+    }
+
+    print_warning!("{}", t!("common.input_cancel_hint"));
+    let name: String = Input::new()
+        .with_prompt(t!("mcp.set.prompt_name"))
+        .allow_empty(true)
+        .interact_text()?;
+    let name = name.trim();
+    if name.is_empty() {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let mut server = match manager.get_mcp_servers() {
+        Ok(mut servers) => servers.remove(name).unwrap_or_default(),
+        Err(e) => {
+            print_error!("\n{}", t!("mcp.error").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+    if !server.command.is_empty() || !server.extra.is_empty() {
+        println!("{}", t!("mcp.set.editing").replace("{}", name).cyan());
+    }
+
+    let command: String = Input::new()
+        .with_prompt(t!("mcp.set.prompt_command"))
+        .default(server.command.clone())
+        .allow_empty(true)
+        .interact_text()?;
+    server.command = command.trim().to_string();
+
+    let args: String = Input::new()
+        .with_prompt(t!("mcp.set.prompt_args"))
+        .default(server.args.join(" "))
+        .allow_empty(true)
+        .interact_text()?;
+    server.args = args.split_whitespace().map(str::to_string).collect();
+
+    prompt_env(&mut server)?;
+
+    if server.command.is_empty() && server.extra.is_empty() {
+        print_warning!("\n{}", t!("mcp.set.command_required"));
+        return Ok(());
+    }
+
+    match manager.set_mcp_server(name, &server) {
+        Ok(()) => print_success!("\n{}", t!("mcp.set.success").replace("{}", name)),
+        Err(e) => print_error!("\n{}", t!("mcp.error").replace("{}", &e.to_string())),
+    }
+    Ok(())
+}
+
+/// 逐个输入 KEY=VALUE 修改服务器的环境变量，KEY= 删除变量，空行结束
+fn prompt_env(server: &mut McpServer) -> Result<()> {
+    if !server.env.is_empty() {
+        let keys: Vec<&str> = server.env.keys().map(String::as_str).collect();
+        println!("{}", t!("mcp.set.current_env").replace("{}", &keys.join(", ")));
+    }
+    println!("{}", t!("mcp.set.env_hint"));
+
+    loop {
+        let line: String = Input::new()
+            .with_prompt(t!("mcp.set.prompt_env"))
+            .allow_empty(true)
+            .interact_text()?;
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(());
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            print_warning!("{}", t!("mcp.set.env_invalid"));
+            continue;
+        };
+        let key = key.trim();
+        if key.is_empty() {
+            print_warning!("{}", t!("mcp.set.env_invalid"));
+        } else if value.is_empty() {
+            server.env.remove(key);
+        } else {
+            server.env.insert(key.to_string(), value.to_string());
+        }
+    }
+}
+
+fn remove_server(manager: &ClaudeConfigManager, directory: &Directory) -> Result<()> {
+    if super::directory::refuse_if_locked(directory) {
+        return Ok(());
+    }
+
+    let names: Vec<String> = match manager.get_mcp_servers() {
+        Ok(servers) => servers.into_keys().collect(),
+        Err(e) => {
+            print_error!("\n{}", t!("mcp.error").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+    if names.is_empty() {
+        print_warning!("\n{}", t!("mcp.list.empty"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(names.iter().cloned());
+    let name = match FuzzySelect::new()
+        .with_prompt(t!("mcp.remove.prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &names[idx - 1],
+        _ => return Ok(()),
+    };
+
+    if !Confirm::new()
+        .with_prompt(t!("mcp.remove.confirm").replace("{}", name))
+        .default(false)
+        .interact()?
+    {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    match manager.remove_mcp_server(name) {
+        Ok(_) => print_success!("\n{}", t!("mcp.remove.success").replace("{}", name)),
+        Err(e) => print_error!("\n{}", t!("mcp.error").replace("{}", &e.to_string())),
+    }
+    Ok(())
+}
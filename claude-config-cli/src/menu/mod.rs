@@ -1,15 +1,134 @@
 pub mod account;
 pub mod base_url;
 pub mod directory;
+pub mod external_changes;
+pub mod global;
 pub mod logs;
+pub mod mcp;
+pub mod model;
+pub mod profile;
 pub mod settings;
+pub mod setup;
 pub mod switch;
+pub mod template;
 pub mod webdav;
 
+use crate::{print_warning, t};
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use colored::Colorize;
 use comfy_table::{presets::UTF8_FULL, Table};
+use dialoguer::{Confirm, Input, Select};
 
 pub fn create_table() -> Table {
     let mut table = Table::new();
     table.load_preset(UTF8_FULL);
+    // 无颜色模式下不输出单元格颜色
+    if crate::output::no_color() {
+        table.force_no_tty();
+    }
     table
 }
+
+/// 分页的总页数，至少为 1 页
+pub fn page_count(total: usize, page_size: usize) -> usize {
+    total.div_ceil(page_size.max(1)).max(1)
+}
+
+/// 第 `page` 页（从 0 开始）在列表中的范围
+pub fn page_range(page: usize, page_size: usize, total: usize) -> std::ops::Range<usize> {
+    let page_size = page_size.max(1);
+    let start = (page * page_size).min(total);
+    start..(start + page_size).min(total)
+}
+
+/// 输出分页信息并选择翻页，返回要显示的页码（从 0 开始），None 表示退出
+///
+/// 只有一页时只输出总数，直接返回 None
+pub fn prompt_page(page: usize, pages: usize, total: usize) -> Result<Option<usize>> {
+    let info = t!("pager.info")
+        .replacen("{}", &(page + 1).to_string(), 1)
+        .replacen("{}", &pages.to_string(), 1)
+        .replacen("{}", &total.to_string(), 1);
+    println!("{}", info.cyan());
+    if pages <= 1 {
+        return Ok(None);
+    }
+
+    let mut choices = Vec::new();
+    if page + 1 < pages {
+        choices.push((t!("pager.next"), page + 1));
+    }
+    if page > 0 {
+        choices.push((t!("pager.prev"), page - 1));
+    }
+    let items: Vec<&str> = choices
+        .iter()
+        .map(|(label, _)| *label)
+        .chain(std::iter::once(t!("pager.quit")))
+        .collect();
+
+    let selection = Select::new()
+        .with_prompt(t!("pager.prompt"))
+        .items(&items)
+        .default(0)
+        .interact_opt()?;
+    Ok(selection.and_then(|idx| choices.get(idx).map(|(_, target)| *target)))
+}
+
+/// 确认批量操作：条数低于阈值时确认一次，达到阈值时需要输入条数确认
+pub async fn confirm_bulk(prompt: &str, count: usize, default: bool) -> Result<bool> {
+    let threshold = crate::settings::load_bulk_confirm_threshold().await;
+    if count < threshold {
+        return Ok(Confirm::new().with_prompt(prompt).default(default).interact()?);
+    }
+
+    print_warning!("\n{}", prompt);
+    let count = count.to_string();
+    let typed: String = Input::new()
+        .with_prompt(t!("bulk.type_count").replace("{}", &count))
+        .allow_empty(true)
+        .interact_text()?;
+    if typed.trim() == count {
+        return Ok(true);
+    }
+    if !typed.trim().is_empty() {
+        print_warning!("{}", t!("bulk.count_mismatch"));
+    }
+    Ok(false)
+}
+
+/// 将时间格式化为相对时间，如 "2天前"；None 显示为 "从未"
+pub fn humanize_since(time: Option<DateTime<Utc>>) -> String {
+    let Some(time) = time else {
+        return t!("time.never").to_string();
+    };
+
+    let seconds = (Utc::now() - time).num_seconds().max(0);
+    let (value, key) = match seconds {
+        0..=59 => return t!("time.just_now").to_string(),
+        60..=3599 => (seconds / 60, "time.minutes_ago"),
+        3600..=86399 => (seconds / 3600, "time.hours_ago"),
+        86400..=2591999 => (seconds / 86400, "time.days_ago"),
+        2592000..=31535999 => (seconds / 2592000, "time.months_ago"),
+        _ => (seconds / 31536000, "time.years_ago"),
+    };
+
+    t!(key).replace("{}", &value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_page_range() {
+        assert_eq!(page_count(0, 20), 1);
+        assert_eq!(page_count(40, 20), 2);
+        assert_eq!(page_count(41, 20), 3);
+
+        assert_eq!(page_range(0, 20, 45), 0..20);
+        assert_eq!(page_range(2, 20, 45), 40..45);
+        assert_eq!(page_range(3, 20, 45), 45..45);
+    }
+}
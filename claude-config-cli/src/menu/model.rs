@@ -0,0 +1,237 @@
+use crate::{
+    claude_config::ClaudeConfigManager,
+    models::*,
+    print_error, print_success, print_warning, settings, t, DbState,
+};
+use anyhow::Result;
+use colored::Colorize;
+use dialoguer::{FuzzySelect, Input, Select};
+
+/// 模型选择列表中的常用模型
+const COMMON_MODELS: &[&str] = &[
+    "claude-opus-4-1-20250805",
+    "claude-opus-4-20250514",
+    "claude-sonnet-4-5-20250929",
+    "claude-sonnet-4-20250514",
+    "claude-3-7-sonnet-20250219",
+    "claude-3-5-haiku-20241022",
+];
+
+const MODEL_ENV_KEY: &str = "ANTHROPIC_MODEL";
+
+/// 模型选择结果
+enum ModelChoice {
+    Set(String),
+    Clear,
+}
+
+/// 模型菜单：为目录写入 ANTHROPIC_MODEL，或设置账号切换时默认写入的模型
+pub async fn model_menu(db: &DbState) -> Result<()> {
+    let mut last_selection = 0;
+
+    loop {
+        let items = vec![
+            t!("common.back"),
+            t!("model.menu.directory"),
+            t!("model.menu.account_default"),
+        ];
+
+        let selection = match Select::new()
+            .with_prompt(format!("\n{} (ESC {})", t!("model.menu.title"), t!("common.to_back")))
+            .items(&items)
+            .default(last_selection)
+            .interact_opt()?
+        {
+            Some(sel) => sel,
+            None => break,
+        };
+
+        last_selection = selection;
+
+        match selection {
+            0 => break,
+            1 => set_directory_model(db).await?,
+            2 => set_account_default_model(db).await?,
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
+
+/// 选择模型：常用模型、自定义输入或清除，`current` 会标记为当前值；取消时返回 None
+fn pick_model(current: &str) -> Result<Option<ModelChoice>> {
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(COMMON_MODELS.iter().map(|model| {
+        if *model == current {
+            format!("{} {}", model, t!("model.pick.current"))
+        } else {
+            model.to_string()
+        }
+    }));
+    items.push(t!("model.pick.custom").to_string());
+    items.push(t!("model.pick.clear").to_string());
+
+    let selection = match FuzzySelect::new()
+        .with_prompt(t!("model.pick.prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => idx - 1,
+        _ => return Ok(None),
+    };
+
+    if let Some(model) = COMMON_MODELS.get(selection) {
+        return Ok(Some(ModelChoice::Set(model.to_string())));
+    }
+    if selection > COMMON_MODELS.len() {
+        return Ok(Some(ModelChoice::Clear));
+    }
+
+    let custom: String = Input::new()
+        .with_prompt(t!("model.pick.prompt_custom"))
+        .with_initial_text(current)
+        .validate_with(|input: &String| -> Result<(), String> {
+            if input.trim().is_empty() {
+                Err(t!("model.pick.custom_empty").to_string())
+            } else {
+                Ok(())
+            }
+        })
+        .interact_text()?;
+    Ok(Some(ModelChoice::Set(custom.trim().to_string())))
+}
+
+/// 选择目录并写入或删除其 ANTHROPIC_MODEL
+async fn set_directory_model(db: &DbState) -> Result<()> {
+    let directories = db.lock().await.get_directories().await?;
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("directory.list.no_records"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(
+        directories
+            .iter()
+            .map(|d| format!("{} - {}", d.name, d.resolved_path(&workspace_root))),
+    );
+
+    let directory = match FuzzySelect::new()
+        .with_prompt(t!("model.directory.prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+    if super::directory::refuse_if_locked(directory) {
+        return Ok(());
+    }
+    if !super::directory::confirm_claude_dir_target(&directory.resolved_path(&workspace_root)).await? {
+        return Ok(());
+    }
+
+    let manager = ClaudeConfigManager::new(directory.resolved_path(&workspace_root))
+        .with_write_format(settings::load_write_format().await)
+        .with_indent(settings::load_indent().await);
+    let current = match manager.get_env_config() {
+        Ok(env) => env.get(MODEL_ENV_KEY).cloned().unwrap_or_default(),
+        Err(e) => {
+            print_error!("\n{}", t!("model.error").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+    if !current.is_empty() {
+        println!("\n{}", t!("model.current").replace("{}", &current).cyan());
+    }
+
+    let result = match pick_model(&current)? {
+        Some(ModelChoice::Set(model)) => manager.set_env_var(MODEL_ENV_KEY, &model).map(|()| {
+            t!("model.directory.set")
+                .replacen("{}", &directory.name, 1)
+                .replacen("{}", &model, 1)
+        }),
+        Some(ModelChoice::Clear) => manager
+            .remove_env_var(MODEL_ENV_KEY)
+            .map(|_| t!("model.directory.cleared").replace("{}", &directory.name)),
+        None => return Ok(()),
+    };
+    match result {
+        Ok(message) => print_success!("\n{}", message),
+        Err(e) => print_error!("\n{}", t!("model.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+/// 设置账号的默认模型，切换到该账号时自动写入 ANTHROPIC_MODEL
+async fn set_account_default_model(db: &DbState) -> Result<()> {
+    let accounts = db
+        .lock()
+        .await
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(1000),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts;
+    if accounts.is_empty() {
+        print_warning!("\n{}", t!("switch.no_accounts"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(accounts.iter().map(|account| {
+        if account.model.is_empty() {
+            account.name.clone()
+        } else {
+            format!("{} ({})", account.name, account.model)
+        }
+    }));
+
+    let account = match FuzzySelect::new()
+        .with_prompt(t!("model.account.prompt"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &accounts[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let model = match pick_model(&account.model)? {
+        Some(ModelChoice::Set(model)) => model,
+        Some(ModelChoice::Clear) => String::new(),
+        None => return Ok(()),
+    };
+
+    let request = UpdateAccountRequest {
+        name: None,
+        token: None,
+        base_url: None,
+        model: Some(model.clone()),
+        auth_style: None,
+        proxy: None,
+        note: None,
+        extra_auth_env_key: None,
+        staging_base_url: None,
+        token_command: None,
+    };
+    match db.lock().await.update_account(account.id, request).await {
+        Ok(_) if model.is_empty() => {
+            print_success!("\n{}", t!("model.account.cleared").replace("{}", &account.name));
+        }
+        Ok(_) => print_success!(
+            "\n{}",
+            t!("model.account.set")
+                .replacen("{}", &account.name, 1)
+                .replacen("{}", &model, 1)
+        ),
+        Err(e) => print_error!("\n{}", t!("model.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use crate::{models::*, print_error, print_success, print_warning, t, DbState};
+use anyhow::Result;
+use colored::Colorize;
+use comfy_table::{Attribute, Cell, Color};
+use dialoguer::{Confirm, FuzzySelect, Input, Select};
+use std::collections::HashMap;
+
+/// 配置档案菜单：保存常用的账号 + 模板 + 权限 + 代理组合，一步应用到目录
+pub async fn profile_menu(db: &DbState) -> Result<()> {
+    let mut last_selection = 0;
+
+    loop {
+        let items = vec![
+            t!("common.back"),
+            t!("profile.menu.apply"),
+            t!("profile.menu.list"),
+            t!("profile.menu.add"),
+            t!("profile.menu.delete"),
+        ];
+
+        let selection = match Select::new()
+            .with_prompt(format!("\n{} (ESC {})", t!("profile.menu.title"), t!("common.to_back")))
+            .items(&items)
+            .default(last_selection)
+            .interact_opt()?
+        {
+            Some(sel) => sel,
+            None => break,
+        };
+
+        last_selection = selection;
+
+        match selection {
+            0 => break,
+            1 => apply_profile(db).await?,
+            2 => list_profiles(db).await?,
+            3 => add_profile(db).await?,
+            4 => delete_profile(db).await?,
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
+
+/// 选择一个配置档案，没有档案时提示并返回 None
+async fn select_profile(db: &DbState, prompt: &str) -> Result<Option<Profile>> {
+    let profiles = db.lock().await.get_profiles().await?;
+    if profiles.is_empty() {
+        print_warning!("\n{}", t!("profile.list.no_records"));
+        return Ok(None);
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(profiles.iter().map(|profile| profile.name.clone()));
+    match FuzzySelect::new().with_prompt(prompt).items(&items).interact_opt()? {
+        Some(idx) if idx > 0 => Ok(Some(profiles[idx - 1].clone())),
+        _ => Ok(None),
+    }
+}
+
+async fn apply_profile(db: &DbState) -> Result<()> {
+    if let Some(profile) = select_profile(db, t!("profile.apply.prompt")).await? {
+        super::switch::apply_profile(db, &profile).await?;
+    }
+    Ok(())
+}
+
+async fn list_profiles(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let profiles = db_lock.get_profiles().await?;
+    let accounts: HashMap<i64, String> = db_lock
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(100),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts
+        .into_iter()
+        .map(|account| (account.id, account.name))
+        .collect();
+    let templates: HashMap<i64, String> = db_lock
+        .get_templates()
+        .await?
+        .into_iter()
+        .map(|template| (template.id, template.name))
+        .collect();
+    drop(db_lock);
+
+    if profiles.is_empty() {
+        print_warning!("\n{}", t!("profile.list.no_records"));
+        return Ok(());
+    }
+
+    let mut table = super::create_table();
+    table.set_header(
+        [
+            t!("profile.header.name"),
+            t!("profile.header.account"),
+            t!("profile.header.template"),
+            t!("profile.header.skip_permissions"),
+            t!("profile.header.use_proxy"),
+        ]
+        .into_iter()
+        .map(|header| Cell::new(header).add_attribute(Attribute::Bold).fg(Color::Cyan)),
+    );
+
+    let on_off = |value: bool| if value { t!("common.enabled") } else { t!("common.disabled") };
+    for profile in &profiles {
+        let account = match profile.account_id.and_then(|id| accounts.get(&id)) {
+            Some(name) => Cell::new(name),
+            None => Cell::new(t!("profile.list.account_missing")).fg(Color::Red),
+        };
+        let template = profile
+            .template_id
+            .and_then(|id| templates.get(&id))
+            .map_or("-", String::as_str);
+        table.add_row(vec![
+            Cell::new(&profile.name),
+            account,
+            Cell::new(template),
+            Cell::new(on_off(profile.skip_permissions)),
+            Cell::new(on_off(profile.use_proxy)),
+        ]);
+    }
+
+    println!("\n{}", table);
+
+    let _ = Input::<String>::new()
+        .with_prompt(t!("common.continue"))
+        .allow_empty(true)
+        .interact()?;
+
+    Ok(())
+}
+
+async fn add_profile(db: &DbState) -> Result<()> {
+    println!("\n{}", t!("profile.add.title").green().bold());
+    print_warning!("{}", t!("common.input_cancel_hint"));
+
+    let name: String = Input::new()
+        .with_prompt(t!("profile.add.prompt_name"))
+        .allow_empty(true)
+        .interact_text()?;
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let accounts = db
+        .lock()
+        .await
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(100),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts;
+    if accounts.is_empty() {
+        print_warning!("\n{}", t!("switch.no_accounts"));
+        return Ok(());
+    }
+
+    let mut account_items: Vec<String> = vec![t!("common.cancel").to_string()];
+    account_items.extend(accounts.iter().map(|a| format!("{} - {}", a.name, a.base_url)));
+    let account = match FuzzySelect::new()
+        .with_prompt(t!("switch.select_account"))
+        .items(&account_items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &accounts[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let template = super::template::select_template(db).await?;
+    let skip_permissions = Confirm::new()
+        .with_prompt(t!("switch.prompt_skip_permissions"))
+        .default(true)
+        .interact()?;
+    let use_proxy = Confirm::new()
+        .with_prompt(t!("switch.prompt_use_proxy"))
+        .default(!account.proxy_config().is_empty())
+        .interact()?;
+
+    let request = CreateProfileRequest {
+        name: name.clone(),
+        account_id: account.id,
+        template_id: template.map(|template| template.id),
+        skip_permissions,
+        use_proxy,
+    };
+    match db.lock().await.create_profile(request).await {
+        Ok(_) => print_success!("\n{}", t!("profile.add.success").replace("{}", &name)),
+        Err(e) => print_error!("\n{}", t!("profile.add.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
+
+async fn delete_profile(db: &DbState) -> Result<()> {
+    let Some(profile) = select_profile(db, t!("profile.delete.prompt")).await? else {
+        return Ok(());
+    };
+
+    if !Confirm::new()
+        .with_prompt(t!("profile.delete.confirm").replace("{}", &profile.name))
+        .default(false)
+        .interact()?
+    {
+        return Ok(());
+    }
+
+    match db.lock().await.delete_profile(profile.id).await {
+        Ok(_) => print_success!("\n{}", t!("profile.delete.success")),
+        Err(e) => print_error!("\n{}", t!("profile.delete.error").replace("{}", &e.to_string())),
+    }
+
+    Ok(())
+}
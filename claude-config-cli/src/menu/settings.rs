@@ -1,11 +1,18 @@
 use anyhow::Result;
 use colored::Colorize;
-use dialoguer::{Input, Select};
+use dialoguer::{Confirm, Input, Select};
+use std::path::Path;
 
 use crate::i18n::{self, Language};
+use crate::models::{JsonIndent, WriteFormat};
+use crate::settings::SettingsManager;
+use crate::{print_error, print_success, print_warning, DbState};
+
+/// 设置备份文件的默认路径
+const DEFAULT_BACKUP_FILE: &str = "claude-config-settings.json";
 
 /// 设置菜单
-pub async fn settings_menu() -> Result<()> {
+pub async fn settings_menu(db: &DbState) -> Result<()> {
     loop {
         let current_lang = i18n::current_language();
         let lang_display = match current_lang {
@@ -35,9 +42,103 @@ pub async fn settings_menu() -> Result<()> {
         );
         println!();
 
+        let app_settings = crate::settings::load_or_default().await;
+        let on_off = |enabled: bool| {
+            if enabled {
+                i18n::translate("common.enabled")
+            } else {
+                i18n::translate("common.disabled")
+            }
+        };
         let items = vec![
-            i18n::translate("menu.settings.language"),
-            i18n::translate("menu.settings.back"),
+            i18n::translate("menu.settings.language").to_string(),
+            i18n::translate("menu.settings.network").to_string(),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.hotkey_menu"),
+                on_off(app_settings.hotkey_menu)
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.offline"),
+                on_off(crate::offline::is_enabled())
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.plain_output"),
+                on_off(app_settings.plain_output)
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.console_timestamps"),
+                on_off(app_settings.console_timestamps)
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.preserve_format"),
+                on_off(app_settings.write_format == WriteFormat::Preserve)
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.indent"),
+                indent_label(app_settings.indent)
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.page_size"),
+                app_settings.page_size
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.max_name_length"),
+                app_settings.max_name_length
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.directory_env_summary"),
+                on_off(app_settings.directory_env_summary)
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.gitignore_generated"),
+                on_off(app_settings.gitignore_generated)
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.allow_symlinked_claude_dir"),
+                on_off(app_settings.allow_symlinked_claude_dir)
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.omit_empty_env"),
+                on_off(app_settings.omit_empty_env)
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.bulk_confirm_threshold"),
+                app_settings.bulk_confirm_threshold
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.health_check"),
+                if app_settings.health_check {
+                    i18n::translate("menu.settings.health_check_every")
+                        .replace("{}", &app_settings.health_check_interval_mins.to_string())
+                } else {
+                    i18n::translate("common.disabled").to_string()
+                }
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.post_switch_hook"),
+                app_settings
+                    .post_switch_hook
+                    .clone()
+                    .unwrap_or_else(|| i18n::translate("common.disabled").to_string())
+            ),
+            i18n::translate("menu.settings.export").to_string(),
+            i18n::translate("menu.settings.import").to_string(),
+            i18n::translate("menu.settings.back").to_string(),
         ];
 
         let selection = match Select::new()
@@ -54,6 +155,60 @@ pub async fn settings_menu() -> Result<()> {
                 language_settings().await?;
             }
             1 => {
+                network_settings().await?;
+            }
+            2 => {
+                toggle_hotkey_menu().await?;
+            }
+            3 => {
+                toggle_offline().await?;
+            }
+            4 => {
+                toggle_plain_output().await?;
+            }
+            5 => {
+                toggle_console_timestamps().await?;
+            }
+            6 => {
+                toggle_write_format().await?;
+            }
+            7 => {
+                indent_settings().await?;
+            }
+            8 => {
+                page_size_settings().await?;
+            }
+            9 => {
+                max_name_length_settings().await?;
+            }
+            10 => {
+                toggle_directory_env_summary().await?;
+            }
+            11 => {
+                toggle_gitignore_generated().await?;
+            }
+            12 => {
+                toggle_allow_symlinked_claude_dir().await?;
+            }
+            13 => {
+                toggle_omit_empty_env().await?;
+            }
+            14 => {
+                bulk_confirm_threshold_settings().await?;
+            }
+            15 => {
+                health_check_settings().await?;
+            }
+            16 => {
+                post_switch_hook_settings().await?;
+            }
+            17 => {
+                export_settings(db).await?;
+            }
+            18 => {
+                import_settings(db).await?;
+            }
+            19 => {
                 break;
             }
             _ => unreachable!(),
@@ -81,7 +236,7 @@ async fn language_settings() -> Result<()> {
     );
     println!();
 
-    let languages = vec![
+    let languages = [
         ("中文 (简体)", Language::ZhCN),
         ("English (US)", Language::EnUS),
     ];
@@ -104,11 +259,7 @@ async fn language_settings() -> Result<()> {
 
     if new_lang != current_lang {
         i18n::set_language(new_lang);
-        println!(
-            "\n{} {}",
-            "✓".green(),
-            i18n::translate("menu.settings.lang_changed").green()
-        );
+        print_success!("\n{} {}", crate::output::symbol("✓"), i18n::translate("menu.settings.lang_changed"));
         println!(
             "{}: {}",
             i18n::translate("menu.settings.current_lang"),
@@ -123,3 +274,462 @@ async fn language_settings() -> Result<()> {
 
     Ok(())
 }
+
+/// 网络与同步设置（超时、重试次数、同步间隔）
+async fn network_settings() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+
+    loop {
+        println!(
+            "\n{}",
+            "========================================".bright_blue()
+        );
+        println!(
+            "{}",
+            format!("      {}      ", i18n::translate("menu.settings.network"))
+                .bright_blue()
+                .bold()
+        );
+        println!(
+            "{}",
+            "========================================".bright_blue()
+        );
+        println!(
+            "{}",
+            i18n::translate("menu.settings.file").replace("{}", &manager.settings_file().display().to_string())
+        );
+
+        let items = vec![
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.http_timeout"),
+                settings.http_timeout_secs
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.max_retries"),
+                settings.max_retries
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.sync_interval"),
+                settings.sync_interval_mins
+            ),
+            format!(
+                "{}: {}",
+                i18n::translate("menu.settings.workspace_root"),
+                crate::settings::workspace_root(&settings).display()
+            ),
+            i18n::translate("common.back").to_string(),
+        ];
+
+        let selection = match Select::new()
+            .with_prompt(format!("\n{} (ESC {})", i18n::translate("common.select_operation"), i18n::translate("common.to_back")))
+            .items(&items)
+            .default(0)
+            .interact_opt()? {
+                Some(sel) => sel,
+                None => break,
+            };
+
+        match selection {
+            0 => {
+                settings.http_timeout_secs = Input::new()
+                    .with_prompt(i18n::translate("menu.settings.http_timeout"))
+                    .default(settings.http_timeout_secs)
+                    .validate_with(|value: &u64| validate_positive(*value))
+                    .interact_text()?;
+            }
+            1 => {
+                // 重试次数允许为 0，表示不重试
+                settings.max_retries = Input::new()
+                    .with_prompt(i18n::translate("menu.settings.max_retries"))
+                    .default(settings.max_retries)
+                    .interact_text()?;
+            }
+            2 => {
+                settings.sync_interval_mins = Input::new()
+                    .with_prompt(i18n::translate("menu.settings.sync_interval"))
+                    .default(settings.sync_interval_mins)
+                    .validate_with(|value: &u64| validate_positive(*value))
+                    .interact_text()?;
+            }
+            3 => {
+                print_warning!("{}", i18n::translate("menu.settings.workspace_root_hint"));
+                let root: String = Input::new()
+                    .with_prompt(i18n::translate("menu.settings.workspace_root"))
+                    .default(settings.workspace_root.clone().unwrap_or_default())
+                    .allow_empty(true)
+                    .interact_text()?;
+                settings.workspace_root = Some(root.trim().to_string()).filter(|root| !root.is_empty());
+            }
+            4 => break,
+            _ => unreachable!(),
+        }
+
+        manager.save_settings(&settings).await?;
+        print_success!("{} {}", crate::output::symbol("✓"), i18n::translate("menu.settings.saved"));
+    }
+
+    Ok(())
+}
+
+/// 切换主菜单的单键快捷模式
+async fn toggle_hotkey_menu() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.hotkey_menu = !settings.hotkey_menu;
+    manager.save_settings(&settings).await?;
+
+    print_success!(
+        "{} {}",
+        crate::output::symbol("✓"),
+        i18n::translate(if settings.hotkey_menu {
+            "menu.settings.hotkey_menu_on"
+        } else {
+            "menu.settings.hotkey_menu_off"
+        })
+    );
+    Ok(())
+}
+
+/// 切换目录列表中的配置摘要列
+async fn toggle_directory_env_summary() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.directory_env_summary = !settings.directory_env_summary;
+    manager.save_settings(&settings).await?;
+
+    print_success!(
+        "{}",
+        i18n::translate(if settings.directory_env_summary {
+            "menu.settings.directory_env_summary_on"
+        } else {
+            "menu.settings.directory_env_summary_off"
+        })
+    );
+    Ok(())
+}
+
+/// 切换是否自动把生成的配置文件加入 .gitignore
+async fn toggle_gitignore_generated() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.gitignore_generated = !settings.gitignore_generated;
+    manager.save_settings(&settings).await?;
+
+    print_success!(
+        "{}",
+        i18n::translate(if settings.gitignore_generated {
+            "menu.settings.gitignore_generated_on"
+        } else {
+            "menu.settings.gitignore_generated_off"
+        })
+    );
+    Ok(())
+}
+
+/// 切换是否允许 `.claude` 通过符号链接指向目录之外
+async fn toggle_allow_symlinked_claude_dir() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.allow_symlinked_claude_dir = !settings.allow_symlinked_claude_dir;
+    manager.save_settings(&settings).await?;
+
+    print_success!(
+        "{}",
+        i18n::translate(if settings.allow_symlinked_claude_dir {
+            "menu.settings.allow_symlinked_claude_dir_on"
+        } else {
+            "menu.settings.allow_symlinked_claude_dir_off"
+        })
+    );
+    Ok(())
+}
+
+/// 切换是否不写入值为空的环境变量
+async fn toggle_omit_empty_env() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.omit_empty_env = !settings.omit_empty_env;
+    manager.save_settings(&settings).await?;
+
+    print_success!(
+        "{}",
+        i18n::translate(if settings.omit_empty_env {
+            "menu.settings.omit_empty_env_on"
+        } else {
+            "menu.settings.omit_empty_env_off"
+        })
+    );
+    Ok(())
+}
+
+/// 切换离线模式（--offline 启动时始终离线）
+async fn toggle_offline() -> Result<()> {
+    if crate::offline::is_forced() {
+        print_warning!("{}", i18n::translate("menu.settings.offline_forced"));
+        return Ok(());
+    }
+
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.offline = !settings.offline;
+    manager.save_settings(&settings).await?;
+    crate::offline::set_persisted(settings.offline);
+
+    print_success!(
+        "{} {}",
+        crate::output::symbol("✓"),
+        i18n::translate(if settings.offline {
+            "menu.settings.offline_on"
+        } else {
+            "menu.settings.offline_off"
+        })
+    );
+    Ok(())
+}
+
+/// 切换纯文本输出（不使用表情符号和颜色）
+async fn toggle_plain_output() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.plain_output = !settings.plain_output;
+    manager.save_settings(&settings).await?;
+    crate::output::set_plain_output(settings.plain_output);
+
+    print_success!(
+        "{} {}",
+        crate::output::symbol("✓"),
+        i18n::translate(if settings.plain_output {
+            "menu.settings.plain_output_on"
+        } else {
+            "menu.settings.plain_output_off"
+        })
+    );
+    Ok(())
+}
+
+/// 切换控制台消息的时间和级别前缀
+async fn toggle_console_timestamps() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.console_timestamps = !settings.console_timestamps;
+    manager.save_settings(&settings).await?;
+    crate::output::set_console_timestamps(settings.console_timestamps);
+
+    print_success!(
+        "{}",
+        i18n::translate(if settings.console_timestamps {
+            "menu.settings.console_timestamps_on"
+        } else {
+            "menu.settings.console_timestamps_off"
+        })
+    );
+    Ok(())
+}
+
+/// 切换配置写入格式：按原格式写回 YAML / TOML，或始终写入 settings.local.json
+async fn toggle_write_format() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.write_format = match settings.write_format {
+        WriteFormat::Json => WriteFormat::Preserve,
+        WriteFormat::Preserve => WriteFormat::Json,
+    };
+    manager.save_settings(&settings).await?;
+
+    print_success!(
+        "{} {}",
+        crate::output::symbol("✓"),
+        i18n::translate(match settings.write_format {
+            WriteFormat::Preserve => "menu.settings.preserve_format_on",
+            WriteFormat::Json => "menu.settings.preserve_format_off",
+        })
+    );
+    Ok(())
+}
+
+fn indent_label(indent: JsonIndent) -> &'static str {
+    i18n::translate(match indent {
+        JsonIndent::TwoSpaces => "menu.settings.indent_two",
+        JsonIndent::FourSpaces => "menu.settings.indent_four",
+        JsonIndent::Tab => "menu.settings.indent_tab",
+    })
+}
+
+/// 设置写入 JSON 配置时的缩进
+async fn indent_settings() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    let options = [JsonIndent::TwoSpaces, JsonIndent::FourSpaces, JsonIndent::Tab];
+    let items: Vec<&str> = options.iter().map(|indent| indent_label(*indent)).collect();
+    let current = options.iter().position(|indent| *indent == settings.indent).unwrap_or(0);
+    let Some(selection) = Select::new()
+        .with_prompt(i18n::translate("menu.settings.indent"))
+        .items(&items)
+        .default(current)
+        .interact_opt()?
+    else {
+        return Ok(());
+    };
+    settings.indent = options[selection];
+    manager.save_settings(&settings).await?;
+
+    print_success!("{} {}", crate::output::symbol("✓"), i18n::translate("menu.settings.saved"));
+    Ok(())
+}
+
+/// 设置列表每页显示的条数
+async fn page_size_settings() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.page_size = Input::new()
+        .with_prompt(i18n::translate("menu.settings.page_size"))
+        .default(settings.page_size)
+        .validate_with(|value: &usize| validate_positive(*value as u64))
+        .interact_text()?;
+    manager.save_settings(&settings).await?;
+
+    print_success!("{} {}", crate::output::symbol("✓"), i18n::translate("menu.settings.saved"));
+    Ok(())
+}
+
+/// 设置账号、目录名称的最大长度，已保存的名称不受影响
+async fn max_name_length_settings() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.max_name_length = Input::new()
+        .with_prompt(i18n::translate("menu.settings.max_name_length"))
+        .default(settings.max_name_length)
+        .validate_with(|value: &usize| validate_positive(*value as u64))
+        .interact_text()?;
+    manager.save_settings(&settings).await?;
+    crate::names::init(settings.max_name_length);
+
+    print_success!("{} {}", crate::output::symbol("✓"), i18n::translate("menu.settings.saved"));
+    Ok(())
+}
+
+/// 设置批量操作需要输入条数确认的阈值
+async fn bulk_confirm_threshold_settings() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.bulk_confirm_threshold = Input::new()
+        .with_prompt(i18n::translate("menu.settings.bulk_confirm_threshold"))
+        .default(settings.bulk_confirm_threshold)
+        .validate_with(|value: &usize| validate_positive(*value as u64))
+        .interact_text()?;
+    manager.save_settings(&settings).await?;
+
+    print_success!("{} {}", crate::output::symbol("✓"), i18n::translate("menu.settings.saved"));
+    Ok(())
+}
+
+/// 设置是否在后台定期检测账号可用性及检测间隔
+async fn health_check_settings() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.health_check = Confirm::new()
+        .with_prompt(i18n::translate("menu.settings.health_check_enable"))
+        .default(settings.health_check)
+        .interact()?;
+    if settings.health_check {
+        settings.health_check_interval_mins = Input::new()
+            .with_prompt(i18n::translate("menu.settings.health_check_interval"))
+            .default(settings.health_check_interval_mins)
+            .validate_with(|value: &u64| validate_positive(*value))
+            .interact_text()?;
+    }
+    manager.save_settings(&settings).await?;
+
+    print_success!("{} {}", crate::output::symbol("✓"), i18n::translate("menu.settings.saved"));
+    if settings.health_check && crate::offline::is_enabled() {
+        print_warning!("{}", i18n::translate("menu.settings.health_check_offline"));
+    }
+    Ok(())
+}
+
+/// 设置切换后钩子，默认禁用，需要显式确认才会启用
+async fn post_switch_hook_settings() -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    let enable = Confirm::new()
+        .with_prompt(i18n::translate("menu.settings.post_switch_hook_enable"))
+        .default(settings.post_switch_hook.is_some())
+        .interact()?;
+    if enable {
+        println!("{}", i18n::translate("menu.settings.post_switch_hook_hint").cyan());
+        let command: String = Input::new()
+            .with_prompt(i18n::translate("menu.settings.post_switch_hook_command"))
+            .with_initial_text(settings.post_switch_hook.clone().unwrap_or_default())
+            .validate_with(|input: &String| -> Result<(), String> {
+                if input.trim().is_empty() {
+                    Err(i18n::translate("menu.settings.post_switch_hook_empty").to_string())
+                } else {
+                    Ok(())
+                }
+            })
+            .interact_text()?;
+        settings.post_switch_hook = Some(command.trim().to_string());
+        settings.post_switch_hook_strict = Confirm::new()
+            .with_prompt(i18n::translate("menu.settings.post_switch_hook_strict"))
+            .default(settings.post_switch_hook_strict)
+            .interact()?;
+    } else {
+        settings.post_switch_hook = None;
+    }
+    manager.save_settings(&settings).await?;
+
+    print_success!("{} {}", crate::output::symbol("✓"), i18n::translate("menu.settings.saved"));
+    Ok(())
+}
+
+/// 导出应用设置备份，密码需要显式确认才会导出
+async fn export_settings(db: &DbState) -> Result<()> {
+    let path: String = Input::new()
+        .with_prompt(i18n::translate("menu.settings.backup_path"))
+        .default(DEFAULT_BACKUP_FILE.to_string())
+        .interact_text()?;
+    let include_secrets = Confirm::new()
+        .with_prompt(i18n::translate("menu.settings.include_secrets"))
+        .default(false)
+        .interact()?;
+
+    println!();
+    if let Err(e) = crate::commands::app_settings::export(db, Path::new(path.trim()), include_secrets).await {
+        print_error!("{}", i18n::translate("app_settings.error").replace("{}", &e.to_string()));
+    }
+    Ok(())
+}
+
+/// 从备份文件恢复应用设置
+async fn import_settings(db: &DbState) -> Result<()> {
+    let path: String = Input::new()
+        .with_prompt(i18n::translate("menu.settings.backup_path"))
+        .default(DEFAULT_BACKUP_FILE.to_string())
+        .interact_text()?;
+    if !Confirm::new()
+        .with_prompt(i18n::translate("menu.settings.import_confirm"))
+        .default(false)
+        .interact()?
+    {
+        print_warning!("\n{}", i18n::translate("common.cancel"));
+        return Ok(());
+    }
+
+    println!();
+    if let Err(e) = crate::commands::app_settings::import(db, Path::new(path.trim())).await {
+        print_error!("{}", i18n::translate("app_settings.error").replace("{}", &e.to_string()));
+    }
+    Ok(())
+}
+
+fn validate_positive(value: u64) -> Result<(), &'static str> {
+    if value == 0 {
+        Err(i18n::translate("menu.settings.must_be_positive"))
+    } else {
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use crate::{models::*, print_warning, t, DbState};
+use anyhow::Result;
+use colored::Colorize;
+use dialoguer::Confirm;
+
+/// 向导的步骤数
+const TOTAL_STEPS: usize = 3;
+
+async fn load_accounts(db: &DbState) -> Result<Vec<Account>> {
+    let db_lock = db.lock().await;
+    let response = db_lock
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(100),
+            search: None,
+            base_url: None,
+        })
+        .await?;
+    Ok(response.accounts)
+}
+
+async fn load_directories(db: &DbState) -> Result<Vec<Directory>> {
+    Ok(db.lock().await.get_directories().await?)
+}
+
+/// 数据库中既没有账号也没有目录时视为首次运行
+pub async fn needs_setup(db: &DbState) -> Result<bool> {
+    Ok(load_accounts(db).await?.is_empty() && load_directories(db).await?.is_empty())
+}
+
+fn print_step(step: usize, title: &str) {
+    println!(
+        "\n{}",
+        t!("setup.step")
+            .replacen("{}", &step.to_string(), 1)
+            .replacen("{}", &TOTAL_STEPS.to_string(), 1)
+            .replacen("{}", title, 1)
+            .cyan()
+            .bold()
+    );
+}
+
+/// 首次运行向导：依次添加账号、添加目录，可选立即应用，最后输出汇总
+pub async fn run_setup_wizard(db: &DbState) -> Result<()> {
+    println!("\n{}", t!("setup.title").green().bold());
+    println!("{}", t!("setup.intro"));
+
+    // 1. 添加账号（已有账号时 --setup 也允许再添加一个）
+    print_step(1, t!("setup.step_account"));
+    let accounts_before = load_accounts(db).await?.len();
+    super::account::add_account(db).await?;
+    if load_accounts(db).await?.len() == accounts_before {
+        print_warning!("\n{}", t!("setup.aborted"));
+        return Ok(());
+    }
+
+    // 2. 添加目录
+    print_step(2, t!("setup.step_directory"));
+    let directories_before = load_directories(db).await?.len();
+    super::directory::add_directory(db).await?;
+    if load_directories(db).await?.len() == directories_before {
+        print_warning!("\n{}", t!("setup.aborted"));
+        return Ok(());
+    }
+
+    // 3. 可选：立即应用
+    print_step(3, t!("setup.step_apply"));
+    if Confirm::new()
+        .with_prompt(t!("setup.prompt_apply"))
+        .default(true)
+        .interact()?
+    {
+        super::switch::switch_menu(db).await?;
+    }
+
+    let accounts = load_accounts(db).await?;
+    let directories = load_directories(db).await?;
+    let account_names: Vec<&str> = accounts.iter().map(|a| a.name.as_str()).collect();
+    let directory_names: Vec<&str> = directories.iter().map(|d| d.name.as_str()).collect();
+
+    println!("\n{}", t!("setup.summary_title").green().bold());
+    println!("{}", t!("setup.summary_accounts").replace("{}", &account_names.join(", ")));
+    println!("{}", t!("setup.summary_directories").replace("{}", &directory_names.join(", ")));
+    println!("{}", t!("setup.summary_hint").cyan());
+
+    Ok(())
+}
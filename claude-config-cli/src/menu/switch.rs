@@ -1,22 +1,55 @@
-use crate::{claude_config::ClaudeConfigManager, models::*, t, DbState};
-use anyhow::Result;
+use crate::{
+    batch_switch::{self, BatchEntry, BatchRecord, BatchStatus},
+    change_log::ChangeRecorder,
+    claude_config::{self, ClaudeConfigManager},
+    connectivity,
+    dir_filter::{DirectoryPattern, PatternKind},
+    hooks,
+    interrupt::{self, MutationGuard},
+    models::*,
+    output,
+    print_error, print_warning,
+    progress::Progress,
+    settings, t,
+    undo::UndoPoint,
+    worktree, DbState,
+};
+use anyhow::{Context, Result};
+use claude_config_core::audit::{self, ConfigAction, ConfigChange};
+use claude_config_core::error::ConfigError;
 use colored::Colorize;
-use dialoguer::Select;
+use dialoguer::FuzzySelect;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-// 写入 Claude 配置到 .claude/settings.local.json
-fn write_claude_settings(
-    directory_path: &str,
+/// 切换菜单中“最近使用”区域显示的账号数量
+const RECENT_ACCOUNT_LIMIT: i64 = 3;
+
+/// 状态栏脚本，切换时复制到 .claude 目录
+const STATUS_SCRIPT: &str = include_str!("../../resources/config/show-status.mjs");
+
+/// 配置无变化时也强制重写（--force）
+static FORCE_REWRITE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_force_rewrite(force: bool) {
+    FORCE_REWRITE.store(force, Ordering::Relaxed);
+}
+
+// 生成要写入 .claude/settings.local.json 的 Claude 配置
+#[allow(clippy::too_many_arguments)]
+fn build_claude_settings(
     claude_settings_json: &str,
     account_token: &str,
     account_base_url: &str,
     account_model: &str,
     account_name: &str,
-    api_key_name: &str,
+    auth_style: AuthStyle,
+    extra_auth_env_key: Option<&str>,
+    proxy: &ProxyConfig,
     skip_permissions: bool,
     use_proxy: bool,
-) -> Result<()> {
+) -> Result<serde_json::Value> {
     use serde_json::Value;
 
     // 解析 Claude 配置
@@ -62,11 +95,18 @@ fn write_claude_settings(
         .as_object_mut()
         .unwrap();
 
-    // 添加账号相关的环境变量 - 根据 api_key_name 参数决定使用哪个环境变量名
-    env_obj.insert(
-        api_key_name.to_string(),
-        Value::String(account_token.to_string()),
-    );
+    // 添加账号相关的环境变量 - 根据账号的认证方式决定使用哪些环境变量名
+    for key in AuthStyle::Both.env_keys() {
+        if auth_style.env_keys().contains(key) {
+            env_obj.insert(key.to_string(), Value::String(account_token.to_string()));
+        } else {
+            env_obj.remove(*key);
+        }
+    }
+    // 部分网关从自定义变量读取 Token
+    if let Some(key) = extra_auth_env_key.filter(|key| claude_config::is_valid_env_key(key)) {
+        env_obj.insert(key.to_string(), Value::String(account_token.to_string()));
+    }
     env_obj.insert(
         "ANTHROPIC_BASE_URL".to_string(),
         Value::String(account_base_url.to_string()),
@@ -87,10 +127,19 @@ fn write_claude_settings(
     // 处理代理配置
     if !use_proxy {
         // 如果未启用代理，删除代理环境变量
-        env_obj.remove("HTTP_PROXY");
-        env_obj.remove("HTTPS_PROXY");
+        for key in PROXY_ENV_KEYS {
+            env_obj.remove(*key);
+        }
+    } else if !proxy.is_empty() {
+        // 账号配置了代理时使用账号的代理，未设置的代理变量不写入
+        for key in PROXY_ENV_KEYS {
+            env_obj.remove(*key);
+        }
+        for (key, value) in proxy.env_entries() {
+            env_obj.insert(key.to_string(), Value::String(value.to_string()));
+        }
     }
-    // 如果启用代理，保留从数据库加载的代理配置（已经在 env 中）
+    // 否则保留从数据库加载的代理配置（已经在 env 中）
 
     // 添加 statusLine 配置
     settings_obj.insert(
@@ -102,28 +151,632 @@ fn write_claude_settings(
         }),
     );
 
-    // 创建 .claude 目录
-    let claude_dir = Path::new(directory_path).join(".claude");
-    fs::create_dir_all(&claude_dir)?;
+    Ok(claude_settings)
+}
 
-    // 写入 settings.local.json
-    let settings_file = claude_dir.join("settings.local.json");
-    let settings_json = serde_json::to_string_pretty(&claude_settings)?;
-    fs::write(&settings_file, settings_json)?;
+// 写入 Claude 配置到写入目标（默认 .claude/settings.local.json，按原格式写回时为生效的 YAML / TOML 文件）
+fn write_claude_settings(manager: &ClaudeConfigManager, claude_settings: &serde_json::Value) -> Result<()> {
+    // 合并进已有配置，mcpServers、模型菜单设置的 ANTHROPIC_MODEL 和用户自己的变量都会保留；
+    // 原子写入（临时文件 + 重命名），按 Ctrl-C 或崩溃时不会留下被截断的配置文件；
+    // 权限不足或只读挂载时返回带路径的“不可写”错误，而不是原始的系统错误
+    manager.merge_settings(claude_settings)?;
 
     // Copy show-status.mjs to .claude directory
-    let status_script_content = include_str!("../../resources/config/show-status.mjs");
-    let status_script_file = claude_dir.join("show-status.mjs");
-    if let Err(e) = fs::write(&status_script_file, status_script_content) {
+    let status_script_file = manager.claude_dir_path().join("show-status.mjs");
+    if let Err(e) = fs::write(&status_script_file, STATUS_SCRIPT) {
         eprintln!("警告: 复制 show-status.mjs 失败: {}，但不影响主要功能", e);
     }
 
     Ok(())
 }
 
+/// 把本次切换的配置合并进目录中的文件后内容不会变化时返回 true（只读，不修改任何文件）
+fn is_noop_switch(
+    manager: &ClaudeConfigManager,
+    expected: &serde_json::Value,
+) -> Result<bool> {
+    // 按原格式写回 YAML / TOML 时会另外写入生效文件，不做判断
+    if manager.write_target_path() != manager.settings_file_path() {
+        return Ok(false);
+    }
+
+    let settings_file = manager.settings_file_path();
+    let Ok(content) = fs::read_to_string(&settings_file) else {
+        return Ok(false);
+    };
+    let Ok(current) = serde_json::from_str::<serde_json::Value>(&content) else {
+        return Ok(false);
+    };
+    let mut merged = current.clone();
+    claude_config::merge_json(&mut merged, expected);
+    if merged != current {
+        return Ok(false);
+    }
+
+    let status_script = manager.claude_dir_path().join("show-status.mjs");
+    if fs::read_to_string(status_script).ok().as_deref() != Some(STATUS_SCRIPT) {
+        return Ok(false);
+    }
+
+    if manager.claude_local_md_action(false)? != claude_config::ClaudeMdAction::SkipIdentical {
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+/// 在目标目录打开终端：Windows 新开 pwsh/cmd 窗口，macOS 打开 Terminal.app，
+/// 其他平台在当前终端启动 $SHELL，退出后回到菜单
+fn open_terminal(path: &str) -> Result<()> {
+    if !Path::new(path).is_dir() {
+        anyhow::bail!("目录不存在: {}", path);
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        let shell = if std::process::Command::new("pwsh")
+            .arg("-Version")
+            .output()
+            .is_ok()
+        {
+            "pwsh"
+        } else {
+            "cmd"
+        };
+        std::process::Command::new("cmd")
+            .args(["/C", "start", "", "/D", path, shell])
+            .spawn()
+            .with_context(|| format!("无法启动终端: {}", shell))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .args(["-a", "Terminal", path])
+            .spawn()
+            .context("无法打开 Terminal.app")?;
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    {
+        let shell = std::env::var("SHELL")
+            .ok()
+            .filter(|shell| !shell.trim().is_empty())
+            .unwrap_or_else(|| "/bin/sh".to_string());
+        println!("{}", t!("switch.terminal.shell_hint").replace("{}", &shell).cyan());
+        // 子 Shell 的退出码只反映最后一条命令，不作为失败处理
+        std::process::Command::new(&shell)
+            .current_dir(path)
+            .status()
+            .with_context(|| format!("无法启动终端: {}", shell))?;
+    }
+
+    Ok(())
+}
+
+/// 当前进程设置了 ANTHROPIC_* 环境变量时提示：Claude Code 会优先使用它们而不是刚写入的配置
+fn warn_process_env_overrides() {
+    let overrides = claude_config::process_env_overrides();
+    if overrides.is_empty() {
+        return;
+    }
+    let keys: Vec<&str> = overrides.iter().map(|(key, _)| *key).collect();
+    println!(
+        "\n{}",
+        t!("switch.env_override").replace("{}", &keys.join(", ")).magenta()
+    );
+}
+
+/// 一次切换所需的全部参数
+struct SwitchPlan<'a> {
+    account: &'a Account,
+    directory: &'a Directory,
+    directory_path: String,
+    token: String,
+    template: Option<Template>,
+    skip_permissions: bool,
+    use_proxy: bool,
+}
+
+/// 账号配置了备用账号链时询问是否先测试连通性（默认不测试）；开启后依次测试主账号和备用账号，
+/// 返回第一个可用的账号并报告尝试过程。全部不可用且用户不继续时返回 None
+async fn resolve_fallback_chain<'a>(
+    db: &DbState,
+    primary: &'a Account,
+    accounts: &'a [Account],
+) -> Result<Option<&'a Account>> {
+    let fallback_ids = db.lock().await.get_account_fallbacks(primary.id).await?;
+    let chain: Vec<&Account> = std::iter::once(primary)
+        .chain(fallback_ids.iter().filter_map(|id| accounts.iter().find(|a| a.id == *id)))
+        .collect();
+    if chain.len() < 2 || crate::offline::is_enabled() {
+        return Ok(Some(primary));
+    }
+
+    if !dialoguer::Confirm::new()
+        .with_prompt(
+            t!("switch.fallback.confirm")
+                .replacen("{}", &primary.name, 1)
+                .replacen("{}", &(chain.len() - 1).to_string(), 1),
+        )
+        .default(false)
+        .interact()?
+    {
+        return Ok(Some(primary));
+    }
+
+    let timeout = std::time::Duration::from_secs(settings::load_or_default().await.http_timeout_secs);
+    let client = reqwest::Client::new();
+    println!("\n{}", t!("switch.fallback.testing").cyan());
+    for account in &chain {
+        let result = connectivity::test_account(&client, account, timeout).await;
+        crate::health::save_result(&*db.lock().await, &result).await;
+
+        if result.is_success() {
+            println!(
+                "  {} {} ({} ms)",
+                output::symbol("✓").green(),
+                account.name,
+                result.latency.as_millis()
+            );
+            if account.id != primary.id {
+                println!(
+                    "\n{}",
+                    t!("switch.fallback.used")
+                        .replacen("{}", &primary.name, 1)
+                        .replacen("{}", &account.name, 1)
+                        .magenta()
+                );
+                tracing::info!("账号 {} 不可用，已回退到备用账号 {}", primary.name, account.name);
+            }
+            return Ok(Some(account));
+        }
+
+        let reason = match (result.status, &result.error) {
+            (_, Some(error)) => error.clone(),
+            (Some(status), None) => format!("HTTP {}", status),
+            (None, None) => String::new(),
+        };
+        println!("  {} {}: {}", output::symbol("✗").red(), account.name, reason.red());
+    }
+
+    print_error!("\n{}", t!("switch.fallback.all_failed"));
+    let proceed = dialoguer::Confirm::new()
+        .with_prompt(t!("switch.fallback.use_primary").replace("{}", &primary.name))
+        .default(false)
+        .interact()?;
+    Ok(proceed.then_some(primary))
+}
+
+/// 取得并校验账号 Token（配置了命令时执行命令获取），写入配置的是去除空白后的值；
+/// 命令失败、Token 无效、或疑似与 Base URL 填反且用户不继续时返回 None
+async fn validated_token(account: &Account) -> Result<Option<String>> {
+    let token = match crate::token_source::resolve(account).await {
+        Ok(token) => token,
+        Err(e) => {
+            print_error!("\n{}", e);
+            return Ok(None);
+        }
+    };
+    let token = match claude_config::validate_token(&token) {
+        Ok(token) => token,
+        Err(e) => {
+            print_error!("\n{}", t!("switch.invalid_token").replace("{}", super::account::token_error_message(&e)));
+            return Ok(None);
+        }
+    };
+    if !claude_config::has_expected_token_prefix(&token) {
+        print_warning!("{}", t!("account.token.unexpected_prefix"));
+    }
+    if !super::account::confirm_credentials_not_swapped(&account.base_url, &token)? {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(None);
+    }
+    Ok(Some(token))
+}
+
+/// 读取上次切换的 (目录 ID, 账号 ID)；任一已不存在时清除记录并返回 None
+async fn load_last_switch(accounts: &[Account], directories: &[Directory]) -> Option<(i64, i64)> {
+    let app_settings = settings::load_or_default().await;
+    let (directory_id, account_id) = (app_settings.last_directory_id?, app_settings.last_account_id?);
+
+    if accounts.iter().any(|a| a.id == account_id) && directories.iter().any(|d| d.id == directory_id) {
+        return Some((directory_id, account_id));
+    }
+
+    if let Err(e) = settings::save_last_switch(None, None).await {
+        tracing::warn!("清除上次切换记录失败: {}", e);
+    }
+    None
+}
+
+/// 一次切换写入文件的结果
+enum SwitchOutcome {
+    Applied,
+    /// 配置已与账号一致，没有重写任何文件
+    Unchanged,
+    /// 环境变量已更新，但写入 Claude 配置失败
+    EnvOnly(anyhow::Error),
+}
+
+/// 生成本次切换要写入的 Claude 配置：全局配置合并模板后填入账号信息
+async fn resolve_claude_settings(db: &DbState, plan: &SwitchPlan<'_>) -> Result<serde_json::Value> {
+    let SwitchPlan {
+        account,
+        token,
+        template,
+        skip_permissions,
+        use_proxy,
+        ..
+    } = plan;
+
+    // 获取 Claude 配置
+    let claude_settings_json = match db.lock().await.get_claude_settings().await {
+        Ok(json) => json,
+        Err(e) => {
+            print_warning!("\n{}", t!("switch.warn_claude_config").replace("{}", &e.to_string()));
+            // 使用默认配置
+            serde_json::to_string(&serde_json::json!({
+                "permissions": {
+                    "defaultMode": "bypassPermissions",
+                    "allow": ["*"]
+                },
+                "env": {
+                    "IS_SANDBOX": "1",
+                    "DISABLE_AUTOUPDATER": 1
+                }
+            }))
+            .unwrap()
+        }
+    };
+
+    // 合并配置模板（模板中的 env 会被账号配置覆盖）
+    let claude_settings_json = match template {
+        Some(template) => {
+            let mut settings: serde_json::Value = serde_json::from_str(&claude_settings_json)?;
+            let overlay: serde_json::Value = serde_json::from_str(&template.settings_json)?;
+            claude_config::merge_json(&mut settings, &overlay);
+            serde_json::to_string(&settings)?
+        }
+        None => claude_settings_json,
+    };
+    let mut claude_settings = build_claude_settings(
+        &claude_settings_json,
+        token,
+        &account.base_url,
+        &account.model,
+        &account.name,
+        account.auth_style,
+        account.extra_auth_env_key.as_deref(),
+        &account.proxy_config(),
+        *skip_permissions,
+        *use_proxy,
+    )
+    .map_err(|e| anyhow::anyhow!(t!("switch.error").replace("{}", &e.to_string())))?;
+    // 有意留空的 Base URL 或 Token 不写入，交给全局配置
+    if settings::load_omit_empty_env().await {
+        claude_config::remove_empty_env_values(&mut claude_settings);
+    }
+    Ok(claude_settings)
+}
+
+/// 把生成好的配置写入一个目录（不涉及数据库）；内容完全一致时不重写任何文件（--force 强制重写）
+async fn write_switch_files(
+    plan: &SwitchPlan<'_>,
+    directory_path: &str,
+    claude_settings: &serde_json::Value,
+) -> Result<SwitchOutcome> {
+    let SwitchPlan {
+        account,
+        token,
+        use_proxy,
+        ..
+    } = plan;
+    // 沙盒模式默认开启
+    let is_sandbox = true;
+
+    // 更新环境配置文件
+    let config_manager = ClaudeConfigManager::new(directory_path.to_string())
+        .with_claude_local_md(account.claude_md_template.clone())
+        .with_extra_auth_env_key(account.extra_auth_env_key.clone())
+        .with_write_format(settings::load_write_format().await)
+        .with_indent(settings::load_indent().await)
+        .with_omit_empty_env(settings::load_omit_empty_env().await);
+
+    if !FORCE_REWRITE.load(Ordering::Relaxed)
+        && is_noop_switch(&config_manager, claude_settings)?
+    {
+        tracing::info!("目录 {} 的配置与账号 {} 一致，跳过写入", directory_path, account.name);
+        return Ok(SwitchOutcome::Unchanged);
+    }
+
+    let env_proxy = if *use_proxy {
+        account.proxy_config()
+    } else {
+        ProxyConfig::default()
+    };
+    if let Err(e) = config_manager.update_env_config_full(
+        token.clone(),
+        account.base_url.clone(),
+        account.auth_style,
+        is_sandbox,
+        &env_proxy,
+    ) {
+        anyhow::bail!(t!("switch.error_update").replace("{}", &e.to_string()));
+    }
+
+    // 写入 Claude 配置，与环境变量写入同一个目标文件
+    if let Err(e) = write_claude_settings(&config_manager, claude_settings) {
+        // 读回的内容与写入的不一致时磁盘上的配置不可信，按切换失败处理
+        if matches!(e.downcast_ref::<ConfigError>(), Some(ConfigError::VerifyFailed { .. })) {
+            anyhow::bail!(t!("switch.error_update").replace("{}", &e.to_string()));
+        }
+        return Ok(SwitchOutcome::EnvOnly(e));
+    }
+
+    Ok(SwitchOutcome::Applied)
+}
+
+/// 切换账号并写入配置，不做任何交互；返回的错误信息可以直接展示给用户
+async fn perform_switch(db: &DbState, plan: &SwitchPlan<'_>) -> Result<SwitchOutcome> {
+    let SwitchPlan {
+        account,
+        directory,
+        directory_path,
+        ..
+    } = plan;
+    let _guard = MutationGuard::begin();
+
+    let request = SwitchAccountRequest {
+        account_id: account.id,
+        directory_id: directory.id,
+    };
+    if let Err(e) = db.lock().await.switch_account(request).await {
+        anyhow::bail!(t!("switch.error").replace("{}", &e.to_string()));
+    }
+
+    let claude_settings = resolve_claude_settings(db, plan).await?;
+
+    // 环境变量与 Claude 配置分两步写入，合并记录为一次变更
+    let settings_path = ClaudeConfigManager::new(directory_path.clone())
+        .with_write_format(settings::load_write_format().await)
+        .write_target_path();
+    let before_hash = audit::file_hash(&settings_path);
+    let undo_point = UndoPoint::capture(ConfigAction::Apply, &directory.name, &settings_path);
+    match write_switch_files(plan, directory_path, &claude_settings).await? {
+        SwitchOutcome::Applied => {}
+        outcome => return Ok(outcome),
+    }
+    undo_point.commit();
+
+    // 命令获取的 Token 不在数据库中保存，快照同样只保留哈希
+    let recorder = ChangeRecorder::new(directory.id)
+        .with_base_url(Some(account.base_url.clone()))
+        .with_snapshot_content(account.token_command.is_none());
+    let after_hash = audit::file_hash(&settings_path);
+    if before_hash != after_hash {
+        recorder.record(ConfigChange {
+            action: ConfigAction::Apply,
+            settings_path: settings_path.clone(),
+            before_hash,
+            after_hash,
+        });
+    }
+
+    let db_lock = db.lock().await;
+    recorder.flush(&db_lock).await;
+    if let Err(e) = db_lock.mark_directory_switched(directory.id).await {
+        tracing::warn!("记录目录切换时间失败: {}", e);
+    }
+    if let Err(e) = db_lock.mark_account_used(account.id).await {
+        tracing::warn!("记录账号使用时间失败: {}", e);
+    }
+    drop(db_lock);
+    if let Err(e) = settings::save_last_switch(Some(directory.id), Some(account.id)).await {
+        tracing::warn!("记录上次切换失败: {}", e);
+    }
+    let app_settings = settings::load_or_default().await;
+    if app_settings.gitignore_generated {
+        ensure_gitignore(Path::new(directory_path));
+    }
+    if let Some(template) = &app_settings.post_switch_hook {
+        let ctx = hooks::HookContext {
+            dir: directory_path,
+            account: &account.name,
+            base_url: &account.base_url,
+        };
+        if let Err(e) = hooks::run_post_switch_hook(template, &ctx).await {
+            let message = t!("switch.hook_failed").replace("{}", &format!("{:#}", e));
+            if app_settings.post_switch_hook_strict {
+                anyhow::bail!(message);
+            }
+            print_warning!("{}", message);
+        }
+    }
+
+    Ok(SwitchOutcome::Applied)
+}
+
+/// 把生成的配置文件加入 git 仓库的 .gitignore，追加了条目时提示用户
+fn ensure_gitignore(directory_path: &Path) {
+    match crate::gitignore::ensure_generated_ignored(directory_path) {
+        Ok(added) if !added.is_empty() => {
+            println!("{}", t!("switch.gitignore_updated").replace("{}", &added.join(", ")));
+        }
+        Ok(_) => {}
+        Err(e) => print_warning!("{}", t!("switch.gitignore_failed").replace("{}", &e.to_string())),
+    }
+}
+
+/// 写入配置并输出切换结果
+async fn apply_switch(db: &DbState, plan: &SwitchPlan<'_>) -> Result<()> {
+    warn_if_not_expected(db, plan.account, plan.directory).await;
+    if !super::directory::confirm_claude_dir_target(&plan.directory_path).await? {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    // 执行切换
+    println!("\n{}", t!("switch.switching").cyan());
+
+    let outcome = match perform_switch(db, plan).await {
+        Ok(outcome) => outcome,
+        Err(e) => {
+            print_error!("\n{}", e);
+            return Ok(());
+        }
+    };
+
+    let SwitchPlan {
+        account,
+        directory,
+        directory_path,
+        template,
+        skip_permissions,
+        use_proxy,
+        ..
+    } = plan;
+
+    match outcome {
+        SwitchOutcome::Unchanged => {
+            print_warning!("\n{}", t!("switch.unchanged"));
+            println!("{}", t!("switch.unchanged_hint"));
+        }
+        SwitchOutcome::EnvOnly(e) => {
+            println!("\n{}", t!("switch.success_env").green().bold());
+            println!("{}", t!("switch.account").replace("{}", &account.name));
+            println!("{}", t!("switch.directory").replace("{}", &directory.name));
+            println!("{}", t!("switch.path").replace("{}", directory_path));
+            println!("{}", t!("switch.sandbox"));
+            let message = t!("switch.warn_write_fail").replace("{}", &e.to_string());
+            // 目录不可写需要用户处理权限，用红色突出显示
+            if matches!(e.downcast_ref::<ConfigError>(), Some(ConfigError::NotWritable { .. })) {
+                print_error!("\n{}", message);
+            } else {
+                print_warning!("\n{}", message);
+            }
+        }
+        SwitchOutcome::Applied => {
+            println!("\n{}", t!("switch.success").green().bold());
+            println!("{}", t!("switch.account").replace("{}", &account.name));
+            println!("{}", t!("switch.directory").replace("{}", &directory.name));
+            println!("{}", t!("switch.path").replace("{}", directory_path));
+            println!("{}", t!("switch.sandbox"));
+            if let Some(template) = template {
+                println!("{}", t!("switch.template").replace("{}", &template.name));
+            }
+            println!(
+                "{}",
+                t!("switch.permission").replace(
+                    "{}",
+                    if *skip_permissions {
+                        t!("switch.permission_skipped")
+                    } else {
+                        t!("switch.permission_required")
+                    }
+                )
+            );
+            println!(
+                "{}",
+                t!("switch.proxy").replace(
+                    "{}",
+                    if *use_proxy {
+                        t!("switch.proxy_enabled")
+                    } else {
+                        t!("switch.proxy_disabled")
+                    }
+                )
+            );
+
+            warn_process_env_overrides();
+
+            // 可选：直接在该目录打开终端，失败不影响切换结果
+            if dialoguer::Confirm::new()
+                .with_prompt(t!("switch.terminal.prompt"))
+                .default(false)
+                .interact()?
+            {
+                if let Err(e) = open_terminal(directory_path) {
+                    print_warning!("{}", t!("switch.terminal.error").replace("{}", &e.to_string()));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// 命令行 `switch --path` 使用：按快速重复切换的默认选项（跳过权限确认、账号有代理时启用代理、
+/// 不使用模板）应用账号，不询问是否打开终端；配置未完整写入时返回错误
+pub async fn apply_with_defaults(db: &DbState, account: &Account, directory: &Directory) -> Result<()> {
+    if directory.is_locked {
+        anyhow::bail!(t!("directory.lock.refused").replace("{}", &directory.name));
+    }
+    let Some(token) = validated_token(account).await? else {
+        anyhow::bail!(t!("common.cancel"));
+    };
+
+    let directory_path = directory.resolved_path(&settings::load_workspace_root().await);
+    if !super::directory::confirm_claude_dir_target(&directory_path).await? {
+        anyhow::bail!(t!("common.cancel"));
+    }
+    let plan = SwitchPlan {
+        account,
+        directory,
+        directory_path,
+        token,
+        template: None,
+        skip_permissions: true,
+        use_proxy: !account.proxy_config().is_empty(),
+    };
+    match perform_switch(db, &plan).await? {
+        SwitchOutcome::Applied => {
+            println!("{}", t!("switch.success").green().bold());
+            println!("{}", t!("switch.account").replace("{}", &account.name));
+            println!("{}", t!("switch.directory").replace("{}", &directory.name));
+            println!("{}", t!("switch.path").replace("{}", &plan.directory_path));
+            warn_process_env_overrides();
+        }
+        SwitchOutcome::Unchanged => print_warning!("{}", t!("switch.unchanged")),
+        SwitchOutcome::EnvOnly(e) => anyhow::bail!(t!("switch.warn_write_fail").replace("{}", &e.to_string())),
+    }
+    Ok(())
+}
+
 pub async fn switch_menu(db: &DbState) -> Result<()> {
     println!("\n{}", t!("switch.title").green().bold());
+    switch_flow(db, None).await
+}
+
+/// 配置当前工作目录：按规范化路径匹配已登记的目录，未登记时询问是否登记，然后直接进入账号选择
+pub async fn switch_current_directory(db: &DbState) -> Result<()> {
+    println!("\n{}", t!("switch.current_dir.title").green().bold());
+
+    let cwd = std::env::current_dir()?;
+    let directory = match crate::commands::switch::find_or_register_directory(db, &cwd, false).await {
+        Ok(Some(directory)) => directory,
+        Ok(None) => {
+            print_warning!("\n{}", t!("common.cancel"));
+            return Ok(());
+        }
+        Err(e) => {
+            print_error!("\n{}", e);
+            return Ok(());
+        }
+    };
+    println!(
+        "{}",
+        t!("switch.current_dir.matched")
+            .replacen("{}", &directory.name, 1)
+            .replacen("{}", &cwd.display().to_string(), 1)
+            .cyan()
+    );
 
+    switch_flow(db, Some(directory)).await
+}
+
+/// 交互式切换流程；`preset` 为已确定的目录时跳过目录选择
+async fn switch_flow(db: &DbState, preset: Option<Directory>) -> Result<()> {
     // 获取所有账号
     let db_lock = db.lock().await;
     let accounts_response = db_lock
@@ -136,188 +789,756 @@ pub async fn switch_menu(db: &DbState) -> Result<()> {
         .await?;
 
     let directories = db_lock.get_directories().await?;
+    let recent_accounts = db_lock.get_recent_accounts(RECENT_ACCOUNT_LIMIT).await?;
     drop(db_lock);
 
     if accounts_response.accounts.is_empty() {
-        println!("\n{}", t!("switch.no_accounts").yellow());
+        print_warning!("\n{}", t!("switch.no_accounts"));
         return Ok(());
     }
 
-    if directories.is_empty() {
-        println!("\n{}", t!("switch.no_directories").yellow());
+    if preset.is_none() && directories.is_empty() {
+        print_warning!("\n{}", t!("switch.no_directories"));
         return Ok(());
     }
 
-    // 选择账号
+    // 上次切换的目录和账号作为默认选项
+    let last = load_last_switch(&accounts_response.accounts, &directories).await;
+
+    // 选择账号：最近使用的账号排在最前面，方便快速切换
+    let account_choices: Vec<&Account> = recent_accounts
+        .iter()
+        .chain(accounts_response.accounts.iter())
+        .collect();
     let mut account_items: Vec<String> = vec![t!("common.back_cancel").to_string()];
-    account_items.extend(
-        accounts_response
-            .accounts
-            .iter()
-            .map(|a| format!("{} - {}", a.name, a.base_url)),
-    );
+    account_items.extend(account_choices.iter().enumerate().map(|(i, a)| {
+        let mut label = format!("{} - {}", a.name, a.base_url);
+        if let Some(note) = &a.note {
+            label = format!("{} {}", label, format!("({})", super::account::truncate_note(note)).dimmed());
+        }
+        if i < recent_accounts.len() {
+            t!("switch.recent_account").replace("{}", &label)
+        } else {
+            label
+        }
+    }));
 
-    let account_selection = Select::new()
+    let account_default = last
+        .and_then(|(_, account_id)| account_choices.iter().position(|a| a.id == account_id))
+        .map_or(0, |pos| pos + 1);
+    let account_selection = FuzzySelect::new()
         .with_prompt(t!("switch.select_account"))
         .items(&account_items)
+        .default(account_default)
         .interact_opt()?;
 
     if account_selection.is_none() || account_selection == Some(0) {
         return Ok(());
     }
 
-    let account = &accounts_response.accounts[account_selection.unwrap() - 1];
+    let account = account_choices[account_selection.unwrap() - 1];
+    let Some(account) = resolve_fallback_chain(db, account, &accounts_response.accounts).await? else {
+        return Ok(());
+    };
+
+    // 账号配置了备用地址时，询问本次切换是否使用备用地址（默认使用主地址）
+    let staged_account;
+    let account = match account.with_staging_base_url() {
+        Some(staged)
+            if dialoguer::Confirm::new()
+                .with_prompt(t!("switch.prompt_use_staging").replace("{}", &staged.base_url))
+                .default(false)
+                .interact()? =>
+        {
+            staged_account = staged;
+            &staged_account
+        }
+        _ => account,
+    };
 
     // 选择目录
+    let workspace_root = settings::load_workspace_root().await;
+    let directory = match &preset {
+        Some(directory) => directory,
+        None => {
+            let mut directory_items: Vec<String> = vec![t!("common.back_cancel").to_string()];
+            directory_items.extend(directories.iter().map(|d| {
+                let path = d.resolved_path(&workspace_root);
+                let exists = if std::path::Path::new(&path).exists() {
+                    output::symbol("✓")
+                } else {
+                    output::symbol("✗")
+                };
+                format!("{} {} - {}", exists, d.name, path)
+            }));
+
+            let directory_default = last
+                .and_then(|(directory_id, _)| directories.iter().position(|d| d.id == directory_id))
+                .map_or(0, |pos| pos + 1);
+            let directory_selection = FuzzySelect::new()
+                .with_prompt(t!("switch.select_directory"))
+                .items(&directory_items)
+                .default(directory_default)
+                .interact_opt()?;
+
+            match directory_selection {
+                Some(idx) if idx > 0 => &directories[idx - 1],
+                _ => return Ok(()),
+            }
+        }
+    };
+    if super::directory::refuse_if_locked(directory) {
+        return Ok(());
+    }
+    let directory_path = directory.resolved_path(&workspace_root);
+
+    // 选择配置模板（可选）
+    let template = super::template::select_template(db).await?;
+
+    // 询问权限配置
+    let skip_permissions = dialoguer::Confirm::new()
+        .with_prompt(t!("switch.prompt_skip_permissions"))
+        .default(true)
+        .interact()?;
+
+    // 询问是否使用代理
+    let account_proxy = account.proxy_config();
+    let use_proxy = dialoguer::Confirm::new()
+        .with_prompt(t!("switch.prompt_use_proxy"))
+        .default(!account_proxy.is_empty())
+        .interact()?;
+
+    let Some(token) = validated_token(account).await? else {
+        return Ok(());
+    };
+
+    // 预览将对工作区文件执行的动作
+    let preview_manager =
+        ClaudeConfigManager::new(directory_path.clone()).with_claude_local_md(account.claude_md_template.clone());
+    let claude_md_action = match preview_manager.claude_local_md_action(false)? {
+        claude_config::ClaudeMdAction::Create => t!("switch.preview.claude_md_create"),
+        claude_config::ClaudeMdAction::Overwrite => t!("switch.preview.claude_md_overwrite"),
+        claude_config::ClaudeMdAction::SkipIdentical => t!("switch.preview.claude_md_skip_identical"),
+        claude_config::ClaudeMdAction::SkipModified => t!("switch.preview.claude_md_skip_modified"),
+    };
+    println!("\n{}", t!("switch.preview.title").cyan().bold());
+    println!("  CLAUDE.local.md: {}", claude_md_action);
+
+    if !dialoguer::Confirm::new()
+        .with_prompt(t!("switch.preview.confirm"))
+        .default(true)
+        .interact()?
+    {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let plan = SwitchPlan {
+        account,
+        directory,
+        directory_path,
+        token,
+        template,
+        skip_permissions,
+        use_proxy,
+    };
+    apply_switch(db, &plan).await?;
+    apply_to_worktrees(db, &plan).await?;
+
+    let _ = dialoguer::Input::<String>::new()
+        .with_prompt(t!("common.continue"))
+        .allow_empty(true)
+        .interact()?;
+
+    Ok(())
+}
+
+/// 目录设置了预期账号而本次应用的是其他账号时给出警告（不阻止切换）
+async fn warn_if_not_expected(db: &DbState, account: &Account, directory: &Directory) {
+    let Some(expected_id) = directory.expected_account_id.filter(|id| *id != account.id) else {
+        return;
+    };
+    let expected = match db.lock().await.get_account(expected_id).await {
+        Ok(expected) => expected.name,
+        Err(_) => expected_id.to_string(),
+    };
+    tracing::warn!("目录 {} 应用的账号 {} 不是预期账号 {}", directory.name, account.name, expected);
+    print_warning!(
+        "\n{}",
+        t!("switch.not_expected_account")
+            .replacen("{}", &directory.name, 1)
+            .replacen("{}", &expected, 1)
+    );
+}
+
+/// 目录是 git 仓库且存在其他工作树时，询问是否把同一账号应用到全部工作树并逐个报告结果；
+/// 工作树无需登记为目录，已登记且锁定的工作树会被跳过
+async fn apply_to_worktrees(db: &DbState, plan: &SwitchPlan<'_>) -> Result<()> {
+    let worktrees = worktree::sibling_worktrees(Path::new(&plan.directory_path));
+    if worktrees.is_empty() {
+        return Ok(());
+    }
+
+    println!(
+        "\n{}",
+        t!("switch.worktree.found").replace("{}", &worktrees.len().to_string()).cyan()
+    );
+    for path in &worktrees {
+        println!("  {}", path.display());
+    }
+    if !dialoguer::Confirm::new()
+        .with_prompt(t!("switch.worktree.confirm"))
+        .default(false)
+        .interact()?
+    {
+        return Ok(());
+    }
+
+    let claude_settings = match resolve_claude_settings(db, plan).await {
+        Ok(claude_settings) => claude_settings,
+        Err(e) => {
+            print_error!("\n{}", e);
+            return Ok(());
+        }
+    };
+
+    let workspace_root = settings::load_workspace_root().await;
+    let locked: Vec<PathBuf> = db
+        .lock()
+        .await
+        .get_directories()
+        .await?
+        .iter()
+        .filter(|d| d.is_locked)
+        .filter_map(|d| Path::new(&d.resolved_path(&workspace_root)).canonicalize().ok())
+        .collect();
+
+    let (mut updated, mut unchanged, mut failed) = (0, 0, 0);
+    println!();
+    for path in &worktrees {
+        let display = path.display().to_string();
+        if locked.contains(path) {
+            println!("  - {}", t!("switch.worktree.locked").replace("{}", &display).yellow());
+            continue;
+        }
+        match write_switch_files(plan, &display, &claude_settings).await {
+            Ok(SwitchOutcome::Applied) => {
+                updated += 1;
+                println!("  {} {}", output::symbol("✓").green(), display);
+            }
+            Ok(SwitchOutcome::Unchanged) => {
+                unchanged += 1;
+                println!("  - {}", t!("switch.worktree.unchanged").replace("{}", &display));
+            }
+            Ok(SwitchOutcome::EnvOnly(e)) | Err(e) => {
+                failed += 1;
+                println!("  {} {}: {}", output::symbol("✗").red(), display, e.to_string().red());
+            }
+        }
+    }
+    println!(
+        "\n{}",
+        t!("switch.worktree.summary")
+            .replacen("{}", &updated.to_string(), 1)
+            .replacen("{}", &unchanged.to_string(), 1)
+            .replacen("{}", &failed.to_string(), 1)
+    );
+
+    Ok(())
+}
+
+/// 将配置档案应用到一个目录：账号、模板、权限和代理都取自档案，只需选择目录
+pub async fn apply_profile(db: &DbState, profile: &Profile) -> Result<()> {
+    let Some(account_id) = profile.account_id else {
+        print_warning!("\n{}", t!("profile.apply.no_account").replace("{}", &profile.name));
+        return Ok(());
+    };
+
+    let db_lock = db.lock().await;
+    let account = match db_lock.get_account(account_id).await {
+        Ok(account) => account,
+        Err(e) => {
+            print_error!("\n{}", t!("profile.apply.account_error").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+    // 模板已被删除时不使用模板
+    let template = match profile.template_id {
+        Some(template_id) => db_lock.get_template(template_id).await.ok(),
+        None => None,
+    };
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("switch.no_directories"));
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
     let mut directory_items: Vec<String> = vec![t!("common.back_cancel").to_string()];
     directory_items.extend(directories.iter().map(|d| {
-        let exists = if std::path::Path::new(&d.path).exists() {
-            "✓"
+        let path = d.resolved_path(&workspace_root);
+        let exists = if Path::new(&path).exists() {
+            output::symbol("✓")
         } else {
-            "✗"
+            output::symbol("✗")
         };
-        format!("{} {} - {}", exists, d.name, d.path)
+        format!("{} {} - {}", exists, d.name, path)
     }));
-
-    let directory_selection = Select::new()
+    let directory = match FuzzySelect::new()
         .with_prompt(t!("switch.select_directory"))
         .items(&directory_items)
-        .interact_opt()?;
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &directories[idx - 1],
+        _ => return Ok(()),
+    };
+    if super::directory::refuse_if_locked(directory) {
+        return Ok(());
+    }
+    let directory_path = directory.resolved_path(&workspace_root);
 
-    if directory_selection.is_none() || directory_selection == Some(0) {
+    if !dialoguer::Confirm::new()
+        .with_prompt(
+            t!("profile.apply.confirm")
+                .replacen("{}", &profile.name, 1)
+                .replacen("{}", &directory.name, 1),
+        )
+        .default(true)
+        .interact()?
+    {
+        print_warning!("\n{}", t!("common.cancel"));
         return Ok(());
     }
 
-    let directory = &directories[directory_selection.unwrap() - 1];
+    let Some(token) = validated_token(&account).await? else {
+        return Ok(());
+    };
 
-    // 询问权限配置
+    apply_switch(
+        db,
+        &SwitchPlan {
+            account: &account,
+            directory,
+            directory_path,
+            token,
+            template,
+            skip_permissions: profile.skip_permissions,
+            use_proxy: profile.use_proxy,
+        },
+    )
+    .await?;
+
+    let _ = dialoguer::Input::<String>::new()
+        .with_prompt(t!("common.continue"))
+        .allow_empty(true)
+        .interact()?;
+
+    Ok(())
+}
+
+/// 快速重复上次切换：只需确认一次，使用默认选项（跳过权限确认、账号有代理时启用代理、不使用模板）
+pub async fn repeat_last_switch(db: &DbState) -> Result<()> {
+    println!("\n{}", t!("switch.repeat.title").green().bold());
+
+    let db_lock = db.lock().await;
+    let accounts = db_lock
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(100),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    let Some((directory_id, account_id)) = load_last_switch(&accounts, &directories).await else {
+        print_warning!("\n{}", t!("switch.repeat.none"));
+        return Ok(());
+    };
+    let (Some(account), Some(directory)) = (
+        accounts.iter().find(|a| a.id == account_id),
+        directories.iter().find(|d| d.id == directory_id),
+    ) else {
+        return Ok(());
+    };
+    if super::directory::refuse_if_locked(directory) {
+        return Ok(());
+    }
+
+    let workspace_root = settings::load_workspace_root().await;
+    let directory_path = directory.resolved_path(&workspace_root);
+
+    if !dialoguer::Confirm::new()
+        .with_prompt(
+            t!("switch.repeat.confirm")
+                .replacen("{}", &account.name, 1)
+                .replacen("{}", &directory.name, 1)
+                .replacen("{}", &directory_path, 1),
+        )
+        .default(true)
+        .interact()?
+    {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let Some(token) = validated_token(account).await? else {
+        return Ok(());
+    };
+
+    apply_switch(
+        db,
+        &SwitchPlan {
+            account,
+            directory,
+            directory_path,
+            token,
+            template: None,
+            skip_permissions: true,
+            use_proxy: !account.proxy_config().is_empty(),
+        },
+    )
+    .await?;
+
+    let _ = dialoguer::Input::<String>::new()
+        .with_prompt(t!("common.continue"))
+        .allow_empty(true)
+        .interact()?;
+
+    Ok(())
+}
+
+/// 按通配符或正则表达式预选要批量切换的目录，返回多选列表的预选状态（不筛选时全不选，ESC 返回 None）
+fn prefilter_directories(directories: &[(&str, String)]) -> Result<Option<Vec<bool>>> {
+    let kinds = [
+        t!("switch.batch.filter_none"),
+        t!("switch.batch.filter_glob"),
+        t!("switch.batch.filter_regex"),
+    ];
+    loop {
+        let kind = match dialoguer::Select::new()
+            .with_prompt(t!("switch.batch.filter_prompt"))
+            .items(&kinds)
+            .default(0)
+            .interact_opt()?
+        {
+            Some(1) => PatternKind::Glob,
+            Some(2) => PatternKind::Regex,
+            Some(_) => return Ok(Some(vec![false; directories.len()])),
+            None => return Ok(None),
+        };
+
+        let input: String = dialoguer::Input::new()
+            .with_prompt(t!("switch.batch.filter_pattern"))
+            .allow_empty(true)
+            .interact_text()?;
+        if input.trim().is_empty() {
+            continue;
+        }
+        let pattern = match DirectoryPattern::parse(kind, &input) {
+            Ok(pattern) => pattern,
+            Err(e) => {
+                print_error!("{}", t!("switch.batch.filter_invalid").replace("{}", &e));
+                continue;
+            }
+        };
+
+        let defaults: Vec<bool> = directories
+            .iter()
+            .map(|(name, path)| pattern.matches(name, path))
+            .collect();
+        let matched = defaults.iter().filter(|selected| **selected).count();
+        if matched == 0 {
+            print_warning!("{}", t!("switch.batch.filter_no_match").replace("{}", input.trim()));
+            continue;
+        }
+        println!(
+            "{}",
+            t!("switch.batch.filter_matched")
+                .replacen("{}", &matched.to_string(), 1)
+                .replacen("{}", &directories.len().to_string(), 1)
+                .cyan()
+        );
+        return Ok(Some(defaults));
+    }
+}
+
+/// 批量切换：将一个账号应用到多个目录，记录每个目录的结果，可只重试失败的目录
+pub async fn batch_switch_menu(db: &DbState) -> Result<()> {
+    println!("\n{}", t!("switch.batch.title").green().bold());
+
+    // 上次批量切换还有未完成的目录时，先询问是否继续
+    if let Some(record) = batch_switch::load().filter(|record| record.retry_count() > 0) {
+        let items = vec![
+            t!("common.back_cancel").to_string(),
+            t!("switch.batch.retry_failed").replace("{}", &record.retry_count().to_string()),
+            t!("switch.batch.new").to_string(),
+        ];
+        print_warning!(
+            "{}",
+            t!("switch.batch.last_record")
+                .replacen("{}", &record.account_name, 1)
+                .replacen("{}", &record.started_at.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string(), 1)
+        );
+        match dialoguer::Select::new()
+            .with_prompt(t!("common.select_operation"))
+            .items(&items)
+            .default(1)
+            .interact_opt()?
+        {
+            Some(1) => return retry_batch_switch(db, record).await,
+            Some(2) => {}
+            _ => return Ok(()),
+        }
+    }
+
+    let db_lock = db.lock().await;
+    let accounts = db_lock
+        .get_accounts(GetAccountsRequest {
+            page: Some(1),
+            per_page: Some(100),
+            search: None,
+            base_url: None,
+        })
+        .await?
+        .accounts;
+    let directories = db_lock.get_directories().await?;
+    drop(db_lock);
+
+    if accounts.is_empty() {
+        print_warning!("\n{}", t!("switch.no_accounts"));
+        return Ok(());
+    }
+
+    // 锁定的目录不参与批量切换
+    let (locked, directories): (Vec<Directory>, Vec<Directory>) =
+        directories.into_iter().partition(|d| d.is_locked);
+    if !locked.is_empty() {
+        let names: Vec<&str> = locked.iter().map(|d| d.name.as_str()).collect();
+        print_warning!("{}", t!("switch.batch.locked_skipped").replace("{}", &names.join(", ")));
+    }
+    if directories.is_empty() {
+        print_warning!("\n{}", t!("switch.no_directories"));
+        return Ok(());
+    }
+
+    let mut account_items: Vec<String> = vec![t!("common.back_cancel").to_string()];
+    account_items.extend(accounts.iter().map(|a| format!("{} - {}", a.name, a.base_url)));
+    let account = match FuzzySelect::new()
+        .with_prompt(t!("switch.select_account"))
+        .items(&account_items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &accounts[idx - 1],
+        _ => return Ok(()),
+    };
+
+    let workspace_root = settings::load_workspace_root().await;
+    let directory_paths: Vec<(&str, String)> = directories
+        .iter()
+        .map(|d| (d.name.as_str(), d.resolved_path(&workspace_root)))
+        .collect();
+    let Some(defaults) = prefilter_directories(&directory_paths)? else {
+        return Ok(());
+    };
+    let directory_items: Vec<String> = directory_paths
+        .iter()
+        .map(|(name, path)| format!("{} - {}", name, path))
+        .collect();
+    let Some(chosen) = dialoguer::MultiSelect::new()
+        .with_prompt(t!("switch.batch.select_directories"))
+        .items(&directory_items)
+        .defaults(&defaults)
+        .interact_opt()?
+    else {
+        return Ok(());
+    };
+    if chosen.is_empty() {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let template = super::template::select_template(db).await?;
     let skip_permissions = dialoguer::Confirm::new()
         .with_prompt(t!("switch.prompt_skip_permissions"))
         .default(true)
         .interact()?;
-
-    // 询问是否使用代理
     let use_proxy = dialoguer::Confirm::new()
         .with_prompt(t!("switch.prompt_use_proxy"))
-        .default(false)
+        .default(!account.proxy_config().is_empty())
         .interact()?;
 
-    // 沙盒模式默认开启
-    let is_sandbox = true;
+    let Some(token) = validated_token(account).await? else {
+        return Ok(());
+    };
 
-    // 执行切换
-    println!("\n{}", t!("switch.switching").cyan());
+    let not_expected: Vec<&str> = chosen
+        .iter()
+        .map(|&idx| &directories[idx])
+        .filter(|d| d.expected_account_id.is_some_and(|id| id != account.id))
+        .map(|d| d.name.as_str())
+        .collect();
+    if !not_expected.is_empty() {
+        print_warning!("\n{}", t!("switch.batch.not_expected_account").replace("{}", &not_expected.join(", ")));
+    }
 
-    let db_lock = db.lock().await;
-    let request = SwitchAccountRequest {
+    let prompt = t!("switch.batch.confirm")
+        .replacen("{}", &account.name, 1)
+        .replacen("{}", &chosen.len().to_string(), 1);
+    if !super::confirm_bulk(&prompt, chosen.len(), true).await? {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let record = BatchRecord {
         account_id: account.id,
-        directory_id: directory.id,
+        account_name: account.name.clone(),
+        template_id: template.as_ref().map(|template| template.id),
+        skip_permissions,
+        use_proxy,
+        started_at: chrono::Utc::now(),
+        entries: chosen
+            .iter()
+            .map(|&idx| BatchEntry {
+                directory_id: directories[idx].id,
+                directory_name: directories[idx].name.clone(),
+                status: BatchStatus::Pending,
+                error: None,
+            })
+            .collect(),
     };
+    run_batch_switch(db, record, account, &token, template).await
+}
 
-    match db_lock.switch_account(request).await {
-        Ok(_) => {
-            // 获取所有 BaseUrl 列表
-            let base_urls = db_lock.get_base_urls().await?;
+/// 只重试上次批量切换中失败或未执行的目录
+async fn retry_batch_switch(db: &DbState, record: BatchRecord) -> Result<()> {
+    let db_lock = db.lock().await;
+    let account = db_lock.get_account(record.account_id).await;
+    let template = match record.template_id {
+        Some(id) => match db_lock.get_template(id).await {
+            Ok(template) => Some(template),
+            Err(e) => {
+                tracing::warn!("批量切换使用的模板 {} 已不存在: {}", id, e);
+                print_warning!("{}", t!("switch.batch.template_missing"));
+                None
+            }
+        },
+        None => None,
+    };
+    drop(db_lock);
 
-            // 查找与 account.base_url 匹配的 BaseUrl，获取其 api_key
-            let api_key_name = base_urls
-                .iter()
-                .find(|bu| bu.url == account.base_url)
-                .map(|bu| bu.api_key.clone())
-                .unwrap_or_else(|| "ANTHROPIC_API_KEY".to_string());
-
-            // 获取 Claude 配置
-            let claude_settings_json = match db_lock.get_claude_settings().await {
-                Ok(json) => json,
-                Err(e) => {
-                    println!(
-                        "\n{}",
-                        t!("switch.warn_claude_config").replace("{}", &e.to_string()).yellow()
-                    );
-                    // 使用默认配置
-                    serde_json::to_string(&serde_json::json!({
-                        "permissions": {
-                            "defaultMode": "bypassPermissions",
-                            "allow": ["*"]
-                        },
-                        "env": {
-                            "IS_SANDBOX": "1",
-                            "DISABLE_AUTOUPDATER": 1
-                        }
-                    }))
-                    .unwrap()
-                }
-            };
+    let Ok(account) = account else {
+        print_error!("\n{}", t!("switch.batch.account_missing").replace("{}", &record.account_name));
+        return Ok(());
+    };
+    let Some(token) = validated_token(&account).await? else {
+        return Ok(());
+    };
+
+    run_batch_switch(db, record, &account, &token, template).await
+}
 
-            drop(db_lock);
-
-            // 更新环境配置文件
-            let config_manager = ClaudeConfigManager::new(directory.path.clone());
-            match config_manager.update_env_config_with_options(
-                account.token.clone(),
-                account.base_url.clone(),
-                api_key_name.clone(),
-                is_sandbox,
-            ) {
-                Ok(_) => {
-                    // 写入 Claude 配置到 .claude/settings.local.json
-                    match write_claude_settings(
-                        &directory.path,
-                        &claude_settings_json,
-                        &account.token,
-                        &account.base_url,
-                        &account.model,
-                        &account.name,
-                        &api_key_name,
-                        skip_permissions,
-                        use_proxy,
-                    ) {
-                        Ok(_) => {
-                            println!("\n{}", t!("switch.success").green().bold());
-                            println!("{}", t!("switch.account").replace("{}", &account.name));
-                            println!("{}", t!("switch.directory").replace("{}", &directory.name));
-                            println!("{}", t!("switch.path").replace("{}", &directory.path));
-                            println!("{}", t!("switch.sandbox"));
-                            println!(
-                                "{}",
-                                t!("switch.permission").replace(
-                                    "{}",
-                                    if skip_permissions {
-                                        t!("switch.permission_skipped")
-                                    } else {
-                                        t!("switch.permission_required")
-                                    }
-                                )
-                            );
-                            println!(
-                                "{}",
-                                t!("switch.proxy").replace(
-                                    "{}",
-                                    if use_proxy {
-                                        t!("switch.proxy_enabled")
-                                    } else {
-                                        t!("switch.proxy_disabled")
-                                    }
-                                )
-                            );
-                        }
-                        Err(e) => {
-                            println!("\n{}", t!("switch.success_env").green().bold());
-                            println!("{}", t!("switch.account").replace("{}", &account.name));
-                            println!("{}", t!("switch.directory").replace("{}", &directory.name));
-                            println!("{}", t!("switch.path").replace("{}", &directory.path));
-                            println!("{}", t!("switch.sandbox"));
-                            println!("\n{}", t!("switch.warn_write_fail").replace("{}", &e.to_string()).yellow());
-                        }
+/// 依次切换记录中需要执行的目录，每完成一个目录就保存记录
+async fn run_batch_switch(
+    db: &DbState,
+    mut record: BatchRecord,
+    account: &Account,
+    token: &str,
+    template: Option<Template>,
+) -> Result<()> {
+    // 整个批量切换期间持有，Ctrl-C 后处理完当前目录、输出结果再退出
+    let _guard = MutationGuard::begin();
+    loop {
+        let directories = db.lock().await.get_directories().await?;
+        let workspace_root = settings::load_workspace_root().await;
+        let pending: Vec<i64> = record
+            .entries
+            .iter()
+            .filter(|entry| entry.status.needs_retry())
+            .map(|entry| entry.directory_id)
+            .collect();
+
+        println!("\n{}", t!("switch.batch.running").replace("{}", &pending.len().to_string()).cyan());
+        let mut progress = Progress::new(t!("switch.batch.progress"), pending.len(), true);
+        for directory_id in pending {
+            if interrupt::requested() {
+                break;
+            }
+            let directory = directories.iter().find(|d| d.id == directory_id);
+            // .claude 指向目录之外时无法逐个确认，批量切换中直接跳过
+            let escape = match directory {
+                Some(directory) => super::directory::claude_dir_escape(&directory.resolved_path(&workspace_root)).await,
+                None => None,
+            };
+            let (status, error) = match directory {
+                None => (BatchStatus::Failed, Some(t!("switch.batch.directory_missing").to_string())),
+                Some(directory) if directory.is_locked => (
+                    BatchStatus::Failed,
+                    Some(t!("directory.lock.refused").replace("{}", &directory.name)),
+                ),
+                Some(_) if escape.is_some() => (
+                    BatchStatus::Failed,
+                    escape.map(|target| t!("switch.batch.symlink_escape").replace("{}", &target.display().to_string())),
+                ),
+                Some(directory) => {
+                    let plan = SwitchPlan {
+                        account,
+                        directory,
+                        directory_path: directory.resolved_path(&workspace_root),
+                        token: token.to_string(),
+                        template: template.clone(),
+                        skip_permissions: record.skip_permissions,
+                        use_proxy: record.use_proxy,
+                    };
+                    match perform_switch(db, &plan).await {
+                        Ok(SwitchOutcome::Applied) => (BatchStatus::Succeeded, None),
+                        Ok(SwitchOutcome::Unchanged) => (BatchStatus::Unchanged, None),
+                        // 只写入了一半，重试时整体重新写入
+                        Ok(SwitchOutcome::EnvOnly(e)) => (
+                            BatchStatus::Failed,
+                            Some(t!("switch.warn_write_fail").replace("{}", &e.to_string())),
+                        ),
+                        Err(e) => (BatchStatus::Failed, Some(e.to_string())),
                     }
                 }
-                Err(e) => {
-                    println!("\n{}", t!("switch.error_update").replace("{}", &e.to_string()).red());
-                }
+            };
+            if let Some(error) = &error {
+                tracing::warn!("批量切换目录 {} 失败: {}", directory_id, error);
+            }
+            record.set_status(directory_id, status, error);
+            if let Err(e) = batch_switch::save(&record) {
+                tracing::warn!("{}", e);
             }
+            let name = record
+                .entries
+                .iter()
+                .find(|entry| entry.directory_id == directory_id)
+                .map(|entry| entry.directory_name.clone())
+                .unwrap_or_default();
+            progress.inc(&name);
         }
-        Err(e) => {
-            println!("\n{}", t!("switch.error").replace("{}", &e.to_string()).red());
+        progress.finish();
+
+        print_batch_record(&record);
+
+        if interrupt::requested() {
+            print_warning!(
+                "\n{}",
+                t!("switch.batch.interrupted").replace("{}", &record.count(BatchStatus::Pending).to_string())
+            );
+            return Ok(());
+        }
+        if record.retry_count() == 0
+            || !dialoguer::Confirm::new()
+                .with_prompt(t!("switch.batch.retry_prompt").replace("{}", &record.retry_count().to_string()))
+                .default(false)
+                .interact()?
+        {
+            break;
         }
     }
 
@@ -325,6 +1546,252 @@ pub async fn switch_menu(db: &DbState) -> Result<()> {
         .with_prompt(t!("common.continue"))
         .allow_empty(true)
         .interact()?;
-
     Ok(())
 }
+
+fn print_batch_record(record: &BatchRecord) {
+    use comfy_table::{Attribute, Cell, Color};
+
+    let mut table = super::create_table();
+    table.set_header(vec![
+        Cell::new(t!("directory.list.header_name"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("account.list.header_status"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("switch.batch.header_error"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+    ]);
+    for entry in &record.entries {
+        let (label, color) = match entry.status {
+            BatchStatus::Succeeded => (t!("switch.batch.status_succeeded"), Color::Green),
+            BatchStatus::Unchanged => (t!("switch.batch.status_unchanged"), Color::Grey),
+            BatchStatus::Failed => (t!("switch.batch.status_failed"), Color::Red),
+            BatchStatus::Pending => (t!("switch.batch.status_pending"), Color::Yellow),
+        };
+        table.add_row(vec![
+            Cell::new(&entry.directory_name),
+            Cell::new(label).fg(color),
+            Cell::new(entry.error.as_deref().unwrap_or_default()),
+        ]);
+    }
+    println!("\n{}", table);
+    println!(
+        "{}",
+        t!("switch.batch.summary")
+            .replacen("{}", &record.count(BatchStatus::Succeeded).to_string(), 1)
+            .replacen("{}", &record.count(BatchStatus::Unchanged).to_string(), 1)
+            .replacen("{}", &record.retry_count().to_string(), 1)
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_noop_switch_leaves_files_untouched() {
+        let dir = std::env::temp_dir().join(format!("claude-config-switch-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let directory_path = dir.display().to_string();
+        let token = "sk-ant-test-token";
+        let base_url = "https://api.anthropic.com";
+
+        let build = |base_url: &str| {
+            build_claude_settings(
+                r#"{"env": {"IS_SANDBOX": "1"}}"#,
+                token,
+                base_url,
+                "claude-sonnet-4-20250514",
+                "test",
+                AuthStyle::default(),
+                None,
+                &ProxyConfig::default(),
+                true,
+                false,
+            )
+            .unwrap()
+        };
+        let manager = ClaudeConfigManager::new(directory_path.clone());
+        let expected = build(base_url);
+
+        // 目录还没有配置时需要写入
+        assert!(!is_noop_switch(&manager, &expected).unwrap());
+
+        manager
+            .update_env_config_full(
+                token.to_string(),
+                base_url.to_string(),
+                AuthStyle::default(),
+                true,
+                &ProxyConfig::default(),
+            )
+            .unwrap();
+        write_claude_settings(&manager, &expected).unwrap();
+
+        let settings_file = manager.settings_file_path();
+        let before = fs::metadata(&settings_file).unwrap().modified().unwrap();
+        let content = fs::read_to_string(&settings_file).unwrap();
+
+        assert!(is_noop_switch(&manager, &expected).unwrap());
+        assert_eq!(fs::metadata(&settings_file).unwrap().modified().unwrap(), before);
+        assert_eq!(fs::read_to_string(&settings_file).unwrap(), content);
+
+        // 换一个 Base URL 就不再是空操作
+        let other = "https://proxy.example.com";
+        assert!(!is_noop_switch(&manager, &build(other)).unwrap());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_write_claude_settings_preserves_yaml() {
+        let dir = std::env::temp_dir().join(format!("claude-config-switch-{}", uuid::Uuid::new_v4()));
+        let yaml = dir.join(".claude").join("settings.local.yaml");
+        fs::create_dir_all(yaml.parent().unwrap()).unwrap();
+        fs::write(&yaml, "env:\n  ANTHROPIC_BASE_URL: https://old.example.com\n").unwrap();
+
+        let manager = ClaudeConfigManager::new(dir.display().to_string()).with_write_format(WriteFormat::Preserve);
+        let settings = serde_json::json!({ "env": { "ANTHROPIC_BASE_URL": "https://new.example.com" } });
+        write_claude_settings(&manager, &settings).unwrap();
+
+        // 写回 YAML 文件，不另外生成遮盖它的 settings.local.json
+        assert!(!manager.settings_file_path().exists());
+        assert!(fs::read_to_string(&yaml).unwrap().contains("https://new.example.com"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_switch_writes_verified_settings() {
+        let dir = std::env::temp_dir().join(format!("claude-config-switch-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let directory_path = dir.display().to_string();
+        let (account, directory) = switch_fixture(&directory_path);
+        let plan = SwitchPlan {
+            account: &account,
+            directory: &directory,
+            directory_path: directory_path.clone(),
+            token: account.token.clone(),
+            template: None,
+            skip_permissions: true,
+            use_proxy: false,
+        };
+        let expected = build_claude_settings(
+            r#"{"env": {"IS_SANDBOX": "1"}}"#,
+            &account.token,
+            &account.base_url,
+            &account.model,
+            &account.name,
+            account.auth_style,
+            None,
+            &ProxyConfig::default(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        let outcome = write_switch_files(&plan, &directory_path, &expected).await.unwrap();
+        assert!(matches!(outcome, SwitchOutcome::Applied));
+
+        // 切换最终留在磁盘上的就是生成的配置（写入后已读回核对）
+        let manager = ClaudeConfigManager::new(directory_path);
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(manager.settings_file_path()).unwrap()).unwrap();
+        assert_eq!(written["env"], expected["env"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_switch_keeps_unrelated_settings() {
+        let dir = std::env::temp_dir().join(format!("claude-config-switch-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(dir.join(".claude")).unwrap();
+        let directory_path = dir.display().to_string();
+        let (account, directory) = switch_fixture(&directory_path);
+
+        let manager = ClaudeConfigManager::new(directory_path.clone());
+        fs::write(
+            manager.settings_file_path(),
+            r#"{"mcpServers": {"docs": {"command": "npx"}}, "env": {"ANTHROPIC_MODEL": "claude-opus", "MY_VAR": "keep"}}"#,
+        )
+        .unwrap();
+
+        let plan = SwitchPlan {
+            account: &account,
+            directory: &directory,
+            directory_path: directory_path.clone(),
+            token: account.token.clone(),
+            template: None,
+            skip_permissions: true,
+            use_proxy: false,
+        };
+        let expected = build_claude_settings(
+            r#"{"env": {"IS_SANDBOX": "1"}}"#,
+            &account.token,
+            &account.base_url,
+            &account.model,
+            &account.name,
+            account.auth_style,
+            None,
+            &ProxyConfig::default(),
+            true,
+            false,
+        )
+        .unwrap();
+
+        let outcome = write_switch_files(&plan, &directory_path, &expected).await.unwrap();
+        assert!(matches!(outcome, SwitchOutcome::Applied));
+
+        // 切换只改动账号相关的配置，mcpServers、模型和用户自己的变量都保留
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(manager.settings_file_path()).unwrap()).unwrap();
+        assert_eq!(written["mcpServers"]["docs"]["command"], "npx");
+        assert_eq!(written["env"]["ANTHROPIC_MODEL"], "claude-opus");
+        assert_eq!(written["env"]["MY_VAR"], "keep");
+        assert_eq!(written["env"]["ANTHROPIC_BASE_URL"], account.base_url.as_str());
+        assert_eq!(written["permissions"], expected["permissions"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    fn switch_fixture(directory_path: &str) -> (Account, Directory) {
+        let now = chrono::Utc::now();
+        let account = Account {
+            id: 1,
+            name: "test".to_string(),
+            token: "sk-ant-test-token".to_string(),
+            base_url: "https://api.anthropic.com".to_string(),
+            model: String::new(),
+            auth_style: AuthStyle::default(),
+            http_proxy: String::new(),
+            https_proxy: String::new(),
+            no_proxy: String::new(),
+            note: None,
+            extra_auth_env_key: None,
+            staging_base_url: None,
+            token_command: None,
+            claude_md_template: None,
+            is_active: false,
+            last_used_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        let directory = Directory {
+            id: 1,
+            path: directory_path.to_string(),
+            name: "test".to_string(),
+            is_active: false,
+            is_relative: false,
+            is_locked: false,
+            expected_account_id: None,
+            last_switched_at: None,
+            deleted_at: None,
+            created_at: now,
+            updated_at: now,
+        };
+        (account, directory)
+    }
+}
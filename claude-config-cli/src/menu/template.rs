@@ -0,0 +1,327 @@
+use crate::{claude_config, models::*, print_error, print_success, print_warning, t, DbState};
+use anyhow::Result;
+use colored::Colorize;
+use comfy_table::{Attribute, Cell, Color};
+use dialoguer::{Confirm, Input, Select};
+
+pub async fn template_menu(db: &DbState) -> Result<()> {
+    let mut last_selection = 0;
+
+    loop {
+        let items = vec![
+            t!("common.back"),
+            t!("template.menu.list"),
+            t!("template.menu.add"),
+            t!("template.menu.edit"),
+            t!("template.menu.delete"),
+        ];
+
+        let selection = match Select::new()
+            .with_prompt(format!("\n{} (ESC {})", t!("template.menu.title"), t!("common.to_back")))
+            .items(&items)
+            .default(last_selection)
+            .interact_opt()? {
+                Some(sel) => sel,
+                None => break, // 用户按了ESC，返回上一级
+            };
+
+        last_selection = selection;
+
+        match selection {
+            0 => break,
+            1 => list_templates(db).await?,
+            2 => add_template(db).await?,
+            3 => edit_template(db).await?,
+            4 => delete_template(db).await?,
+            _ => unreachable!(),
+        }
+    }
+
+    Ok(())
+}
+
+/// 校验模板 JSON：必须是对象，且不能包含敏感信息
+fn validate_template_json(input: &str) -> Result<serde_json::Value, String> {
+    let value: serde_json::Value = serde_json::from_str(input)
+        .map_err(|e| t!("template.error.invalid_json").replace("{}", &e.to_string()))?;
+
+    if !value.is_object() {
+        return Err(t!("template.error.not_object").to_string());
+    }
+
+    let secret_keys = claude_config::find_secret_keys(&value);
+    if !secret_keys.is_empty() {
+        return Err(t!("template.error.contains_secret").replace("{}", &secret_keys.join(", ")));
+    }
+
+    Ok(value)
+}
+
+/// 在切换流程中选择模板（可不选）
+pub async fn select_template(db: &DbState) -> Result<Option<Template>> {
+    let db_lock = db.lock().await;
+    let templates = db_lock.get_templates().await?;
+    drop(db_lock);
+
+    if templates.is_empty() {
+        return Ok(None);
+    }
+
+    let mut items: Vec<String> = vec![t!("template.select.none").to_string()];
+    items.extend(templates.iter().map(|tpl| match &tpl.description {
+        Some(description) if !description.is_empty() => {
+            format!("{} - {}", tpl.name, description)
+        }
+        _ => tpl.name.clone(),
+    }));
+
+    let selection = Select::new()
+        .with_prompt(t!("template.select.prompt"))
+        .items(&items)
+        .default(0)
+        .interact_opt()?;
+
+    match selection {
+        Some(idx) if idx > 0 => Ok(Some(templates[idx - 1].clone())),
+        _ => Ok(None),
+    }
+}
+
+async fn list_templates(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let templates = db_lock.get_templates().await?;
+    drop(db_lock);
+
+    if templates.is_empty() {
+        print_warning!("\n{}", t!("template.list.no_records"));
+        return Ok(());
+    }
+
+    let mut table = super::create_table();
+    table.set_header(vec![
+        Cell::new(t!("template.list.header_id"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("template.list.header_name"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("template.list.header_description"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("template.list.header_settings"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+        Cell::new(t!("template.list.header_builtin"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
+    ]);
+
+    for template in &templates {
+        let is_builtin = if template.is_builtin { t!("template.list.builtin_yes") } else { "" };
+        table.add_row(vec![
+            template.id.to_string(),
+            template.name.clone(),
+            template.description.clone().unwrap_or_default(),
+            template.settings_json.clone(),
+            is_builtin.to_string(),
+        ]);
+    }
+
+    println!("\n{}", table);
+    println!("{}", t!("template.list.total").replace("{}", &templates.len().to_string()));
+
+    let _ = Input::<String>::new()
+        .with_prompt(t!("common.continue"))
+        .allow_empty(true)
+        .interact()?;
+
+    Ok(())
+}
+
+async fn add_template(db: &DbState) -> Result<()> {
+    println!("\n{}", t!("template.add.title").green().bold());
+    print_warning!("{}", t!("common.input_cancel_hint"));
+
+    let name: String = Input::new()
+        .with_prompt(t!("template.add.prompt_name"))
+        .allow_empty(true)
+        .interact_text()?;
+
+    if name.trim().is_empty() || name.trim().eq_ignore_ascii_case("q") {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let description: String = Input::new()
+        .with_prompt(t!("template.add.prompt_description"))
+        .allow_empty(true)
+        .interact_text()?;
+
+    print_warning!("{}", t!("template.add.json_hint"));
+    let settings_json: String = Input::new()
+        .with_prompt(t!("template.add.prompt_settings"))
+        .allow_empty(true)
+        .validate_with(|input: &String| -> Result<(), String> {
+            if input.trim().is_empty() {
+                return Ok(());
+            }
+            validate_template_json(input).map(|_| ())
+        })
+        .interact_text()?;
+
+    if settings_json.trim().is_empty() {
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    let db_lock = db.lock().await;
+    let request = CreateTemplateRequest {
+        name: name.clone(),
+        description: if description.is_empty() {
+            None
+        } else {
+            Some(description)
+        },
+        settings_json,
+    };
+
+    match db_lock.create_template(request).await {
+        Ok(_) => {
+            print_success!("\n{}", t!("template.add.success").replace("{}", &name));
+        }
+        Err(e) => {
+            print_error!("\n{}", t!("template.add.error").replace("{}", &e.to_string()));
+        }
+    }
+
+    Ok(())
+}
+
+async fn edit_template(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let templates = db_lock.get_templates().await?;
+    drop(db_lock);
+
+    if templates.is_empty() {
+        print_warning!("\n{}", t!("template.list.no_records"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(templates.iter().map(|tpl| tpl.name.clone()));
+
+    let selection = Select::new()
+        .with_prompt(t!("template.edit.prompt"))
+        .items(&items)
+        .interact_opt()?;
+
+    if let Some(idx) = selection {
+        if idx == 0 {
+            return Ok(());
+        }
+        let idx = idx - 1;
+        let template = &templates[idx];
+
+        print_warning!("{}", t!("common.input_cancel_hint"));
+
+        let name: String = Input::new()
+            .with_prompt(t!("template.add.prompt_name"))
+            .default(template.name.clone())
+            .allow_empty(true)
+            .interact_text()?;
+
+        let name = if name.trim().is_empty() {
+            template.name.clone()
+        } else {
+            name
+        };
+
+        let description: String = Input::new()
+            .with_prompt(t!("template.add.prompt_description"))
+            .default(template.description.clone().unwrap_or_default())
+            .allow_empty(true)
+            .interact_text()?;
+
+        let settings_json: String = Input::new()
+            .with_prompt(t!("template.add.prompt_settings"))
+            .default(template.settings_json.clone())
+            .allow_empty(true)
+            .validate_with(|input: &String| -> Result<(), String> {
+                if input.trim().is_empty() {
+                    return Ok(());
+                }
+                validate_template_json(input).map(|_| ())
+            })
+            .interact_text()?;
+
+        let settings_json = if settings_json.trim().is_empty() {
+            template.settings_json.clone()
+        } else {
+            settings_json
+        };
+
+        let db_lock = db.lock().await;
+        // 描述留空表示清除，数据库中保存为 NULL
+        let request = UpdateTemplateRequest {
+            name: Some(name),
+            description: Some(description),
+            settings_json: Some(settings_json),
+        };
+
+        match db_lock.update_template(template.id, request).await {
+            Ok(_) => {
+                print_success!("\n{}", t!("template.edit.success"));
+            }
+            Err(e) => {
+                print_error!("\n{}", t!("template.edit.error").replace("{}", &e.to_string()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn delete_template(db: &DbState) -> Result<()> {
+    let db_lock = db.lock().await;
+    let templates = db_lock.get_templates().await?;
+    drop(db_lock);
+
+    if templates.is_empty() {
+        print_warning!("\n{}", t!("template.list.no_records"));
+        return Ok(());
+    }
+
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    items.extend(templates.iter().map(|tpl| tpl.name.clone()));
+
+    let selection = Select::new()
+        .with_prompt(t!("template.delete.prompt"))
+        .items(&items)
+        .interact_opt()?;
+
+    if let Some(idx) = selection {
+        if idx == 0 {
+            return Ok(());
+        }
+        let idx = idx - 1;
+        let template = &templates[idx];
+
+        if Confirm::new()
+            .with_prompt(t!("template.delete.confirm").replace("{}", &template.name))
+            .default(false)
+            .interact()?
+        {
+            let db_lock = db.lock().await;
+            match db_lock.delete_template(template.id).await {
+                Ok(_) => {
+                    print_success!("\n{}", t!("template.delete.success"));
+                }
+                Err(e) => {
+                    print_error!("\n{}", t!("template.delete.error").replace("{}", &e.to_string()));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
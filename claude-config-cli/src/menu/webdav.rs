@@ -1,8 +1,12 @@
-use crate::{models::*, t, webdav, DbState};
+use crate::{bundle, models::*, offline, print_error, print_success, print_warning, settings, t, webdav, DbState};
 use anyhow::Result;
+use claude_config_core::checksum::ChecksumMismatch;
 use colored::Colorize;
 use comfy_table::{Attribute, Cell, Color};
-use dialoguer::{Confirm, Input, Select};
+use dialoguer::{Confirm, Input, Password, Select};
+
+/// 回显配置时代替密码显示的内容
+const MASKED_PASSWORD: &str = "********";
 
 pub async fn webdav_menu(db: &DbState) -> Result<()> {
     let mut last_selection = 0;
@@ -16,6 +20,8 @@ pub async fn webdav_menu(db: &DbState) -> Result<()> {
             t!("webdav.menu.upload_config"),
             t!("webdav.menu.download_config"),
             t!("webdav.menu.list_remote"),
+            t!("webdav.menu.browse_remote"),
+            t!("webdav.menu.namespace"),
             t!("webdav.menu.delete_config"),
         ];
 
@@ -32,13 +38,17 @@ pub async fn webdav_menu(db: &DbState) -> Result<()> {
 
         match selection {
             0 => break,
+            // 测试连接、上传、下载、列出和浏览远程文件需要联网
+            3..=7 if offline::refuse() => {}
             1 => list_configs(db).await?,
             2 => add_config(db).await?,
             3 => test_connection(db).await?,
             4 => upload_config(db).await?,
             5 => download_config(db).await?,
             6 => list_remote_files(db).await?,
-            7 => delete_config(db).await?,
+            7 => browse_remote(db).await?,
+            8 => set_namespace(db).await?,
+            9 => delete_config(db).await?,
             _ => unreachable!(),
         }
     }
@@ -53,7 +63,7 @@ async fn list_configs(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if configs.is_empty() {
-        println!("\n{}", t!("webdav.list.no_config").yellow());
+        print_warning!("\n{}", t!("webdav.list.no_config"));
         return Ok(());
     }
 
@@ -74,6 +84,9 @@ async fn list_configs(db: &DbState) -> Result<()> {
         Cell::new(t!("webdav.list.header_remote_path"))
             .add_attribute(Attribute::Bold)
             .fg(Color::Cyan),
+        Cell::new(t!("webdav.list.header_namespace"))
+            .add_attribute(Attribute::Bold)
+            .fg(Color::Cyan),
         Cell::new(t!("webdav.list.header_auto_sync"))
             .add_attribute(Attribute::Bold)
             .fg(Color::Cyan),
@@ -96,6 +109,10 @@ async fn list_configs(db: &DbState) -> Result<()> {
             config.url.clone(),
             config.username.clone(),
             config.remote_path.clone(),
+            config
+                .namespace
+                .clone()
+                .unwrap_or_else(|| t!("webdav.namespace.shared").to_string()),
             auto_sync.to_string(),
             status.to_string(),
         ]);
@@ -114,7 +131,7 @@ async fn list_configs(db: &DbState) -> Result<()> {
 
 async fn add_config(db: &DbState) -> Result<()> {
     println!("\n{}", t!("webdav.add.title").green().bold());
-    println!("{}", t!("common.input_cancel_hint").yellow());
+    print_warning!("{}", t!("common.input_cancel_hint"));
 
     let name: String = Input::new()
         .with_prompt(t!("webdav.add.prompt_name"))
@@ -122,7 +139,7 @@ async fn add_config(db: &DbState) -> Result<()> {
         .interact_text()?;
 
     if name.trim().is_empty() || name.trim().eq_ignore_ascii_case("q") {
-        println!("\n{}", t!("common.cancel").yellow());
+        print_warning!("\n{}", t!("common.cancel"));
         return Ok(());
     }
 
@@ -132,7 +149,7 @@ async fn add_config(db: &DbState) -> Result<()> {
         .interact_text()?;
 
     if url.trim().is_empty() || url.trim().eq_ignore_ascii_case("q") {
-        println!("\n{}", t!("common.cancel").yellow());
+        print_warning!("\n{}", t!("common.cancel"));
         return Ok(());
     }
 
@@ -142,24 +159,59 @@ async fn add_config(db: &DbState) -> Result<()> {
         .interact_text()?;
 
     if username.trim().is_empty() || username.trim().eq_ignore_ascii_case("q") {
-        println!("\n{}", t!("common.cancel").yellow());
+        print_warning!("\n{}", t!("common.cancel"));
         return Ok(());
     }
 
-    let password: String = Input::new()
+    let password: String = Password::new()
         .with_prompt(t!("webdav.add.prompt_password"))
-        .allow_empty(true)
-        .interact_text()?;
+        .allow_empty_password(true)
+        .interact()?;
 
     if password.trim().is_empty() || password.trim().eq_ignore_ascii_case("q") {
-        println!("\n{}", t!("common.cancel").yellow());
+        print_warning!("\n{}", t!("common.cancel"));
+        return Ok(());
+    }
+
+    // 回显时遮盖密码
+    println!("\n{}", t!("webdav.add.summary").bold());
+    println!("  {}: {}", t!("webdav.add.prompt_url"), url.trim());
+    println!("  {}: {}", t!("webdav.add.prompt_username"), username.trim());
+    println!("  {}: {}", t!("webdav.add.prompt_password"), MASKED_PASSWORD);
+
+    println!("\n{}", t!("webdav.test.testing").cyan());
+    let outcome = webdav::probe_endpoint(url.trim(), username.trim(), &password).await;
+    match &outcome {
+        webdav::ProbeOutcome::Success => print_success!("{}", t!("webdav.test.success")),
+        webdav::ProbeOutcome::AuthFailed(status) => print_error!(
+            "{}",
+            t!("webdav.add.probe_auth_failed").replace("{}", &status.to_string())
+        ),
+        webdav::ProbeOutcome::UnexpectedStatus(status) => print_error!(
+            "{}",
+            t!("webdav.add.probe_status").replace("{}", &status.to_string())
+        ),
+        webdav::ProbeOutcome::Unreachable(reason) => print_error!(
+            "{}",
+            t!("webdav.add.probe_unreachable").replace("{}", reason)
+        ),
+    }
+
+    // 连接测试未通过时，只有明确确认才保存
+    if !outcome.is_success()
+        && !Confirm::new()
+            .with_prompt(t!("webdav.add.save_anyway"))
+            .default(false)
+            .interact()?
+    {
+        print_warning!("\n{}", t!("webdav.add.not_saved"));
         return Ok(());
     }
 
     // 使用固定的默认值，不再询问用户
     let remote_path = "/claude-config";
     let auto_sync = false;
-    let sync_interval: i64 = 3600;
+    let sync_interval = (settings::load_or_default().await.sync_interval_mins * 60) as i64;
 
     let db_lock = db.lock().await;
     let pool = db_lock.get_pool();
@@ -177,10 +229,10 @@ async fn add_config(db: &DbState) -> Result<()> {
     .await
     {
         Ok(_) => {
-            println!("\n{}", t!("webdav.add.success").replace("{}", &name).green());
+            print_success!("\n{}", t!("webdav.add.success").replace("{}", &name));
         }
         Err(e) => {
-            println!("\n{}", t!("webdav.add.error").replace("{}", &e.to_string()).red());
+            print_error!("\n{}", t!("webdav.add.error").replace("{}", &e.to_string()));
         }
     }
 
@@ -194,7 +246,7 @@ async fn test_connection(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if configs.is_empty() {
-        println!("\n{}", t!("webdav.list.no_config").yellow());
+        print_warning!("\n{}", t!("webdav.list.no_config"));
         return Ok(());
     }
 
@@ -219,10 +271,10 @@ async fn test_connection(db: &DbState) -> Result<()> {
 
         match manager.test_connection().await {
             Ok(_) => {
-                println!("{}", t!("webdav.test.success").green());
+                print_success!("{}", t!("webdav.test.success"));
             }
             Err(e) => {
-                println!("{}", t!("webdav.test.error").replace("{}", &e.to_string()).red());
+                print_error!("{}", t!("webdav.test.error").replace("{}", &e.to_string()));
             }
         }
 
@@ -242,7 +294,7 @@ async fn upload_config(db: &DbState) -> Result<()> {
 
     if configs.is_empty() {
         drop(db_lock);
-        println!("\n{}", t!("webdav.list.no_config").yellow());
+        print_warning!("\n{}", t!("webdav.list.no_config"));
         return Ok(());
     }
 
@@ -270,41 +322,21 @@ async fn upload_config(db: &DbState) -> Result<()> {
                 chrono::Local::now().format("%Y%m%d-%H%M%S")
             ))
             .interact()?;
+        let note: String = Input::new()
+            .with_prompt(t!("webdav.upload.prompt_note"))
+            .allow_empty(true)
+            .interact_text()?;
 
         println!("\n{}", t!("webdav.upload.uploading").cyan());
 
         // 导出数据库配置
-        let db_lock = db.lock().await;
-        let accounts = db_lock
-            .get_accounts(GetAccountsRequest {
-                page: Some(1),
-                per_page: Some(1000),
-                search: None,
-                base_url: None,
-            })
-            .await?;
-
-        let base_urls = db_lock.get_base_urls().await?;
-        let claude_settings_json = db_lock.get_claude_settings().await?;
-        let claude_settings: serde_json::Value = serde_json::from_str(&claude_settings_json)?;
-
-        drop(db_lock);
-
-        let data = serde_json::json!({
-            "accounts": accounts.accounts,
-            "base_urls": base_urls,
-            "claude_settings": claude_settings,
-            "exported_at": chrono::Utc::now().to_rfc3339(),
-        });
+        let data = bundle::export_bundle(&*db.lock().await, Some(&note)).await?;
 
         let manager = webdav::WebDavManager::from_config(config.clone()).await?;
 
         match manager.upload_config(&data, &filename).await {
             Ok(_) => {
-                println!(
-                    "{}",
-                    t!("webdav.upload.success").replace("{}", &filename).green()
-                );
+                print_success!("{}", t!("webdav.upload.success").replace("{}", &filename));
 
                 // 记录同步日志
                 let db_lock = db.lock().await;
@@ -323,7 +355,7 @@ async fn upload_config(db: &DbState) -> Result<()> {
                 let _ = webdav::update_last_sync_time(pool, config.id).await;
             }
             Err(e) => {
-                println!("{}", t!("webdav.upload.error").replace("{}", &e.to_string()).red());
+                print_error!("{}", t!("webdav.upload.error").replace("{}", &e.to_string()));
             }
         }
 
@@ -343,7 +375,7 @@ async fn download_config(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if configs.is_empty() {
-        println!("\n{}", t!("webdav.list.no_config").yellow());
+        print_warning!("\n{}", t!("webdav.list.no_config"));
         return Ok(());
     }
 
@@ -368,7 +400,7 @@ async fn download_config(db: &DbState) -> Result<()> {
         let files = manager.list_remote_files().await?;
 
         if files.is_empty() {
-            println!("{}", t!("webdav.download.no_files").yellow());
+            print_warning!("{}", t!("webdav.download.no_files"));
             return Ok(());
         }
 
@@ -387,138 +419,340 @@ async fn download_config(db: &DbState) -> Result<()> {
             let file_idx = file_idx - 1;
             let filename = &files[file_idx];
 
-            println!("\n{}", t!("webdav.download.downloading").cyan());
-
-            match manager.download_config(filename).await {
-                Ok(data) => {
-                    // 导入配置到数据库
-                    let db_lock = db.lock().await;
-                    let pool = db_lock.get_pool();
-
-                    // 先删除所有现有账号和 Base URLs,实现完全覆盖
-                    println!("\n{}", t!("webdav.upload.clearing").yellow());
-
-                    let _ = sqlx::query("DELETE FROM accounts").execute(pool).await;
-
-                    let _ = sqlx::query("DELETE FROM base_urls").execute(pool).await;
-
-                    println!("{}", t!("webdav.upload.cleared").green());
-
-                    // 解析账号数据
-                    if let Some(accounts_array) = data.get("accounts").and_then(|v| v.as_array()) {
-                        println!("\n{}", t!("webdav.upload.importing_accounts").cyan());
-                        let mut success_count = 0;
-
-                        for account_data in accounts_array {
-                            if let (Some(name), Some(token), Some(base_url)) = (
-                                account_data.get("name").and_then(|v| v.as_str()),
-                                account_data.get("token").and_then(|v| v.as_str()),
-                                account_data.get("base_url").and_then(|v| v.as_str()),
-                            ) {
-                                let model = account_data
-                                    .get("model")
-                                    .and_then(|v| v.as_str())
-                                    .unwrap_or("claude-sonnet-4-20250514");
-
-                                let request = CreateAccountRequest {
-                                    name: name.to_string(),
-                                    token: token.to_string(),
-                                    base_url: base_url.to_string(),
-                                    model: model.to_string(),
-                                };
-
-                                if let Ok(_) = db_lock.create_account(request).await {
-                                    success_count += 1;
-                                }
-                            }
-                        }
-
-                        println!("{}", t!("webdav.upload.imported_accounts").replace("{}", &success_count.to_string()).green());
-                    }
+            import_remote_bundle(db, config, &manager, filename).await?;
 
-                    // 解析 Base URLs 数据
-                    if let Some(base_urls_array) = data.get("base_urls").and_then(|v| v.as_array())
-                    {
-                        println!("\n{}", t!("webdav.upload.importing_urls").cyan());
-                        let mut success_count = 0;
-
-                        for base_url_data in base_urls_array {
-                            if let (Some(name), Some(url)) = (
-                                base_url_data.get("name").and_then(|v| v.as_str()),
-                                base_url_data.get("url").and_then(|v| v.as_str()),
-                            ) {
-                                let description = base_url_data
-                                    .get("description")
-                                    .and_then(|v| v.as_str())
-                                    .map(|s| s.to_string());
-                                let api_key = base_url_data
-                                    .get("api_key")
-                                    .and_then(|v| v.as_str())
-                                    .map(|s| s.to_string());
-                                let is_default =
-                                    base_url_data.get("is_default").and_then(|v| v.as_bool());
-
-                                let request = CreateBaseUrlRequest {
-                                    name: name.to_string(),
-                                    url: url.to_string(),
-                                    description,
-                                    api_key,
-                                    is_default,
-                                };
-
-                                if let Ok(_) = db_lock.create_base_url(request).await {
-                                    success_count += 1;
-                                }
-                            }
-                        }
-
-                        println!(
-                            "{}",
-                            t!("webdav.upload.imported_urls").replace("{}", &success_count.to_string()).green()
-                        );
-                    }
+            let _ = Input::<String>::new()
+                .with_prompt(t!("common.continue"))
+                .allow_empty(true)
+                .interact()?;
+        }
+    }
+
+    Ok(())
+}
+
+/// 选择一个 WebDAV 配置，没有配置或取消时返回 None
+async fn select_config(db: &DbState) -> Result<Option<WebDavConfig>> {
+    let configs = webdav::get_webdav_configs(db.lock().await.get_pool()).await?;
+    if configs.is_empty() {
+        print_warning!("\n{}", t!("webdav.list.no_config"));
+        return Ok(None);
+    }
+
+    let mut items: Vec<String> = vec![t!("common.back_cancel").to_string()];
+    items.extend(configs.iter().map(|c| format!("{} - {}", c.name, c.url)));
+    match Select::new()
+        .with_prompt(t!("webdav.upload.select_config"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => Ok(configs.into_iter().nth(idx - 1)),
+        _ => Ok(None),
+    }
+}
+
+/// 浏览远程路径下各台机器的命名空间，选择其中的配置包导入
+async fn browse_remote(db: &DbState) -> Result<()> {
+    let Some(config) = select_config(db).await? else {
+        return Ok(());
+    };
+
+    println!("\n{}", t!("webdav.download.getting_files").cyan());
+    let manager = webdav::WebDavManager::from_config(config.clone()).await?;
+    let namespaces = match manager.list_namespaces().await {
+        Ok(namespaces) => namespaces,
+        Err(e) => {
+            print_error!("{}", t!("webdav.browse.error").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+
+    let mut items: Vec<String> = vec![
+        t!("common.back_cancel").to_string(),
+        format!("{} ({})", t!("webdav.namespace.shared"), config.remote_path),
+    ];
+    items.extend(namespaces.iter().map(|namespace| {
+        if config.namespace.as_deref() == Some(namespace.as_str()) {
+            format!("{} {}", namespace, t!("webdav.browse.this_machine"))
+        } else {
+            namespace.clone()
+        }
+    }));
+    let namespace = match Select::new()
+        .with_prompt(t!("webdav.browse.select_namespace"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(0) | None => return Ok(()),
+        Some(1) => None,
+        Some(idx) => Some(namespaces[idx - 2].clone()),
+    };
+
+    let manager = manager.with_namespace(namespace);
+    let files = match manager.list_remote_files().await {
+        Ok(files) => files,
+        Err(e) => {
+            print_error!("{}", t!("webdav.browse.error").replace("{}", &e.to_string()));
+            return Ok(());
+        }
+    };
+    if files.is_empty() {
+        print_warning!("{}", t!("webdav.download.no_files"));
+        return Ok(());
+    }
 
-                    // 解析 Claude 设置数据
-                    if let Some(claude_settings) = data.get("claude_settings") {
-                        let settings_json = serde_json::to_string(claude_settings)?;
-                        let _ = db_lock.save_claude_settings(&settings_json).await;
+    let mut file_items: Vec<String> = vec![t!("common.back_cancel").to_string()];
+    file_items.extend(files.iter().cloned());
+    let filename = match Select::new()
+        .with_prompt(t!("webdav.download.select_file"))
+        .items(&file_items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => &files[idx - 1],
+        _ => return Ok(()),
+    };
+
+    import_remote_bundle(db, &config, &manager, filename).await?;
+
+    let _ = Input::<String>::new()
+        .with_prompt(t!("common.continue"))
+        .allow_empty(true)
+        .interact()?;
+    Ok(())
+}
+
+/// 设置本机使用的命名空间：配置包上传到 远程路径/命名空间/ 下，与其他机器隔离
+async fn set_namespace(db: &DbState) -> Result<()> {
+    let Some(config) = select_config(db).await? else {
+        return Ok(());
+    };
+
+    println!(
+        "\n{}",
+        t!("webdav.namespace.current").replace(
+            "{}",
+            &config
+                .namespace
+                .clone()
+                .unwrap_or_else(|| t!("webdav.namespace.shared").to_string())
+        )
+    );
+
+    let hostname = webdav::hostname_namespace();
+    let mut items: Vec<String> = vec![t!("common.cancel").to_string()];
+    if let Some(hostname) = &hostname {
+        items.push(t!("webdav.namespace.use_hostname").replace("{}", hostname));
+    }
+    items.push(t!("webdav.namespace.custom").to_string());
+    items.push(t!("webdav.namespace.use_shared").to_string());
+
+    let selection = match Select::new()
+        .with_prompt(t!("common.select_operation"))
+        .items(&items)
+        .interact_opt()?
+    {
+        Some(idx) if idx > 0 => idx,
+        _ => return Ok(()),
+    };
+
+    // 选项：[取消, (主机名), 自定义, 共享路径]
+    let namespace = match (selection, hostname) {
+        (1, Some(hostname)) => Some(hostname),
+        (idx, _) if idx == items.len() - 1 => None,
+        _ => {
+            let input: String = Input::new()
+                .with_prompt(t!("webdav.namespace.prompt"))
+                .with_initial_text(config.namespace.clone().unwrap_or_default())
+                .validate_with(|input: &String| -> Result<(), String> {
+                    if webdav::sanitize_namespace(input).is_empty() {
+                        Err(t!("webdav.namespace.invalid").to_string())
+                    } else {
+                        Ok(())
                     }
+                })
+                .interact_text()?;
+            Some(webdav::sanitize_namespace(&input))
+        }
+    };
+
+    webdav::update_webdav_namespace(db.lock().await.get_pool(), config.id, namespace.as_deref()).await?;
+    match namespace {
+        Some(namespace) => print_success!(
+            "\n{}",
+            t!("webdav.namespace.updated")
+                .replace("{}", &format!("{}/{}", config.remote_path.trim_end_matches('/'), namespace))
+        ),
+        None => print_success!(
+            "\n{}",
+            t!("webdav.namespace.updated").replace("{}", &config.remote_path)
+        ),
+    }
+    Ok(())
+}
 
-                    println!(
-                        "{}",
-                        t!("webdav.download.success").replace("{}", filename).green()
-                    );
-
-                    // 记录同步日志
-                    let pool = db_lock.get_pool();
-                    let _ = webdav::create_sync_log(
-                        pool,
-                        CreateSyncLogRequest {
-                            webdav_config_id: config.id,
-                            sync_type: "download".to_string(),
-                            status: "success".to_string(),
-                            message: Some(t!("webdav.download.success_log").replace("{}", filename).to_string()),
-                        },
-                    )
-                    .await;
-
-                    let _ = webdav::update_last_sync_time(pool, config.id).await;
+/// 下载远程配置包，预览后按选择的方式导入
+async fn import_remote_bundle(
+    db: &DbState,
+    config: &WebDavConfig,
+    manager: &webdav::WebDavManager,
+    filename: &str,
+) -> Result<()> {
+    println!("\n{}", t!("webdav.download.downloading").cyan());
+
+    match manager.download_config(filename).await {
+        Ok(data) => {
+            // 先预览导入会产生的变化，确认后再修改数据库
+            print_bundle_meta(&data);
+            let db_lock = db.lock().await;
+            let plan = bundle::preview_import(&db_lock, &data).await?;
+            drop(db_lock);
+
+            print_import_plan(&plan);
+
+            let modes = vec![
+                t!("common.cancel"),
+                t!("webdav.import.mode_merge"),
+                t!("webdav.import.mode_overwrite"),
+            ];
+            let merge = match Select::new()
+                .with_prompt(t!("webdav.import.select_mode"))
+                .items(&modes)
+                .default(1)
+                .interact_opt()?
+            {
+                Some(1) => true,
+                Some(2) => false,
+                _ => {
+                    print_warning!("\n{}", t!("webdav.download.local_unchanged"));
+                    return Ok(());
                 }
+            };
+
+            let db_lock = db.lock().await;
+            if !merge {
+                print_warning!("\n{}", t!("webdav.upload.clearing"));
+            }
+            let summary = match bundle::import_bundle(&db_lock, &data, merge).await {
+                Ok(summary) => summary,
                 Err(e) => {
-                    println!("{}", t!("webdav.download.error").replace("{}", &e.to_string()).red());
+                    tracing::error!("导入配置包失败，已回滚: {:#}", e);
+                    print_error!("\n{}", t!("webdav.import.rolled_back").replace("{}", &format!("{:#}", e)));
+                    return Ok(());
                 }
+            };
+            if !merge {
+                print_success!("{}", t!("webdav.upload.cleared"));
             }
 
-            let _ = Input::<String>::new()
-                .with_prompt(t!("common.continue"))
-                .allow_empty(true)
-                .interact()?;
+            print_success!("{}", t!("webdav.upload.imported_accounts").replace("{}", &summary.accounts.to_string()));
+            print_success!(
+                "{}",
+                t!("webdav.upload.imported_urls").replace("{}", &summary.base_urls.to_string())
+            );
+            if summary.directory_locks > 0 {
+                print_success!(
+                    "{}",
+                    t!("webdav.upload.imported_locks")
+                        .replace("{}", &summary.directory_locks.to_string())
+                );
+            }
+
+            print_success!("{}", t!("webdav.download.success").replace("{}", filename));
+
+            // 记录同步日志
+            let pool = db_lock.get_pool();
+            let _ = webdav::create_sync_log(
+                pool,
+                CreateSyncLogRequest {
+                    webdav_config_id: config.id,
+                    sync_type: "download".to_string(),
+                    status: "success".to_string(),
+                    message: Some(t!("webdav.download.success_log").replace("{}", filename).to_string()),
+                },
+            )
+            .await;
+
+            let _ = webdav::update_last_sync_time(pool, config.id).await;
+        }
+        Err(e) => {
+            if let Some(mismatch) = e.downcast_ref::<ChecksumMismatch>() {
+                print_error!(
+                    "{}",
+                    t!("webdav.download.checksum_mismatch")
+                        .replacen("{}", &mismatch.expected, 1)
+                        .replacen("{}", &mismatch.actual, 1)
+                );
+                print_warning!("{}", t!("webdav.download.local_unchanged"));
+            } else {
+                print_error!("{}", t!("webdav.download.error").replace("{}", &e.to_string()));
+            }
         }
     }
 
     Ok(())
 }
 
+/// 以表格展示导入计划
+/// 输出配置包的导出时间、版本和备注
+pub fn print_bundle_meta(data: &serde_json::Value) {
+    let Some(meta) = bundle::bundle_meta(data) else {
+        return;
+    };
+    println!("\n{}", t!("webdav.import.meta_title").cyan().bold());
+    let unknown = || t!("webdav.import.meta_unknown").to_string();
+    println!("{}", t!("webdav.import.meta_exported_at").replace("{}", &meta.exported_at.unwrap_or_else(unknown)));
+    println!("{}", t!("webdav.import.meta_app_version").replace("{}", &meta.app_version.unwrap_or_else(unknown)));
+    if let Some(note) = meta.note {
+        println!("{}", t!("webdav.import.meta_note").replace("{}", &note));
+    }
+}
+
+fn print_import_plan(plan: &bundle::ImportPlan) {
+    println!("\n{}", t!("webdav.import.preview_title").cyan().bold());
+
+    if plan.items.is_empty() && plan.local_only.is_empty() {
+        print_warning!("{}", t!("webdav.import.empty"));
+        return;
+    }
+
+    let kind_label = |kind: bundle::ImportKind| match kind {
+        bundle::ImportKind::Account => t!("webdav.import.kind_account"),
+        bundle::ImportKind::BaseUrl => t!("webdav.import.kind_base_url"),
+    };
+
+    let mut table = super::create_table();
+    table.set_header(vec![
+        Cell::new(t!("webdav.import.header_kind")).add_attribute(Attribute::Bold),
+        Cell::new(t!("webdav.import.header_name")).add_attribute(Attribute::Bold),
+        Cell::new(t!("webdav.import.header_action")).add_attribute(Attribute::Bold),
+    ]);
+    for item in &plan.items {
+        let action = match item.action {
+            bundle::ImportAction::Add => Cell::new(t!("webdav.import.action_add")).fg(Color::Green),
+            bundle::ImportAction::Update => Cell::new(t!("webdav.import.action_update")).fg(Color::Yellow),
+            bundle::ImportAction::Skip => Cell::new(t!("webdav.import.action_skip")),
+        };
+        table.add_row(vec![Cell::new(kind_label(item.kind)), Cell::new(&item.key), action]);
+    }
+    for item in &plan.local_only {
+        table.add_row(vec![
+            Cell::new(kind_label(item.kind)),
+            Cell::new(&item.key),
+            Cell::new(t!("webdav.import.action_local_only")).fg(Color::Red),
+        ]);
+    }
+    println!("{}", table);
+
+    println!(
+        "{}",
+        t!("webdav.import.summary")
+            .replacen("{}", &plan.count(bundle::ImportAction::Add).to_string(), 1)
+            .replacen("{}", &plan.count(bundle::ImportAction::Update).to_string(), 1)
+            .replacen("{}", &plan.count(bundle::ImportAction::Skip).to_string(), 1)
+            .replacen("{}", &plan.local_only.len().to_string(), 1)
+    );
+}
+
 async fn list_remote_files(db: &DbState) -> Result<()> {
     let db_lock = db.lock().await;
     let pool = db_lock.get_pool();
@@ -526,7 +760,7 @@ async fn list_remote_files(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if configs.is_empty() {
-        println!("\n{}", t!("webdav.list.no_config").yellow());
+        print_warning!("\n{}", t!("webdav.list.no_config"));
         return Ok(());
     }
 
@@ -552,7 +786,7 @@ async fn list_remote_files(db: &DbState) -> Result<()> {
         match manager.list_remote_files().await {
             Ok(files) => {
                 if files.is_empty() {
-                    println!("{}", t!("webdav.download.no_files").yellow());
+                    print_warning!("{}", t!("webdav.download.no_files"));
                 } else {
                     println!("\n{}", t!("webdav.list.title").green().bold());
                     for (i, file) in files.iter().enumerate() {
@@ -561,7 +795,7 @@ async fn list_remote_files(db: &DbState) -> Result<()> {
                 }
             }
             Err(e) => {
-                println!("{}", t!("webdav.list.error").replace("{}", &e.to_string()).red());
+                print_error!("{}", t!("webdav.list.error").replace("{}", &e.to_string()));
             }
         }
 
@@ -581,7 +815,7 @@ async fn delete_config(db: &DbState) -> Result<()> {
     drop(db_lock);
 
     if configs.is_empty() {
-        println!("\n{}", t!("webdav.list.no_config").yellow());
+        print_warning!("\n{}", t!("webdav.list.no_config"));
         return Ok(());
     }
 
@@ -610,10 +844,10 @@ async fn delete_config(db: &DbState) -> Result<()> {
 
             match webdav::delete_webdav_config(pool, config.id).await {
                 Ok(_) => {
-                    println!("\n{}", t!("webdav.delete.success").green());
+                    print_success!("\n{}", t!("webdav.delete.success"));
                 }
                 Err(e) => {
-                    println!("\n{}", t!("webdav.delete.error").replace("{}", &e.to_string()).red());
+                    print_error!("\n{}", t!("webdav.delete.error").replace("{}", &e.to_string()));
                 }
             }
         }
@@ -1,6 +1,10 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::path::Path;
+
+pub use claude_config_core::models::{AuthStyle, ProxyConfig, PROXY_ENV_KEYS};
+pub use claude_config_core::settings_format::{JsonIndent, WriteFormat};
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
 pub struct Account {
@@ -9,17 +13,74 @@ pub struct Account {
     pub token: String,
     pub base_url: String,
     pub model: String,
+    pub auth_style: AuthStyle,
+    pub http_proxy: String,
+    pub https_proxy: String,
+    pub no_proxy: String,
+    /// 备注（如用途、到期时间），只用于显示
+    pub note: Option<String>,
+    /// 额外写入 Token 的自定义环境变量名，供不读取 Anthropic 标准变量的网关使用
+    pub extra_auth_env_key: Option<String>,
+    /// 备用（测试）Base URL，切换时可选择临时使用
+    pub staging_base_url: Option<String>,
+    /// 获取 Token 的命令（如 `pass show claude/work`），设置时 token 为空，切换时执行命令取得 Token
+    pub token_command: Option<String>,
+    /// 切换时写入的 CLAUDE.local.md 内容，None 时使用内置模板
+    pub claude_md_template: Option<String>,
     pub is_active: bool,
+    pub last_used_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// 账号 Token 的来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    /// Token 直接保存在数据库中
+    Inline(String),
+    /// 切换时执行命令，以去除首尾空白的标准输出作为 Token，不保存结果
+    Command(String),
+}
+
+impl Account {
+    pub fn token_source(&self) -> TokenSource {
+        match self.token_command.as_deref().map(str::trim) {
+            Some(command) if !command.is_empty() => TokenSource::Command(command.to_string()),
+            _ => TokenSource::Inline(self.token.clone()),
+        }
+    }
+
+    /// 使用备用地址的副本，只用于本次切换
+    pub fn with_staging_base_url(&self) -> Option<Account> {
+        let staging = self.staging_base_url.as_deref()?;
+        Some(Account {
+            base_url: staging.to_string(),
+            ..self.clone()
+        })
+    }
+
+    pub fn proxy_config(&self) -> ProxyConfig {
+        ProxyConfig {
+            http_proxy: self.http_proxy.clone(),
+            https_proxy: self.https_proxy.clone(),
+            no_proxy: self.no_proxy.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateAccountRequest {
     pub name: String,
     pub token: String,
     pub base_url: String,
     pub model: String,
+    pub auth_style: AuthStyle,
+    pub proxy: ProxyConfig,
+    pub note: Option<String>,
+    pub extra_auth_env_key: Option<String>,
+    pub staging_base_url: Option<String>,
+    /// 设置时不保存 token
+    pub token_command: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -28,6 +89,16 @@ pub struct UpdateAccountRequest {
     pub token: Option<String>,
     pub base_url: Option<String>,
     pub model: Option<String>,
+    pub auth_style: Option<AuthStyle>,
+    pub proxy: Option<ProxyConfig>,
+    /// 空字符串表示清除备注
+    pub note: Option<String>,
+    /// 空字符串表示不再额外写入
+    pub extra_auth_env_key: Option<String>,
+    /// 空字符串表示清除备用地址
+    pub staging_base_url: Option<String>,
+    /// 空字符串表示改为直接保存 Token；设置命令时同时清空已保存的 Token
+    pub token_command: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
@@ -36,20 +107,40 @@ pub struct Directory {
     pub path: String,
     pub name: String,
     pub is_active: bool,
+    pub is_relative: bool,
+    /// 锁定后拒绝切换、重置等修改配置的操作
+    pub is_locked: bool,
+    /// 预期账号：核对时磁盘配置与该账号不一致即视为偏离预期，应用其他账号时给出警告
+    pub expected_account_id: Option<i64>,
+    pub last_switched_at: Option<DateTime<Utc>>,
+    pub deleted_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Directory {
+    /// 解析目录的绝对路径：相对路径基于工作区根目录展开
+    pub fn resolved_path(&self, workspace_root: &Path) -> String {
+        if self.is_relative {
+            workspace_root.join(&self.path).to_string_lossy().to_string()
+        } else {
+            self.path.clone()
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateDirectoryRequest {
     pub path: String,
     pub name: String,
+    pub is_relative: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct UpdateDirectoryRequest {
     pub path: Option<String>,
     pub name: Option<String>,
+    pub is_relative: Option<bool>,
 }
 
 #[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
@@ -82,6 +173,55 @@ pub struct UpdateBaseUrlRequest {
     pub is_default: Option<bool>,
 }
 
+// 配置模板模型
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct Template {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub settings_json: String,
+    pub is_builtin: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateTemplateRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub settings_json: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateTemplateRequest {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub settings_json: Option<String>,
+}
+
+// 配置档案：可重复使用的账号 + 模板 + 权限 + 代理组合
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct Profile {
+    pub id: i64,
+    pub name: String,
+    /// 引用的账号被删除后为 None
+    pub account_id: Option<i64>,
+    pub template_id: Option<i64>,
+    pub skip_permissions: bool,
+    pub use_proxy: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateProfileRequest {
+    pub name: String,
+    pub account_id: i64,
+    pub template_id: Option<i64>,
+    pub skip_permissions: bool,
+    pub use_proxy: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SwitchAccountRequest {
     pub account_id: i64,
@@ -150,6 +290,8 @@ pub struct WebDavConfig {
     pub username: String,
     pub password: String,
     pub remote_path: String,
+    /// 远程路径下的子目录，用于区分多台机器；None 时使用共享的远程路径
+    pub namespace: Option<String>,
     pub auto_sync: bool,
     pub sync_interval: i64,
     pub is_active: bool,
@@ -158,6 +300,41 @@ pub struct WebDavConfig {
     pub updated_at: DateTime<Utc>,
 }
 
+// 目录配置变更记录（只保存配置文件哈希，不含 Token 原文）
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct ConfigAuditEntry {
+    pub id: i64,
+    pub directory_id: i64,
+    pub action: String,
+    pub before_hash: Option<String>,
+    pub after_hash: Option<String>,
+    /// 切换时实际写入的 Base URL，其他操作为 None
+    pub base_url: Option<String>,
+    pub actor: String,
+    pub created_at: DateTime<Utc>,
+}
+
+// 账号最近一次连通性测试结果（后台检测或手动测试）
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct AccountHealth {
+    pub account_id: i64,
+    /// HTTP 状态码，请求未完成时为 None
+    pub status: Option<i64>,
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+// 目录最近一次由本工具写入的配置快照；文件过大时只保存哈希
+#[derive(Debug, Serialize, Deserialize, FromRow, Clone)]
+pub struct SettingsSnapshot {
+    pub directory_id: i64,
+    pub settings_path: String,
+    /// 写入后的文件哈希，None 表示写入后文件不存在（例如重置目录）
+    pub content_hash: Option<String>,
+    pub content: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateSyncLogRequest {
     pub webdav_config_id: i64,
@@ -165,3 +342,82 @@ pub struct CreateSyncLogRequest {
     pub status: String,
     pub message: Option<String>,
 }
+
+// 应用设置（网络超时、重试、同步间隔）
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppSettings {
+    pub http_timeout_secs: u64,
+    pub max_retries: u32,
+    pub sync_interval_mins: u64,
+    /// 相对路径目录的工作区根目录，未设置时使用 $WORKSPACE 或用户主目录
+    pub workspace_root: Option<String>,
+    /// 上次切换的目录 ID，切换菜单以此作为默认选项
+    pub last_directory_id: Option<i64>,
+    /// 上次切换的账号 ID
+    pub last_account_id: Option<i64>,
+    /// 主菜单使用单键快捷模式
+    pub hotkey_menu: bool,
+    /// 离线模式：拒绝所有网络请求
+    pub offline: bool,
+    /// 纯文本输出：不使用表情符号和颜色
+    pub plain_output: bool,
+    /// 控制台消息前加时间和级别，并同时写入日志文件
+    pub console_timestamps: bool,
+    /// 写入格式：生效配置为 YAML / TOML 时是否按原格式写回
+    pub write_format: WriteFormat,
+    /// 写入 JSON 配置时的缩进
+    pub indent: JsonIndent,
+    /// 目录、账号列表和日志每页显示的条数
+    pub page_size: usize,
+    /// 账号、目录名称的最大长度（字符数）
+    pub max_name_length: usize,
+    /// 目录列表中显示每个目录的 Base URL 主机和是否已写入 Token（需要读取每个目录的配置文件）
+    pub directory_env_summary: bool,
+    /// 切换账号后确保 git 仓库的 .gitignore 忽略生成的 settings.local.json 和 CLAUDE.local.md
+    pub gitignore_generated: bool,
+    /// 允许 `.claude` 通过符号链接指向目录之外（如共享的配置目录），写入前不再确认
+    pub allow_symlinked_claude_dir: bool,
+    /// 值为空的环境变量（如有意留空、改用全局配置的 Base URL 或 Token）不写入 env，已有的同名变量被移除
+    pub omit_empty_env: bool,
+    /// 批量操作影响的条数达到此值时，需要输入条数确认
+    pub bulk_confirm_threshold: usize,
+    /// 后台定期检测账号可用性
+    pub health_check: bool,
+    /// 后台检测的间隔（分钟）
+    pub health_check_interval_mins: u64,
+    /// 切换成功后执行的命令模板，支持 {dir}、{account}、{base_url}（以环境变量 CLAUDE_HOOK_* 传入）；None 表示不执行
+    pub post_switch_hook: Option<String>,
+    /// 钩子执行失败时切换也视为失败
+    pub post_switch_hook_strict: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            http_timeout_secs: 30,
+            max_retries: 0,
+            sync_interval_mins: 60,
+            workspace_root: None,
+            last_directory_id: None,
+            last_account_id: None,
+            hotkey_menu: false,
+            offline: false,
+            plain_output: false,
+            console_timestamps: false,
+            write_format: WriteFormat::default(),
+            indent: JsonIndent::default(),
+            page_size: 20,
+            max_name_length: crate::names::DEFAULT_MAX_NAME_LENGTH,
+            directory_env_summary: false,
+            gitignore_generated: true,
+            allow_symlinked_claude_dir: false,
+            omit_empty_env: false,
+            bulk_confirm_threshold: 10,
+            health_check: false,
+            health_check_interval_mins: 30,
+            post_switch_hook: None,
+            post_switch_hook_strict: false,
+        }
+    }
+}
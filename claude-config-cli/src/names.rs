@@ -0,0 +1,105 @@
+//! 账号、目录名称的校验：去除首尾空白，限制长度，拒绝控制字符（包括换行），
+//! 避免表格错位和终端显示异常。列表中显示时再截断，数据库保存完整名称。
+
+use crate::t;
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// 名称默认的最大长度（字符数）
+pub const DEFAULT_MAX_NAME_LENGTH: usize = 64;
+
+/// 列表中显示名称的最大字符数，超出部分以省略号表示
+const NAME_DISPLAY_CHARS: usize = 32;
+
+static MAX_NAME_LENGTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_NAME_LENGTH);
+
+/// 按设置初始化名称的最大长度，0 视为默认值
+pub fn init(max_length: usize) {
+    let max_length = if max_length == 0 { DEFAULT_MAX_NAME_LENGTH } else { max_length };
+    MAX_NAME_LENGTH.store(max_length, Ordering::Relaxed);
+}
+
+pub fn max_length() -> usize {
+    MAX_NAME_LENGTH.load(Ordering::Relaxed)
+}
+
+/// 名称不合法的原因
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameError {
+    Empty,
+    TooLong { max: usize, actual: usize },
+    ControlChar,
+}
+
+impl fmt::Display for NameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "{}", t!("name.empty")),
+            NameError::TooLong { max, actual } => write!(
+                f,
+                "{}",
+                t!("name.too_long")
+                    .replacen("{}", &max.to_string(), 1)
+                    .replacen("{}", &actual.to_string(), 1)
+            ),
+            NameError::ControlChar => write!(f, "{}", t!("name.control_char")),
+        }
+    }
+}
+
+impl std::error::Error for NameError {}
+
+/// 校验名称并返回去除首尾空白后的值
+pub fn validate_name(raw: &str) -> Result<String, NameError> {
+    validate_name_with(raw, max_length())
+}
+
+fn validate_name_with(raw: &str, max: usize) -> Result<String, NameError> {
+    let name = raw.trim();
+    if name.is_empty() {
+        return Err(NameError::Empty);
+    }
+    if name.chars().any(char::is_control) {
+        return Err(NameError::ControlChar);
+    }
+    let actual = name.chars().count();
+    if actual > max {
+        return Err(NameError::TooLong { max, actual });
+    }
+    Ok(name.to_string())
+}
+
+/// 列表中显示的名称，过长时截断并以省略号结尾
+pub fn display_name(name: &str) -> String {
+    if name.chars().count() <= NAME_DISPLAY_CHARS {
+        return name.to_string();
+    }
+    let head: String = name.chars().take(NAME_DISPLAY_CHARS - 1).collect();
+    format!("{}…", head)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_name() {
+        assert_eq!(validate_name_with("  work  ", 64).unwrap(), "work");
+        assert_eq!(validate_name_with("   ", 64), Err(NameError::Empty));
+        assert_eq!(validate_name_with("a\nb", 64), Err(NameError::ControlChar));
+        assert_eq!(validate_name_with("a\u{1b}[31mb", 64), Err(NameError::ControlChar));
+        assert_eq!(validate_name_with(&"名".repeat(64), 64).unwrap().chars().count(), 64);
+        assert_eq!(
+            validate_name_with(&"x".repeat(65), 64),
+            Err(NameError::TooLong { max: 64, actual: 65 })
+        );
+    }
+
+    #[test]
+    fn test_display_name_truncates_long_names() {
+        assert_eq!(display_name("short"), "short");
+        let display = display_name(&"x".repeat(40));
+        assert_eq!(display.chars().count(), NAME_DISPLAY_CHARS);
+        assert!(display.ends_with('…'));
+    }
+}
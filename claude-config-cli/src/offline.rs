@@ -0,0 +1,46 @@
+//! 离线模式：启用后拒绝所有网络请求（账号连通性测试、WebDAV 同步），配置文件操作不受影响
+
+use crate::t;
+use colored::Colorize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// 命令行 --offline 强制开启，设置中无法关闭
+static FORCED: AtomicBool = AtomicBool::new(false);
+/// 设置中保存的离线模式
+static PERSISTED: AtomicBool = AtomicBool::new(false);
+
+/// 启动时根据命令行参数和应用设置初始化
+pub fn init(forced: bool, persisted: bool) {
+    FORCED.store(forced, Ordering::Relaxed);
+    PERSISTED.store(persisted, Ordering::Relaxed);
+}
+
+/// 设置中切换离线模式后同步运行时状态
+pub fn set_persisted(enabled: bool) {
+    PERSISTED.store(enabled, Ordering::Relaxed);
+}
+
+/// 是否由 --offline 强制开启
+pub fn is_forced() -> bool {
+    FORCED.load(Ordering::Relaxed)
+}
+
+pub fn is_enabled() -> bool {
+    is_forced() || PERSISTED.load(Ordering::Relaxed)
+}
+
+/// 发起网络请求前调用，离线模式下返回错误
+pub fn ensure_online() -> anyhow::Result<()> {
+    if is_enabled() {
+        anyhow::bail!(t!("offline.enabled"));
+    }
+    Ok(())
+}
+
+/// 菜单入口使用：离线模式下打印提示并返回 true
+pub fn refuse() -> bool {
+    if is_enabled() {
+        println!("\n{}", t!("offline.enabled").yellow());
+    }
+    is_enabled()
+}
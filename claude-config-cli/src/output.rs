@@ -0,0 +1,199 @@
+//! 输出样式：无表情符号 / 无颜色模式，照顾色弱用户和不支持 emoji 的终端
+//!
+//! 命令行 --no-emoji / --no-color 与设置中的“纯文本输出”任一开启即生效，
+//! 设置了 NO_COLOR 环境变量时同样关闭颜色。
+//!
+//! 菜单中的成功 / 警告 / 错误消息通过 `print_success!` 等宏输出，开启“控制台时间和级别”后
+//! 加上与日志文件一致的时间和级别前缀，并以 `console` 为 target 同时写入日志文件。
+
+use crate::t;
+use colored::Colorize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::Level;
+
+static NO_EMOJI_FLAG: AtomicBool = AtomicBool::new(false);
+static NO_COLOR_FLAG: AtomicBool = AtomicBool::new(false);
+/// 设置中保存的纯文本输出（同时关闭表情和颜色）
+static PLAIN_OUTPUT: AtomicBool = AtomicBool::new(false);
+/// 设置中保存的控制台时间和级别前缀
+static CONSOLE_TIMESTAMPS: AtomicBool = AtomicBool::new(false);
+
+/// 启动时根据命令行参数和应用设置初始化
+pub fn init(no_emoji: bool, no_color: bool, plain_output: bool) {
+    NO_EMOJI_FLAG.store(no_emoji, Ordering::Relaxed);
+    NO_COLOR_FLAG.store(no_color, Ordering::Relaxed);
+    PLAIN_OUTPUT.store(plain_output, Ordering::Relaxed);
+    apply_color();
+}
+
+/// 设置中切换纯文本输出后同步运行时状态
+pub fn set_plain_output(enabled: bool) {
+    PLAIN_OUTPUT.store(enabled, Ordering::Relaxed);
+    apply_color();
+}
+
+/// 启动时和设置中切换后同步控制台时间前缀
+pub fn set_console_timestamps(enabled: bool) {
+    CONSOLE_TIMESTAMPS.store(enabled, Ordering::Relaxed);
+}
+
+pub fn no_emoji() -> bool {
+    NO_EMOJI_FLAG.load(Ordering::Relaxed) || PLAIN_OUTPUT.load(Ordering::Relaxed)
+}
+
+pub fn no_color() -> bool {
+    NO_COLOR_FLAG.load(Ordering::Relaxed)
+        || PLAIN_OUTPUT.load(Ordering::Relaxed)
+        || std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
+}
+
+/// colored 的全局开关；表格颜色在 create_table 中单独处理
+fn apply_color() {
+    if no_color() {
+        colored::control::set_override(false);
+    } else {
+        colored::control::unset_override();
+    }
+}
+
+/// 有含义的符号替换为 ASCII 标签，其余表情符号直接去掉
+fn ascii_label(c: char) -> Option<&'static str> {
+    match c {
+        '✓' | '✅' => Some("[OK]"),
+        '✗' | '❌' => Some("[X]"),
+        '⚠' => Some("[!]"),
+        '🔒' => Some("[LOCKED]"),
+        _ => None,
+    }
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF | 0x2300..=0x23FF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0xFE0F | 0x200D
+    )
+}
+
+/// 去掉文本中的表情符号，并清理其后多余的空格
+pub fn strip_emoji(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut skip_spaces = false;
+
+    for c in text.chars() {
+        if let Some(label) = ascii_label(c) {
+            out.push_str(label);
+            skip_spaces = false;
+        } else if is_emoji(c) {
+            skip_spaces = out.is_empty() || out.ends_with(' ');
+        } else if !(skip_spaces && c == ' ') {
+            skip_spaces = false;
+            out.push(c);
+        }
+    }
+
+    out
+}
+
+/// 代码中直接使用的符号（如 "✓"），无表情模式下换成 ASCII 标签
+pub fn symbol(symbol: &'static str) -> &'static str {
+    if !no_emoji() {
+        return symbol;
+    }
+    symbol.chars().next().and_then(ascii_label).unwrap_or(symbol)
+}
+
+/// 活跃状态标签
+pub fn status_label(active: bool) -> &'static str {
+    match (active, no_emoji()) {
+        (true, false) => t!("account.list.status_active"),
+        (false, false) => t!("account.list.status_inactive"),
+        (true, true) => t!("output.ascii.active"),
+        (false, true) => t!("output.ascii.inactive"),
+    }
+}
+
+/// 目录是否存在的标签
+pub fn exists_label(exists: bool) -> &'static str {
+    match (exists, no_emoji()) {
+        (true, false) => t!("directory.list.exists"),
+        (false, false) => t!("directory.list.not_exists"),
+        (true, true) => t!("output.ascii.exists"),
+        (false, true) => t!("output.ascii.missing"),
+    }
+}
+
+/// 级别标签，宽度与日志文件一致（右对齐 5 个字符）
+fn level_label(level: Level) -> String {
+    let label = format!("{:>5}", level.as_str());
+    match level {
+        Level::ERROR => label.red().to_string(),
+        Level::WARN => label.yellow().to_string(),
+        _ => label.green().to_string(),
+    }
+}
+
+/// 输出一条菜单消息：按级别着色；开启时间前缀时加上时间和级别，并写入日志文件。
+/// 消息开头的换行保留在前缀之前，与原来 `println!("\n{}", ...)` 的排版一致
+pub fn console(level: Level, message: String) {
+    let body = message.trim_start_matches('\n');
+    for _ in body.len()..message.len() {
+        println!();
+    }
+    let colored = match level {
+        Level::ERROR => body.red(),
+        Level::WARN => body.yellow(),
+        _ => body.green(),
+    };
+
+    if !CONSOLE_TIMESTAMPS.load(Ordering::Relaxed) {
+        println!("{}", colored);
+        return;
+    }
+
+    match level {
+        Level::ERROR => tracing::error!(target: "console", "{}", body),
+        Level::WARN => tracing::warn!(target: "console", "{}", body),
+        _ => tracing::info!(target: "console", "{}", body),
+    }
+    let time = chrono::Local::now().format("%H:%M:%S").to_string();
+    println!("{} {} {}", time.dimmed(), level_label(level), colored);
+}
+
+/// 成功消息（绿色，INFO）
+#[macro_export]
+macro_rules! print_success {
+    ($($arg:tt)*) => {
+        $crate::output::console(tracing::Level::INFO, format!($($arg)*))
+    };
+}
+
+/// 提示 / 警告消息（黄色，WARN）
+#[macro_export]
+macro_rules! print_warning {
+    ($($arg:tt)*) => {
+        $crate::output::console(tracing::Level::WARN, format!($($arg)*))
+    };
+}
+
+/// 错误消息（红色，ERROR）
+#[macro_export]
+macro_rules! print_error {
+    ($($arg:tt)*) => {
+        $crate::output::console(tracing::Level::ERROR, format!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_emoji() {
+        assert_eq!(strip_emoji("📋 账号管理"), "账号管理");
+        assert_eq!(strip_emoji("🗑️  Delete Account"), "Delete Account");
+        assert_eq!(strip_emoji("✓ 存在"), "[OK] 存在");
+        assert_eq!(strip_emoji("⚠️ 警告: {}"), "[!] 警告: {}");
+        assert_eq!(strip_emoji("状态 🟢 活跃"), "状态 活跃");
+        assert_eq!(strip_emoji("a → b"), "a → b");
+    }
+}
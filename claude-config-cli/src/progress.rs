@@ -0,0 +1,89 @@
+use crate::t;
+use indicatif::{ProgressBar, ProgressStyle};
+use std::time::Instant;
+
+/// 非交互模式下输出日志的次数（约每 10% 输出一次）
+const LOG_STEPS: u64 = 10;
+
+/// 进度的展示方式
+enum ProgressMode {
+    /// 终端进度条
+    Bar(ProgressBar),
+    /// 非终端输出时逐项打印 "[n/total]"
+    Plain,
+    /// 非交互模式：不显示进度条，只定期写日志
+    Log,
+}
+
+/// 批量操作的进度报告
+pub struct Progress {
+    mode: ProgressMode,
+    label: String,
+    total: u64,
+    done: u64,
+    started: Instant,
+}
+
+impl Progress {
+    /// 交互模式下，标准错误是终端时显示进度条，否则逐项打印；非交互模式只写日志
+    pub fn new(label: &str, total: usize, interactive: bool) -> Self {
+        let total = total as u64;
+        let mode = if !interactive {
+            ProgressMode::Log
+        } else if console::Term::stderr().is_term() {
+            let bar = ProgressBar::new(total);
+            if let Ok(style) =
+                ProgressStyle::with_template("{prefix} [{bar:40.cyan/blue}] {pos}/{len} {msg}")
+            {
+                bar.set_style(style.progress_chars("=> "));
+            }
+            bar.set_prefix(label.to_string());
+            ProgressMode::Bar(bar)
+        } else {
+            ProgressMode::Plain
+        };
+
+        Self {
+            mode,
+            label: label.to_string(),
+            total,
+            done: 0,
+            started: Instant::now(),
+        }
+    }
+
+    /// 完成一项
+    pub fn inc(&mut self, item: &str) {
+        self.done += 1;
+        match &self.mode {
+            ProgressMode::Bar(bar) => {
+                bar.set_message(item.to_string());
+                bar.inc(1);
+            }
+            ProgressMode::Plain => eprintln!("[{}/{}] {}", self.done, self.total, item),
+            ProgressMode::Log => {
+                let step = (self.total / LOG_STEPS).max(1);
+                if self.done.is_multiple_of(step) || self.done == self.total {
+                    tracing::info!("{}: {}/{}", self.label, self.done, self.total);
+                }
+            }
+        }
+    }
+
+    /// 结束进度并输出汇总
+    pub fn finish(self) {
+        let summary = t!("progress.summary")
+            .replacen("{}", &self.label, 1)
+            .replacen("{}", &self.done.to_string(), 1)
+            .replacen("{}", &format!("{:.1}", self.started.elapsed().as_secs_f64()), 1);
+
+        match self.mode {
+            ProgressMode::Bar(bar) => {
+                bar.finish_and_clear();
+                println!("{}", summary);
+            }
+            ProgressMode::Plain => println!("{}", summary),
+            ProgressMode::Log => tracing::info!("{}", summary),
+        }
+    }
+}
@@ -1,5 +1,9 @@
-use crate::models::AppSettings;
-use std::path::PathBuf;
+use crate::database::Database;
+use crate::models::{AppSettings, JsonIndent, WebDavConfig, WriteFormat};
+use crate::webdav;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
 pub struct SettingsManager {
@@ -7,107 +11,341 @@ pub struct SettingsManager {
 }
 
 impl SettingsManager {
-    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new() -> Result<Self> {
         let settings_dir = Self::get_settings_directory()?;
-        
+
         // 确保配置目录存在
         if !settings_dir.exists() {
             std::fs::create_dir_all(&settings_dir)?;
         }
-        
+
         let settings_file = settings_dir.join("settings.json");
-        
-        Ok(Self {
-            settings_file,
-        })
+        Self::migrate_legacy_settings(&settings_file, Self::get_legacy_settings_directory().as_deref());
+
+        Ok(Self { settings_file })
     }
-    
-    fn get_settings_directory() -> Result<PathBuf, Box<dyn std::error::Error>> {
-        #[cfg(target_os = "windows")]
-        {
-            let appdata = std::env::var("APPDATA")
-                .map_err(|_| "APPDATA environment variable not found")?;
-            Ok(PathBuf::from(appdata).join("ClaudeConfigManager"))
+
+    fn get_settings_directory() -> Result<PathBuf> {
+        Ok(crate::config_manager::ConfigManager::get_app_data_dir())
+    }
+
+    /// 旧版本的设置目录：Windows 为 %APPDATA%\ClaudeConfigManager，
+    /// macOS 为 ~/Library/Application Support/ClaudeConfigManager，其他平台与新位置相同
+    fn get_legacy_settings_directory() -> Option<PathBuf> {
+        if cfg!(target_os = "windows") {
+            std::env::var("APPDATA")
+                .ok()
+                .map(|appdata| PathBuf::from(appdata).join("ClaudeConfigManager"))
+        } else if cfg!(target_os = "macos") {
+            std::env::var("HOME")
+                .ok()
+                .map(|home| PathBuf::from(home).join("Library/Application Support/ClaudeConfigManager"))
+        } else {
+            None
         }
-        
-        #[cfg(target_os = "macos")]
-        {
-            let home = std::env::var("HOME")
-                .map_err(|_| "HOME environment variable not found")?;
-            Ok(PathBuf::from(home).join("Library/Application Support/ClaudeConfigManager"))
+    }
+
+    /// 首次运行新版本时，把旧设置目录中的 settings.json 复制到新的应用数据目录；旧文件保留不动
+    fn migrate_legacy_settings(target: &Path, legacy_dir: Option<&Path>) {
+        if target.exists() {
+            return;
         }
-        
-        #[cfg(target_os = "linux")]
-        {
-            // 按照XDG规范
-            let config_home = std::env::var("XDG_CONFIG_HOME")
-                .unwrap_or_else(|_| {
-                    let home = std::env::var("HOME").unwrap_or_else(|_| "/tmp".to_string());
-                    format!("{}/.config", home)
-                });
-            Ok(PathBuf::from(config_home).join("claude-config-manager"))
+        let Some(legacy) = legacy_dir.map(|dir| dir.join("settings.json")) else {
+            return;
+        };
+        if legacy == target || !legacy.is_file() {
+            return;
         }
+
+        match std::fs::copy(&legacy, target) {
+            Ok(_) => tracing::info!("已将旧设置 {} 迁移到 {}", legacy.display(), target.display()),
+            Err(e) => tracing::warn!("迁移旧设置 {} 失败: {}", legacy.display(), e),
+        }
+    }
+
+    /// 获取设置文件路径
+    pub fn settings_file(&self) -> &PathBuf {
+        &self.settings_file
     }
-    
-    pub async fn load_settings(&self) -> Result<AppSettings, Box<dyn std::error::Error>> {
+
+    pub async fn load_settings(&self) -> Result<AppSettings> {
         if !self.settings_file.exists() {
             // 如果设置文件不存在，返回默认设置并创建文件
             let default_settings = AppSettings::default();
             self.save_settings(&default_settings).await?;
             return Ok(default_settings);
         }
-        
+
         let content = fs::read_to_string(&self.settings_file).await?;
-        let settings: AppSettings = serde_json::from_str(&content)
-            .unwrap_or_else(|_| AppSettings::default());
-        
+        let settings: AppSettings = serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!("设置文件解析失败，使用默认设置: {}", e);
+            AppSettings::default()
+        });
+
         Ok(settings)
     }
-    
-    pub async fn save_settings(&self, settings: &AppSettings) -> Result<(), Box<dyn std::error::Error>> {
+
+    pub async fn save_settings(&self, settings: &AppSettings) -> Result<()> {
         let json_content = serde_json::to_string_pretty(settings)?;
         fs::write(&self.settings_file, json_content).await?;
         Ok(())
     }
-    
-    pub fn get_default_allowed_tools() -> Vec<String> {
-        vec![
-            "Read".to_string(),
-            "Write".to_string(),
-            "Edit".to_string(),
-            "MultiEdit".to_string(),
-            "Bash".to_string(),
-            "Glob".to_string(),
-            "Grep".to_string(),
-            "WebSearch".to_string(),
-            "WebFetch".to_string(),
-            "Task".to_string(),
-            "TodoWrite".to_string(),
-            "BashOutput".to_string(),
-            "KillBash".to_string(),
-            "NotebookEdit".to_string(),
-        ]
+}
+
+/// 读取应用设置，失败时回退到默认值（与未引入设置文件前的行为一致）
+pub async fn load_or_default() -> AppSettings {
+    let result = match SettingsManager::new() {
+        Ok(manager) => manager.load_settings().await,
+        Err(e) => Err(e),
+    };
+
+    result.unwrap_or_else(|e| {
+        tracing::warn!("读取应用设置失败，使用默认设置: {}", e);
+        AppSettings::default()
+    })
+}
+
+/// 记录上次切换的目录和账号，传入 None 清除记录
+pub async fn save_last_switch(directory_id: Option<i64>, account_id: Option<i64>) -> Result<()> {
+    let manager = SettingsManager::new()?;
+    let mut settings = manager.load_settings().await?;
+    settings.last_directory_id = directory_id;
+    settings.last_account_id = account_id;
+    manager.save_settings(&settings).await
+}
+
+/// 当前工作区根目录：设置中的值优先，其次是 $WORKSPACE 环境变量，最后是用户主目录
+pub fn workspace_root(settings: &AppSettings) -> PathBuf {
+    settings
+        .workspace_root
+        .as_deref()
+        .filter(|root| !root.trim().is_empty())
+        .map(PathBuf::from)
+        .or_else(|| {
+            std::env::var("WORKSPACE")
+                .ok()
+                .filter(|root| !root.trim().is_empty())
+                .map(PathBuf::from)
+        })
+        .or_else(|| directories::UserDirs::new().map(|dirs| dirs.home_dir().to_path_buf()))
+        .unwrap_or_else(|| PathBuf::from("."))
+}
+
+/// 读取设置并返回当前工作区根目录
+pub async fn load_workspace_root() -> PathBuf {
+    workspace_root(&load_or_default().await)
+}
+
+/// 当前设置的配置写入格式
+pub async fn load_write_format() -> WriteFormat {
+    load_or_default().await.write_format
+}
+
+/// 当前设置的 JSON 配置缩进
+pub async fn load_indent() -> JsonIndent {
+    load_or_default().await.indent
+}
+
+/// 当前设置是否不写入值为空的环境变量
+pub async fn load_omit_empty_env() -> bool {
+    load_or_default().await.omit_empty_env
+}
+
+/// 当前设置的每页显示条数（至少为 1）
+pub async fn load_page_size() -> usize {
+    load_or_default().await.page_size.max(1)
+}
+
+/// 当前设置的批量操作确认阈值（至少为 1）
+pub async fn load_bulk_confirm_threshold() -> usize {
+    load_or_default().await.bulk_confirm_threshold.max(1)
+}
+
+/// 应用设置备份的格式版本
+const APP_SETTINGS_BACKUP_VERSION: u32 = 1;
+
+/// 应用自身设置的备份（不含账号、目录等数据），用于单独保存和恢复偏好设置
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AppSettingsBackup {
+    pub version: u32,
+    pub exported_at: String,
+    pub app_settings: AppSettings,
+    #[serde(default)]
+    pub webdav_configs: Vec<WebDavConfigBackup>,
+}
+
+/// 备份中的 WebDAV 配置，默认不含密码
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebDavConfigBackup {
+    pub name: String,
+    pub url: String,
+    pub username: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    pub remote_path: String,
+    pub auto_sync: bool,
+    pub sync_interval: i64,
+}
+
+impl WebDavConfigBackup {
+    fn from_config(config: WebDavConfig, include_secrets: bool) -> Self {
+        Self {
+            name: config.name,
+            url: config.url,
+            username: config.username,
+            password: include_secrets.then_some(config.password),
+            remote_path: config.remote_path,
+            auto_sync: config.auto_sync,
+            sync_interval: config.sync_interval,
+        }
     }
-    
-    pub async fn check_for_updates(&self) -> Result<String, Box<dyn std::error::Error>> {
-        // 这里是一个模拟的更新检查功能
-        // 在实际应用中，这应该连接到实际的更新服务器
-        
-        // 模拟网络请求延迟
-        tokio::time::sleep(tokio::time::Duration::from_millis(1000)).await;
-        
-        // 模拟检查结果
-        let current_version = env!("CARGO_PKG_VERSION");
-        
-        // 这里可以实现真实的版本检查逻辑
-        // 比如从GitHub API或者自己的更新服务器获取最新版本信息
-        
-        Ok(format!("当前版本: {}。暂无可用更新。", current_version))
+}
+
+/// 恢复应用设置的结果
+#[derive(Debug, Default)]
+pub struct AppSettingsImportSummary {
+    pub webdav_created: usize,
+    pub webdav_updated: usize,
+    /// 新建但备份中没有密码的 WebDAV 配置，需要手动补填
+    pub missing_passwords: Vec<String>,
+}
+
+/// 将应用设置和 WebDAV 配置导出到文件，`include_secrets` 为 false 时不导出 WebDAV 密码
+pub async fn export_app_settings(db: &Database, path: &Path, include_secrets: bool) -> Result<()> {
+    let mut app_settings = load_or_default().await;
+    // 上次切换记录是本机的运行状态，不属于偏好设置
+    app_settings.last_directory_id = None;
+    app_settings.last_account_id = None;
+
+    let webdav_configs = webdav::get_webdav_configs(db.get_pool())
+        .await?
+        .into_iter()
+        .map(|config| WebDavConfigBackup::from_config(config, include_secrets))
+        .collect();
+
+    let backup = AppSettingsBackup {
+        version: APP_SETTINGS_BACKUP_VERSION,
+        exported_at: chrono::Utc::now().to_rfc3339(),
+        app_settings,
+        webdav_configs,
+    };
+    fs::write(path, serde_json::to_string_pretty(&backup)?)
+        .await
+        .with_context(|| format!("写入备份文件失败: {}", path.display()))?;
+    tracing::info!("已导出应用设置到 {}，包含密码: {}", path.display(), include_secrets);
+    Ok(())
+}
+
+/// 从备份文件恢复应用设置；WebDAV 配置按名称新增或更新，备份中没有密码时保留本地密码
+pub async fn import_app_settings(db: &Database, path: &Path) -> Result<AppSettingsImportSummary> {
+    let content = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("读取备份文件失败: {}", path.display()))?;
+    let backup: AppSettingsBackup = serde_json::from_str(&content)?;
+    if backup.version > APP_SETTINGS_BACKUP_VERSION {
+        anyhow::bail!("不支持的备份版本: {}", backup.version);
+    }
+
+    let manager = SettingsManager::new()?;
+    let current = manager.load_settings().await?;
+    let mut app_settings = backup.app_settings;
+    app_settings.last_directory_id = current.last_directory_id;
+    app_settings.last_account_id = current.last_account_id;
+    manager.save_settings(&app_settings).await?;
+
+    let pool = db.get_pool();
+    let existing = webdav::get_webdav_configs(pool).await?;
+    let mut summary = AppSettingsImportSummary::default();
+    for config in backup.webdav_configs {
+        match existing.iter().find(|local| local.name == config.name) {
+            Some(local) => {
+                webdav::update_webdav_config(
+                    pool,
+                    local.id,
+                    &config.url,
+                    &config.username,
+                    config.password.as_deref(),
+                    &config.remote_path,
+                    config.auto_sync,
+                    config.sync_interval,
+                )
+                .await?;
+                summary.webdav_updated += 1;
+            }
+            None => {
+                webdav::create_webdav_config(
+                    pool,
+                    &config.name,
+                    &config.url,
+                    &config.username,
+                    config.password.as_deref().unwrap_or(""),
+                    &config.remote_path,
+                    config.auto_sync,
+                    config.sync_interval,
+                )
+                .await?;
+                if config.password.is_none() {
+                    summary.missing_passwords.push(config.name.clone());
+                }
+                summary.webdav_created += 1;
+            }
+        }
     }
+
+    tracing::info!("已从 {} 恢复应用设置: {:?}", path.display(), summary);
+    Ok(summary)
 }
 
-impl Default for SettingsManager {
-    fn default() -> Self {
-        Self::new().expect("Failed to initialize SettingsManager")
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_webdav_backup_excludes_password_by_default() {
+        let config = || WebDavConfig {
+            id: 1,
+            name: "nas".to_string(),
+            url: "https://dav.example.com".to_string(),
+            username: "me".to_string(),
+            password: "secret".to_string(),
+            remote_path: "/claude-config".to_string(),
+            namespace: None,
+            auto_sync: false,
+            sync_interval: 3600,
+            is_active: true,
+            last_sync_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let json = serde_json::to_string(&WebDavConfigBackup::from_config(config(), false)).unwrap();
+        assert!(!json.contains("password"));
+        assert!(!json.contains("secret"));
+
+        let backup = WebDavConfigBackup::from_config(config(), true);
+        assert_eq!(backup.password.as_deref(), Some("secret"));
+    }
+
+    #[test]
+    fn test_migrate_legacy_settings() {
+        let root = std::env::temp_dir().join(format!("claude-config-settings-{}", uuid::Uuid::new_v4()));
+        let legacy_dir = root.join("legacy");
+        let target_dir = root.join("new");
+        std::fs::create_dir_all(&legacy_dir).unwrap();
+        std::fs::create_dir_all(&target_dir).unwrap();
+        std::fs::write(legacy_dir.join("settings.json"), r#"{"http_timeout_secs": 90}"#).unwrap();
+        let target = target_dir.join("settings.json");
+
+        SettingsManager::migrate_legacy_settings(&target, Some(&legacy_dir));
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), r#"{"http_timeout_secs": 90}"#);
+        assert!(legacy_dir.join("settings.json").exists());
+
+        // 新位置已有设置时不覆盖
+        std::fs::write(legacy_dir.join("settings.json"), r#"{"http_timeout_secs": 10}"#).unwrap();
+        SettingsManager::migrate_legacy_settings(&target, Some(&legacy_dir));
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), r#"{"http_timeout_secs": 90}"#);
+
+        std::fs::remove_dir_all(&root).unwrap();
     }
-}
\ No newline at end of file
+}
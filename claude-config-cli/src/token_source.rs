@@ -0,0 +1,80 @@
+//! 账号 Token 的获取：直接保存的 Token 原样返回；配置了命令的账号在切换时执行命令
+//! （如 `pass show claude/work`、`op read op://...`），以标准输出作为 Token，结果不写入数据库和日志。
+
+use crate::{
+    hooks,
+    models::{Account, TokenSource},
+    t,
+};
+use anyhow::{bail, Context, Result};
+use std::process::Stdio;
+use std::time::Duration;
+
+/// 获取 Token 命令的最长执行时间；密码管理器可能需要用户解锁，因此留足时间
+const TOKEN_COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// 取得账号的 Token；命令执行失败时返回包含命令 stderr 的错误
+pub async fn resolve(account: &Account) -> Result<String> {
+    match account.token_source() {
+        TokenSource::Inline(token) => Ok(token),
+        TokenSource::Command(command) => run_command(&command).await,
+    }
+}
+
+/// 返回 Token 已填好的账号副本，用于需要比对或写入 Token 的场景
+pub async fn resolved_account(account: &Account) -> Result<Account> {
+    Ok(Account {
+        token: resolve(account).await?,
+        ..account.clone()
+    })
+}
+
+async fn run_command(command: &str) -> Result<String> {
+    tracing::info!("执行命令获取 Token: {}", command);
+
+    let mut process = hooks::shell_command(command);
+    // stdin 继承终端，便于密码管理器提示解锁
+    process
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let output = tokio::time::timeout(TOKEN_COMMAND_TIMEOUT, process.output())
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(t!("token_command.timeout").replace("{}", &TOKEN_COMMAND_TIMEOUT.as_secs().to_string()))
+        })?
+        .with_context(|| t!("token_command.spawn_failed").replace("{}", command))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let detail = match stderr.trim() {
+            "" => output.status.to_string(),
+            stderr => stderr.to_string(),
+        };
+        tracing::warn!("获取 Token 的命令失败: {}", detail);
+        bail!("{}", t!("token_command.failed").replace("{}", &detail));
+    }
+
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if token.is_empty() {
+        bail!("{}", t!("token_command.empty_output"));
+    }
+    Ok(token)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_command_trims_stdout_and_reports_stderr() {
+        assert_eq!(run_command("printf '  sk-ant-secret\\n'").await.unwrap(), "sk-ant-secret");
+
+        let error = run_command("echo 'vault is locked' >&2; exit 1").await.unwrap_err();
+        assert!(error.to_string().contains("vault is locked"), "{}", error);
+
+        assert!(run_command("true").await.is_err());
+    }
+}
@@ -0,0 +1,150 @@
+//! 本次会话的撤销栈：切换、清除、重置修改目录配置前保存原文件内容，撤销时按后进先出恢复
+//!
+//! 只保存在内存中，程序退出即清空；超过大小上限的文件不保存，对应操作不可撤销。
+
+use chrono::{DateTime, Local};
+use claude_config_core::audit::ConfigAction;
+use std::collections::VecDeque;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 最多保留的撤销步数，超过时丢弃最早的一步
+const UNDO_LIMIT: usize = 20;
+
+/// 单个快照的大小上限
+const SNAPSHOT_MAX_BYTES: u64 = 256 * 1024;
+
+static STACK: Mutex<VecDeque<UndoEntry>> = Mutex::new(VecDeque::new());
+
+/// 一步可撤销的修改：恢复为 `before`，None 表示修改前文件不存在
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoEntry {
+    pub action: ConfigAction,
+    pub directory_name: String,
+    pub path: PathBuf,
+    pub before: Option<Vec<u8>>,
+    pub at: DateTime<Local>,
+}
+
+impl UndoEntry {
+    /// 把文件恢复为修改前的内容；修改前不存在的文件会被删除
+    pub fn restore(&self) -> io::Result<()> {
+        match &self.before {
+            Some(content) => {
+                if let Some(parent) = self.path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&self.path, content)
+            }
+            None => match fs::remove_file(&self.path) {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+                result => result,
+            },
+        }
+    }
+}
+
+/// 修改前保存的快照，修改成功后调用 [`UndoPoint::commit`] 入栈
+pub struct UndoPoint {
+    entry: Option<UndoEntry>,
+}
+
+impl UndoPoint {
+    /// 读取修改前的文件内容；文件过大或无法读取时该操作不可撤销
+    pub fn capture(action: ConfigAction, directory_name: &str, path: &Path) -> Self {
+        let before = match fs::metadata(path) {
+            Ok(meta) if meta.len() > SNAPSHOT_MAX_BYTES => {
+                tracing::info!("{} 超过撤销快照大小上限，不保存", path.display());
+                return Self { entry: None };
+            }
+            Ok(_) => match fs::read(path) {
+                Ok(content) => Some(content),
+                Err(e) => {
+                    tracing::warn!("读取 {} 失败，无法保存撤销快照: {}", path.display(), e);
+                    return Self { entry: None };
+                }
+            },
+            Err(_) => None,
+        };
+
+        Self {
+            entry: Some(UndoEntry {
+                action,
+                directory_name: directory_name.to_string(),
+                path: path.to_path_buf(),
+                before,
+                at: Local::now(),
+            }),
+        }
+    }
+
+    /// 修改完成后入栈；文件内容没有变化时不入栈
+    pub fn commit(self) {
+        let Some(entry) = self.entry else {
+            return;
+        };
+        if fs::read(&entry.path).ok() == entry.before {
+            return;
+        }
+
+        let mut stack = STACK.lock().unwrap();
+        if stack.len() == UNDO_LIMIT {
+            stack.pop_front();
+        }
+        stack.push_back(entry);
+    }
+}
+
+/// 最近一步可撤销的修改
+pub fn peek() -> Option<UndoEntry> {
+    STACK.lock().unwrap().back().cloned()
+}
+
+/// 取出最近一步可撤销的修改
+pub fn pop() -> Option<UndoEntry> {
+    STACK.lock().unwrap().pop_back()
+}
+
+/// 当前可撤销的步数
+pub fn len() -> usize {
+    STACK.lock().unwrap().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_point_restores_previous_content() {
+        let dir = std::env::temp_dir().join(format!("claude-config-undo-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("settings.local.json");
+
+        // 新建文件，撤销时删除
+        let point = UndoPoint::capture(ConfigAction::Apply, "demo", &path);
+        fs::write(&path, "{\"env\":{}}").unwrap();
+        point.commit();
+
+        // 内容未变化时不入栈
+        let point = UndoPoint::capture(ConfigAction::Clear, "demo", &path);
+        point.commit();
+
+        let point = UndoPoint::capture(ConfigAction::Reset, "demo", &path);
+        fs::write(&path, "{}").unwrap();
+        point.commit();
+
+        let entry = pop().unwrap();
+        assert_eq!(entry.action, ConfigAction::Reset);
+        entry.restore().unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{\"env\":{}}");
+
+        let entry = pop().unwrap();
+        assert_eq!(entry.action, ConfigAction::Apply);
+        entry.restore().unwrap();
+        assert!(!path.exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
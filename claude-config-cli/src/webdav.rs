@@ -1,41 +1,141 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use claude_config_core::checksum;
+use reqwest_dav::list_cmd::ListEntity;
 use reqwest_dav::{Auth, Client, ClientBuilder, Depth};
 use serde_json::Value;
 use sqlx::SqlitePool;
+use std::future::Future;
+use std::time::Duration;
 use tracing::{error, info, warn};
 
-use crate::models::{CreateSyncLogRequest, WebDavConfig};
+use crate::models::{AppSettings, CreateSyncLogRequest, WebDavConfig};
+use crate::settings;
+
+/// 保存配置前探测 WebDAV 地址的结果
+#[derive(Debug)]
+pub enum ProbeOutcome {
+    /// 地址可达且认证通过
+    Success,
+    /// 服务器拒绝了用户名或密码（401 / 403）
+    AuthFailed(u16),
+    /// 服务器可达，但返回了其他错误状态
+    UnexpectedStatus(u16),
+    /// 无法连接到服务器
+    Unreachable(String),
+}
+
+impl ProbeOutcome {
+    pub fn is_success(&self) -> bool {
+        matches!(self, ProbeOutcome::Success)
+    }
+
+    fn from_status(status: u16) -> Self {
+        match status {
+            200..=299 => ProbeOutcome::Success,
+            401 | 403 => ProbeOutcome::AuthFailed(status),
+            _ => ProbeOutcome::UnexpectedStatus(status),
+        }
+    }
+}
+
+/// 用给定的凭据对 WebDAV 地址发送 PROPFIND（Depth: 0），在保存配置前确认地址和认证可用
+pub async fn probe_endpoint(url: &str, username: &str, password: &str) -> ProbeOutcome {
+    if let Err(e) = crate::offline::ensure_online() {
+        return ProbeOutcome::Unreachable(e.to_string());
+    }
+
+    let timeout = Duration::from_secs(settings::load_or_default().await.http_timeout_secs);
+    let client = match reqwest::Client::builder().timeout(timeout).build() {
+        Ok(client) => client,
+        Err(e) => return ProbeOutcome::Unreachable(e.to_string()),
+    };
+    let method = reqwest::Method::from_bytes(b"PROPFIND").expect("PROPFIND 是合法的 HTTP 方法");
+
+    match client
+        .request(method, url.trim())
+        .basic_auth(username, Some(password))
+        .header("Depth", "0")
+        .send()
+        .await
+    {
+        Ok(response) => {
+            let outcome = ProbeOutcome::from_status(response.status().as_u16());
+            info!("WebDAV 地址探测完成: {} -> {:?}", url, outcome);
+            outcome
+        }
+        Err(e) => {
+            warn!("WebDAV 地址探测失败: {} -> {}", url, e);
+            ProbeOutcome::Unreachable(e.to_string())
+        }
+    }
+}
 
 /// WebDAV 客户端管理器
 pub struct WebDavManager {
     config: WebDavConfig,
     client: Client,
+    max_retries: u32,
 }
 
 impl WebDavManager {
     /// 从数据库配置创建 WebDAV 管理器
     pub async fn from_config(config: WebDavConfig) -> Result<Self> {
+        crate::offline::ensure_online()?;
+        let app_settings: AppSettings = settings::load_or_default().await;
         let auth = Auth::Basic(config.username.clone(), config.password.clone());
 
+        let agent = reqwest::Client::builder()
+            .timeout(Duration::from_secs(app_settings.http_timeout_secs))
+            .build()
+            .context("Failed to build HTTP client")?;
+
         let client = ClientBuilder::new()
+            .set_agent(agent)
             .set_host(config.url.clone())
             .set_auth(auth)
             .build()
             .context("Failed to build WebDAV client")?;
 
-        Ok(Self { config, client })
+        Ok(Self {
+            config,
+            client,
+            max_retries: app_settings.max_retries,
+        })
+    }
+
+    /// 按设置中的重试次数执行网络操作
+    async fn with_retries<T, E, F, Fut>(&self, operation: &str, mut f: F) -> Result<T, E>
+    where
+        E: std::fmt::Display,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match f().await {
+                Ok(value) => return Ok(value),
+                Err(e) if attempt < self.max_retries => {
+                    attempt += 1;
+                    warn!(
+                        "WebDAV {} 失败，正在重试 ({}/{}): {}",
+                        operation, attempt, self.max_retries, e
+                    );
+                }
+                Err(e) => return Err(e),
+            }
+        }
     }
 
-    /// 规范化 WebDAV 路径
+    /// 改用另一个命名空间（浏览其他机器的配置包时使用），None 表示共享路径
+    pub fn with_namespace(mut self, namespace: Option<String>) -> Self {
+        self.config.namespace = namespace;
+        self
+    }
+
+    /// 规范化 WebDAV 路径：配置包位于 远程路径/命名空间/ 下
     fn normalize_path(&self, filename: &str) -> String {
-        let remote_path = self.config.remote_path.trim_end_matches('/');
-        let remote_path = if remote_path.is_empty() {
-            "/"
-        } else if !remote_path.starts_with('/') {
-            &format!("/{}", remote_path)
-        } else {
-            remote_path
-        };
+        let remote_path = remote_dir(&self.config.remote_path, self.config.namespace.as_deref());
+        let remote_path = remote_path.as_str();
 
         if filename.is_empty() {
             remote_path.to_string()
@@ -46,7 +146,10 @@ impl WebDavManager {
 
     /// 测试 WebDAV 连接
     pub async fn test_connection(&self) -> Result<bool> {
-        match self.client.list("", Depth::Number(0)).await {
+        match self
+            .with_retries("test_connection", || self.client.list("", Depth::Number(0)))
+            .await
+        {
             Ok(_) => {
                 info!("WebDAV connection test successful");
                 Ok(true)
@@ -73,14 +176,25 @@ impl WebDavManager {
         self.ensure_remote_dir().await?;
 
         // 上传文件
-        self.client
-            .put(&remote_file, json_data.as_bytes().to_vec())
-            .await
+        self.with_retries("upload", || {
+            self.client.put(&remote_file, json_data.as_bytes().to_vec())
+        })
+        .await
             .context(format!(
                 "上传文件失败: {}. 请检查路径格式和服务器权限",
                 remote_file
             ))?;
 
+        // 同时写入校验文件，下载时用于验证配置包完整性
+        let checksum_file = self.normalize_path(&checksum::checksum_file_name(filename));
+        let checksum_content = checksum::format_checksum_file(json_data.as_bytes(), filename);
+        self.with_retries("upload_checksum", || {
+            self.client
+                .put(&checksum_file, checksum_content.as_bytes().to_vec())
+        })
+        .await
+        .context(format!("上传校验文件失败: {}", checksum_file))?;
+
         info!("Config uploaded successfully to {}", remote_file);
         Ok(())
     }
@@ -92,8 +206,7 @@ impl WebDavManager {
         info!("Downloading config from WebDAV: {}", remote_file);
 
         let response = self
-            .client
-            .get(&remote_file)
+            .with_retries("download", || self.client.get(&remote_file))
             .await
             .context(format!("下载文件失败: {}", remote_file))?;
 
@@ -102,6 +215,15 @@ impl WebDavManager {
             .await
             .context("Failed to read response bytes")?;
 
+        // 解析前先校验，校验失败时调用方不会改动本地数据
+        match self.fetch_checksum(filename).await? {
+            Some(expected) => {
+                checksum::verify(&data, &expected)?;
+                info!("Checksum verified for {}", remote_file);
+            }
+            None => warn!("远程没有 {} 的校验文件，跳过完整性校验", filename),
+        }
+
         let json_str =
             String::from_utf8(data.to_vec()).context("Failed to parse downloaded data as UTF-8")?;
 
@@ -112,6 +234,31 @@ impl WebDavManager {
         Ok(config)
     }
 
+    /// 读取配置包对应的校验值；旧版本上传的配置包没有校验文件（404），返回 None。
+    /// 认证失败、服务器错误、超时等其他失败直接返回错误，不能借此跳过完整性校验
+    async fn fetch_checksum(&self, filename: &str) -> Result<Option<String>> {
+        let checksum_file = self.normalize_path(&checksum::checksum_file_name(filename));
+        let response = self
+            .client
+            .get_raw(&checksum_file)
+            .await
+            .with_context(|| format!("获取校验文件失败: {}", checksum_file))?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            bail!("获取校验文件失败: {}: HTTP {}", checksum_file, status);
+        }
+        let content = response
+            .text()
+            .await
+            .context(format!("读取校验文件失败: {}", checksum_file))?;
+        let hash = checksum::parse_checksum_file(&content)
+            .with_context(|| format!("校验文件格式不正确: {}", checksum_file))?;
+        Ok(Some(hash))
+    }
+
     /// 列出远程目录中的文件
     pub async fn list_remote_files(&self) -> Result<Vec<String>> {
         let remote_dir = self.normalize_path("");
@@ -156,41 +303,118 @@ impl WebDavManager {
                 }
                 None
             })
+            // 校验文件随配置包一起上传，不单独展示
+            .filter(|filename| !checksum::is_checksum_file(filename))
             .collect();
 
         info!("Found {} files in remote directory", files.len());
         Ok(files)
     }
 
-    /// 确保远程目录存在
-    async fn ensure_remote_dir(&self) -> Result<()> {
-        let remote_dir = self.normalize_path("");
+    /// 列出远程路径下的命名空间（子目录），不包括共享路径本身
+    pub async fn list_namespaces(&self) -> Result<Vec<String>> {
+        let root = remote_dir(&self.config.remote_path, None);
+        info!("Listing namespaces in remote directory: {}", root);
 
-        // 如果是根目录，不需要创建
-        if remote_dir == "/" || remote_dir.is_empty() {
-            info!("Using root directory, no need to create");
-            return Ok(());
-        }
+        let list = self
+            .with_retries("list_namespaces", || self.client.list(&root, Depth::Number(1)))
+            .await
+            .context("Failed to list remote namespaces")?;
+
+        let mut namespaces: Vec<String> = list
+            .into_iter()
+            .filter_map(|item| match item {
+                ListEntity::Folder(folder) => {
+                    let href = folder.href.trim_end_matches('/');
+                    // 结果中包含远程路径自身
+                    if href.is_empty() || href.ends_with(root.trim_end_matches('/')) {
+                        return None;
+                    }
+                    href.rsplit('/').next().map(str::to_string)
+                }
+                ListEntity::File(_) => None,
+            })
+            .collect();
+        namespaces.sort();
+        Ok(namespaces)
+    }
 
-        info!("Ensuring remote directory exists: {}", remote_dir);
+    /// 确保远程目录存在（MKCOL 不会创建中间目录，远程路径和命名空间逐级创建）
+    async fn ensure_remote_dir(&self) -> Result<()> {
+        let mut dirs = vec![remote_dir(&self.config.remote_path, None)];
+        if self.config.namespace.is_some() {
+            dirs.push(self.normalize_path(""));
+        }
 
-        // 尝试创建目录,如果已存在会失败但不影响后续操作
-        match self.client.mkcol(&remote_dir).await {
-            Ok(_) => {
-                info!("Remote directory created: {}", remote_dir);
+        for remote_dir in dirs {
+            // 如果是根目录，不需要创建
+            if remote_dir == "/" || remote_dir.is_empty() {
+                info!("Using root directory, no need to create");
+                continue;
             }
-            Err(e) => {
-                // 目录可能已存在,记录警告但不报错
-                warn!(
-                    "Failed to create remote directory (may already exist): {}. Path: {}",
-                    e, remote_dir
-                );
+
+            info!("Ensuring remote directory exists: {}", remote_dir);
+
+            // 尝试创建目录,如果已存在会失败但不影响后续操作
+            match self.client.mkcol(&remote_dir).await {
+                Ok(_) => {
+                    info!("Remote directory created: {}", remote_dir);
+                }
+                Err(e) => {
+                    // 目录可能已存在,记录警告但不报错
+                    warn!(
+                        "Failed to create remote directory (may already exist): {}. Path: {}",
+                        e, remote_dir
+                    );
+                }
             }
         }
         Ok(())
     }
 }
 
+/// 拼接远程目录：以 `/` 开头、不以 `/` 结尾（根目录为 `/`），命名空间为空时使用共享路径
+fn remote_dir(remote_path: &str, namespace: Option<&str>) -> String {
+    let remote_path = remote_path.trim_end_matches('/');
+    let remote_path = if remote_path.is_empty() || remote_path.starts_with('/') {
+        remote_path.to_string()
+    } else {
+        format!("/{}", remote_path)
+    };
+
+    match namespace.map(|ns| ns.trim_matches('/')).filter(|ns| !ns.is_empty()) {
+        Some(namespace) => format!("{}/{}", remote_path, namespace),
+        None if remote_path.is_empty() => "/".to_string(),
+        None => remote_path,
+    }
+}
+
+/// 把任意文本整理为可用作远程目录名的命名空间：只保留字母、数字、`-`、`_`、`.`
+pub fn sanitize_namespace(raw: &str) -> String {
+    raw.trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.') { c } else { '-' })
+        .collect::<String>()
+        .trim_matches('-')
+        .to_string()
+}
+
+/// 根据本机主机名生成默认命名空间，无法获取主机名时返回 None
+pub fn hostname_namespace() -> Option<String> {
+    let hostname = std::env::var("COMPUTERNAME")
+        .or_else(|_| std::env::var("HOSTNAME"))
+        .ok()
+        .or_else(|| {
+            let output = std::process::Command::new("hostname").output().ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+        })?;
+    Some(sanitize_namespace(&hostname)).filter(|ns| !ns.is_empty())
+}
+
 /// 数据库操作 - WebDAV 配置
 pub async fn get_webdav_configs(pool: &SqlitePool) -> Result<Vec<WebDavConfig>> {
     let configs =
@@ -212,6 +436,7 @@ pub async fn get_webdav_config_by_id(pool: &SqlitePool, id: i64) -> Result<Optio
     Ok(config)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn create_webdav_config(
     pool: &SqlitePool,
     name: &str,
@@ -244,6 +469,49 @@ pub async fn create_webdav_config(
     Ok(config)
 }
 
+/// 更新 WebDAV 配置，`password` 为 None 时保留原密码
+#[allow(clippy::too_many_arguments)]
+pub async fn update_webdav_config(
+    pool: &SqlitePool,
+    id: i64,
+    url: &str,
+    username: &str,
+    password: Option<&str>,
+    remote_path: &str,
+    auto_sync: bool,
+    sync_interval: i64,
+) -> Result<()> {
+    sqlx::query(
+        "UPDATE webdav_configs
+         SET url = ?, username = ?, password = COALESCE(?, password), remote_path = ?,
+             auto_sync = ?, sync_interval = ?, updated_at = CURRENT_TIMESTAMP
+         WHERE id = ?",
+    )
+    .bind(url)
+    .bind(username)
+    .bind(password)
+    .bind(remote_path)
+    .bind(auto_sync)
+    .bind(sync_interval)
+    .bind(id)
+    .execute(pool)
+    .await
+    .context("Failed to update WebDAV config")?;
+
+    Ok(())
+}
+
+/// 设置 WebDAV 配置的命名空间，None 表示使用共享路径
+pub async fn update_webdav_namespace(pool: &SqlitePool, id: i64, namespace: Option<&str>) -> Result<()> {
+    sqlx::query("UPDATE webdav_configs SET namespace = ?, updated_at = CURRENT_TIMESTAMP WHERE id = ?")
+        .bind(namespace.filter(|ns| !ns.is_empty()))
+        .bind(id)
+        .execute(pool)
+        .await
+        .context("Failed to update WebDAV namespace")?;
+    Ok(())
+}
+
 pub async fn delete_webdav_config(pool: &SqlitePool, id: i64) -> Result<()> {
     sqlx::query("DELETE FROM webdav_configs WHERE id = ?")
         .bind(id)
@@ -281,3 +549,25 @@ pub async fn update_last_sync_time(pool: &SqlitePool, config_id: i64) -> Result<
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe_outcome_from_status() {
+        assert!(ProbeOutcome::from_status(207).is_success());
+        assert!(matches!(ProbeOutcome::from_status(401), ProbeOutcome::AuthFailed(401)));
+        assert!(matches!(ProbeOutcome::from_status(403), ProbeOutcome::AuthFailed(403)));
+        assert!(matches!(ProbeOutcome::from_status(404), ProbeOutcome::UnexpectedStatus(404)));
+    }
+
+    #[test]
+    fn test_remote_dir_with_namespace() {
+        assert_eq!(remote_dir("/claude-config/", None), "/claude-config");
+        assert_eq!(remote_dir("claude-config", Some("laptop")), "/claude-config/laptop");
+        assert_eq!(remote_dir("/", None), "/");
+        assert_eq!(remote_dir("", Some("/nas/")), "/nas");
+        assert_eq!(sanitize_namespace(" My MacBook.local "), "my-macbook.local");
+    }
+}
@@ -0,0 +1,107 @@
+//! Git worktree 检测：根据目录下的 .git 找到共享的 gitdir，列出同一仓库的其他工作树
+//!
+//! 只读取 .git 文件和 gitdir 下的 worktrees 目录，不依赖 git 命令。
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// 读取 `gitdir: <path>` 形式的指针文件，相对路径基于文件所在目录解析
+fn read_pointer(file: &Path, prefix: &str) -> Option<PathBuf> {
+    let content = fs::read_to_string(file).ok()?;
+    let target = content.trim().strip_prefix(prefix)?.trim();
+    if target.is_empty() {
+        return None;
+    }
+    Some(file.parent()?.join(target))
+}
+
+/// 目录所属仓库的公共 gitdir；目录不是仓库（或工作树）根目录时返回 None
+fn common_dir(directory: &Path) -> Option<PathBuf> {
+    let dot_git = directory.join(".git");
+    if dot_git.is_dir() {
+        return Some(dot_git);
+    }
+
+    // 关联工作树的 .git 是文件，指向 <公共 gitdir>/worktrees/<名称>
+    let gitdir = read_pointer(&dot_git, "gitdir:")?;
+    let commondir = gitdir.join("commondir");
+    if commondir.is_file() {
+        let target = fs::read_to_string(&commondir).ok()?;
+        return Some(gitdir.join(target.trim()));
+    }
+    let parent = gitdir.parent()?;
+    if parent.file_name()? != "worktrees" {
+        return None;
+    }
+    parent.parent().map(Path::to_path_buf)
+}
+
+/// 列出与目录同属一个仓库的其他工作树（含主工作树），不包含目录本身；
+/// 非 git 目录或没有其他工作树时返回空列表
+pub fn sibling_worktrees(directory: &Path) -> Vec<PathBuf> {
+    let Some(common) = common_dir(directory).and_then(|dir| dir.canonicalize().ok()) else {
+        return Vec::new();
+    };
+
+    let mut worktrees = Vec::new();
+    // 非 bare 仓库的公共 gitdir 就是主工作树下的 .git
+    if common.file_name().is_some_and(|name| name == ".git") {
+        if let Some(main) = common.parent() {
+            worktrees.push(main.to_path_buf());
+        }
+    }
+    if let Ok(entries) = fs::read_dir(common.join("worktrees")) {
+        for entry in entries.flatten() {
+            // gitdir 文件记录工作树中 .git 文件的路径；工作树已被删除时跳过
+            let Some(dot_git) = read_pointer(&entry.path().join("gitdir"), "") else {
+                continue;
+            };
+            if let Some(worktree) = dot_git.parent().filter(|path| path.is_dir()) {
+                worktrees.push(worktree.to_path_buf());
+            }
+        }
+    }
+
+    let this = directory.canonicalize().unwrap_or_else(|_| directory.to_path_buf());
+    let mut worktrees: Vec<PathBuf> = worktrees
+        .into_iter()
+        .filter_map(|path| path.canonicalize().ok())
+        .filter(|path| *path != this)
+        .collect();
+    worktrees.sort();
+    worktrees.dedup();
+    worktrees
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sibling_worktrees() {
+        let root = std::env::temp_dir().join(format!("claude-config-worktree-{}", uuid::Uuid::new_v4()));
+        let main = root.join("repo");
+        let linked = root.join("repo-feature");
+        let plain = root.join("plain");
+        fs::create_dir_all(main.join(".git/worktrees/repo-feature")).unwrap();
+        fs::create_dir_all(&linked).unwrap();
+        fs::create_dir_all(&plain).unwrap();
+
+        // 按 git worktree add 的布局写入指针文件
+        let admin = main.join(".git/worktrees/repo-feature");
+        fs::write(linked.join(".git"), format!("gitdir: {}\n", admin.display())).unwrap();
+        fs::write(admin.join("gitdir"), format!("{}\n", linked.join(".git").display())).unwrap();
+        fs::write(admin.join("commondir"), "../..\n").unwrap();
+        // 已删除的工作树不应出现在结果中
+        fs::create_dir_all(main.join(".git/worktrees/gone")).unwrap();
+        fs::write(main.join(".git/worktrees/gone/gitdir"), root.join("gone/.git").display().to_string()).unwrap();
+
+        let main = main.canonicalize().unwrap();
+        let linked = linked.canonicalize().unwrap();
+        assert_eq!(sibling_worktrees(&main), vec![linked.clone()]);
+        assert_eq!(sibling_worktrees(&linked), vec![main]);
+        assert!(sibling_worktrees(&plain).is_empty());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,80 @@
+//! 配置变更审计
+//!
+//! `ClaudeConfigManager` 的写入方法在成功修改配置文件后调用注册的钩子，
+//! 只传递文件内容的 SHA-256，不包含任何 Token 原文，调用方可以放心持久化或分享。
+
+use crate::checksum::sha256_hex;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// 修改配置的操作类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigAction {
+    /// 写入账号配置（切换账号、自动修复等）
+    Apply,
+    /// 清除认证相关环境变量
+    Clear,
+    /// 重置目录
+    Reset,
+}
+
+impl ConfigAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ConfigAction::Apply => "apply",
+            ConfigAction::Clear => "clear",
+            ConfigAction::Reset => "reset",
+        }
+    }
+}
+
+impl fmt::Display for ConfigAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ConfigAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "apply" => Ok(ConfigAction::Apply),
+            "clear" => Ok(ConfigAction::Clear),
+            "reset" => Ok(ConfigAction::Reset),
+            other => Err(format!("未知的操作类型: {}", other)),
+        }
+    }
+}
+
+/// 一次配置变更，哈希为 None 表示文件不存在
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigChange {
+    pub action: ConfigAction,
+    pub settings_path: PathBuf,
+    pub before_hash: Option<String>,
+    pub after_hash: Option<String>,
+}
+
+/// 配置变更钩子
+pub type ChangeHook = Arc<dyn Fn(&ConfigChange) + Send + Sync>;
+
+/// 文件内容的 SHA-256，文件不存在或无法读取时返回 None
+pub fn file_hash(path: impl AsRef<Path>) -> Option<String> {
+    std::fs::read(path).ok().map(|content| sha256_hex(&content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_action_roundtrip() {
+        for action in [ConfigAction::Apply, ConfigAction::Clear, ConfigAction::Reset] {
+            assert_eq!(action.as_str().parse::<ConfigAction>(), Ok(action));
+        }
+        assert!("delete".parse::<ConfigAction>().is_err());
+    }
+}
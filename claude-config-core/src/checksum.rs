@@ -0,0 +1,96 @@
+//! 同步配置包的 SHA-256 校验
+//!
+//! 上传配置包时在同一目录写入 `<文件名>.sha256`（与 `sha256sum` 输出格式一致），
+//! 下载后重新计算哈希并比对，避免导入传输中损坏或被截断的配置包。
+
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// 校验文件后缀
+pub const CHECKSUM_SUFFIX: &str = ".sha256";
+
+/// 计算数据的 SHA-256（小写十六进制）
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// 配置包对应的校验文件名
+pub fn checksum_file_name(filename: &str) -> String {
+    format!("{}{}", filename, CHECKSUM_SUFFIX)
+}
+
+/// 是否为校验文件（列出远程文件时应隐藏）
+pub fn is_checksum_file(filename: &str) -> bool {
+    filename.ends_with(CHECKSUM_SUFFIX)
+}
+
+/// 生成校验文件内容：`<hash>  <文件名>`
+pub fn format_checksum_file(data: &[u8], filename: &str) -> String {
+    format!("{}  {}\n", sha256_hex(data), filename)
+}
+
+/// 从校验文件内容中取出哈希，格式不正确时返回 None
+pub fn parse_checksum_file(content: &str) -> Option<String> {
+    let hash = content.split_whitespace().next()?.to_ascii_lowercase();
+    if hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()) {
+        Some(hash)
+    } else {
+        None
+    }
+}
+
+/// 下载内容与记录的校验值不一致
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for ChecksumMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "SHA-256 校验失败: 期望 {}, 实际 {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for ChecksumMismatch {}
+
+/// 校验数据与期望的哈希是否一致
+pub fn verify(data: &[u8], expected: &str) -> Result<(), ChecksumMismatch> {
+    let expected = expected.trim().to_ascii_lowercase();
+    let actual = sha256_hex(data);
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(ChecksumMismatch { expected, actual })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_file_roundtrip() {
+        let data = br#"{"accounts":[]}"#;
+        let content = format_checksum_file(data, "bundle.json");
+        assert!(content.ends_with("  bundle.json\n"));
+
+        let expected = parse_checksum_file(&content).unwrap();
+        assert_eq!(expected, sha256_hex(data));
+        assert!(verify(data, &expected).is_ok());
+
+        let err = verify(br#"{"accounts":[{}]}"#, &expected).unwrap_err();
+        assert_eq!(err.expected, expected);
+        assert_ne!(err.actual, expected);
+
+        assert_eq!(parse_checksum_file("not-a-hash  bundle.json"), None);
+        assert!(is_checksum_file(&checksum_file_name("bundle.json")));
+    }
+}
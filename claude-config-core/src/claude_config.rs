@@ -0,0 +1,2879 @@
+use crate::audit::{self, ChangeHook, ConfigAction, ConfigChange};
+use crate::checksum::sha256_hex;
+use crate::error::{ConfigError, ConfigResult};
+use crate::models::{parse_env_value, AuthStyle, ProxyConfig, PROXY_ENV_KEYS};
+use crate::settings_format::{parse_settings, serialize_settings_with_indent, JsonIndent, SettingsFormat, WriteFormat};
+use anyhow::Result;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use tracing::{info, warn};
+
+/// 不允许出现在模板中的敏感环境变量（这些值只能来自账号）
+pub const SECRET_ENV_KEYS: &[&str] = &[
+    "ANTHROPIC_API_KEY",
+    "ANTHROPIC_AUTH_TOKEN",
+    "CLAUDE_API_KEY",
+];
+
+/// 常见的 API Token 前缀，不匹配时仅告警
+const EXPECTED_TOKEN_PREFIXES: &[&str] = &["sk-ant-", "sk-"];
+
+/// Token 校验错误
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    /// 去除首尾空白后为空
+    Empty,
+    /// Token 中间包含空白字符（通常是粘贴错误）
+    ContainsWhitespace,
+}
+
+impl std::fmt::Display for TokenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TokenError::Empty => write!(f, "Token 不能为空"),
+            TokenError::ContainsWhitespace => write!(f, "Token 中不能包含空白字符"),
+        }
+    }
+}
+
+impl std::error::Error for TokenError {}
+
+/// 校验并规范化 Token：去除首尾空白，拒绝空值和内部空白
+pub fn validate_token(raw: &str) -> Result<String, TokenError> {
+    let token = raw.trim();
+
+    if token.is_empty() {
+        return Err(TokenError::Empty);
+    }
+    if token.chars().any(char::is_whitespace) {
+        return Err(TokenError::ContainsWhitespace);
+    }
+    if !has_expected_token_prefix(token) {
+        warn!("Token 前缀不是常见的 {:?}，请确认是否输入正确", EXPECTED_TOKEN_PREFIXES);
+    }
+
+    Ok(token.to_string())
+}
+
+/// Base URL 与 Token 疑似填反的情况
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapSuspicion {
+    /// Base URL 看起来像 Token（以 sk-ant- 开头，或既没有协议也没有域名中的点）
+    BaseUrlLooksLikeToken,
+    /// Token 看起来像 URL（以 http 开头）
+    TokenLooksLikeUrl,
+}
+
+/// 检查 Base URL 与 Token 是否可能填反；部分代理的格式比较特殊，调用方应只做警告
+pub fn detect_swapped_credentials(base_url: &str, token: &str) -> Option<SwapSuspicion> {
+    let base_url = base_url.trim();
+    let token = token.trim();
+
+    if base_url.starts_with("sk-ant-") || (!base_url.contains("://") && !base_url.contains('.')) {
+        return Some(SwapSuspicion::BaseUrlLooksLikeToken);
+    }
+    if token.to_ascii_lowercase().starts_with("http") {
+        return Some(SwapSuspicion::TokenLooksLikeUrl);
+    }
+    None
+}
+
+/// Token 是否以常见前缀开头
+pub fn has_expected_token_prefix(token: &str) -> bool {
+    EXPECTED_TOKEN_PREFIXES
+        .iter()
+        .any(|prefix| token.starts_with(prefix))
+}
+
+/// 是否为合法的环境变量名：以字母或下划线开头，只包含 ASCII 字母、数字和下划线
+pub fn is_valid_env_key(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Token 指纹（SHA-256 前 8 位十六进制），用于比较和展示而不暴露原文
+pub fn token_fingerprint(token: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    Sha256::digest(token.as_bytes())
+        .iter()
+        .take(4)
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// 内置的 settings.local.json 结构描述（仅覆盖本工具管理的字段）
+const SETTINGS_SCHEMA: &str = include_str!("../resources/config/settings.schema.json");
+
+/// 配置校验问题
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// 出错字段的路径，如 `$.env.ANTHROPIC_BASE_URL`
+    pub path: String,
+    pub message: String,
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+/// 按 schema 的 type / properties / items 校验，未在 schema 中声明的键不报错
+fn validate_against_schema(value: &Value, schema: &Value, path: &str, issues: &mut Vec<ValidationIssue>) {
+    if let Some(expected) = schema.get("type").and_then(|t| t.as_str()) {
+        let actual = json_type_name(value);
+        if actual != expected {
+            issues.push(ValidationIssue {
+                path: path.to_string(),
+                message: format!("{} 应为 {}，实际为 {}", path, expected, actual),
+            });
+            return;
+        }
+    }
+
+    if let (Some(properties), Some(object)) = (
+        schema.get("properties").and_then(|p| p.as_object()),
+        value.as_object(),
+    ) {
+        for (key, property_schema) in properties {
+            if let Some(child) = object.get(key) {
+                let child_path = format!("{}.{}", path, key);
+                validate_against_schema(child, property_schema, &child_path, issues);
+            }
+        }
+    }
+
+    if let (Some(items_schema), Some(items)) = (schema.get("items"), value.as_array()) {
+        for (index, item) in items.iter().enumerate() {
+            let item_path = format!("{}[{}]", path, index);
+            validate_against_schema(item, items_schema, &item_path, issues);
+        }
+    }
+}
+
+/// 校验配置内容是否符合内置 schema
+pub fn validate_settings_value(settings: &Value) -> Vec<ValidationIssue> {
+    let schema: Value = serde_json::from_str(SETTINGS_SCHEMA).expect("内置 settings schema 不是合法 JSON");
+    let mut issues = Vec::new();
+    validate_against_schema(settings, &schema, "$", &mut issues);
+    issues
+}
+
+/// 使用 include_str! 在编译时嵌入 CLAUDE.local.md 内容
+const CLAUDE_LOCAL_MD_CONTENT: &str = include_str!("../resources/config/CLAUDE.local.md");
+
+/// 内置的 CLAUDE.local.md 内容
+pub fn claude_local_md_template() -> &'static str {
+    CLAUDE_LOCAL_MD_CONTENT
+}
+
+/// 以前版本内置 CLAUDE.local.md 的 SHA-256（按 LF 换行计算）。更新内置模板时把旧版本的哈希追加到这里，
+/// 内容与旧版本完全一致的文件会被识别为过时，可以安全刷新
+const PREVIOUS_CLAUDE_LOCAL_MD_SHA256: &[&str] = &[
+    // 当前的内置模板，发布以来尚未改动过：账号改用自己的模板时，未修改过的内置模板同样可以刷新
+    "923689ed000feb71c6eb1c0271195ef6f39f4b4424266dc9a0e4c85a90fb43c9",
+];
+
+/// 目录中 CLAUDE.local.md 与模板的比较结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaudeMdStatus {
+    /// 与当前模板一致
+    Identical,
+    /// 与以前版本的内置模板一致，未被用户修改过
+    Outdated,
+    /// 被用户修改过
+    Modified,
+    /// 文件不存在
+    Missing,
+}
+
+/// 比较 CLAUDE.local.md 内容：`previous` 为以前版本模板的哈希；
+/// 比较前统一为 LF 换行，Git 的 autocrlf 或编辑器改写换行不算作修改
+fn classify_claude_md(existing: Option<&str>, expected: &str, previous: &[&str]) -> ClaudeMdStatus {
+    let Some(content) = existing else {
+        return ClaudeMdStatus::Missing;
+    };
+    let content = content.replace("\r\n", "\n");
+    if content == expected.replace("\r\n", "\n") {
+        ClaudeMdStatus::Identical
+    } else if previous.contains(&sha256_hex(content.as_bytes()).as_str()) {
+        ClaudeMdStatus::Outdated
+    } else {
+        ClaudeMdStatus::Modified
+    }
+}
+
+/// 写入 CLAUDE.local.md 时的动作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClaudeMdAction {
+    /// 目标文件不存在，将创建
+    Create,
+    /// 目标文件内容不同，将覆盖
+    Overwrite,
+    /// 目标文件与内置内容一致，无需写入
+    SkipIdentical,
+    /// 目标文件被用户修改过，保留不动
+    SkipModified,
+}
+
+/// CLAUDE.md 中识别的环境变量行（`KEY=value`）
+const CLAUDE_MD_ENV_KEYS: &[&str] = &[
+    "ANTHROPIC_API_KEY",
+    "ANTHROPIC_AUTH_TOKEN",
+    "ANTHROPIC_BASE_URL",
+    "CLAUDE_API_KEY",
+];
+
+/// 重置目录时移除的由本工具管理的环境变量
+const MANAGED_ENV_KEYS: &[&str] = &[
+    "ANTHROPIC_API_KEY",
+    "ANTHROPIC_AUTH_TOKEN",
+    "ANTHROPIC_BASE_URL",
+    "ANTHROPIC_MODEL",
+];
+
+/// 重置目录时要执行的步骤
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ResetOptions {
+    /// 移除 Anthropic 相关环境变量
+    pub remove_env_keys: bool,
+    /// 移除 IS_SANDBOX
+    pub remove_sandbox: bool,
+    /// 删除本工具生成的 CLAUDE.local.md（内容与内置模板一致时才删除）
+    pub remove_claude_local_md: bool,
+    /// .claude 目录为空时将其删除
+    pub remove_empty_claude_dir: bool,
+}
+
+/// 重置目录的执行结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResetReport {
+    /// 已移除的环境变量
+    pub removed_keys: Vec<String>,
+    /// settings.local.json 清空后已删除
+    pub settings_file_removed: bool,
+    /// 已删除 CLAUDE.local.md
+    pub claude_local_md_removed: bool,
+    /// CLAUDE.local.md 被用户修改过，已保留
+    pub claude_local_md_kept: bool,
+    /// 已删除空的 .claude 目录
+    pub claude_dir_removed: bool,
+}
+
+/// 迁移旧配置文件的结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MigrationReport {
+    /// 已迁移的旧配置文件，None 表示当前生效的不是旧配置文件，无需迁移
+    pub migrated_file: Option<PathBuf>,
+    /// 旧配置文件重命名后的路径（.migrated）
+    pub renamed_to: Option<PathBuf>,
+}
+
+/// 从其他目录复制配置的结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CopyReport {
+    /// 复制的环境变量名（按名称排序）
+    pub env_keys: Vec<String>,
+    /// 是否复制了 CLAUDE.local.md
+    pub claude_local_md_copied: bool,
+}
+
+/// 配置中 MCP 服务器定义所在的键
+pub const MCP_SERVERS_KEY: &str = "mcpServers";
+
+/// 一个 MCP 服务器定义
+///
+/// 只有 command / args / env 由本工具编辑，其余字段（如 type、url、headers）以及类型不符的字段
+/// 保存在 `extra` 中原样写回
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct McpServer {
+    pub command: String,
+    pub args: Vec<String>,
+    pub env: BTreeMap<String, String>,
+    pub extra: serde_json::Map<String, Value>,
+}
+
+impl McpServer {
+    pub fn from_value(value: &Value) -> Self {
+        let mut server = McpServer {
+            extra: value.as_object().cloned().unwrap_or_default(),
+            ..Default::default()
+        };
+        if let Some(Value::String(command)) = server.extra.get("command") {
+            server.command = command.clone();
+            server.extra.remove("command");
+        }
+        if let Some(Value::Array(args)) = server.extra.get("args") {
+            if let Some(args) = args.iter().map(|arg| arg.as_str().map(str::to_string)).collect() {
+                server.args = args;
+                server.extra.remove("args");
+            }
+        }
+        if let Some(Value::Object(env)) = server.extra.get("env") {
+            if let Some(env) = env
+                .iter()
+                .map(|(key, value)| value.as_str().map(|value| (key.clone(), value.to_string())))
+                .collect()
+            {
+                server.env = env;
+                server.extra.remove("env");
+            }
+        }
+        server
+    }
+
+    /// 写回配置：在 `existing` 定义的基础上更新，未编辑的字段保持不变；空的 args / env 会被移除
+    fn merge_into(&self, existing: Option<&Value>) -> Value {
+        let mut obj = existing.and_then(|value| value.as_object()).cloned().unwrap_or_default();
+        for (key, value) in &self.extra {
+            obj.insert(key.clone(), value.clone());
+        }
+        let mut set_or_remove = |key: &str, value: Option<Value>| match value {
+            Some(value) => {
+                obj.insert(key.to_string(), value);
+            }
+            None => {
+                obj.remove(key);
+            }
+        };
+        set_or_remove("command", (!self.command.is_empty()).then(|| json!(self.command)));
+        set_or_remove("args", (!self.args.is_empty()).then(|| json!(self.args)));
+        set_or_remove("env", (!self.env.is_empty()).then(|| json!(self.env)));
+        Value::Object(obj)
+    }
+}
+
+/// 解析 RFC 6901 JSON Pointer 为路径片段（处理 ~1、~0 转义），根路径返回空列表
+pub fn parse_json_pointer(pointer: &str) -> Result<Vec<String>, String> {
+    if pointer.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(rest) = pointer.strip_prefix('/') else {
+        return Err("必须以 / 开头".to_string());
+    };
+    rest.split('/')
+        .map(|token| {
+            if token.replace("~0", "").replace("~1", "").contains('~') {
+                Err(format!("非法的转义: {}", token))
+            } else {
+                Ok(token.replace("~1", "/").replace("~0", "~"))
+            }
+        })
+        .collect()
+}
+
+/// 按 JSON Pointer 写入值：数组下标等于长度或为 `-` 时追加；
+/// `create_missing` 为 true 时自动创建缺失的中间对象，否则中间路径不存在时报错
+pub fn set_json_pointer(root: &mut Value, pointer: &str, value: Value, create_missing: bool) -> Result<(), String> {
+    let tokens = parse_json_pointer(pointer)?;
+    let Some((last, parents)) = tokens.split_last() else {
+        return Err("不能替换整个配置".to_string());
+    };
+
+    let mut current = root;
+    for token in parents {
+        current = match current {
+            Value::Object(map) => {
+                if !map.contains_key(token) {
+                    if !create_missing {
+                        return Err(format!("路径不存在: {}", token));
+                    }
+                    map.insert(token.clone(), json!({}));
+                }
+                map.get_mut(token).unwrap()
+            }
+            Value::Array(items) => {
+                let index: usize = token.parse().map_err(|_| format!("不是有效的数组下标: {}", token))?;
+                let len = items.len();
+                items
+                    .get_mut(index)
+                    .ok_or_else(|| format!("数组下标越界: {} (长度 {})", index, len))?
+            }
+            _ => return Err(format!("{} 的上级不是对象或数组", token)),
+        };
+    }
+
+    match current {
+        Value::Object(map) => {
+            map.insert(last.clone(), value);
+        }
+        Value::Array(items) => {
+            let index = if last == "-" {
+                items.len()
+            } else {
+                last.parse().map_err(|_| format!("不是有效的数组下标: {}", last))?
+            };
+            match index.cmp(&items.len()) {
+                std::cmp::Ordering::Less => items[index] = value,
+                std::cmp::Ordering::Equal => items.push(value),
+                std::cmp::Ordering::Greater => {
+                    return Err(format!("数组下标越界: {} (长度 {})", index, items.len()));
+                }
+            }
+        }
+        _ => return Err(format!("{} 的上级不是对象或数组", last)),
+    }
+    Ok(())
+}
+
+/// 数组合并策略
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArrayMerge {
+    /// overlay 中的数组整体替换 base 中的数组
+    #[default]
+    Replace,
+    /// overlay 中的元素追加到 base 数组末尾
+    Append,
+}
+
+/// 递归合并 JSON：对象逐键合并，标量冲突时 overlay 优先，数组默认整体替换
+pub fn merge_json(base: &mut Value, overlay: &Value) {
+    merge_json_with(base, overlay, ArrayMerge::Replace);
+}
+
+/// 递归合并 JSON，可指定数组合并策略
+pub fn merge_json_with(base: &mut Value, overlay: &Value, arrays: ArrayMerge) {
+    match (base, overlay) {
+        (Value::Object(base_obj), Value::Object(overlay_obj)) => {
+            for (key, value) in overlay_obj {
+                match base_obj.get_mut(key) {
+                    Some(existing) => merge_json_with(existing, value, arrays),
+                    None => {
+                        base_obj.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        (Value::Array(base_arr), Value::Array(overlay_arr)) if arrays == ArrayMerge::Append => {
+            base_arr.extend(overlay_arr.iter().cloned());
+        }
+        (base, overlay) => {
+            *base = overlay.clone();
+        }
+    }
+}
+
+/// 会被进程环境变量覆盖的配置项：与 Claude Code 一致，真实环境变量优先于配置文件
+pub const PROCESS_OVERRIDE_ENV_KEYS: &[&str] = &[
+    "ANTHROPIC_API_KEY",
+    "ANTHROPIC_AUTH_TOKEN",
+    "ANTHROPIC_BASE_URL",
+];
+
+/// 生效环境变量的来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvSource {
+    /// 来自配置文件
+    File(PathBuf),
+    /// 来自当前进程的环境变量
+    Process,
+}
+
+impl EnvSource {
+    pub fn is_process(&self) -> bool {
+        matches!(self, EnvSource::Process)
+    }
+
+    /// 来源文件，进程环境变量时为 None
+    pub fn file(&self) -> Option<&Path> {
+        match self {
+            EnvSource::File(path) => Some(path),
+            EnvSource::Process => None,
+        }
+    }
+}
+
+/// 生效环境变量中的一项：最终取值及其来源
+#[derive(Debug, Clone, PartialEq)]
+pub struct EffectiveEnvEntry {
+    pub key: String,
+    pub value: String,
+    pub source: EnvSource,
+    /// 被进程环境变量覆盖的配置文件取值
+    pub masked_value: Option<String>,
+}
+
+/// 分层合并后的项目配置：settings.json 在下，本地配置（settings.local.json）在上，
+/// 与 Claude Code 实际看到的内容一致
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EffectiveSettings {
+    pub settings: Value,
+    /// 每个叶子值的来源文件，键为 JSON Pointer（如 `/env/ANTHROPIC_BASE_URL`）
+    pub sources: BTreeMap<String, PathBuf>,
+}
+
+impl EffectiveSettings {
+    /// 查询某个值来自哪个文件
+    pub fn source_of(&self, pointer: &str) -> Option<&Path> {
+        self.sources.get(pointer).map(PathBuf::as_path)
+    }
+
+    /// env 中的变量（非字符串值按 JSON 文本显示）及其来源
+    pub fn env(&self) -> Vec<(String, String, Option<&Path>)> {
+        let Some(env) = self.settings.get("env").and_then(|env| env.as_object()) else {
+            return Vec::new();
+        };
+        env.iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                let source = self.source_of(&format!("/env/{}", escape_pointer_token(key)));
+                (key.clone(), value, source)
+            })
+            .collect()
+    }
+
+    /// 在当前结果之上叠加一层配置
+    fn overlay(&mut self, layer: &Value, source: &Path) {
+        merge_json(&mut self.settings, layer);
+        record_sources(layer, &mut String::new(), source, &mut self.sources);
+    }
+}
+
+/// JSON Pointer 中的键需要转义 `~` 和 `/`
+fn escape_pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+/// 记录一层配置中每个叶子值的来源；对象与非对象互相替换时清除被替换部分的旧记录
+fn record_sources(value: &Value, pointer: &mut String, source: &Path, sources: &mut BTreeMap<String, PathBuf>) {
+    match value {
+        Value::Object(obj) => {
+            sources.remove(pointer.as_str());
+            for (key, child) in obj {
+                let len = pointer.len();
+                pointer.push('/');
+                pointer.push_str(&escape_pointer_token(key));
+                record_sources(child, pointer, source, sources);
+                pointer.truncate(len);
+            }
+        }
+        _ => {
+            let prefix = format!("{}/", pointer);
+            sources.retain(|key, _| !key.starts_with(&prefix));
+            sources.insert(pointer.clone(), source.to_path_buf());
+        }
+    }
+}
+
+/// 当前进程中设置了（非空）的可覆盖环境变量
+pub fn process_env_overrides() -> Vec<(&'static str, String)> {
+    PROCESS_OVERRIDE_ENV_KEYS
+        .iter()
+        .filter_map(|key| {
+            let value = std::env::var(key).ok().filter(|v| !v.trim().is_empty())?;
+            Some((*key, value))
+        })
+        .collect()
+}
+
+/// 用进程环境变量覆盖配置文件中的取值，结果仍按变量名排序
+pub fn apply_env_overrides(
+    entries: Vec<EffectiveEnvEntry>,
+    overrides: &[(&str, String)],
+) -> Vec<EffectiveEnvEntry> {
+    let mut effective: std::collections::BTreeMap<String, EffectiveEnvEntry> =
+        entries.into_iter().map(|entry| (entry.key.clone(), entry)).collect();
+    for (key, value) in overrides {
+        let masked_value = effective.get(*key).map(|entry| entry.value.clone());
+        effective.insert(
+            key.to_string(),
+            EffectiveEnvEntry {
+                key: key.to_string(),
+                value: value.clone(),
+                source: EnvSource::Process,
+                masked_value,
+            },
+        );
+    }
+    effective.into_values().collect()
+}
+
+/// 全局 Claude 配置目录（~/.claude）
+pub fn global_claude_dir() -> Option<PathBuf> {
+    directories::BaseDirs::new().map(|dirs| dirs.home_dir().join(DEFAULT_CLAUDE_DIR_NAME))
+}
+
+/// 查找模板中包含的敏感环境变量
+pub fn find_secret_keys(template: &Value) -> Vec<String> {
+    template
+        .get("env")
+        .and_then(|env| env.as_object())
+        .map(|env| {
+            env.keys()
+                .filter(|key| SECRET_ENV_KEYS.contains(&key.as_str()))
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// 拆分 CLAUDE.md 中的 `KEY=value` 行，仅识别 CLAUDE_MD_ENV_KEYS 中的键
+fn split_claude_md_env_line(line: &str) -> Option<(&'static str, &str)> {
+    let trimmed = line.trim_start();
+    CLAUDE_MD_ENV_KEYS.iter().find_map(|key| {
+        trimmed
+            .strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix('='))
+            .map(|value| (*key, value))
+    })
+}
+
+/// 替换 CLAUDE.md 内容中识别的环境变量行，只改动值，缩进和换行符原样保留
+fn update_claude_md_env(content: &str, env: &HashMap<String, String>) -> String {
+    let mut written = Vec::new();
+    let mut result = String::with_capacity(content.len());
+
+    for line in content.split_inclusive('\n') {
+        let body = line.trim_end_matches(['\r', '\n']);
+        let ending = &line[body.len()..];
+
+        match split_claude_md_env_line(body).and_then(|(key, _)| env.get(key).map(|v| (key, v))) {
+            Some((key, value)) => {
+                let indent = &body[..body.len() - body.trim_start().len()];
+                result.push_str(&format!("{}{}={}{}", indent, key, value, ending));
+                written.push(key);
+            }
+            None => result.push_str(line),
+        }
+    }
+
+    let missing: Vec<&str> = CLAUDE_MD_ENV_KEYS
+        .iter()
+        .copied()
+        .filter(|key| env.contains_key(*key) && !written.contains(key))
+        .collect();
+
+    if !missing.is_empty() {
+        if !result.is_empty() && !result.ends_with('\n') {
+            result.push('\n');
+        }
+        for key in missing {
+            result.push_str(&format!("{}={}\n", key, env[key]));
+        }
+    }
+
+    result
+}
+
+/// 解析一行 `KEY=VALUE`，允许 `export ` 前缀，值两侧成对的引号会去掉；不含 `=` 时返回 None
+fn parse_env_line(line: &str) -> Option<(&str, &str)> {
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let (key, value) = line.split_once('=')?;
+    let value = value.trim();
+    let value = ['"', '\'']
+        .iter()
+        .find_map(|quote| value.strip_prefix(*quote).and_then(|v| v.strip_suffix(*quote)))
+        .unwrap_or(value);
+    Some((key.trim(), value))
+}
+
+/// 严格解析 `KEY=VALUE` 文本（如从剪贴板粘贴的内容）：空行和 `#` 注释忽略，其余每一行都必须是
+/// 合法变量名的键值对，否则返回第一个无效行的行号（从 1 开始）
+pub fn parse_env_block(raw: &str) -> Result<serde_json::Map<String, Value>, usize> {
+    let mut env = serde_json::Map::new();
+    for (index, line) in raw.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match parse_env_line(line) {
+            Some((key, value)) if is_valid_env_key(key) => {
+                env.insert(key.to_string(), json!(value));
+            }
+            _ => return Err(index + 1),
+        }
+    }
+    Ok(env)
+}
+
+/// 从配置的 env 中移除值为空字符串的变量，返回被移除的变量名
+///
+/// 用于“空值不写入”策略：有意留空的 Base URL 或 Token 不以 `""` 写入，而是交给全局配置。
+pub fn remove_empty_env_values(settings: &mut Value) -> Vec<String> {
+    let Some(env) = settings.get_mut("env").and_then(|env| env.as_object_mut()) else {
+        return Vec::new();
+    };
+    let empty: Vec<String> = env
+        .iter()
+        .filter(|(_, value)| value.as_str() == Some(""))
+        .map(|(key, _)| key.clone())
+        .collect();
+    for key in &empty {
+        env.remove(key);
+    }
+    empty
+}
+
+/// env 为字符串时尝试按 `KEY=VALUE` 行解析为对象，返回是否修复
+///
+/// 解析不出任何键值对时保持原样，避免丢失用户内容；修复后 env 为对象，再次调用不会改动。
+fn repair_string_env(settings: &mut Value) -> bool {
+    let Some(raw) = settings.get("env").and_then(|env| env.as_str()) else {
+        return false;
+    };
+
+    let mut env = serde_json::Map::new();
+    for line in raw.split(['\n', ';']) {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = parse_env_line(line) {
+            if key.is_empty() {
+                continue;
+            }
+            env.insert(key.to_string(), json!(value));
+        }
+    }
+
+    if env.is_empty() {
+        warn!("env 为字符串但无法解析出 KEY=VALUE，保持原样");
+        return false;
+    }
+
+    settings["env"] = Value::Object(env);
+    true
+}
+
+fn read_file(path: impl AsRef<Path>) -> ConfigResult<String> {
+    let path = path.as_ref();
+    fs::read_to_string(path).map_err(|e| ConfigError::io(path, e))
+}
+
+fn write_file(path: impl AsRef<Path>, content: impl AsRef<[u8]>) -> ConfigResult<()> {
+    let path = path.as_ref();
+    fs::write(path, content).map_err(|e| ConfigError::write(path, e))
+}
+
+/// 先写入同目录下的临时文件再重命名，避免写到一半时留下损坏的配置文件
+fn write_file_atomic(path: impl AsRef<Path>, content: impl AsRef<[u8]>) -> ConfigResult<()> {
+    let path = path.as_ref();
+    let mut temp = path.as_os_str().to_owned();
+    temp.push(".tmp");
+    let temp = PathBuf::from(temp);
+
+    write_file(&temp, content)?;
+    fs::rename(&temp, path).map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        ConfigError::write(path, e)
+    })
+}
+
+fn parse_json(path: impl AsRef<Path>, content: &str) -> ConfigResult<Value> {
+    serde_json::from_str(content).map_err(|e| ConfigError::invalid_json(path, e))
+}
+
+/// 默认的 Claude 配置子目录名
+pub const DEFAULT_CLAUDE_DIR_NAME: &str = ".claude";
+
+pub struct ClaudeConfigManager {
+    directory_path: String,
+    claude_dir_name: String,
+    /// 是否为全局配置（~/.claude/settings.json）
+    global: bool,
+    /// 配置文件发生变更后调用
+    change_hook: Option<ChangeHook>,
+    /// 自定义的 CLAUDE.local.md 内容，None 时使用内置模板
+    claude_local_md: Option<String>,
+    /// 写入配置时使用的格式
+    write_format: WriteFormat,
+    /// 写入 JSON 配置时的缩进
+    indent: JsonIndent,
+    /// 额外写入 Token 的自定义变量名（部分网关不读取 Anthropic 的标准变量）
+    extra_auth_env_key: Option<String>,
+    /// 值为空的环境变量不写入，已存在的同名变量被移除
+    omit_empty_env: bool,
+}
+
+impl ClaudeConfigManager {
+    pub fn new(directory_path: String) -> Self {
+        Self::new_with_dir_name(directory_path, DEFAULT_CLAUDE_DIR_NAME)
+    }
+
+    /// 使用自定义的配置子目录名（默认为 .claude）
+    pub fn new_with_dir_name(directory_path: String, dir_name: &str) -> Self {
+        Self {
+            directory_path,
+            claude_dir_name: dir_name.to_string(),
+            global: false,
+            change_hook: None,
+            claude_local_md: None,
+            write_format: WriteFormat::default(),
+            indent: JsonIndent::default(),
+            extra_auth_env_key: None,
+            omit_empty_env: false,
+        }
+    }
+
+    /// 全局配置：读写用户主目录下的 ~/.claude/settings.json，无法确定主目录时返回 None
+    pub fn global() -> Option<Self> {
+        let home = directories::BaseDirs::new()?.home_dir().to_path_buf();
+        Some(Self::global_at(&home))
+    }
+
+    /// 以指定目录作为主目录的全局配置
+    pub fn global_at(home: &Path) -> Self {
+        Self {
+            directory_path: home.to_string_lossy().to_string(),
+            claude_dir_name: DEFAULT_CLAUDE_DIR_NAME.to_string(),
+            global: true,
+            change_hook: None,
+            claude_local_md: None,
+            write_format: WriteFormat::default(),
+            indent: JsonIndent::default(),
+            extra_auth_env_key: None,
+            omit_empty_env: false,
+        }
+    }
+
+    /// 注册配置变更钩子：写入、清除、重置成功且配置文件内容发生变化时调用
+    pub fn with_change_hook(mut self, hook: ChangeHook) -> Self {
+        self.change_hook = Some(hook);
+        self
+    }
+
+    /// 使用自定义的 CLAUDE.local.md 内容（如账号级模板），None 时使用内置模板
+    pub fn with_claude_local_md(mut self, content: Option<String>) -> Self {
+        self.claude_local_md = content;
+        self
+    }
+
+    /// 写入认证变量时同时以该变量名写入 Token，清除认证配置时一并移除
+    pub fn with_extra_auth_env_key(mut self, key: Option<String>) -> Self {
+        self.extra_auth_env_key = key.filter(|key| is_valid_env_key(key));
+        self
+    }
+
+    /// 设置写入格式：Preserve 时生效的 YAML / TOML 配置按原格式写回
+    pub fn with_write_format(mut self, write_format: WriteFormat) -> Self {
+        self.write_format = write_format;
+        self
+    }
+
+    /// 设置写入 JSON 配置时的缩进
+    pub fn with_indent(mut self, indent: JsonIndent) -> Self {
+        self.indent = indent;
+        self
+    }
+
+    /// 设置为 true 时值为空的环境变量不写入 env，已存在的同名变量被移除（默认写入空字符串）
+    pub fn with_omit_empty_env(mut self, omit: bool) -> Self {
+        self.omit_empty_env = omit;
+        self
+    }
+
+    /// 是否不写入值为空的环境变量
+    pub fn omits_empty_env(&self) -> bool {
+        self.omit_empty_env
+    }
+
+    /// 写入 CLAUDE.local.md 时使用的内容
+    fn claude_local_md_content(&self) -> &str {
+        self.claude_local_md.as_deref().unwrap_or(CLAUDE_LOCAL_MD_CONTENT)
+    }
+
+    pub fn is_global(&self) -> bool {
+        self.global
+    }
+
+    /// 执行修改配置文件的操作，并把前后哈希通知给变更钩子
+    fn audited<T>(&self, action: ConfigAction, op: impl FnOnce() -> ConfigResult<T>) -> ConfigResult<T> {
+        let Some(hook) = &self.change_hook else {
+            return op();
+        };
+
+        let settings_path = self.write_target_path();
+        let before_hash = audit::file_hash(&settings_path);
+        let result = op()?;
+        let after_hash = audit::file_hash(&settings_path);
+
+        if before_hash != after_hash {
+            hook(&ConfigChange {
+                action,
+                settings_path,
+                before_hash,
+                after_hash,
+            });
+        }
+        Ok(result)
+    }
+
+    fn get_claude_dir(&self) -> String {
+        format!("{}/{}", self.directory_path, self.claude_dir_name)
+    }
+
+    fn get_settings_file(&self) -> String {
+        let file_name = if self.global {
+            "settings.json"
+        } else {
+            "settings.local.json"
+        };
+        format!("{}/{}", self.get_claude_dir(), file_name)
+    }
+
+    /// 获取 .claude 目录路径
+    pub fn claude_dir_path(&self) -> PathBuf {
+        PathBuf::from(self.get_claude_dir())
+    }
+
+    /// 获取 settings.local.json 文件路径
+    pub fn settings_file_path(&self) -> PathBuf {
+        PathBuf::from(self.get_settings_file())
+    }
+
+    fn get_alternative_settings_files(&self) -> Vec<String> {
+        // 全局配置只有 settings.json 一个文件
+        if self.global {
+            return Vec::new();
+        }
+        vec![
+            format!("{}/settings.local.yaml", self.get_claude_dir()),
+            format!("{}/settings.local.yml", self.get_claude_dir()),
+            format!("{}/settings.local.toml", self.get_claude_dir()),
+            format!("{}/settings.json", self.get_claude_dir()),
+            format!("{}/claude_config.json", self.get_claude_dir()),
+            format!("{}/.claude_config", self.directory_path),
+            format!("{}/CLAUDE.md", self.directory_path),
+        ]
+    }
+
+    /// 按读取优先级列出所有候选配置文件
+    pub fn settings_file_candidates(&self) -> Vec<PathBuf> {
+        std::iter::once(self.get_settings_file())
+            .chain(self.get_alternative_settings_files())
+            .map(PathBuf::from)
+            .collect()
+    }
+
+    /// 解析当前生效的配置文件：第一个存在的候选文件，都不存在时为 settings.local.json
+    pub fn resolve_active_settings_path(&self) -> PathBuf {
+        self.settings_file_candidates()
+            .into_iter()
+            .find(|path| path.exists())
+            .unwrap_or_else(|| self.settings_file_path())
+    }
+
+    /// `.claude` 目录（通常经由符号链接）实际位于项目目录之外时返回其真实路径；
+    /// 目录不存在或无法解析时返回 None
+    pub fn claude_dir_outside_directory(&self) -> Option<PathBuf> {
+        let claude_dir = fs::canonicalize(self.get_claude_dir()).ok()?;
+        let root = fs::canonicalize(&self.directory_path).ok()?;
+        (!claude_dir.starts_with(&root)).then_some(claude_dir)
+    }
+
+    /// 写入配置的目标文件：默认是 settings.local.json；
+    /// 写入格式为 Preserve 且生效配置是 YAML / TOML 文件时写回该文件
+    pub fn write_target_path(&self) -> PathBuf {
+        if self.write_format == WriteFormat::Preserve {
+            let active = self.resolve_active_settings_path();
+            if active.exists() && SettingsFormat::from_path(&active) != SettingsFormat::Json {
+                return active;
+            }
+        }
+        self.settings_file_path()
+    }
+
+    /// 计算会话实际看到的环境变量：全局配置在下，项目配置在上，进程环境变量最优先
+    pub fn effective_env(&self) -> ConfigResult<Vec<EffectiveEnvEntry>> {
+        let entries = self.effective_env_with_global(global_claude_dir().as_deref())?;
+        Ok(apply_env_overrides(entries, &process_env_overrides()))
+    }
+
+    /// 按优先级从低到高叠加全局 settings.json 和项目的分层配置（见 `read_effective_settings`），
+    /// 返回按变量名排序的结果
+    pub fn effective_env_with_global(&self, global_dir: Option<&Path>) -> ConfigResult<Vec<EffectiveEnvEntry>> {
+        let mut effective: BTreeMap<String, EffectiveEnvEntry> = BTreeMap::new();
+        let mut insert = |key: String, value: String, source: &Path| {
+            effective.insert(
+                key.clone(),
+                EffectiveEnvEntry {
+                    key,
+                    value,
+                    source: EnvSource::File(source.to_path_buf()),
+                    masked_value: None,
+                },
+            );
+        };
+
+        if let Some(global_dir) = global_dir.filter(|dir| *dir != self.claude_dir_path()) {
+            let global_file = global_dir.join("settings.json");
+            if global_file.exists() {
+                let content = read_file(&global_file)?;
+                let mut settings = parse_json(&global_file, &content)?;
+                repair_string_env(&mut settings);
+                let mut global = EffectiveSettings::default();
+                global.overlay(&settings, &global_file);
+                for (key, value, _) in global.env() {
+                    insert(key, value, &global_file);
+                }
+            }
+        }
+
+        let project = self.read_effective_settings()?;
+        for (key, value, source) in project.env() {
+            if let Some(source) = source {
+                insert(key, value, source);
+            }
+        }
+
+        Ok(effective.into_values().collect())
+    }
+
+    /// 按 Claude Code 的方式分层读取项目配置：项目 settings.json 为基础，生效的本地配置
+    /// （通常是 settings.local.json）深度合并在上，并记录每个值的来源。写入仍只针对本地配置
+    pub fn read_effective_settings(&self) -> ConfigResult<EffectiveSettings> {
+        let mut effective = EffectiveSettings {
+            settings: json!({}),
+            sources: BTreeMap::new(),
+        };
+
+        let active = self.resolve_active_settings_path();
+        let shared = self.claude_dir_path().join("settings.json");
+        if shared != active && shared.exists() {
+            let content = read_file(&shared)?;
+            let mut settings = parse_json(&shared, &content)?;
+            repair_string_env(&mut settings);
+            effective.overlay(&settings, &shared);
+        }
+        if active.exists() {
+            effective.overlay(&self.read_settings()?, &active);
+        }
+
+        Ok(effective)
+    }
+
+    /// 分层合并后的 env 中的字符串变量（见 `read_effective_settings`）
+    pub fn get_effective_env_config(&self) -> ConfigResult<HashMap<String, String>> {
+        let effective = self.read_effective_settings()?;
+        let mut env_config = HashMap::new();
+        if let Some(env) = effective.settings.get("env").and_then(|env| env.as_object()) {
+            for (key, value) in env {
+                if let Some(str_value) = value.as_str() {
+                    env_config.insert(key.clone(), str_value.to_string());
+                }
+            }
+        }
+        Ok(env_config)
+    }
+
+    fn ensure_claude_dir(&self) -> Result<()> {
+        // 不存在的项目目录会被创建，但路径被普通文件占用时直接报错
+        let project_dir = Path::new(&self.directory_path);
+        if project_dir.exists() && !project_dir.is_dir() {
+            return Err(ConfigError::NotADirectory(project_dir.to_path_buf()).into());
+        }
+
+        let claude_dir = self.get_claude_dir();
+        if !Path::new(&claude_dir).exists() {
+            fs::create_dir_all(&claude_dir).map_err(|e| ConfigError::write(&claude_dir, e))?;
+        }
+        Ok(())
+    }
+
+    /// 除生效文件外仍存在的其他候选配置文件（不会被读取，容易造成误解）
+    pub fn shadowed_settings_files(&self) -> Vec<PathBuf> {
+        self.settings_file_candidates()
+            .into_iter()
+            .filter(|path| path.exists())
+            .skip(1)
+            .collect()
+    }
+
+    /// 同时存在多个候选配置文件时提示哪个文件生效，每个目录只提示一次
+    fn warn_shadowed_settings_once(&self) {
+        static WARNED: OnceLock<Mutex<HashSet<String>>> = OnceLock::new();
+
+        let shadowed = self.shadowed_settings_files();
+        if shadowed.is_empty() {
+            return;
+        }
+
+        let mut warned = WARNED
+            .get_or_init(|| Mutex::new(HashSet::new()))
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        if !warned.insert(self.directory_path.clone()) {
+            return;
+        }
+
+        let ignored: Vec<String> = shadowed.iter().map(|p| p.display().to_string()).collect();
+        warn!(
+            "目录 {} 存在多个配置文件，生效的是 {}，以下文件被忽略: {}",
+            self.directory_path,
+            self.resolve_active_settings_path().display(),
+            ignored.join(", ")
+        );
+    }
+
+    fn read_settings(&self) -> Result<Value> {
+        self.warn_shadowed_settings_once();
+        let mut settings = self.read_settings_raw()?;
+        if repair_string_env(&mut settings) {
+            info!("检测到 {} 中 env 为字符串，已按 KEY=VALUE 解析为对象", self.directory_path);
+        }
+        Ok(settings)
+    }
+
+    /// 读取当前生效的配置文件内容，不做任何修复
+    fn read_settings_raw(&self) -> Result<Value> {
+        let settings_file = self.get_settings_file();
+
+        if Path::new(&settings_file).exists() {
+            let content = read_file(&settings_file)?;
+            return Ok(parse_json(&settings_file, &content)?);
+        }
+
+        // 检查其他可能的配置文件
+        for alt_file in self.get_alternative_settings_files() {
+            if Path::new(&alt_file).exists() {
+                // 如果是 CLAUDE.md 文件，需要特殊处理
+                if alt_file.ends_with("CLAUDE.md") {
+                    return self.parse_claude_md(&alt_file);
+                }
+
+                let content = read_file(&alt_file)?;
+                match parse_settings(&alt_file, &content) {
+                    Ok(settings) => return Ok(settings),
+                    Err(e) => warn!("无法解析配置文件，已跳过: {}", e),
+                }
+            }
+        }
+
+        Ok(json!({}))
+    }
+
+    fn parse_claude_md(&self, file_path: &str) -> Result<Value> {
+        let content = read_file(file_path)?;
+
+        // 简单解析CLAUDE.md中的环境变量
+        let mut env_config = json!({});
+
+        for line in content.lines() {
+            if let Some((key, value)) = split_claude_md_env_line(line) {
+                env_config[key] = json!(value.trim());
+            }
+        }
+
+        if env_config.as_object().unwrap().is_empty() {
+            return Ok(json!({}));
+        }
+
+        Ok(json!({ "env": env_config }))
+    }
+
+    /// CLAUDE.md 中识别出的环境变量，文件不存在时为空
+    pub fn claude_md_env(&self) -> ConfigResult<HashMap<String, String>> {
+        let file_path = format!("{}/CLAUDE.md", self.directory_path);
+        if self.global || !Path::new(&file_path).exists() {
+            return Ok(HashMap::new());
+        }
+        let content = read_file(&file_path)?;
+        Ok(content
+            .lines()
+            .filter_map(split_claude_md_env_line)
+            .map(|(key, value)| (key.to_string(), value.trim().to_string()))
+            .collect())
+    }
+
+    /// 更新 CLAUDE.md 中识别的环境变量行，不存在的键追加到文件末尾，其余内容保持不变
+    pub fn write_claude_md_env(&self, env: &HashMap<String, String>) -> ConfigResult<()> {
+        let file_path = format!("{}/CLAUDE.md", self.directory_path);
+        let content = read_file(&file_path)?;
+        write_file(&file_path, update_claude_md_env(&content, env))
+    }
+
+    fn write_settings(&self, settings: &Value) -> Result<()> {
+        self.ensure_claude_dir()?;
+        let settings_file = self.write_target_path();
+        let content = serialize_settings_with_indent(&settings_file, settings, self.indent)?;
+        write_file_atomic(&settings_file, content)?;
+        Ok(())
+    }
+
+    /// 把配置深度合并进写入目标：overlay 中没有的键（mcpServers、用户自行添加的变量等）保持不变，
+    /// 按目标文件的格式序列化，先写临时文件再重命名，中断时不会截断原文件；
+    /// 写入后读回核对 env，不一致时返回 VerifyFailed
+    pub fn merge_settings(&self, overlay: &Value) -> ConfigResult<()> {
+        let mut settings = self.read_settings()?;
+        let mut overlay = overlay.clone();
+        let env = overlay
+            .as_object_mut()
+            .and_then(|obj| obj.remove("env"))
+            .unwrap_or_else(|| json!({}));
+        merge_json(&mut settings, &overlay);
+        let env = self.merge_env_overlay(&mut settings, env);
+
+        self.write_settings(&settings)?;
+        self.verify_env_written(&env)
+    }
+
+    /// 把 env 合并进配置并返回实际合并的变量；`omit_empty_env` 时值为空的变量不写入，
+    /// 并移除配置中已有的同名变量
+    fn merge_env_overlay(&self, settings: &mut Value, env: Value) -> Value {
+        let mut overlay = json!({ "env": env });
+        if self.omit_empty_env {
+            let empty = remove_empty_env_values(&mut overlay);
+            if let Some(existing) = settings.get_mut("env").and_then(|env| env.as_object_mut()) {
+                for key in &empty {
+                    existing.remove(key);
+                }
+            }
+        }
+        merge_json(settings, &overlay);
+        overlay["env"].take()
+    }
+
+    /// 重新读取并解析写入目标，确认 env 中写入的每个键都与预期一致，之后才报告写入成功
+    fn verify_env_written(&self, expected: &Value) -> ConfigResult<()> {
+        let settings_file = self.write_target_path();
+        let written = parse_settings(&settings_file, &read_file(&settings_file)?)?;
+        let written_env = written.get("env");
+        for (key, value) in expected.as_object().into_iter().flatten() {
+            if written_env.and_then(|env| env.get(key)) != Some(value) {
+                warn!("写入校验失败: {} 中的 {} 与写入的值不一致", settings_file.display(), key);
+                return Err(ConfigError::VerifyFailed {
+                    path: settings_file,
+                    key: key.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn update_env_config_with_options(
+        &self,
+        token: String,
+        base_url: String,
+        auth_style: AuthStyle,
+        is_sandbox: bool,
+    ) -> ConfigResult<bool> {
+        self.audited(ConfigAction::Apply, || {
+            Ok(self.apply_env_config(token, base_url, auth_style, is_sandbox, None)?)
+        })
+    }
+
+    /// 同 update_env_config_with_options，并按账号代理配置写入 HTTP_PROXY / HTTPS_PROXY / NO_PROXY，
+    /// 未设置的代理变量会被移除
+    pub fn update_env_config_full(
+        &self,
+        token: String,
+        base_url: String,
+        auth_style: AuthStyle,
+        is_sandbox: bool,
+        proxy: &ProxyConfig,
+    ) -> ConfigResult<bool> {
+        self.audited(ConfigAction::Apply, || {
+            Ok(self.apply_env_config(token, base_url, auth_style, is_sandbox, Some(proxy))?)
+        })
+    }
+
+    /// `proxy` 为 None 时保留已有的代理变量不动
+    fn apply_env_config(
+        &self,
+        token: String,
+        base_url: String,
+        auth_style: AuthStyle,
+        is_sandbox: bool,
+        proxy: Option<&ProxyConfig>,
+    ) -> Result<bool> {
+        let mut settings = self.read_settings()?;
+
+        if !settings.is_object() {
+            settings = json!({});
+        }
+
+        let auth_keys = auth_style.env_keys();
+
+        // 移除当前认证方式不使用的认证变量，避免切换后残留旧 Token
+        if let Some(env) = settings.get_mut("env").and_then(|env| env.as_object_mut()) {
+            for key in AuthStyle::Both.env_keys() {
+                if !auth_keys.contains(key) {
+                    env.remove(*key);
+                }
+            }
+            if !is_sandbox {
+                env.remove("IS_SANDBOX");
+            }
+            if proxy.is_some() {
+                for key in PROXY_ENV_KEYS {
+                    env.remove(*key);
+                }
+            }
+        }
+
+        let mut env_overlay = json!({
+            "ANTHROPIC_BASE_URL": base_url,
+        });
+
+        // 根据认证方式写入对应的环境变量
+        for key in auth_keys {
+            env_overlay[*key] = json!(token);
+        }
+        if let Some(key) = &self.extra_auth_env_key {
+            env_overlay[key] = json!(token);
+        }
+
+        // 添加可选的环境变量
+        if is_sandbox {
+            env_overlay["IS_SANDBOX"] = json!("1");
+        }
+        if let Some(proxy) = proxy {
+            for (key, value) in proxy.env_entries() {
+                env_overlay[key] = json!(value);
+            }
+        }
+
+        let env_overlay = self.merge_env_overlay(&mut settings, env_overlay);
+
+        self.write_settings(&settings)?;
+        self.verify_env_written(&env_overlay)?;
+
+        // 复制 CLAUDE.local.md 文件
+        self.copy_claude_local_md()?;
+
+        Ok(true)
+    }
+
+    /// GUI 使用的写入方式：按 URL 指定的变量名写入 Token，并叠加 URL 默认变量与账号自定义变量；
+    /// 与 update_env_config_full 一样合并进已有的 env，用户自行添加的变量保持不变
+    pub fn update_env_config_with_extended_options(
+        &self,
+        token: String,
+        base_url: String,
+        api_key_name: String,
+        is_sandbox: bool,
+        base_url_default_env_vars: Option<HashMap<String, String>>,
+        account_custom_env_vars: Option<HashMap<String, String>>,
+    ) -> ConfigResult<bool> {
+        self.audited(ConfigAction::Apply, || {
+            self.apply_extended_env_config(
+                token,
+                base_url,
+                api_key_name,
+                is_sandbox,
+                base_url_default_env_vars,
+                account_custom_env_vars,
+            )
+        })
+    }
+
+    fn apply_extended_env_config(
+        &self,
+        token: String,
+        base_url: String,
+        api_key_name: String,
+        is_sandbox: bool,
+        base_url_default_env_vars: Option<HashMap<String, String>>,
+        account_custom_env_vars: Option<HashMap<String, String>>,
+    ) -> ConfigResult<bool> {
+        let mut settings = self.read_settings()?;
+
+        if !settings.is_object() {
+            settings = json!({});
+        }
+
+        // 移除本次不使用的认证变量，避免切换后残留旧 Token
+        if let Some(env) = settings.get_mut("env").and_then(|env| env.as_object_mut()) {
+            for key in AuthStyle::Both.env_keys() {
+                if *key != api_key_name {
+                    env.remove(*key);
+                }
+            }
+            if !is_sandbox {
+                env.remove("IS_SANDBOX");
+            }
+        }
+
+        let mut env_config = json!({});
+
+        // 1. 设置基础必需的环境变量
+        env_config["ANTHROPIC_BASE_URL"] = json!(base_url);
+        env_config[&api_key_name] = json!(token);
+
+        // 2. 添加 URL 级别的默认环境变量
+        if let Some(default_vars) = base_url_default_env_vars {
+            for (key, value) in default_vars {
+                env_config[&key] = parse_env_value(&value);
+            }
+        }
+
+        // 3. 添加账号级别的自定义环境变量（覆盖默认值）
+        if let Some(custom_vars) = account_custom_env_vars {
+            for (key, value) in custom_vars {
+                env_config[&key] = parse_env_value(&value);
+            }
+        }
+
+        // 4. 添加沙盒模式环境变量
+        if is_sandbox {
+            env_config["IS_SANDBOX"] = json!("1");
+        }
+
+        let env_config = self.merge_env_overlay(&mut settings, env_config);
+
+        self.write_settings(&settings)?;
+        self.verify_env_written(&env_config)?;
+
+        // 复制 CLAUDE.local.md 文件
+        self.copy_claude_local_md()?;
+
+        Ok(true)
+    }
+
+    pub fn get_env_config(&self) -> ConfigResult<HashMap<String, String>> {
+        let settings = self.read_settings()?;
+        let mut env_config = HashMap::new();
+
+        if let Some(env) = settings.get("env") {
+            if let Some(obj) = env.as_object() {
+                for (key, value) in obj {
+                    if let Some(str_value) = value.as_str() {
+                        env_config.insert(key.clone(), str_value.to_string());
+                    }
+                }
+            }
+        }
+
+        Ok(env_config)
+    }
+
+    /// 用 `source` 目录的 env 替换本目录的 env（原样复制，包括 Token），其余配置保持不变，
+    /// 源目录没有 env 时不修改本目录；`include_claude_local_md` 为 true 且源目录存在
+    /// CLAUDE.local.md 时一并复制
+    pub fn copy_env_from(&self, source: &ClaudeConfigManager, include_claude_local_md: bool) -> ConfigResult<CopyReport> {
+        self.audited(ConfigAction::Apply, || {
+            let source_env = source
+                .read_settings()?
+                .get("env")
+                .and_then(|env| env.as_object())
+                .cloned()
+                .unwrap_or_default();
+
+            if !source_env.is_empty() {
+                let mut settings = self.read_settings()?;
+                if !settings.is_object() {
+                    settings = json!({});
+                }
+                settings["env"] = Value::Object(source_env.clone());
+                self.write_settings(&settings)?;
+            }
+
+            let mut report = CopyReport {
+                env_keys: source_env.keys().cloned().collect(),
+                claude_local_md_copied: false,
+            };
+            report.env_keys.sort();
+
+            let source_md = Path::new(&source.directory_path).join("CLAUDE.local.md");
+            if include_claude_local_md && !self.global && source_md.exists() {
+                let target_md = Path::new(&self.directory_path).join("CLAUDE.local.md");
+                write_file(&target_md, read_file(&source_md)?)?;
+                report.claude_local_md_copied = true;
+            }
+
+            info!("已从 {} 复制配置到 {}: {:?}", source.directory_path, self.directory_path, report);
+            Ok(report)
+        })
+    }
+
+    /// 设置单个环境变量，其余配置保持不变
+    pub fn set_env_var(&self, key: &str, value: &str) -> ConfigResult<()> {
+        self.audited(ConfigAction::Apply, || {
+            let mut settings = self.read_settings()?;
+            if !settings.is_object() {
+                settings = json!({});
+            }
+            self.merge_env_overlay(&mut settings, json!({ key: value }));
+            Ok(self.write_settings(&settings)?)
+        })
+    }
+
+    /// 把一组环境变量合并进 env，同名变量覆盖，其余配置保持不变
+    pub fn merge_env_vars(&self, vars: &serde_json::Map<String, Value>) -> ConfigResult<()> {
+        self.audited(ConfigAction::Apply, || {
+            let mut settings = self.read_settings()?;
+            if !settings.is_object() {
+                settings = json!({});
+            }
+            self.merge_env_overlay(&mut settings, Value::Object(vars.clone()));
+            self.write_settings(&settings)?;
+            info!("已向 {} 合并环境变量: {:?}", self.directory_path, vars.keys().collect::<Vec<_>>());
+            Ok(())
+        })
+    }
+
+    /// 按 JSON Pointer（如 `/permissions/allow/0`）读取配置中的值，路径不存在时返回 None
+    pub fn get_setting(&self, pointer: &str) -> ConfigResult<Option<Value>> {
+        parse_json_pointer(pointer).map_err(|reason| ConfigError::InvalidPointer {
+            pointer: pointer.to_string(),
+            reason,
+        })?;
+        let settings = self.read_settings()?;
+        Ok(settings.pointer(pointer).cloned())
+    }
+
+    /// 按 JSON Pointer 写入配置中的值，中间路径必须已存在
+    pub fn set_setting(&self, pointer: &str, value: Value) -> ConfigResult<()> {
+        self.set_setting_with_options(pointer, value, false)
+    }
+
+    /// 同 set_setting，`create_missing` 为 true 时自动创建缺失的中间对象
+    pub fn set_setting_with_options(&self, pointer: &str, value: Value, create_missing: bool) -> ConfigResult<()> {
+        self.audited(ConfigAction::Apply, || {
+            let mut settings = self.read_settings()?;
+            if !settings.is_object() {
+                settings = json!({});
+            }
+            set_json_pointer(&mut settings, pointer, value, create_missing).map_err(|reason| {
+                ConfigError::InvalidPointer {
+                    pointer: pointer.to_string(),
+                    reason,
+                }
+            })?;
+            Ok(self.write_settings(&settings)?)
+        })
+    }
+
+    /// 读取 mcpServers 中的所有 MCP 服务器，按名称排序
+    pub fn get_mcp_servers(&self) -> ConfigResult<BTreeMap<String, McpServer>> {
+        let settings = self.read_settings()?;
+        Ok(settings
+            .get(MCP_SERVERS_KEY)
+            .and_then(|servers| servers.as_object())
+            .map(|servers| {
+                servers
+                    .iter()
+                    .map(|(name, value)| (name.clone(), McpServer::from_value(value)))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// 新增或更新 MCP 服务器，已有定义中的其他字段保持不变，env 等其他配置不受影响
+    pub fn set_mcp_server(&self, name: &str, server: &McpServer) -> ConfigResult<()> {
+        self.audited(ConfigAction::Apply, || {
+            let mut settings = self.read_settings()?;
+            if !settings.is_object() {
+                settings = json!({});
+            }
+            let obj = settings.as_object_mut().unwrap();
+            if !obj.get(MCP_SERVERS_KEY).is_some_and(|servers| servers.is_object()) {
+                obj.insert(MCP_SERVERS_KEY.to_string(), json!({}));
+            }
+            let servers = obj.get_mut(MCP_SERVERS_KEY).and_then(|servers| servers.as_object_mut()).unwrap();
+            let merged = server.merge_into(servers.get(name));
+            servers.insert(name.to_string(), merged);
+            self.write_settings(&settings)?;
+            info!("已更新 MCP 服务器 {}: {}", name, self.directory_path);
+            Ok(())
+        })
+    }
+
+    /// 删除 MCP 服务器，返回是否存在；删除后 mcpServers 为空时一并移除
+    pub fn remove_mcp_server(&self, name: &str) -> ConfigResult<bool> {
+        self.audited(ConfigAction::Apply, || {
+            let mut settings = self.read_settings()?;
+            let Some(obj) = settings.as_object_mut() else {
+                return Ok(false);
+            };
+            let Some(servers) = obj.get_mut(MCP_SERVERS_KEY).and_then(|servers| servers.as_object_mut()) else {
+                return Ok(false);
+            };
+            if servers.remove(name).is_none() {
+                return Ok(false);
+            }
+            if servers.is_empty() {
+                obj.remove(MCP_SERVERS_KEY);
+            }
+            self.write_settings(&settings)?;
+            info!("已删除 MCP 服务器 {}: {}", name, self.directory_path);
+            Ok(true)
+        })
+    }
+
+    /// 删除单个环境变量，返回变量是否存在
+    pub fn remove_env_var(&self, key: &str) -> ConfigResult<bool> {
+        self.audited(ConfigAction::Clear, || {
+            let mut settings = self.read_settings()?;
+            let Some(obj) = settings.as_object_mut() else {
+                return Ok(false);
+            };
+            let Some(env) = obj.get_mut("env").and_then(|env| env.as_object_mut()) else {
+                return Ok(false);
+            };
+            if env.remove(key).is_none() {
+                return Ok(false);
+            }
+            if env.is_empty() {
+                obj.remove("env");
+            }
+
+            self.write_settings(&settings)?;
+            Ok(true)
+        })
+    }
+
+    /// 按内置 schema 校验当前生效的配置，只报告已知字段的类型错误
+    pub fn validate_settings(&self) -> ConfigResult<Vec<ValidationIssue>> {
+        let settings = self.read_settings_raw()?;
+        Ok(validate_settings_value(&settings))
+    }
+
+    /// 将配置文件中字符串形式的 env 改写为标准的对象形式，返回是否进行了修复
+    pub fn repair_settings(&self) -> ConfigResult<bool> {
+        let settings_path = self.resolve_active_settings_path();
+        if !settings_path.exists() || settings_path.ends_with("CLAUDE.md") {
+            return Ok(false);
+        }
+
+        let mut settings = self.read_settings_raw()?;
+        if !repair_string_env(&mut settings) {
+            return Ok(false);
+        }
+
+        write_file(
+            &settings_path,
+            serialize_settings_with_indent(&settings_path, &settings, self.indent)?,
+        )?;
+
+        // 重新读取确认已是对象形式，避免反复修复
+        let content = read_file(&settings_path)?;
+        let reloaded = parse_settings(&settings_path, &content)?;
+        if reloaded.get("env").is_some_and(|env| env.is_string()) {
+            return Err(anyhow::anyhow!("修复后 env 仍为字符串: {}", settings_path.display()).into());
+        }
+
+        info!("已修复配置文件: {}", settings_path.display());
+        Ok(true)
+    }
+
+    pub fn clear_env_config(&self) -> ConfigResult<bool> {
+        self.audited(ConfigAction::Clear, || self.clear_auth_env())
+    }
+
+    fn clear_auth_env(&self) -> ConfigResult<bool> {
+        let mut settings = self.read_settings()?;
+
+        if let Some(env) = settings.get_mut("env") {
+            if let Some(obj) = env.as_object_mut() {
+                obj.remove("ANTHROPIC_API_KEY");
+                obj.remove("ANTHROPIC_AUTH_TOKEN");
+                obj.remove("ANTHROPIC_BASE_URL");
+                if let Some(key) = &self.extra_auth_env_key {
+                    obj.remove(key);
+                }
+
+                if obj.is_empty() {
+                    settings.as_object_mut().unwrap().remove("env");
+                }
+            }
+        }
+
+        self.write_settings(&settings)?;
+        Ok(true)
+    }
+
+    /// 重置目录：按选项移除本工具写入的配置，只处理 settings.local.json，不删除用户自己的文件
+    pub fn reset_directory(&self, opts: ResetOptions) -> ConfigResult<ResetReport> {
+        self.audited(ConfigAction::Reset, || self.reset_files(opts))
+    }
+
+    fn reset_files(&self, opts: ResetOptions) -> ConfigResult<ResetReport> {
+        let mut report = ResetReport::default();
+        let settings_file = self.settings_file_path();
+
+        if (opts.remove_env_keys || opts.remove_sandbox) && settings_file.exists() {
+            let content = read_file(&settings_file)?;
+            let mut settings = parse_json(&settings_file, &content)?;
+
+            if let Some(obj) = settings.as_object_mut() {
+                if let Some(env) = obj.get_mut("env").and_then(|env| env.as_object_mut()) {
+                    let mut keys: Vec<&str> = Vec::new();
+                    if opts.remove_env_keys {
+                        keys.extend(MANAGED_ENV_KEYS);
+                    }
+                    if opts.remove_sandbox {
+                        keys.push("IS_SANDBOX");
+                    }
+                    for key in keys {
+                        if env.remove(key).is_some() {
+                            report.removed_keys.push(key.to_string());
+                        }
+                    }
+
+                    if env.is_empty() {
+                        obj.remove("env");
+                    }
+                }
+
+                if obj.is_empty() {
+                    fs::remove_file(&settings_file).map_err(|e| ConfigError::write(&settings_file, e))?;
+                    report.settings_file_removed = true;
+                } else if !report.removed_keys.is_empty() {
+                    self.write_settings(&settings)?;
+                }
+            }
+        }
+
+        if opts.remove_claude_local_md {
+            let target_file = Path::new(&self.directory_path).join("CLAUDE.local.md");
+            match self.claude_local_md_action(true)? {
+                ClaudeMdAction::SkipIdentical => {
+                    fs::remove_file(&target_file).map_err(|e| ConfigError::write(&target_file, e))?;
+                    report.claude_local_md_removed = true;
+                }
+                ClaudeMdAction::SkipModified => {
+                    warn!("CLAUDE.local.md 已被用户修改，保留: {}", target_file.display());
+                    report.claude_local_md_kept = true;
+                }
+                ClaudeMdAction::Create | ClaudeMdAction::Overwrite => {}
+            }
+        }
+
+        if opts.remove_empty_claude_dir {
+            let claude_dir = self.claude_dir_path();
+            let is_empty = claude_dir.is_dir()
+                && fs::read_dir(&claude_dir)
+                    .map_err(|e| ConfigError::io(&claude_dir, e))?
+                    .next()
+                    .is_none();
+            if is_empty {
+                fs::remove_dir(&claude_dir).map_err(|e| ConfigError::write(&claude_dir, e))?;
+                report.claude_dir_removed = true;
+            }
+        }
+
+        info!("已重置目录 {}: {:?}", self.directory_path, report);
+        Ok(report)
+    }
+
+    /// 可合并进 settings.local.json 的旧配置文件，按优先级从低到高排列
+    pub fn stale_settings_files(&self) -> Vec<PathBuf> {
+        [
+            format!("{}/claude_config.json", self.get_claude_dir()),
+            format!("{}/settings.json", self.get_claude_dir()),
+        ]
+        .into_iter()
+        .map(PathBuf::from)
+        .filter(|path| path.exists())
+        .collect()
+    }
+
+    /// 将旧的 claude_config.json / settings.json 合并到 settings.local.json，
+    /// settings.local.json 中已有的值优先；合并后旧文件重命名为 .bak。返回已合并的文件
+    pub fn merge_stale_settings(&self) -> ConfigResult<Vec<PathBuf>> {
+        let mut merged = json!({});
+        let mut merged_files = Vec::new();
+
+        for stale in self.stale_settings_files() {
+            let content = read_file(&stale)?;
+            match serde_json::from_str::<Value>(&content) {
+                Ok(mut value) => {
+                    repair_string_env(&mut value);
+                    merge_json(&mut merged, &value);
+                    merged_files.push(stale);
+                }
+                Err(e) => warn!("{} 不是有效的 JSON，跳过合并: {}", stale.display(), e),
+            }
+        }
+
+        if merged_files.is_empty() {
+            return Ok(merged_files);
+        }
+
+        let settings_file = self.settings_file_path();
+        if settings_file.exists() {
+            let content = read_file(&settings_file)?;
+            let mut local = parse_json(&settings_file, &content)?;
+            repair_string_env(&mut local);
+            merge_json(&mut merged, &local);
+        }
+
+        self.write_settings(&merged)?;
+
+        for stale in &merged_files {
+            let mut backup = stale.clone().into_os_string();
+            backup.push(".bak");
+            fs::rename(stale, &backup).map_err(|e| ConfigError::write(stale, e))?;
+            info!("已合并 {} 到 settings.local.json，原文件重命名为 .bak", stale.display());
+        }
+
+        Ok(merged_files)
+    }
+
+    /// 只读不写的旧配置文件（claude_config.json / .claude_config）
+    fn legacy_settings_files(&self) -> Vec<PathBuf> {
+        if self.global {
+            return Vec::new();
+        }
+        vec![
+            PathBuf::from(format!("{}/claude_config.json", self.get_claude_dir())),
+            PathBuf::from(format!("{}/.claude_config", self.directory_path)),
+        ]
+    }
+
+    /// 当前生效的配置来自旧配置文件时，将其写入 settings.local.json，并把旧文件重命名为 .migrated
+    pub fn migrate_legacy_config(&self) -> ConfigResult<MigrationReport> {
+        let active = self.resolve_active_settings_path();
+        if !self.legacy_settings_files().contains(&active) {
+            return Ok(MigrationReport::default());
+        }
+
+        let content = read_file(&active)?;
+        let mut settings = parse_json(&active, &content)?;
+        repair_string_env(&mut settings);
+        self.write_settings(&settings)?;
+
+        let mut renamed_to = active.clone().into_os_string();
+        renamed_to.push(".migrated");
+        let renamed_to = PathBuf::from(renamed_to);
+        fs::rename(&active, &renamed_to).map_err(|e| ConfigError::write(&active, e))?;
+        info!(
+            "已将 {} 迁移到 {}，原文件重命名为 .migrated",
+            active.display(),
+            self.get_settings_file()
+        );
+
+        Ok(MigrationReport {
+            migrated_file: Some(active),
+            renamed_to: Some(renamed_to),
+        })
+    }
+
+    /// 预判写入 CLAUDE.local.md 时会执行的动作（不写入任何文件）
+    ///
+    /// `preserve_modified` 为 true 时，内容与要写入的模板不同的已有文件视为用户修改过，将被跳过。
+    pub fn claude_local_md_action(&self, preserve_modified: bool) -> ConfigResult<ClaudeMdAction> {
+        let target_file = Path::new(&self.directory_path).join("CLAUDE.local.md");
+
+        if !target_file.exists() {
+            return Ok(ClaudeMdAction::Create);
+        }
+
+        let existing = read_file(&target_file)?;
+        if existing == self.claude_local_md_content() {
+            Ok(ClaudeMdAction::SkipIdentical)
+        } else if preserve_modified {
+            Ok(ClaudeMdAction::SkipModified)
+        } else {
+            Ok(ClaudeMdAction::Overwrite)
+        }
+    }
+
+    /// 比较目录中的 CLAUDE.local.md 与要写入的模板（不写入任何文件）
+    pub fn claude_local_md_status(&self) -> ConfigResult<ClaudeMdStatus> {
+        let target_file = Path::new(&self.directory_path).join("CLAUDE.local.md");
+        let existing = if target_file.exists() {
+            Some(read_file(&target_file)?)
+        } else {
+            None
+        };
+        Ok(classify_claude_md(
+            existing.as_deref(),
+            self.claude_local_md_content(),
+            PREVIOUS_CLAUDE_LOCAL_MD_SHA256,
+        ))
+    }
+
+    /// CLAUDE.local.md 过时时刷新为当前模板，返回是否写入；其他状态（尤其是用户修改过的文件）不动
+    pub fn refresh_outdated_claude_local_md(&self) -> ConfigResult<bool> {
+        if self.claude_local_md_status()? != ClaudeMdStatus::Outdated {
+            return Ok(false);
+        }
+        let target_file = Path::new(&self.directory_path).join("CLAUDE.local.md");
+        write_file(&target_file, self.claude_local_md_content())?;
+        info!("已将过时的 CLAUDE.local.md 刷新为当前模板: {}", target_file.display());
+        Ok(true)
+    }
+
+    fn copy_claude_local_md(&self) -> Result<()> {
+        // CLAUDE.local.md 只属于项目目录
+        if self.global {
+            return Ok(());
+        }
+
+        // 目标文件路径
+        let target_file = Path::new(&self.directory_path).join("CLAUDE.local.md");
+
+        match self.claude_local_md_action(false)? {
+            ClaudeMdAction::Create | ClaudeMdAction::Overwrite => {
+                write_file(&target_file, self.claude_local_md_content())?;
+                info!("成功写入 CLAUDE.local.md 到 {}", target_file.display());
+            }
+            ClaudeMdAction::SkipIdentical => {
+                info!("CLAUDE.local.md 内容未变化，跳过写入: {}", target_file.display());
+            }
+            ClaudeMdAction::SkipModified => {
+                info!("CLAUDE.local.md 已被用户修改，跳过写入: {}", target_file.display());
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn temp_project_dir() -> std::path::PathBuf {
+        let dir = std::env::temp_dir().join(format!("claude-config-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_merge_json_nested_objects() {
+        let mut base = json!({
+            "permissions": { "defaultMode": "prompt", "allow": ["Read"] },
+            "env": { "A": "1", "B": "2" },
+            "statusLine": { "type": "command" }
+        });
+        let overlay = json!({
+            "permissions": { "defaultMode": "bypassPermissions" },
+            "env": { "B": "3", "C": "4" }
+        });
+
+        merge_json(&mut base, &overlay);
+
+        assert_eq!(
+            base,
+            json!({
+                "permissions": { "defaultMode": "bypassPermissions", "allow": ["Read"] },
+                "env": { "A": "1", "B": "3", "C": "4" },
+                "statusLine": { "type": "command" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_merge_json_deeply_nested() {
+        let mut base = json!({ "a": { "b": { "c": { "d": 1, "e": 2 } } } });
+        let overlay = json!({ "a": { "b": { "c": { "e": 3 }, "f": true } } });
+
+        merge_json(&mut base, &overlay);
+
+        assert_eq!(base, json!({ "a": { "b": { "c": { "d": 1, "e": 3 }, "f": true } } }));
+    }
+
+    #[test]
+    fn test_merge_json_scalar_and_type_conflicts() {
+        let mut base = json!({ "x": 1, "y": { "z": 1 }, "w": "keep" });
+        let overlay = json!({ "x": "one", "y": "flat", "n": null });
+
+        merge_json(&mut base, &overlay);
+
+        assert_eq!(base, json!({ "x": "one", "y": "flat", "w": "keep", "n": null }));
+    }
+
+    #[test]
+    fn test_merge_json_arrays_replace_by_default() {
+        let mut base = json!({ "permissions": { "allow": ["Read", "Write"] } });
+        let overlay = json!({ "permissions": { "allow": ["Bash"] } });
+
+        merge_json(&mut base, &overlay);
+
+        assert_eq!(base, json!({ "permissions": { "allow": ["Bash"] } }));
+    }
+
+    #[test]
+    fn test_merge_json_arrays_append() {
+        let mut base = json!({ "permissions": { "allow": ["Read"], "deny": [] } });
+        let overlay = json!({ "permissions": { "allow": ["Bash"], "deny": ["WebFetch"] } });
+
+        merge_json_with(&mut base, &overlay, ArrayMerge::Append);
+
+        assert_eq!(
+            base,
+            json!({ "permissions": { "allow": ["Read", "Bash"], "deny": ["WebFetch"] } })
+        );
+    }
+
+    #[test]
+    fn test_merge_json_non_object_base_is_replaced() {
+        let mut base = json!("not an object");
+        let overlay = json!({ "env": { "A": "1" } });
+
+        merge_json(&mut base, &overlay);
+
+        assert_eq!(base, overlay);
+    }
+
+    #[test]
+    fn test_update_env_config_preserves_other_keys() {
+        let dir = temp_project_dir();
+        fs::create_dir_all(dir.join(".claude")).unwrap();
+        fs::write(
+            dir.join(".claude/settings.local.json"),
+            r#"{"env":{"DISABLE_TELEMETRY":"1","ANTHROPIC_AUTH_TOKEN":"old"},"permissions":{"allow":["*"]},"mcpServers":{"fs":{"command":"npx"}}}"#,
+        )
+        .unwrap();
+
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        manager
+            .update_env_config_with_options(
+                "sk-test".to_string(),
+                "https://api.example.com".to_string(),
+                AuthStyle::ApiKey,
+                false,
+            )
+            .unwrap();
+
+        let settings = manager.read_settings().unwrap();
+        assert_eq!(settings["env"]["DISABLE_TELEMETRY"], json!("1"));
+        assert_eq!(settings["env"]["ANTHROPIC_API_KEY"], json!("sk-test"));
+        assert_eq!(settings["env"]["ANTHROPIC_BASE_URL"], json!("https://api.example.com"));
+        assert!(settings["env"].get("ANTHROPIC_AUTH_TOKEN").is_none());
+        assert!(settings["env"].get("IS_SANDBOX").is_none());
+        assert_eq!(settings["permissions"], json!({ "allow": ["*"] }));
+        assert_eq!(settings["mcpServers"], json!({ "fs": { "command": "npx" } }));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_extended_env_config_preserves_other_keys() {
+        let dir = temp_project_dir();
+        fs::create_dir_all(dir.join(".claude")).unwrap();
+        fs::write(
+            dir.join(".claude/settings.local.json"),
+            r#"{"env":{"DISABLE_TELEMETRY":"1","ANTHROPIC_API_KEY":"old","IS_SANDBOX":"1"}}"#,
+        )
+        .unwrap();
+
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        let custom = HashMap::from([("API_TIMEOUT_MS".to_string(), "600000".to_string())]);
+        manager
+            .update_env_config_with_extended_options(
+                "sk-test".to_string(),
+                "https://api.example.com".to_string(),
+                "ANTHROPIC_AUTH_TOKEN".to_string(),
+                false,
+                None,
+                Some(custom),
+            )
+            .unwrap();
+
+        let settings = manager.read_settings().unwrap();
+        assert_eq!(settings["env"]["DISABLE_TELEMETRY"], json!("1"));
+        assert_eq!(settings["env"]["ANTHROPIC_AUTH_TOKEN"], json!("sk-test"));
+        assert_eq!(settings["env"]["ANTHROPIC_BASE_URL"], json!("https://api.example.com"));
+        assert!(settings["env"].get("API_TIMEOUT_MS").is_some());
+        assert!(settings["env"].get("ANTHROPIC_API_KEY").is_none());
+        assert!(settings["env"].get("IS_SANDBOX").is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_mcp_servers_preserve_unknown_fields() {
+        let dir = temp_project_dir();
+        fs::create_dir_all(dir.join(".claude")).unwrap();
+        fs::write(
+            dir.join(".claude/settings.local.json"),
+            r#"{"env":{"IS_SANDBOX":"1"},"mcpServers":{"fs":{"command":"npx","args":["-y","fs"],"timeout":30}}}"#,
+        )
+        .unwrap();
+
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        let mut server = manager.get_mcp_servers().unwrap().remove("fs").unwrap();
+        assert_eq!(server.args, vec!["-y", "fs"]);
+        assert_eq!(server.extra.get("timeout"), Some(&json!(30)));
+
+        server.args.clear();
+        server.env.insert("ROOT".to_string(), "/tmp".to_string());
+        manager.set_mcp_server("fs", &server).unwrap();
+        manager
+            .set_mcp_server("git", &McpServer { command: "uvx".to_string(), ..Default::default() })
+            .unwrap();
+
+        let settings = manager.read_settings().unwrap();
+        assert_eq!(
+            settings["mcpServers"]["fs"],
+            json!({ "command": "npx", "env": { "ROOT": "/tmp" }, "timeout": 30 })
+        );
+        assert_eq!(settings["env"], json!({ "IS_SANDBOX": "1" }));
+
+        assert!(manager.remove_mcp_server("fs").unwrap());
+        assert!(manager.remove_mcp_server("git").unwrap());
+        assert!(!manager.remove_mcp_server("git").unwrap());
+        assert!(manager.read_settings().unwrap().get("mcpServers").is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_custom_claude_dir_name() {
+        let dir = temp_project_dir();
+
+        let manager = ClaudeConfigManager::new_with_dir_name(dir.display().to_string(), ".claude-alt");
+        manager
+            .update_env_config_with_options(
+                "sk-test".to_string(),
+                "https://api.example.com".to_string(),
+                AuthStyle::BearerToken,
+                false,
+            )
+            .unwrap();
+
+        assert!(dir.join(".claude-alt/settings.local.json").exists());
+        assert!(!dir.join(".claude").exists());
+
+        let settings = manager.read_settings().unwrap();
+        assert_eq!(settings["env"]["ANTHROPIC_AUTH_TOKEN"], json!("sk-test"));
+        assert_eq!(manager.settings_file_path(), dir.join(".claude-alt/settings.local.json"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_read_effective_settings_layers_local_over_shared() {
+        let dir = temp_project_dir();
+        let claude_dir = dir.join(".claude");
+        fs::create_dir_all(&claude_dir).unwrap();
+        fs::write(
+            claude_dir.join("settings.json"),
+            r#"{"env":{"ANTHROPIC_BASE_URL":"https://shared.example.com","DISABLE_TELEMETRY":"1"},"permissions":{"allow":["Read"]}}"#,
+        )
+        .unwrap();
+        fs::write(
+            claude_dir.join("settings.local.json"),
+            r#"{"env":{"ANTHROPIC_BASE_URL":"https://local.example.com"},"permissions":"default"}"#,
+        )
+        .unwrap();
+
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        let effective = manager.read_effective_settings().unwrap();
+        let (shared, local) = (claude_dir.join("settings.json"), manager.settings_file_path());
+
+        assert_eq!(effective.settings["env"]["ANTHROPIC_BASE_URL"], "https://local.example.com");
+        assert_eq!(effective.settings["env"]["DISABLE_TELEMETRY"], "1");
+        assert_eq!(effective.source_of("/env/ANTHROPIC_BASE_URL"), Some(local.as_path()));
+        assert_eq!(effective.source_of("/env/DISABLE_TELEMETRY"), Some(shared.as_path()));
+        // 本地配置把对象替换成字符串时，旧的子项来源一并移除
+        assert_eq!(effective.source_of("/permissions"), Some(local.as_path()));
+        assert_eq!(effective.source_of("/permissions/allow"), None);
+
+        let env = manager.get_effective_env_config().unwrap();
+        assert_eq!(env.get("DISABLE_TELEMETRY").map(String::as_str), Some("1"));
+        // 单文件读取仍只看本地配置
+        assert!(!manager.get_env_config().unwrap().contains_key("DISABLE_TELEMETRY"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_effective_env_project_overrides_global() {
+        let dir = temp_project_dir();
+        let global = dir.join("global");
+        fs::create_dir_all(&global).unwrap();
+        fs::write(
+            global.join("settings.json"),
+            r#"{"env":{"ANTHROPIC_BASE_URL":"https://global.example.com","DISABLE_TELEMETRY":"1"}}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(dir.join(".claude")).unwrap();
+        fs::write(
+            dir.join(".claude/settings.local.json"),
+            r#"{"env":{"ANTHROPIC_BASE_URL":"https://project.example.com","MAX_TOKENS":8000}}"#,
+        )
+        .unwrap();
+
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        let env = manager.effective_env_with_global(Some(&global)).unwrap();
+
+        let keys: Vec<&str> = env.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["ANTHROPIC_BASE_URL", "DISABLE_TELEMETRY", "MAX_TOKENS"]);
+        assert_eq!(env[0].value, "https://project.example.com");
+        assert_eq!(env[0].source, EnvSource::File(manager.settings_file_path()));
+        assert_eq!(env[1].source, EnvSource::File(global.join("settings.json")));
+        assert_eq!(env[2].value, "8000");
+
+        // 进程环境变量覆盖配置文件中的值，并保留被覆盖的取值
+        let env = apply_env_overrides(
+            env,
+            &[
+                ("ANTHROPIC_BASE_URL", "https://ci.example.com".to_string()),
+                ("ANTHROPIC_API_KEY", "sk-ant-ci".to_string()),
+            ],
+        );
+        let keys: Vec<&str> = env.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec!["ANTHROPIC_API_KEY", "ANTHROPIC_BASE_URL", "DISABLE_TELEMETRY", "MAX_TOKENS"]
+        );
+        assert!(env[0].source.is_process());
+        assert_eq!(env[0].masked_value, None);
+        assert_eq!(env[1].value, "https://ci.example.com");
+        assert_eq!(env[1].masked_value.as_deref(), Some("https://project.example.com"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_reset_directory_removes_managed_files_only() {
+        let dir = temp_project_dir();
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        manager
+            .update_env_config_with_options(
+                "sk-test".to_string(),
+                "https://api.example.com".to_string(),
+                AuthStyle::Both,
+                true,
+            )
+            .unwrap();
+
+        let all = ResetOptions {
+            remove_env_keys: true,
+            remove_sandbox: true,
+            remove_claude_local_md: true,
+            remove_empty_claude_dir: true,
+        };
+        let report = manager.reset_directory(all).unwrap();
+
+        assert_eq!(
+            report.removed_keys,
+            vec!["ANTHROPIC_API_KEY", "ANTHROPIC_AUTH_TOKEN", "ANTHROPIC_BASE_URL", "IS_SANDBOX"]
+        );
+        assert!(report.settings_file_removed);
+        assert!(report.claude_local_md_removed);
+        assert!(report.claude_dir_removed);
+        assert!(!dir.join(".claude").exists());
+
+        // 用户修改过的 CLAUDE.local.md 不会被删除
+        fs::write(dir.join("CLAUDE.local.md"), "my notes").unwrap();
+        let report = manager.reset_directory(all).unwrap();
+        assert!(report.claude_local_md_kept);
+        assert!(dir.join("CLAUDE.local.md").exists());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_to_read_only_dir_reports_not_writable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_project_dir();
+        let claude_dir = dir.join(".claude");
+        fs::create_dir_all(&claude_dir).unwrap();
+        fs::set_permissions(&claude_dir, fs::Permissions::from_mode(0o555)).unwrap();
+
+        // root 不受权限位限制，无法模拟不可写目录
+        if fs::write(claude_dir.join("probe"), "").is_ok() {
+            fs::set_permissions(&claude_dir, fs::Permissions::from_mode(0o755)).unwrap();
+            fs::remove_dir_all(&dir).unwrap();
+            return;
+        }
+
+        let manager = ClaudeConfigManager::new(dir.to_string_lossy().to_string());
+        let result = manager.update_env_config_with_options(
+            "sk-test".to_string(),
+            "https://api.anthropic.com".to_string(),
+            AuthStyle::default(),
+            true,
+        );
+        fs::set_permissions(&claude_dir, fs::Permissions::from_mode(0o755)).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        match result {
+            Err(ConfigError::NotWritable { path, .. }) => assert!(path.starts_with(&claude_dir)),
+            other => panic!("expected NotWritable, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_change_hook_records_hashes_without_tokens() {
+        let dir = temp_project_dir();
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let recorded = changes.clone();
+        let manager = ClaudeConfigManager::new(dir.display().to_string())
+            .with_change_hook(Arc::new(move |change: &ConfigChange| {
+                recorded.lock().unwrap().push(change.clone());
+            }));
+
+        let apply = || {
+            manager
+                .update_env_config_with_options(
+                    "sk-test".to_string(),
+                    "https://api.example.com".to_string(),
+                    AuthStyle::Both,
+                    false,
+                )
+                .unwrap()
+        };
+        apply();
+        // 内容未变化时不记录
+        apply();
+        manager.clear_env_config().unwrap();
+        // 单个变量的写入和删除同样记录
+        manager.set_env_var("DISABLE_TELEMETRY", "1").unwrap();
+        manager.remove_env_var("DISABLE_TELEMETRY").unwrap();
+
+        let changes = changes.lock().unwrap();
+        let actions: Vec<ConfigAction> = changes.iter().map(|c| c.action).collect();
+        assert_eq!(
+            actions,
+            vec![ConfigAction::Apply, ConfigAction::Clear, ConfigAction::Apply, ConfigAction::Clear]
+        );
+        assert_eq!(changes[0].before_hash, None);
+        assert_eq!(changes[0].after_hash, changes[1].before_hash);
+        assert!(changes.iter().all(|c| !format!("{:?}", c).contains("sk-test")));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_migrate_legacy_config() {
+        let dir = temp_project_dir();
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+
+        // 没有旧配置文件时无需迁移
+        assert_eq!(manager.migrate_legacy_config().unwrap(), MigrationReport::default());
+
+        let legacy = dir.join(".claude_config");
+        fs::write(&legacy, r#"{"env": {"ANTHROPIC_BASE_URL": "https://legacy.example.com"}}"#).unwrap();
+
+        let report = manager.migrate_legacy_config().unwrap();
+        assert_eq!(report.migrated_file, Some(legacy.clone()));
+        assert!(!legacy.exists());
+        assert!(dir.join(".claude_config.migrated").exists());
+        assert_eq!(manager.resolve_active_settings_path(), manager.settings_file_path());
+        assert_eq!(
+            manager.get_env_config().unwrap().get("ANTHROPIC_BASE_URL").map(String::as_str),
+            Some("https://legacy.example.com")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_yaml_settings_write_format() {
+        let dir = temp_project_dir();
+        let claude_dir = dir.join(".claude");
+        fs::create_dir_all(&claude_dir).unwrap();
+        let yaml = claude_dir.join("settings.local.yaml");
+        fs::write(&yaml, "env:\n  ANTHROPIC_BASE_URL: https://yaml.example.com\n").unwrap();
+
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        assert_eq!(manager.resolve_active_settings_path(), yaml);
+        assert_eq!(
+            manager.get_env_config().unwrap().get("ANTHROPIC_BASE_URL").map(String::as_str),
+            Some("https://yaml.example.com")
+        );
+        // 默认写入 settings.local.json
+        assert_eq!(manager.write_target_path(), manager.settings_file_path());
+
+        // Preserve 时按 YAML 写回原文件
+        let manager = manager.with_write_format(WriteFormat::Preserve);
+        assert_eq!(manager.write_target_path(), yaml);
+        manager.set_env_var("IS_SANDBOX", "1").unwrap();
+        assert!(!manager.settings_file_path().exists());
+        let reloaded = parse_settings(&yaml, &fs::read_to_string(&yaml).unwrap()).unwrap();
+        assert_eq!(reloaded["env"]["IS_SANDBOX"], json!("1"));
+        assert_eq!(reloaded["env"]["ANTHROPIC_BASE_URL"], json!("https://yaml.example.com"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_copy_env_from() {
+        let source_dir = temp_project_dir();
+        let target_dir = temp_project_dir();
+        fs::create_dir_all(source_dir.join(".claude")).unwrap();
+        fs::write(
+            source_dir.join(".claude/settings.local.json"),
+            r#"{"env":{"ANTHROPIC_AUTH_TOKEN":"sk-ant-source","ANTHROPIC_BASE_URL":"https://source.example.com"}}"#,
+        )
+        .unwrap();
+        fs::write(source_dir.join("CLAUDE.local.md"), "source notes").unwrap();
+        fs::create_dir_all(target_dir.join(".claude")).unwrap();
+        fs::write(
+            target_dir.join(".claude/settings.local.json"),
+            r#"{"env":{"OLD_KEY":"1"},"permissions":{"allow":["Read"]}}"#,
+        )
+        .unwrap();
+
+        let source = ClaudeConfigManager::new(source_dir.display().to_string());
+        let target = ClaudeConfigManager::new(target_dir.display().to_string());
+        let report = target.copy_env_from(&source, true).unwrap();
+
+        assert_eq!(report.env_keys, vec!["ANTHROPIC_AUTH_TOKEN", "ANTHROPIC_BASE_URL"]);
+        assert!(report.claude_local_md_copied);
+        let env = target.get_env_config().unwrap();
+        assert_eq!(env.get("ANTHROPIC_AUTH_TOKEN").map(String::as_str), Some("sk-ant-source"));
+        assert!(!env.contains_key("OLD_KEY"));
+        // env 以外的配置保持不变
+        assert_eq!(target.get_setting("/permissions/allow/0").unwrap(), Some(json!("Read")));
+        assert_eq!(fs::read_to_string(target_dir.join("CLAUDE.local.md")).unwrap(), "source notes");
+
+        fs::remove_dir_all(&source_dir).unwrap();
+        fs::remove_dir_all(&target_dir).unwrap();
+    }
+
+    #[test]
+    fn test_set_json_pointer() {
+        let mut settings = json!({ "permissions": { "allow": ["Read"] } });
+
+        set_json_pointer(&mut settings, "/permissions/allow/0", json!("Bash"), false).unwrap();
+        set_json_pointer(&mut settings, "/permissions/allow/-", json!("Edit"), false).unwrap();
+        assert_eq!(settings.pointer("/permissions/allow"), Some(&json!(["Bash", "Edit"])));
+
+        // 缺失的中间对象默认不创建
+        assert!(set_json_pointer(&mut settings, "/hooks/PreToolUse", json!([]), false).is_err());
+        assert!(settings.get("hooks").is_none());
+        set_json_pointer(&mut settings, "/hooks/PreToolUse", json!([]), true).unwrap();
+        assert_eq!(settings.pointer("/hooks/PreToolUse"), Some(&json!([])));
+
+        set_json_pointer(&mut settings, "/env/a~1b~0c", json!("1"), true).unwrap();
+        assert_eq!(settings["env"]["a/b~c"], json!("1"));
+
+        assert!(set_json_pointer(&mut settings, "/permissions/allow/5", json!("x"), false).is_err());
+        assert!(set_json_pointer(&mut settings, "permissions", json!({}), false).is_err());
+        assert!(set_json_pointer(&mut settings, "", json!({}), false).is_err());
+    }
+
+    #[test]
+    fn test_get_and_set_setting() {
+        let dir = temp_project_dir();
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+
+        assert_eq!(manager.get_setting("/permissions/defaultMode").unwrap(), None);
+        assert!(matches!(
+            manager.set_setting("/permissions/defaultMode", json!("prompt")),
+            Err(ConfigError::InvalidPointer { .. })
+        ));
+
+        manager
+            .set_setting_with_options("/permissions/defaultMode", json!("prompt"), true)
+            .unwrap();
+        manager.set_setting("/permissions/allow", json!(["Read"])).unwrap();
+        assert_eq!(
+            manager.get_setting("/permissions").unwrap(),
+            Some(json!({ "defaultMode": "prompt", "allow": ["Read"] }))
+        );
+        assert!(manager.get_setting("permissions").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_custom_claude_local_md() {
+        let dir = temp_project_dir();
+        let apply = |manager: &ClaudeConfigManager| {
+            manager
+                .update_env_config_with_options(
+                    "sk-test".to_string(),
+                    "https://api.example.com".to_string(),
+                    AuthStyle::Both,
+                    false,
+                )
+                .unwrap();
+            fs::read_to_string(dir.join("CLAUDE.local.md")).unwrap()
+        };
+
+        let custom = ClaudeConfigManager::new(dir.display().to_string())
+            .with_claude_local_md(Some("# 账号专用说明\n".to_string()));
+        assert_eq!(apply(&custom), "# 账号专用说明\n");
+        assert_eq!(custom.claude_local_md_action(true).unwrap(), ClaudeMdAction::SkipIdentical);
+
+        // 未设置时回退到内置模板
+        let default = ClaudeConfigManager::new(dir.display().to_string()).with_claude_local_md(None);
+        assert_eq!(apply(&default), CLAUDE_LOCAL_MD_CONTENT);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_merge_stale_settings_keeps_local_values() {
+        let dir = temp_project_dir();
+        let claude_dir = dir.join(".claude");
+        fs::create_dir_all(&claude_dir).unwrap();
+        fs::write(
+            claude_dir.join("settings.json"),
+            r#"{"env":{"ANTHROPIC_BASE_URL":"https://old.example.com","DISABLE_TELEMETRY":"1"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            claude_dir.join("settings.local.json"),
+            r#"{"env":{"ANTHROPIC_BASE_URL":"https://new.example.com"}}"#,
+        )
+        .unwrap();
+
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        assert_eq!(manager.shadowed_settings_files(), vec![claude_dir.join("settings.json")]);
+
+        let merged = manager.merge_stale_settings().unwrap();
+        assert_eq!(merged, vec![claude_dir.join("settings.json")]);
+        assert!(!claude_dir.join("settings.json").exists());
+        assert!(claude_dir.join("settings.json.bak").exists());
+
+        let settings = manager.read_settings().unwrap();
+        assert_eq!(settings["env"]["ANTHROPIC_BASE_URL"], json!("https://new.example.com"));
+        assert_eq!(settings["env"]["DISABLE_TELEMETRY"], json!("1"));
+        assert!(manager.shadowed_settings_files().is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_verify_env_written_detects_truncated_file() {
+        let dir = temp_project_dir();
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        manager
+            .update_env_config_with_options(
+                "sk-test".to_string(),
+                "https://api.example.com".to_string(),
+                AuthStyle::BearerToken,
+                false,
+            )
+            .unwrap();
+
+        let expected = json!({ "ANTHROPIC_BASE_URL": "https://api.example.com", "ANTHROPIC_AUTH_TOKEN": "sk-test" });
+        manager.verify_env_written(&expected).unwrap();
+
+        // 模拟文件系统静默截断：Token 只留下一部分
+        fs::write(
+            dir.join(".claude/settings.local.json"),
+            r#"{"env":{"ANTHROPIC_BASE_URL":"https://api.example.com","ANTHROPIC_AUTH_TOKEN":"sk-"}}"#,
+        )
+        .unwrap();
+        match manager.verify_env_written(&expected) {
+            Err(ConfigError::VerifyFailed { key, .. }) => assert_eq!(key, "ANTHROPIC_AUTH_TOKEN"),
+            other => panic!("unexpected: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_omit_empty_env_values() {
+        let dir = temp_project_dir();
+        let apply = |manager: &ClaudeConfigManager, base_url: &str| {
+            manager
+                .update_env_config_with_options("sk-test".to_string(), base_url.to_string(), AuthStyle::BearerToken, false)
+                .unwrap();
+            manager.get_env_config().unwrap()
+        };
+
+        // 默认保持原行为：空值以 "" 写入
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        assert_eq!(apply(&manager, "").get("ANTHROPIC_BASE_URL").map(String::as_str), Some(""));
+
+        // 开启后空值不写入，之前写入的同名变量被移除
+        let manager = manager.with_omit_empty_env(true);
+        apply(&manager, "https://api.example.com");
+        let env = apply(&manager, "");
+        assert!(!env.contains_key("ANTHROPIC_BASE_URL"));
+        assert_eq!(env.get("ANTHROPIC_AUTH_TOKEN").map(String::as_str), Some("sk-test"));
+
+        manager.set_env_var("ANTHROPIC_MODEL", "opus").unwrap();
+        manager.set_env_var("ANTHROPIC_MODEL", "").unwrap();
+        assert!(!manager.get_env_config().unwrap().contains_key("ANTHROPIC_MODEL"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_update_env_config_full_writes_and_clears_proxy() {
+        let dir = temp_project_dir();
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        let proxy = ProxyConfig {
+            http_proxy: "http://proxy.corp:8080".to_string(),
+            https_proxy: "http://proxy.corp:8080".to_string(),
+            no_proxy: "   ".to_string(),
+        };
+
+        manager
+            .update_env_config_full(
+                "sk-test".to_string(),
+                "https://api.example.com".to_string(),
+                AuthStyle::ApiKey,
+                false,
+                &proxy,
+            )
+            .unwrap();
+        let settings = manager.read_settings().unwrap();
+        assert_eq!(settings["env"]["HTTP_PROXY"], json!("http://proxy.corp:8080"));
+        assert_eq!(settings["env"]["HTTPS_PROXY"], json!("http://proxy.corp:8080"));
+        assert!(settings["env"].get("NO_PROXY").is_none());
+
+        manager
+            .update_env_config_full(
+                "sk-test".to_string(),
+                "https://api.example.com".to_string(),
+                AuthStyle::ApiKey,
+                false,
+                &ProxyConfig::default(),
+            )
+            .unwrap();
+        let settings = manager.read_settings().unwrap();
+        assert!(settings["env"].get("HTTP_PROXY").is_none());
+        assert!(settings["env"].get("HTTPS_PROXY").is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_extra_auth_env_key_written_and_cleared() {
+        assert!(is_valid_env_key("OPENAI_API_KEY"));
+        assert!(is_valid_env_key("_KEY1"));
+        assert!(!is_valid_env_key("1KEY"));
+        assert!(!is_valid_env_key("MY-KEY"));
+        assert!(!is_valid_env_key(""));
+
+        let dir = temp_project_dir();
+        let manager = ClaudeConfigManager::new(dir.display().to_string())
+            .with_extra_auth_env_key(Some("OPENAI_API_KEY".to_string()));
+        manager
+            .update_env_config_with_options(
+                "sk-test".to_string(),
+                "https://gateway.example.com".to_string(),
+                AuthStyle::ApiKey,
+                false,
+            )
+            .unwrap();
+        let settings = manager.read_settings().unwrap();
+        assert_eq!(settings["env"]["OPENAI_API_KEY"], json!("sk-test"));
+        assert_eq!(settings["env"]["ANTHROPIC_API_KEY"], json!("sk-test"));
+
+        manager.clear_env_config().unwrap();
+        let settings = manager.read_settings().unwrap();
+        assert!(settings.get("env").is_none());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_update_claude_md_env_preserves_other_content() {
+        let content = "# 项目说明\r\n\nSome prose with ANTHROPIC_BASE_URL mentioned inline.\n\n## 配置\n  ANTHROPIC_BASE_URL=https://old.example.com\nANTHROPIC_API_KEY=sk-old\nOTHER_KEY=keep\n\n- list item\n";
+        let mut env = HashMap::new();
+        env.insert("ANTHROPIC_BASE_URL".to_string(), "https://new.example.com".to_string());
+        env.insert("ANTHROPIC_API_KEY".to_string(), "sk-new".to_string());
+        env.insert("ANTHROPIC_AUTH_TOKEN".to_string(), "token".to_string());
+        env.insert("UNRELATED".to_string(), "ignored".to_string());
+
+        let updated = update_claude_md_env(content, &env);
+
+        assert_eq!(
+            updated,
+            "# 项目说明\r\n\nSome prose with ANTHROPIC_BASE_URL mentioned inline.\n\n## 配置\n  ANTHROPIC_BASE_URL=https://new.example.com\nANTHROPIC_API_KEY=sk-new\nOTHER_KEY=keep\n\n- list item\nANTHROPIC_AUTH_TOKEN=token\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_token() {
+        assert_eq!(validate_token("  sk-ant-abc123\n").unwrap(), "sk-ant-abc123");
+        assert_eq!(validate_token("custom-token").unwrap(), "custom-token");
+        assert!(!has_expected_token_prefix("custom-token"));
+        assert_eq!(validate_token(" \r\n"), Err(TokenError::Empty));
+        assert_eq!(validate_token("sk-ant abc"), Err(TokenError::ContainsWhitespace));
+    }
+
+    #[test]
+    fn test_detect_swapped_credentials() {
+        assert_eq!(detect_swapped_credentials("https://api.anthropic.com", "sk-ant-api03-abc"), None);
+        assert_eq!(detect_swapped_credentials("http://localhost:8080", "token"), None);
+
+        // Token 填进了 Base URL
+        assert_eq!(
+            detect_swapped_credentials("sk-ant-api03-abc", "https://api.anthropic.com"),
+            Some(SwapSuspicion::BaseUrlLooksLikeToken)
+        );
+        assert_eq!(
+            detect_swapped_credentials("abcdef123456", "sk-ant-api03-abc"),
+            Some(SwapSuspicion::BaseUrlLooksLikeToken)
+        );
+
+        // URL 填进了 Token
+        assert_eq!(
+            detect_swapped_credentials("https://api.anthropic.com", "https://proxy.example.com"),
+            Some(SwapSuspicion::TokenLooksLikeUrl)
+        );
+        assert_eq!(
+            detect_swapped_credentials("https://api.anthropic.com", "HTTP://proxy.example.com"),
+            Some(SwapSuspicion::TokenLooksLikeUrl)
+        );
+    }
+
+    #[test]
+    fn test_global_manager_uses_settings_json() {
+        let home = temp_project_dir();
+        let manager = ClaudeConfigManager::global_at(&home);
+        assert!(manager.is_global());
+        assert_eq!(manager.settings_file_path(), home.join(".claude").join("settings.json"));
+
+        manager.set_env_var("DISABLE_TELEMETRY", "1").unwrap();
+        manager
+            .update_env_config_with_options(
+                "sk-ant-test".to_string(),
+                "https://api.example.com".to_string(),
+                AuthStyle::Both,
+                false,
+            )
+            .unwrap();
+
+        let env = manager.get_env_config().unwrap();
+        assert_eq!(env.get("DISABLE_TELEMETRY").map(String::as_str), Some("1"));
+        assert_eq!(env.get("ANTHROPIC_BASE_URL").map(String::as_str), Some("https://api.example.com"));
+        // 全局配置不会写入项目专用的文件
+        assert!(!home.join("CLAUDE.local.md").exists());
+        assert!(!home.join(".claude").join("settings.local.json").exists());
+        assert!(!home.join(".claude").join("settings.json.tmp").exists());
+
+        assert!(manager.remove_env_var("DISABLE_TELEMETRY").unwrap());
+        assert!(!manager.remove_env_var("DISABLE_TELEMETRY").unwrap());
+
+        fs::remove_dir_all(&home).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_claude_dir_outside_directory() {
+        let dir = temp_project_dir();
+        let manager = ClaudeConfigManager::new(dir.to_string_lossy().to_string());
+        assert_eq!(manager.claude_dir_outside_directory(), None);
+
+        fs::create_dir(dir.join(".claude")).unwrap();
+        assert_eq!(manager.claude_dir_outside_directory(), None);
+
+        let shared = temp_project_dir();
+        fs::remove_dir(dir.join(".claude")).unwrap();
+        std::os::unix::fs::symlink(&shared, dir.join(".claude")).unwrap();
+        assert_eq!(manager.claude_dir_outside_directory(), Some(shared.canonicalize().unwrap()));
+
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&shared).unwrap();
+    }
+
+    #[test]
+    fn test_classify_claude_md() {
+        let old = "# old template\n";
+        let previous = [sha256_hex(old.as_bytes())];
+        let previous: Vec<&str> = previous.iter().map(String::as_str).collect();
+
+        assert_eq!(classify_claude_md(None, "new", &previous), ClaudeMdStatus::Missing);
+        assert_eq!(classify_claude_md(Some("new"), "new", &previous), ClaudeMdStatus::Identical);
+        assert_eq!(classify_claude_md(Some(old), "new", &previous), ClaudeMdStatus::Outdated);
+        assert_eq!(classify_claude_md(Some("# my notes\n"), "new", &previous), ClaudeMdStatus::Modified);
+        // 只有换行不同不算修改
+        assert_eq!(classify_claude_md(Some("# old template\r\n"), "new", &previous), ClaudeMdStatus::Outdated);
+        assert_eq!(classify_claude_md(Some("a\r\nb\n"), "a\nb\n", &previous), ClaudeMdStatus::Identical);
+    }
+
+    #[test]
+    fn test_previous_builtin_claude_md_is_outdated() {
+        let dir = temp_project_dir();
+        let target = dir.join("CLAUDE.local.md");
+        let manager =
+            ClaudeConfigManager::new(dir.display().to_string()).with_claude_local_md(Some("# team template\n".to_string()));
+
+        // 未被修改过的内置模板（无论哪种换行）视为过时，可以刷新为账号模板
+        let shipped = CLAUDE_LOCAL_MD_CONTENT.replace("\r\n", "\n").replace('\n', "\r\n");
+        for old in [shipped.clone(), shipped.replace("\r\n", "\n")] {
+            fs::write(&target, &old).unwrap();
+            assert_eq!(manager.claude_local_md_status().unwrap(), ClaudeMdStatus::Outdated);
+        }
+        assert!(manager.refresh_outdated_claude_local_md().unwrap());
+        assert_eq!(fs::read_to_string(&target).unwrap(), "# team template\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_repair_string_env() {
+        let mut settings = json!({
+            "env": "ANTHROPIC_API_KEY=sk-test\nexport ANTHROPIC_BASE_URL=\"https://api.example.com\"\n# comment\n",
+            "permissions": { "allow": ["*"] }
+        });
+
+        assert!(repair_string_env(&mut settings));
+        assert_eq!(
+            settings["env"],
+            json!({
+                "ANTHROPIC_API_KEY": "sk-test",
+                "ANTHROPIC_BASE_URL": "https://api.example.com"
+            })
+        );
+        assert_eq!(settings["permissions"], json!({ "allow": ["*"] }));
+
+        // 已是对象时不再修复
+        assert!(!repair_string_env(&mut settings));
+
+        // 无法解析时保持原样
+        let mut garbage = json!({ "env": "not a key value" });
+        assert!(!repair_string_env(&mut garbage));
+        assert_eq!(garbage["env"], json!("not a key value"));
+    }
+
+    #[test]
+    fn test_parse_env_block() {
+        let env = parse_env_block("# from dashboard\nANTHROPIC_BASE_URL=https://api.example.com\n\nexport ANTHROPIC_AUTH_TOKEN='sk-test'\n").unwrap();
+        assert_eq!(
+            Value::Object(env),
+            json!({
+                "ANTHROPIC_BASE_URL": "https://api.example.com",
+                "ANTHROPIC_AUTH_TOKEN": "sk-test"
+            })
+        );
+
+        assert!(parse_env_block("").unwrap().is_empty());
+        assert_eq!(parse_env_block("ANTHROPIC_MODEL=opus\nsome prose here"), Err(2));
+        assert_eq!(parse_env_block("1BAD=value"), Err(1));
+    }
+
+    #[test]
+    fn test_validate_settings_value() {
+        let settings = json!({
+            "env": {
+                "ANTHROPIC_BASE_URL": 123,
+                "ANTHROPIC_API_KEY": "sk-test",
+                "DISABLE_AUTOUPDATER": 1
+            },
+            "permissions": { "allow": ["*", 42] },
+            "customKey": { "anything": true }
+        });
+
+        let issues = validate_settings_value(&settings);
+        let paths: Vec<&str> = issues.iter().map(|issue| issue.path.as_str()).collect();
+        assert_eq!(paths, vec!["$.env.ANTHROPIC_BASE_URL", "$.permissions.allow[1]"]);
+
+        assert!(validate_settings_value(&json!({ "env": {} })).is_empty());
+        assert_eq!(validate_settings_value(&json!({ "env": "A=1" }))[0].path, "$.env");
+    }
+
+    #[test]
+    fn test_claude_local_md_action() {
+        let dir = temp_project_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let manager = ClaudeConfigManager::new(dir.display().to_string());
+        let target = dir.join("CLAUDE.local.md");
+
+        assert_eq!(manager.claude_local_md_action(false).unwrap(), ClaudeMdAction::Create);
+        assert!(!target.exists());
+
+        fs::write(&target, CLAUDE_LOCAL_MD_CONTENT).unwrap();
+        assert_eq!(manager.claude_local_md_action(false).unwrap(), ClaudeMdAction::SkipIdentical);
+
+        fs::write(&target, "# my notes").unwrap();
+        assert_eq!(manager.claude_local_md_action(false).unwrap(), ClaudeMdAction::Overwrite);
+        assert_eq!(manager.claude_local_md_action(true).unwrap(), ClaudeMdAction::SkipModified);
+        assert_eq!(fs::read_to_string(&target).unwrap(), "# my notes");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,69 @@
+//! 按行比较两段文本，用于展示配置文件在工具外被修改的内容
+
+/// 差异中的一行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+/// 基于最长公共子序列的行级差异；配置文件通常只有几百行，O(n*m) 足够
+pub fn line_diff<'a>(old: &'a str, new: &'a str) -> Vec<DiffLine<'a>> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j]：old[i..] 与 new[j..] 的最长公共子序列长度
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::with_capacity(old.len().max(new.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Same(old[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(DiffLine::Removed(old[i]));
+            i += 1;
+        } else {
+            lines.push(DiffLine::Added(new[j]));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().map(|line| DiffLine::Removed(line)));
+    lines.extend(new[j..].iter().map(|line| DiffLine::Added(line)));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_diff_marks_changed_lines() {
+        let old = "{\n  \"a\": 1,\n  \"b\": 2\n}";
+        let new = "{\n  \"a\": 1,\n  \"b\": 3,\n  \"c\": 4\n}";
+        assert_eq!(
+            line_diff(old, new),
+            vec![
+                DiffLine::Same("{"),
+                DiffLine::Same("  \"a\": 1,"),
+                DiffLine::Removed("  \"b\": 2"),
+                DiffLine::Added("  \"b\": 3,"),
+                DiffLine::Added("  \"c\": 4"),
+                DiffLine::Same("}"),
+            ]
+        );
+        assert!(line_diff(old, old).iter().all(|line| matches!(line, DiffLine::Same(_))));
+    }
+}
@@ -0,0 +1,151 @@
+//! 项目配置读写的错误类型
+//!
+//! 内部实现仍使用 anyhow，`ClaudeConfigManager` 的公开方法在边界处统一转换为 `ConfigError`，
+//! 方便 GUI 按错误类型给出可操作的提示。
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum ConfigError {
+    /// 项目路径存在但不是目录
+    #[error("不是目录: {}", .0.display())]
+    NotADirectory(PathBuf),
+
+    /// 配置文件不是合法的 JSON
+    #[error("JSON 格式错误 {}: {source}", path.display())]
+    InvalidJson {
+        path: PathBuf,
+        #[source]
+        source: serde_json::Error,
+    },
+
+    /// 没有读写权限
+    #[error("没有权限访问 {}: {source}", path.display())]
+    PermissionDenied {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// 写入时目录或文件不可写（权限不足或只读文件系统）
+    #[error("目录不可写，请检查权限: {}", path.display())]
+    NotWritable {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    /// 找不到指定的配置模板
+    #[error("模板不存在: {0}")]
+    TemplateNotFound(String),
+
+    /// YAML / TOML 配置文件无法解析或序列化
+    #[error("{format} 格式错误 {}: {reason}", path.display())]
+    InvalidFormat {
+        path: PathBuf,
+        format: &'static str,
+        reason: String,
+    },
+
+    /// JSON Pointer 格式错误或无法定位
+    #[error("无法定位 JSON Pointer {pointer}: {reason}")]
+    InvalidPointer { pointer: String, reason: String },
+
+    /// 等待配置文件锁超时（其他进程正在写入）
+    #[error("等待文件锁超时 ({}s): {}", timeout.as_secs(), path.display())]
+    LockTimeout { path: PathBuf, timeout: Duration },
+
+    /// 写入后读回的配置与写入的内容不一致（例如文件系统静默截断了文件）
+    #[error("写入校验失败 {}: 读回的 {key} 与写入的值不一致", path.display())]
+    VerifyFailed { path: PathBuf, key: String },
+
+    /// 其他文件读写错误
+    #[error("读写 {} 失败: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+}
+
+impl ConfigError {
+    /// 按 io::Error 的类型区分权限错误与其他读写错误
+    pub fn io(path: impl AsRef<Path>, source: io::Error) -> Self {
+        let path = path.as_ref().to_path_buf();
+        if source.kind() == io::ErrorKind::PermissionDenied {
+            ConfigError::PermissionDenied { path, source }
+        } else {
+            ConfigError::Io { path, source }
+        }
+    }
+
+    /// 写入、删除、重命名失败时使用：权限不足和只读文件系统都归为不可写
+    pub fn write(path: impl AsRef<Path>, source: io::Error) -> Self {
+        match source.kind() {
+            io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem => ConfigError::NotWritable {
+                path: path.as_ref().to_path_buf(),
+                source,
+            },
+            _ => ConfigError::io(path, source),
+        }
+    }
+
+    pub fn invalid_json(path: impl AsRef<Path>, source: serde_json::Error) -> Self {
+        ConfigError::InvalidJson {
+            path: path.as_ref().to_path_buf(),
+            source,
+        }
+    }
+}
+
+/// 内部 anyhow 错误到公开错误类型的转换：优先还原内部已构造的 ConfigError
+impl From<anyhow::Error> for ConfigError {
+    fn from(err: anyhow::Error) -> Self {
+        let err = match err.downcast::<ConfigError>() {
+            Ok(config_error) => return config_error,
+            Err(err) => err,
+        };
+        let err = match err.downcast::<serde_json::Error>() {
+            Ok(json_error) => return ConfigError::invalid_json(PathBuf::new(), json_error),
+            Err(err) => err,
+        };
+        match err.downcast::<io::Error>() {
+            Ok(io_error) => ConfigError::io(PathBuf::new(), io_error),
+            Err(err) => ConfigError::Io {
+                path: PathBuf::new(),
+                source: io::Error::other(format!("{:#}", err)),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_anyhow_keeps_config_error() {
+        let original = ConfigError::NotADirectory(PathBuf::from("/tmp/file"));
+        let converted = ConfigError::from(anyhow::Error::new(original));
+        assert!(matches!(converted, ConfigError::NotADirectory(path) if path == Path::new("/tmp/file")));
+
+        let denied = io::Error::new(io::ErrorKind::PermissionDenied, "denied");
+        let converted = ConfigError::from(anyhow::Error::new(denied));
+        assert!(matches!(converted, ConfigError::PermissionDenied { .. }));
+    }
+
+    #[test]
+    fn test_write_errors_classify_not_writable() {
+        for kind in [io::ErrorKind::PermissionDenied, io::ErrorKind::ReadOnlyFilesystem] {
+            let error = ConfigError::write("/mnt/ro/.claude", io::Error::new(kind, "denied"));
+            assert!(matches!(error, ConfigError::NotWritable { .. }), "{:?}", kind);
+            assert_eq!(error.to_string(), "目录不可写，请检查权限: /mnt/ro/.claude");
+        }
+        let other = ConfigError::write("/tmp/x", io::Error::new(io::ErrorKind::NotFound, "missing"));
+        assert!(matches!(other, ConfigError::Io { .. }));
+    }
+}
@@ -0,0 +1,13 @@
+//! Claude Code 配置管理器的共享核心库
+//!
+//! GUI（src-tauri）与 CLI（claude-config-cli）共用同一份项目配置读写实现，
+//! 避免两边各自维护的代码逐渐产生差异。
+
+pub mod audit;
+pub mod checksum;
+pub mod claude_config;
+pub mod diff;
+pub mod error;
+pub mod models;
+pub mod redact;
+pub mod settings_format;
@@ -0,0 +1,80 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// 账号 Token 的认证方式，决定写入哪些环境变量
+#[derive(Debug, Serialize, Deserialize, sqlx::Type, Clone, Copy, PartialEq, Eq, Default)]
+#[sqlx(type_name = "TEXT", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AuthStyle {
+    /// 仅 ANTHROPIC_API_KEY（x-api-key 请求头）
+    ApiKey,
+    /// 仅 ANTHROPIC_AUTH_TOKEN（Authorization: Bearer 请求头）
+    BearerToken,
+    /// 同时写入两者
+    #[default]
+    Both,
+}
+
+impl AuthStyle {
+    pub const ALL: [AuthStyle; 3] = [AuthStyle::Both, AuthStyle::ApiKey, AuthStyle::BearerToken];
+
+    /// 该认证方式需要写入的环境变量
+    pub fn env_keys(&self) -> &'static [&'static str] {
+        match self {
+            AuthStyle::ApiKey => &["ANTHROPIC_API_KEY"],
+            AuthStyle::BearerToken => &["ANTHROPIC_AUTH_TOKEN"],
+            AuthStyle::Both => &["ANTHROPIC_API_KEY", "ANTHROPIC_AUTH_TOKEN"],
+        }
+    }
+}
+
+/// 解析环境变量值：布尔值、整数、浮点数按原类型写入 JSON，其余作为字符串
+pub fn parse_env_value(value: &str) -> Value {
+    // 1. 尝试解析为布尔值
+    if value.eq_ignore_ascii_case("true") {
+        return json!(true);
+    }
+    if value.eq_ignore_ascii_case("false") {
+        return json!(false);
+    }
+
+    // 2. 尝试解析为整数
+    if let Ok(num) = value.parse::<i64>() {
+        return json!(num);
+    }
+
+    // 3. 尝试解析为浮点数
+    if let Ok(float) = value.parse::<f64>() {
+        return json!(float);
+    }
+
+    // 4. 默认作为字符串
+    json!(value)
+}
+
+/// 代理相关的环境变量
+pub const PROXY_ENV_KEYS: &[&str] = &["HTTP_PROXY", "HTTPS_PROXY", "NO_PROXY"];
+
+/// 账号的代理配置，空字符串表示未设置
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProxyConfig {
+    pub http_proxy: String,
+    pub https_proxy: String,
+    pub no_proxy: String,
+}
+
+impl ProxyConfig {
+    pub fn is_empty(&self) -> bool {
+        self.env_entries().is_empty()
+    }
+
+    /// 需要写入的代理环境变量（忽略空值）
+    pub fn env_entries(&self) -> Vec<(&'static str, &str)> {
+        PROXY_ENV_KEYS
+            .iter()
+            .zip([&self.http_proxy, &self.https_proxy, &self.no_proxy])
+            .map(|(key, value)| (*key, value.trim()))
+            .filter(|(_, value)| !value.is_empty())
+            .collect()
+    }
+}
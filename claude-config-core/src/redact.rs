@@ -0,0 +1,104 @@
+//! 日志脱敏：写入日志前把形如 `sk-ant-...` 的 API Key 替换掉，GUI 与 CLI 的日志订阅器共用
+
+use std::io::{self, Write};
+use tracing_subscriber::fmt::MakeWriter;
+
+/// 日志中密钥的替换文本
+const REDACTED: &str = "***redacted***";
+
+/// 识别为密钥所需的 `sk-` 之后的最少字符数，避免误伤普通单词
+const MIN_SECRET_LEN: usize = 16;
+
+/// 将文本中形如 `sk-ant-...` 的 API Key 替换为 `***redacted***`
+pub fn redact_secrets(input: &str) -> String {
+    let is_key_char = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+
+    let mut output = String::with_capacity(input.len());
+    let mut last = 0;
+    let mut search_from = 0;
+
+    while let Some(offset) = input[search_from..].find("sk-") {
+        let start = search_from + offset;
+        let key_len = input[start + 3..]
+            .find(|c: char| !is_key_char(c))
+            .unwrap_or(input.len() - start - 3);
+        let end = start + 3 + key_len;
+
+        // 前一个字符也是密钥字符时（如 "task-"），说明不是密钥的开头
+        let at_boundary = !input[..start].chars().next_back().is_some_and(is_key_char);
+
+        if at_boundary && key_len >= MIN_SECRET_LEN {
+            output.push_str(&input[last..start]);
+            output.push_str(REDACTED);
+            last = end;
+        }
+        search_from = end.max(start + 3);
+    }
+
+    output.push_str(&input[last..]);
+    output
+}
+
+/// 包装日志写入器，写入前脱敏密钥
+pub struct RedactingMakeWriter<M> {
+    inner: M,
+}
+
+impl<M> RedactingMakeWriter<M> {
+    pub fn new(inner: M) -> Self {
+        Self { inner }
+    }
+}
+
+impl<'a, M: MakeWriter<'a>> MakeWriter<'a> for RedactingMakeWriter<M> {
+    type Writer = RedactingWriter<M::Writer>;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter {
+            inner: self.inner.make_writer(),
+        }
+    }
+}
+
+/// fmt 层每条日志只调用一次 write_all，因此可以按次脱敏
+pub struct RedactingWriter<W> {
+    inner: W,
+}
+
+impl<W: Write> Write for RedactingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let redacted = redact_secrets(&String::from_utf8_lossy(buf));
+        self.inner.write_all(redacted.as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_secrets() {
+        let fake_key = "sk-ant-REDACTED";
+        let message = format!("写入配置: {{\"ANTHROPIC_API_KEY\":\"{}\"}} task-sk-list", fake_key);
+
+        let redacted = redact_secrets(&message);
+
+        assert!(!redacted.contains(fake_key));
+        assert_eq!(
+            redacted,
+            "写入配置: {\"ANTHROPIC_API_KEY\":\"***redacted***\"} task-sk-list"
+        );
+        assert_eq!(redact_secrets("sk-short"), "sk-short");
+
+        let mut buffer = Vec::new();
+        RedactingWriter { inner: &mut buffer }
+            .write_all(message.as_bytes())
+            .unwrap();
+        assert_eq!(String::from_utf8(buffer).unwrap(), redacted);
+    }
+}
@@ -0,0 +1,194 @@
+//! 配置文件格式：JSON 之外也读取 YAML / TOML 形式的配置
+//!
+//! 格式只按扩展名判断，`.json` 或没有可识别扩展名的文件始终按 JSON 解析，
+//! 不会因为内容恰好是合法 YAML 而被当作 YAML 读取。
+
+use crate::error::{ConfigError, ConfigResult};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::path::Path;
+
+/// 配置文件格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsFormat {
+    Json,
+    Yaml,
+    Toml,
+}
+
+impl SettingsFormat {
+    /// 按扩展名判断格式（不区分大小写）
+    pub fn from_path(path: impl AsRef<Path>) -> Self {
+        let extension = path
+            .as_ref()
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("yaml") | Some("yml") => SettingsFormat::Yaml,
+            Some("toml") => SettingsFormat::Toml,
+            _ => SettingsFormat::Json,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SettingsFormat::Json => "JSON",
+            SettingsFormat::Yaml => "YAML",
+            SettingsFormat::Toml => "TOML",
+        }
+    }
+}
+
+impl fmt::Display for SettingsFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// 写入配置时使用的格式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteFormat {
+    /// 始终写入 settings.local.json
+    #[default]
+    Json,
+    /// 生效配置为 YAML / TOML 文件时按原格式写回该文件
+    Preserve,
+}
+
+/// JSON 配置的缩进方式，只影响 JSON 文件
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonIndent {
+    /// 两个空格（与 serde_json::to_string_pretty 一致）
+    #[default]
+    TwoSpaces,
+    FourSpaces,
+    Tab,
+}
+
+impl JsonIndent {
+    fn as_bytes(&self) -> &'static [u8] {
+        match self {
+            JsonIndent::TwoSpaces => b"  ",
+            JsonIndent::FourSpaces => b"    ",
+            JsonIndent::Tab => b"\t",
+        }
+    }
+}
+
+fn invalid(path: &Path, format: SettingsFormat, reason: impl fmt::Display) -> ConfigError {
+    ConfigError::InvalidFormat {
+        path: path.to_path_buf(),
+        format: format.as_str(),
+        reason: reason.to_string(),
+    }
+}
+
+/// 按文件扩展名解析配置内容
+pub fn parse_settings(path: impl AsRef<Path>, content: &str) -> ConfigResult<Value> {
+    let path = path.as_ref();
+    match SettingsFormat::from_path(path) {
+        SettingsFormat::Json => {
+            serde_json::from_str(content).map_err(|e| ConfigError::invalid_json(path, e))
+        }
+        SettingsFormat::Yaml => {
+            // 空的 YAML 文件解析为 null，按空配置处理
+            let value: Value =
+                serde_yaml::from_str(content).map_err(|e| invalid(path, SettingsFormat::Yaml, e))?;
+            Ok(if value.is_null() { Value::Object(Default::default()) } else { value })
+        }
+        SettingsFormat::Toml => {
+            let value: toml::Value =
+                toml::from_str(content).map_err(|e| invalid(path, SettingsFormat::Toml, e))?;
+            serde_json::to_value(value).map_err(|e| invalid(path, SettingsFormat::Toml, e))
+        }
+    }
+}
+
+/// 按文件扩展名序列化配置内容，JSON 使用默认缩进
+pub fn serialize_settings(path: impl AsRef<Path>, value: &Value) -> ConfigResult<String> {
+    serialize_settings_with_indent(path, value, JsonIndent::default())
+}
+
+/// 按文件扩展名序列化配置内容，JSON 使用指定缩进
+pub fn serialize_settings_with_indent(
+    path: impl AsRef<Path>,
+    value: &Value,
+    indent: JsonIndent,
+) -> ConfigResult<String> {
+    let path = path.as_ref();
+    match SettingsFormat::from_path(path) {
+        SettingsFormat::Json => {
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+            let mut buffer = Vec::new();
+            let mut serializer = serde_json::Serializer::with_formatter(&mut buffer, formatter);
+            value
+                .serialize(&mut serializer)
+                .map_err(|e| ConfigError::invalid_json(path, e))?;
+            // serde_json 只输出合法 UTF-8
+            Ok(String::from_utf8(buffer).expect("serde_json 输出应为 UTF-8"))
+        }
+        SettingsFormat::Yaml => {
+            serde_yaml::to_string(value).map_err(|e| invalid(path, SettingsFormat::Yaml, e))
+        }
+        // TOML 不支持 null，含 null 的配置会返回错误
+        SettingsFormat::Toml => {
+            toml::to_string_pretty(value).map_err(|e| invalid(path, SettingsFormat::Toml, e))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_by_extension() {
+        let expected = json!({"env": {"ANTHROPIC_BASE_URL": "https://api.anthropic.com"}});
+
+        let yaml = "env:\n  ANTHROPIC_BASE_URL: https://api.anthropic.com\n";
+        assert_eq!(parse_settings("settings.local.yaml", yaml).unwrap(), expected);
+        assert_eq!(parse_settings("settings.local.yml", yaml).unwrap(), expected);
+
+        let toml = "[env]\nANTHROPIC_BASE_URL = \"https://api.anthropic.com\"\n";
+        assert_eq!(parse_settings("settings.local.toml", toml).unwrap(), expected);
+
+        // 合法的 YAML 但不是 JSON，不会被当作 YAML 解析
+        assert!(matches!(
+            parse_settings("settings.local.json", yaml),
+            Err(ConfigError::InvalidJson { .. })
+        ));
+        assert_eq!(parse_settings("settings.local.yaml", "").unwrap(), json!({}));
+    }
+
+    #[test]
+    fn test_serialize_roundtrip() {
+        let value = json!({"env": {"IS_SANDBOX": "1"}, "permissions": {"allow": ["Bash"]}});
+        for path in ["a.json", "a.yaml", "a.toml"] {
+            let content = serialize_settings(path, &value).unwrap();
+            assert_eq!(parse_settings(path, &content).unwrap(), value, "{}", path);
+        }
+        assert!(serialize_settings("a.toml", &json!({"key": null})).is_err());
+    }
+
+    #[test]
+    fn test_json_indent() {
+        let value = json!({"env": {"IS_SANDBOX": "1"}});
+        assert_eq!(
+            serialize_settings("a.json", &value).unwrap(),
+            serde_json::to_string_pretty(&value).unwrap()
+        );
+        assert_eq!(
+            serialize_settings_with_indent("a.json", &value, JsonIndent::FourSpaces).unwrap(),
+            "{\n    \"env\": {\n        \"IS_SANDBOX\": \"1\"\n    }\n}"
+        );
+        assert_eq!(
+            serialize_settings_with_indent("a.json", &value, JsonIndent::Tab).unwrap(),
+            "{\n\t\"env\": {\n\t\t\"IS_SANDBOX\": \"1\"\n\t}\n}"
+        );
+    }
+}
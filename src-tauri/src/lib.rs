@@ -1,6 +1,5 @@
 mod models;
 mod database;
-mod claude_config;
 mod config_manager;
 mod logger;
 mod webdav;
@@ -10,7 +9,7 @@ use tauri::{State, Manager, WindowEvent, tray::{TrayIconBuilder, TrayIconEvent},
 use tokio::sync::Mutex;
 use models::*;
 use database::Database;
-use claude_config::ClaudeConfigManager;
+use claude_config_core::claude_config::ClaudeConfigManager;
 
 type DbState = Arc<Mutex<Database>>;
 
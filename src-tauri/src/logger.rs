@@ -8,6 +8,7 @@ use tracing_subscriber::{
 };
 use tracing_appender::{rolling, non_blocking};
 use anyhow::Result;
+use claude_config_core::redact::RedactingMakeWriter;
 
 pub struct Logger;
 
@@ -37,7 +38,7 @@ impl Logger {
             .with(env_filter)
             .with(
                 fmt::Layer::new()
-                    .with_writer(non_blocking_file)
+                    .with_writer(RedactingMakeWriter::new(non_blocking_file))
                     .with_ansi(false)
                     .with_target(true)
                     .with_thread_ids(true)
@@ -47,7 +48,7 @@ impl Logger {
             )
             .with(
                 fmt::Layer::new()
-                    .with_writer(non_blocking_stdout)
+                    .with_writer(RedactingMakeWriter::new(non_blocking_stdout))
                     .with_ansi(true)
                     .with_target(false)
                     .with_thread_ids(false)
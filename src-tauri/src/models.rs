@@ -226,29 +226,3 @@ impl BaseUrl {
         }
     }
 }
-
-// 环境变量值类型推断
-pub fn parse_env_value(value: &str) -> serde_json::Value {
-    use serde_json::json;
-
-    // 1. 尝试解析为布尔值
-    if value.eq_ignore_ascii_case("true") {
-        return json!(true);
-    }
-    if value.eq_ignore_ascii_case("false") {
-        return json!(false);
-    }
-
-    // 2. 尝试解析为整数
-    if let Ok(num) = value.parse::<i64>() {
-        return json!(num);
-    }
-
-    // 3. 尝试解析为浮点数
-    if let Ok(float) = value.parse::<f64>() {
-        return json!(float);
-    }
-
-    // 4. 默认作为字符串
-    json!(value)
-}
\ No newline at end of file
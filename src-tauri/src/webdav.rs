@@ -1,4 +1,5 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use claude_config_core::checksum;
 use reqwest_dav::{Auth, Client, ClientBuilder, Depth};
 use serde_json::Value;
 use sqlx::SqlitePool;
@@ -56,6 +57,14 @@ impl WebDavManager {
             .await
             .context("Failed to upload config to WebDAV")?;
 
+        // 同时写入校验文件，下载时用于验证配置包完整性
+        let checksum_file = format!("{}/{}", self.config.remote_path, checksum::checksum_file_name(filename));
+        let checksum_content = checksum::format_checksum_file(json_data.as_bytes(), filename);
+        self.client
+            .put(&checksum_file, checksum_content.into_bytes())
+            .await
+            .context("Failed to upload checksum to WebDAV")?;
+
         info!("Config uploaded successfully");
         Ok(())
     }
@@ -74,6 +83,15 @@ impl WebDavManager {
         let data = response.bytes().await
             .context("Failed to read response bytes")?;
 
+        // 解析前先校验，校验失败时调用方不会改动本地数据
+        match self.fetch_checksum(filename).await? {
+            Some(expected) => {
+                checksum::verify(&data, &expected)?;
+                info!("Checksum verified for {}", remote_file);
+            }
+            None => warn!("远程没有 {} 的校验文件，跳过完整性校验", filename),
+        }
+
         let json_str = String::from_utf8(data.to_vec())
             .context("Failed to parse downloaded data as UTF-8")?;
 
@@ -84,6 +102,26 @@ impl WebDavManager {
         Ok(config)
     }
 
+    /// 读取配置包对应的校验值；旧版本上传的配置包没有校验文件（404），返回 None。
+    /// 认证失败、服务器错误、超时等其他失败直接返回错误，不能借此跳过完整性校验
+    async fn fetch_checksum(&self, filename: &str) -> Result<Option<String>> {
+        let checksum_file = format!("{}/{}", self.config.remote_path, checksum::checksum_file_name(filename));
+        let response = self.client.get_raw(&checksum_file).await
+            .with_context(|| format!("获取校验文件失败: {}", checksum_file))?;
+        let status = response.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        if !status.is_success() {
+            bail!("获取校验文件失败: {}: HTTP {}", checksum_file, status);
+        }
+        let content = response.text().await
+            .context(format!("读取校验文件失败: {}", checksum_file))?;
+        let hash = checksum::parse_checksum_file(&content)
+            .with_context(|| format!("校验文件格式不正确: {}", checksum_file))?;
+        Ok(Some(hash))
+    }
+
     /// 列出远程目录中的文件
     pub async fn list_remote_files(&self) -> Result<Vec<String>> {
         info!("Listing files in remote directory: {}", self.config.remote_path);
@@ -125,6 +163,8 @@ impl WebDavManager {
                 }
                 None
             })
+            // 校验文件随配置包一起上传，不单独展示
+            .filter(|filename| !checksum::is_checksum_file(filename))
             .collect();
 
         info!("Found {} files in remote directory", files.len());